@@ -85,6 +85,13 @@ mod domain {
         }
     }
 
+    pub mod audio_tags {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/audio_tags.rs"
+        ));
+    }
+
     pub mod collection_import {
         include!(concat!(
             env!("CARGO_MANIFEST_DIR"),
@@ -468,6 +475,8 @@ async fn assert_manual_download_playback_path(
         }],
         groups: vec![],
         extra: vec![],
+        playlists: None,
+        rules: None,
 
         created_at: AutoFill::pending(),
     };
@@ -486,6 +495,8 @@ async fn assert_manual_download_playback_path(
             .collect(),
         groups: vec![],
         extra: vec![],
+        playlists: vec![],
+        rules: None,
         created_at: playlist.created_at.clone(),
     };
     domain::playlists::repo::upsert_playlist_surface(&playlist_request, None)
@@ -90,21 +90,46 @@ pub fn run() {
             domain::playlists::get_collection,
             domain::playlists::get_playlist,
             domain::playlists::get_playlist_config,
+            domain::playlists::export_playlist,
+            domain::playlists::import_playlist_file,
             domain::playlists::delete_playlist,
             domain::playlists::upsert_playlist,
             domain::playlists::push_extra,
             domain::playlists::remove_extra,
             domain::playlists::set_collection_updates,
             domain::playlists::update_music,
+            domain::playlists::split_music,
             domain::playlists::set_current_music_liked,
             domain::playlists::create_music,
             domain::playlists::delete_music,
+            domain::playlists::bulk_edit_musics,
             domain::playlists::list_musics_by_file_path,
             domain::playlists::load_spectrum_music_context,
+            domain::playlists::search_library,
             domain::playlists::add_exclude,
             domain::playlists::remove_exclude,
+            domain::playlists::list_trash,
+            domain::playlists::restore_trash_item,
+            domain::playlists::purge_trash_item,
+            domain::playlists::list_collection_groups,
+            domain::playlists::create_collection_group,
+            domain::playlists::rename_collection_group,
+            domain::playlists::move_musics_to_group,
+            domain::playlists::delete_collection_group,
             domain::collection_import::create_local_collection_shell,
             domain::collection_import::import_local_collection,
+            domain::library_integrity::check_library_integrity,
+            domain::library_integrity::relink_music_file,
+            domain::collection_relocation::move_collection_to_root,
+            domain::collection_merge::merge_collection_into,
+            domain::collection_merge::split_group_to_collection,
+            domain::library_backup::backup_library,
+            domain::library_backup::restore_library,
+            domain::audio_segmentation::propose_music_segments,
+            domain::audio_segmentation::commit_music_segments,
+            domain::save_root_migration::migrate_save_root,
+            domain::save_root_migration::resume_save_root_migration,
+            domain::save_root_migration::get_save_root_migration_state,
             domain::playlist_playback::play_playlist,
             domain::playlist_playback::exclude_current_music_and_skip,
             domain::player::set_playback_continuation_mode,
@@ -125,6 +150,9 @@ pub fn run() {
             domain::player::analyze_track_waveform,
             domain::player::prepare_track_waveform,
             domain::player::get_track_waveform_tile,
+            domain::player::list_recent_plays,
+            domain::player::list_track_play_stats,
+            domain::player::list_playlist_play_stats,
             domain::downloads::enqueue_collection_download,
             domain::downloads::resolve_pasted_download_url,
             domain::downloads::probe_download_root_title,
@@ -142,10 +170,12 @@ pub fn run() {
             domain::player::event::NowPlayingTrackChangedEvent,
             domain::player::event::NowPlayingTrackLikedChangedEvent,
             domain::player::event::PlaybackAudioVisualizationFrameEvent,
+            domain::player::event::PlaybackLyricLineChangedEvent,
             domain::player::event::PlaybackSurfaceStatusChangedEvent,
             domain::player::event::PlaybackExcludeCommittedEvent,
             domain::player::event::PlaybackDiagnosticTraceEvent,
-            domain::downloads::service::DownloadTaskChangeSignal
+            domain::downloads::service::DownloadTaskChangeSignal,
+            domain::collection_relocation::CollectionRelocationProgress
         ]);
 
     #[cfg(debug_assertions)]
@@ -258,6 +288,9 @@ pub fn run() {
                     domain::downloads::service::initialize_runtime(handle.clone());
                     domain::playlist_playback::service::initialize_runtime(handle.clone());
                     domain::player::service::initialize_runtime(handle.clone());
+                    domain::save_root_migration::initialize_runtime(handle.clone());
+                    domain::collection_manifest_sync::initialize_runtime(handle.clone());
+                    domain::playlists::exclude_expiry::initialize_runtime();
                     domain::remote_share::initialize_runtime(handle.clone()).await?;
                     utils::binaries::spawn_binary_maintenance(
                         handle.clone(),
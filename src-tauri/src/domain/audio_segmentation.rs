@@ -0,0 +1,369 @@
+use super::audio_tail_trim::{
+    TAIL_CUT_SILENCE_THRESHOLD_DB, TailEvidenceFrame, TailEvidenceSignature, quantile_f32,
+    refine_tail_cut_to_quiet_boundary,
+};
+#[cfg(not(test))]
+use super::playlists::model::Music;
+#[cfg(not(test))]
+use super::playlists::repo as playlists_repo;
+#[cfg(not(test))]
+use crate::utils::binaries::{
+    ManagedBinary, acquire_managed_binary_usage, ensure_managed_binary,
+    wait_for_managed_binary_foreground_release,
+};
+#[cfg(not(test))]
+use anyhow::{Context, anyhow};
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+#[cfg(not(test))]
+use std::path::PathBuf;
+
+#[cfg(test)]
+#[path = "audio_segmentation.test.rs"]
+mod tests;
+
+#[cfg_attr(test, allow(dead_code))]
+const AUDIO_SEGMENTATION_LOG_TARGET: &str = "audio_segmentation";
+
+#[cfg_attr(test, allow(dead_code))]
+const SEGMENT_ANALYSIS_CHUNK_MS: u32 = 5 * 60_000;
+#[cfg_attr(test, allow(dead_code))]
+const SEGMENT_SAMPLE_RATE: u32 = 8_000;
+#[cfg_attr(test, allow(dead_code))]
+const SEGMENT_WINDOW_MS: u32 = 500;
+#[cfg_attr(test, allow(dead_code))]
+const SEGMENT_HOP_MS: u32 = 250;
+#[cfg_attr(test, allow(dead_code))]
+const SEGMENT_SPECTRAL_BANDS: u32 = 8;
+
+/// Tracks shorter than this are never proposed, so intros and breaks inside
+/// a track do not split it.
+const SEGMENT_MIN_TRACK_MS: u32 = 30_000;
+const SEGMENT_MIN_QUIET_MS: u32 = 1_200;
+/// Loudness around a frame is read over this much audio on each side.
+const SEGMENT_LOUDNESS_CONTEXT_MS: u32 = 30_000;
+const SEGMENT_LOUDNESS_REFERENCE_QUANTILE: f32 = 0.75;
+/// A drop this far below the surrounding loudness counts as a gap even when
+/// it stays above the silence threshold, as between crossfaded mix tracks.
+const SEGMENT_LOUDNESS_DROP_DB: f32 = 18.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+pub struct MusicSegmentRange {
+    pub start_ms: u32,
+    pub end_ms: u32,
+}
+
+/// Proposed tracks of one music range, in order and covering it without gaps.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Type)]
+pub struct MusicSegmentProposal {
+    pub url: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub segments: Vec<MusicSegmentRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct QuietRun {
+    start_ms: u32,
+    end_ms: u32,
+    depth_db: f32,
+}
+
+/**
+ * Behavior:
+ *   Propose where one long range splits into tracks, from sustained silence
+ *   and from loudness drops against the surrounding audio.
+ *
+ * Core invariants:
+ *   - A gap must stay quiet for `SEGMENT_MIN_QUIET_MS`; shorter dips are
+ *     part of the track.
+ *   - Each cut is placed where the gap ends, refined by the same quiet
+ *     boundary search tail trim uses, so the next track keeps its attack.
+ *   - Longer and deeper gaps win when two cuts would leave a track shorter
+ *     than `SEGMENT_MIN_TRACK_MS`.
+ *   - Cut points are strictly inside the range and increase.
+ */
+pub(crate) fn propose_segment_cut_points(
+    signature: &TailEvidenceSignature,
+    start_ms: u32,
+    end_ms: u32,
+) -> Vec<u32> {
+    if end_ms.saturating_sub(start_ms) < SEGMENT_MIN_TRACK_MS * 2 {
+        return Vec::new();
+    }
+
+    let mut frames = signature
+        .frames
+        .iter()
+        .filter(|frame| frame.source_start_ms >= start_ms && frame.source_end_ms <= end_ms)
+        .collect::<Vec<_>>();
+    frames.sort_by_key(|frame| frame.source_start_ms);
+
+    let mut runs = quiet_runs(&frames);
+    runs.sort_by(|left, right| {
+        (right.end_ms - right.start_ms)
+            .cmp(&(left.end_ms - left.start_ms))
+            .then_with(|| right.depth_db.total_cmp(&left.depth_db))
+    });
+
+    let mut cuts = Vec::<u32>::new();
+    for run in runs {
+        let cut_ms = refine_tail_cut_to_quiet_boundary(signature, run.end_ms)
+            .clamp(run.start_ms, run.end_ms);
+        let leaves_short_track = cut_ms < start_ms + SEGMENT_MIN_TRACK_MS
+            || cut_ms + SEGMENT_MIN_TRACK_MS > end_ms
+            || cuts
+                .iter()
+                .any(|accepted| accepted.abs_diff(cut_ms) < SEGMENT_MIN_TRACK_MS);
+        if !leaves_short_track {
+            cuts.push(cut_ms);
+        }
+    }
+    cuts.sort_unstable();
+    cuts
+}
+
+fn quiet_runs(frames: &[&TailEvidenceFrame]) -> Vec<QuietRun> {
+    let rms = frames.iter().map(|frame| frame.rms_db).collect::<Vec<_>>();
+    let mut runs = Vec::new();
+    let mut current = None::<QuietRun>;
+    let mut context_start = 0;
+    let mut context_end = 0;
+
+    for frame in frames {
+        while frames[context_start].source_end_ms + SEGMENT_LOUDNESS_CONTEXT_MS
+            < frame.source_start_ms
+        {
+            context_start += 1;
+        }
+        while context_end < frames.len()
+            && frames[context_end].source_start_ms
+                <= frame.source_end_ms + SEGMENT_LOUDNESS_CONTEXT_MS
+        {
+            context_end += 1;
+        }
+        let reference_db = quantile_f32(
+            &rms[context_start..context_end],
+            SEGMENT_LOUDNESS_REFERENCE_QUANTILE,
+        );
+        let quiet = frame.rms_db <= TAIL_CUT_SILENCE_THRESHOLD_DB
+            || frame.rms_db <= reference_db - SEGMENT_LOUDNESS_DROP_DB;
+
+        if !quiet {
+            runs.extend(current.take());
+            continue;
+        }
+        let depth_db = reference_db - frame.rms_db;
+        current = Some(match current {
+            Some(run) if frame.source_start_ms <= run.end_ms => QuietRun {
+                start_ms: run.start_ms,
+                end_ms: frame.source_end_ms,
+                depth_db: run.depth_db.max(depth_db),
+            },
+            previous => {
+                runs.extend(previous);
+                QuietRun {
+                    start_ms: frame.source_start_ms,
+                    end_ms: frame.source_end_ms,
+                    depth_db,
+                }
+            }
+        });
+    }
+    runs.extend(current);
+
+    runs.into_iter()
+        .filter(|run| run.end_ms - run.start_ms >= SEGMENT_MIN_QUIET_MS)
+        .collect()
+}
+
+pub(crate) fn segment_ranges(start_ms: u32, end_ms: u32, cuts: &[u32]) -> Vec<MusicSegmentRange> {
+    std::iter::once(start_ms)
+        .chain(cuts.iter().copied())
+        .zip(cuts.iter().copied().chain(std::iter::once(end_ms)))
+        .map(|(start_ms, end_ms)| MusicSegmentRange { start_ms, end_ms })
+        .collect()
+}
+
+/// Turns confirmed segments back into the cut points of their range; the
+/// segments must cover it in order without gaps or overlaps.
+pub(crate) fn segment_cut_points(
+    start_ms: u32,
+    end_ms: u32,
+    segments: &[MusicSegmentRange],
+) -> Result<Vec<u32>> {
+    if segments.len() < 2 {
+        bail!("a segmentation needs at least two segments");
+    }
+    if segments.first().map(|segment| segment.start_ms) != Some(start_ms)
+        || segments.last().map(|segment| segment.end_ms) != Some(end_ms)
+    {
+        bail!("segments must start and end with the range {start_ms}..{end_ms}");
+    }
+    if segments
+        .iter()
+        .any(|segment| segment.start_ms >= segment.end_ms)
+        || segments
+            .windows(2)
+            .any(|pair| pair[0].end_ms != pair[1].start_ms)
+    {
+        bail!("segments must be non-empty and follow each other without gaps");
+    }
+
+    Ok(segments[1..]
+        .iter()
+        .map(|segment| segment.start_ms)
+        .collect())
+}
+
+#[cfg(not(test))]
+async fn analyze_segment_signature(
+    ffmpeg_path: PathBuf,
+    file_path: PathBuf,
+    start_ms: u32,
+    end_ms: u32,
+) -> Result<TailEvidenceSignature> {
+    wait_for_managed_binary_foreground_release(ManagedBinary::Ffmpeg);
+    let _guard = acquire_managed_binary_usage(ManagedBinary::Ffmpeg, "audio_segmentation");
+    tokio::task::spawn_blocking(move || {
+        let mut frames = Vec::new();
+        let mut chunk_start_ms = start_ms;
+        while chunk_start_ms < end_ms {
+            // Chunks overlap by one window so no frame is lost at a seam, and
+            // trailing silence is kept because it is exactly what is searched.
+            let duration_ms = (end_ms - chunk_start_ms).min(SEGMENT_ANALYSIS_CHUNK_MS);
+            let mut request = ffplayr::AudioTailFingerprintAnalysisRequest::new(&file_path);
+            request.time_range = Some(ffplayr::PlaybackTimeRange {
+                start_ms: chunk_start_ms,
+                duration_ms: Some(duration_ms),
+            });
+            request.sample_rate = SEGMENT_SAMPLE_RATE;
+            request.max_search_ms = duration_ms;
+            request.window_ms = SEGMENT_WINDOW_MS;
+            request.hop_ms = SEGMENT_HOP_MS;
+            request.spectral_bands = SEGMENT_SPECTRAL_BANDS;
+            request.silence_threshold_db = f32::NEG_INFINITY;
+            request.silence_pad_ms = 0;
+            let analysis = ffplayr::analyze_tail_fingerprint_with_binary(&ffmpeg_path, request)
+                .map_err(anyhow::Error::msg)?;
+            frames.extend(analysis.frames.into_iter().map(|frame| TailEvidenceFrame {
+                source_start_ms: frame.source_start_ms,
+                source_end_ms: frame.source_end_ms,
+                rms_db: frame.rms_db,
+                bands: frame.bands,
+            }));
+            chunk_start_ms = (chunk_start_ms + duration_ms)
+                .saturating_sub(SEGMENT_WINDOW_MS)
+                .max(chunk_start_ms + 1);
+            if chunk_start_ms + SEGMENT_WINDOW_MS >= end_ms {
+                break;
+            }
+        }
+        frames.sort_by_key(|frame| frame.source_start_ms);
+        frames.dedup_by_key(|frame| frame.source_start_ms);
+        Ok(TailEvidenceSignature {
+            frames,
+            search_start_ms: start_ms,
+            effective_end_ms: end_ms,
+            window_ms: SEGMENT_WINDOW_MS,
+            hop_ms: SEGMENT_HOP_MS,
+        })
+    })
+    .await
+    .context("audio segmentation analysis task failed")?
+}
+
+/**
+ * Behavior:
+ *   Analyze the audio of one music range and propose the tracks it splits
+ *   into.
+ *
+ * Core invariants:
+ *   - Nothing is written; the proposal is committed separately once the
+ *     user confirms it.
+ *   - A range whose music is no longer in the library proposes nothing.
+ */
+#[cfg(not(test))]
+async fn propose_segments(
+    app: &tauri::AppHandle,
+    url: &str,
+    start_ms: u32,
+    end_ms: u32,
+) -> Result<Option<MusicSegmentProposal>> {
+    let Some((collection_url, music)) =
+        playlists_repo::list_music_occurrences_by_identity(url, start_ms, end_ms)
+            .await?
+            .into_iter()
+            .next()
+    else {
+        return Ok(None);
+    };
+    let Some(collection) = playlists_repo::get_collection_by_url(&collection_url).await? else {
+        return Ok(None);
+    };
+    let save_root = crate::domain::meta::service::resolve_save_root(app).await?;
+    let file_path = playlists_repo::resolve_music_file_path(
+        &save_root,
+        collection.save_root.as_deref(),
+        &collection.folder,
+        music.path.as_deref(),
+    )
+    .filter(|path| path.is_file())
+    .ok_or_else(|| anyhow!("audio file of `{url}` is missing"))?;
+    let ffmpeg_path =
+        ensure_managed_binary(app, ManagedBinary::Ffmpeg).map_err(|error| anyhow!(error))?;
+
+    log::info!(
+        target: AUDIO_SEGMENTATION_LOG_TARGET,
+        "audio_segmentation_started music_url=\"{}\" range={}..{}",
+        url,
+        start_ms,
+        end_ms
+    );
+    let signature = analyze_segment_signature(ffmpeg_path, file_path, start_ms, end_ms).await?;
+    let cuts = propose_segment_cut_points(&signature, start_ms, end_ms);
+    log::info!(
+        target: AUDIO_SEGMENTATION_LOG_TARGET,
+        "audio_segmentation_proposed music_url=\"{}\" frames={} segments={}",
+        url,
+        signature.frames.len(),
+        cuts.len() + 1
+    );
+
+    Ok(Some(MusicSegmentProposal {
+        url: url.to_string(),
+        start_ms,
+        end_ms,
+        segments: segment_ranges(start_ms, end_ms, &cuts),
+    }))
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn propose_music_segments(
+    app: tauri::AppHandle,
+    url: String,
+    start_ms: u32,
+    end_ms: u32,
+) -> Result<Option<MusicSegmentProposal>, String> {
+    propose_segments(&app, &url, start_ms, end_ms)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// Commits confirmed segments as ranged musics through the music split.
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn commit_music_segments(
+    url: String,
+    start_ms: u32,
+    end_ms: u32,
+    segments: Vec<MusicSegmentRange>,
+) -> Result<Option<Vec<Music>>, String> {
+    let cut_points =
+        segment_cut_points(start_ms, end_ms, &segments).map_err(|error| error.to_string())?;
+    crate::domain::playlists::split_music(url, start_ms, end_ms, cut_points).await
+}
@@ -0,0 +1,126 @@
+use super::{MusicSegmentRange, propose_segment_cut_points, segment_cut_points, segment_ranges};
+use crate::domain::audio_tail_trim::{TailEvidenceFrame, TailEvidenceSignature};
+
+const WINDOW_MS: u32 = 500;
+const HOP_MS: u32 = 250;
+
+/// Frames over `0..end_ms` at `loud_db`, with `(start, end, db)` gaps laid
+/// over every frame that falls inside them.
+fn signature(end_ms: u32, loud_db: f32, gaps: &[(u32, u32, f32)]) -> TailEvidenceSignature {
+    let frames = (0..)
+        .map(|index| index * HOP_MS)
+        .take_while(|start_ms| start_ms + WINDOW_MS <= end_ms)
+        .map(|start_ms| {
+            let end_ms = start_ms + WINDOW_MS;
+            let rms_db = gaps
+                .iter()
+                .find(|(gap_start, gap_end, _)| *gap_start <= start_ms && end_ms <= *gap_end)
+                .map(|(_, _, db)| *db)
+                .unwrap_or(loud_db);
+            TailEvidenceFrame {
+                source_start_ms: start_ms,
+                source_end_ms: end_ms,
+                rms_db,
+                bands: vec![],
+            }
+        })
+        .collect();
+    TailEvidenceSignature {
+        frames,
+        search_start_ms: 0,
+        effective_end_ms: end_ms,
+        window_ms: WINDOW_MS,
+        hop_ms: HOP_MS,
+    }
+}
+
+#[test]
+fn sustained_silence_and_loudness_drops_become_cut_points() {
+    let signature = signature(
+        600_000,
+        -12.0,
+        &[
+            (180_000, 182_000, -70.0),
+            (300_000, 300_600, -70.0),
+            (420_000, 422_000, -34.0),
+        ],
+    );
+
+    assert_eq!(
+        propose_segment_cut_points(&signature, 0, 600_000),
+        vec![182_000, 422_000]
+    );
+}
+
+#[test]
+fn gaps_that_would_leave_a_short_track_are_skipped_in_favor_of_longer_ones() {
+    let signature = signature(
+        300_000,
+        -12.0,
+        &[
+            (10_000, 14_000, -70.0),
+            (120_000, 122_000, -70.0),
+            (135_000, 139_000, -70.0),
+        ],
+    );
+
+    assert_eq!(
+        propose_segment_cut_points(&signature, 0, 300_000),
+        vec![139_000]
+    );
+}
+
+#[test]
+fn segments_round_trip_through_cut_points() {
+    let segments = segment_ranges(1_000, 300_000, &[90_000, 200_000]);
+
+    assert_eq!(
+        segments,
+        vec![
+            MusicSegmentRange {
+                start_ms: 1_000,
+                end_ms: 90_000,
+            },
+            MusicSegmentRange {
+                start_ms: 90_000,
+                end_ms: 200_000,
+            },
+            MusicSegmentRange {
+                start_ms: 200_000,
+                end_ms: 300_000,
+            },
+        ]
+    );
+    assert_eq!(
+        segment_cut_points(1_000, 300_000, &segments).expect("segments should tile the range"),
+        vec![90_000, 200_000]
+    );
+}
+
+#[test]
+fn confirmed_segments_must_tile_the_range() {
+    let gap = [
+        MusicSegmentRange {
+            start_ms: 0,
+            end_ms: 90_000,
+        },
+        MusicSegmentRange {
+            start_ms: 91_000,
+            end_ms: 180_000,
+        },
+    ];
+    let short = [
+        MusicSegmentRange {
+            start_ms: 0,
+            end_ms: 90_000,
+        },
+        MusicSegmentRange {
+            start_ms: 90_000,
+            end_ms: 170_000,
+        },
+    ];
+
+    assert!(segment_cut_points(0, 180_000, &gap).is_err());
+    assert!(segment_cut_points(0, 180_000, &short).is_err());
+    assert!(segment_cut_points(0, 180_000, &short[..1]).is_err());
+}
@@ -0,0 +1,361 @@
+use super::playlists::model::MusicTags;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FfmpegMetadataScope {
+    Input,
+    AudioStream,
+    Ignored,
+}
+
+/// Container tags, chapters and duration of one audio file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AudioInputMetadata {
+    pub(crate) tags: Option<MusicTags>,
+    pub(crate) chapters: Vec<AudioChapter>,
+    pub(crate) duration_ms: Option<u32>,
+}
+
+/// One embedded chapter, in milliseconds of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AudioChapter {
+    pub(crate) title: Option<String>,
+    pub(crate) start_ms: u32,
+    pub(crate) end_ms: u32,
+}
+
+/**
+ * Behavior:
+ *   Read embedded container tags (title, artist, album, genre, year, track
+ *   number) from one audio file with the managed FFmpeg binary.
+ *
+ * Core invariants:
+ *   - FFmpeg only opens the input; no stream is decoded, so probing stays
+ *     cheap enough to run for every imported or committed file.
+ *   - Format-level tags win over first-audio-stream tags (Ogg/Opus keep
+ *     their Vorbis comments on the stream); chapter metadata is ignored.
+ *   - A file without usable tags yields `None`.
+ */
+pub(crate) fn probe_audio_tags(ffmpeg_path: &Path, file_path: &Path) -> Result<Option<MusicTags>> {
+    Ok(parse_ffmpeg_input_tags(&describe_audio_input(
+        ffmpeg_path,
+        file_path,
+    )?))
+}
+
+/// Tags, embedded chapters (m4b, mkv, chaptered mp3) and the container
+/// duration from one FFmpeg input probe.
+pub(crate) fn probe_audio_metadata(
+    ffmpeg_path: &Path,
+    file_path: &Path,
+) -> Result<AudioInputMetadata> {
+    let description = describe_audio_input(ffmpeg_path, file_path)?;
+    Ok(AudioInputMetadata {
+        tags: parse_ffmpeg_input_tags(&description),
+        chapters: parse_ffmpeg_input_chapters(&description),
+        duration_ms: parse_ffmpeg_input_duration_ms(&description),
+    })
+}
+
+fn describe_audio_input(ffmpeg_path: &Path, file_path: &Path) -> Result<String> {
+    let mut command = Command::new(ffmpeg_path);
+    command
+        .arg("-hide_banner")
+        .arg("-nostdin")
+        .arg("-i")
+        .arg(file_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+
+    // Without an output file FFmpeg exits non-zero after printing the input
+    // description, so the exit status carries no signal here.
+    let output = command
+        .output()
+        .with_context(|| format!("failed to probe tags of {}", file_path.display()))?;
+    Ok(String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+pub(crate) fn probe_audio_tags_best_effort(
+    ffmpeg_path: &Path,
+    file_path: &Path,
+) -> Option<MusicTags> {
+    match probe_audio_tags(ffmpeg_path, file_path) {
+        Ok(tags) => tags,
+        Err(error) => {
+            log::warn!(
+                target: "audio_tags",
+                "audio_tags_probe_failed path=\"{}\" error=\"{error:#}\"",
+                file_path.display()
+            );
+            None
+        }
+    }
+}
+
+pub(crate) fn parse_ffmpeg_input_tags(description: &str) -> Option<MusicTags> {
+    let mut input_entries = Vec::<(String, String)>::new();
+    let mut stream_entries = Vec::<(String, String)>::new();
+    let mut scope = FfmpegMetadataScope::Ignored;
+    let mut seen_input = false;
+    let mut seen_audio_stream = false;
+    let mut metadata_indent = None::<usize>;
+
+    for line in description.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(block_indent) = metadata_indent {
+            if indent > block_indent {
+                let entries = match scope {
+                    FfmpegMetadataScope::Input => &mut input_entries,
+                    FfmpegMetadataScope::AudioStream => &mut stream_entries,
+                    FfmpegMetadataScope::Ignored => continue,
+                };
+                push_metadata_line(entries, trimmed);
+                continue;
+            }
+            metadata_indent = None;
+        }
+
+        if trimmed == "Metadata:" {
+            metadata_indent = Some(indent);
+            continue;
+        }
+        scope = if trimmed.starts_with("Input #") {
+            if seen_input {
+                break;
+            }
+            seen_input = true;
+            FfmpegMetadataScope::Input
+        } else if trimmed.starts_with("Stream #") && trimmed.contains(": Audio:") {
+            if seen_audio_stream {
+                FfmpegMetadataScope::Ignored
+            } else {
+                seen_audio_stream = true;
+                FfmpegMetadataScope::AudioStream
+            }
+        } else if trimmed.starts_with("Duration:") && scope == FfmpegMetadataScope::Input {
+            FfmpegMetadataScope::Input
+        } else {
+            FfmpegMetadataScope::Ignored
+        };
+    }
+
+    let tags = music_tags_from_entries(input_entries.iter().chain(stream_entries.iter()));
+    (!tags.is_empty()).then_some(tags)
+}
+
+/**
+ * Behavior:
+ *   Read the `Chapters:` block of the first input in an FFmpeg input
+ *   description, with the `title` of each chapter's metadata.
+ *
+ * Core invariants:
+ *   - Chapters whose bounds do not parse or do not move forward are dropped.
+ *   - Chapter order and bounds are kept as the container wrote them; callers
+ *     decide whether the chapters split the file.
+ */
+pub(crate) fn parse_ffmpeg_input_chapters(description: &str) -> Vec<AudioChapter> {
+    let mut chapters = Vec::new();
+    let mut seen_input = false;
+    let mut chapter_indent = None::<usize>;
+
+    for line in description.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(block_indent) = chapter_indent {
+            if indent > block_indent {
+                if let Some((key, value)) = trimmed.split_once(':')
+                    && key.trim().eq_ignore_ascii_case("title")
+                    && !value.trim().is_empty()
+                    && let Some(chapter) = chapters.last_mut()
+                {
+                    fill_text(&mut chapter.title, value.trim());
+                }
+                continue;
+            }
+            chapter_indent = None;
+        }
+
+        if trimmed.starts_with("Input #") {
+            if seen_input {
+                break;
+            }
+            seen_input = true;
+        } else if let Some(bounds) = trimmed.strip_prefix("Chapter #")
+            && let Some(chapter) = parse_ffmpeg_chapter_bounds(bounds)
+        {
+            chapters.push(chapter);
+            chapter_indent = Some(indent);
+        }
+    }
+
+    chapters
+}
+
+/**
+ * Behavior:
+ *   Read the container duration of the first input in an FFmpeg input
+ *   description.
+ *
+ * Core invariants:
+ *   - An input without an audio stream has no duration, so non-audio files
+ *     are never taken for tracks.
+ *   - `Duration: N/A` and zero durations yield `None`; callers measure such
+ *     inputs by decoding them.
+ */
+pub(crate) fn parse_ffmpeg_input_duration_ms(description: &str) -> Option<u32> {
+    let mut seen_input = false;
+    let mut seen_audio_stream = false;
+    let mut duration_ms = None;
+
+    for line in description.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Input #") {
+            if seen_input {
+                break;
+            }
+            seen_input = true;
+        } else if let Some(value) = trimmed.strip_prefix("Duration:") {
+            if duration_ms.is_none() {
+                duration_ms = value.split(',').next().and_then(parse_clock_ms);
+            }
+        } else if trimmed.starts_with("Stream #") && trimmed.contains(": Audio:") {
+            seen_audio_stream = true;
+        }
+    }
+
+    duration_ms.filter(|duration_ms| seen_audio_stream && *duration_ms > 0)
+}
+
+/// `00:03:12.35`
+fn parse_clock_ms(value: &str) -> Option<u32> {
+    let mut parts = value.trim().splitn(3, ':');
+    let hours = parts.next()?.parse::<u32>().ok()?;
+    let minutes = parts.next()?.parse::<u32>().ok()?;
+    let seconds_ms = parse_seconds_ms(parts.next()?)?;
+    hours
+        .checked_mul(3_600_000)?
+        .checked_add(minutes.checked_mul(60_000)?)?
+        .checked_add(seconds_ms)
+}
+
+/// `0:1: start 120.000000, end 300.500000`
+fn parse_ffmpeg_chapter_bounds(bounds: &str) -> Option<AudioChapter> {
+    let (_, bounds) = bounds.split_once("start ")?;
+    let (start, end) = bounds.split_once(", end ")?;
+    let start_ms = parse_seconds_ms(start)?;
+    let end_ms = parse_seconds_ms(end)?;
+    (end_ms > start_ms).then_some(AudioChapter {
+        title: None,
+        start_ms,
+        end_ms,
+    })
+}
+
+fn parse_seconds_ms(value: &str) -> Option<u32> {
+    let seconds = value.trim().parse::<f64>().ok()?;
+    (seconds.is_finite() && seconds >= 0.0)
+        .then(|| (seconds * 1_000.0).round().min(u32::MAX as f64) as u32)
+}
+
+fn push_metadata_line(entries: &mut Vec<(String, String)>, line: &str) {
+    let Some((key, value)) = line.split_once(':') else {
+        return;
+    };
+    let key = key.trim();
+    let value = value.trim();
+    if key.is_empty() {
+        if let Some((_, previous)) = entries.last_mut()
+            && !value.is_empty()
+        {
+            previous.push('\n');
+            previous.push_str(value);
+        }
+        return;
+    }
+    entries.push((key.to_ascii_lowercase(), value.to_string()));
+}
+
+fn music_tags_from_entries<'a>(entries: impl Iterator<Item = &'a (String, String)>) -> MusicTags {
+    let mut tags = MusicTags::default();
+    let mut album_artist = None;
+    for (key, value) in entries {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.as_str() {
+            "title" => fill_text(&mut tags.title, value),
+            "artist" => fill_text(&mut tags.artist, value),
+            "album_artist" | "album artist" => fill_text(&mut album_artist, value),
+            "album" => fill_text(&mut tags.album, value),
+            "genre" => fill_text(&mut tags.genre, value),
+            "date" | "year" | "tyer" | "tdrc" | "originaldate" => {
+                if tags.year.is_none() {
+                    tags.year = parse_tag_year(value);
+                }
+            }
+            "track" | "tracknumber" => {
+                if tags.track_number.is_none() {
+                    tags.track_number = parse_tag_track_number(value);
+                }
+            }
+            _ => {}
+        }
+    }
+    if tags.artist.is_none() {
+        tags.artist = album_artist;
+    }
+    tags
+}
+
+fn fill_text(slot: &mut Option<String>, value: &str) {
+    if slot.is_none() {
+        *slot = Some(value.to_string());
+    }
+}
+
+fn parse_tag_year(value: &str) -> Option<u32> {
+    let digits = value
+        .trim()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    if digits.len() != 4 {
+        return None;
+    }
+    digits.parse().ok().filter(|year| *year > 0)
+}
+
+fn parse_tag_track_number(value: &str) -> Option<u32> {
+    value
+        .split('/')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|track| *track > 0)
+}
+
+#[cfg(test)]
+#[path = "audio_tags.test.rs"]
+mod tests;
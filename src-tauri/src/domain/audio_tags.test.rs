@@ -0,0 +1,134 @@
+use super::{
+    AudioChapter, parse_ffmpeg_input_chapters, parse_ffmpeg_input_duration_ms,
+    parse_ffmpeg_input_tags,
+};
+use crate::domain::playlists::model::MusicTags;
+
+#[test]
+fn input_tags_win_over_stream_tags_and_chapter_titles_are_ignored() {
+    let description = concat!(
+        "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'Intro.m4a':\n",
+        "  Metadata:\n",
+        "    major_brand     : M4A \n",
+        "    title           : Intro (Live)\n",
+        "    album_artist    : Various Artists\n",
+        "    album           : Focus\n",
+        "    genre           : Ambient\n",
+        "    date            : 2019-05-01\n",
+        "    track           : 3/12\n",
+        "  Duration: 00:03:12.35, start: 0.000000, bitrate: 256 kb/s\n",
+        "  Chapters:\n",
+        "    Chapter #0:0: start 0.000000, end 60.000000\n",
+        "      Metadata:\n",
+        "        title           : Part One\n",
+        "  Stream #0:0[0x1](und): Audio: aac (LC), 44100 Hz, stereo, fltp, 255 kb/s (default)\n",
+        "    Metadata:\n",
+        "      title           : Stream title\n",
+        "      artist          : Stream Artist\n",
+        "At least one output file must be specified\n",
+    );
+
+    assert_eq!(
+        parse_ffmpeg_input_tags(description),
+        Some(MusicTags {
+            title: Some("Intro (Live)".to_string()),
+            artist: Some("Stream Artist".to_string()),
+            album: Some("Focus".to_string()),
+            genre: Some("Ambient".to_string()),
+            year: Some(2019),
+            track_number: Some(3),
+        })
+    );
+}
+
+#[test]
+fn vorbis_comments_on_the_audio_stream_are_read() {
+    let description = concat!(
+        "Input #0, ogg, from 'song.opus':\n",
+        "  Duration: 00:02:00.00, start: 0.000000, bitrate: 96 kb/s\n",
+        "  Stream #0:0: Audio: opus, 48000 Hz, stereo, fltp\n",
+        "    Metadata:\n",
+        "      TITLE           : Night Drive\n",
+        "      ARTIST          : Someone\n",
+        "      DATE            : 2021\n",
+        "      TRACKNUMBER     : 07\n",
+    );
+
+    let tags = parse_ffmpeg_input_tags(description).expect("tags");
+
+    assert_eq!(tags.title.as_deref(), Some("Night Drive"));
+    assert_eq!(tags.artist.as_deref(), Some("Someone"));
+    assert_eq!(tags.year, Some(2021));
+    assert_eq!(tags.track_number, Some(7));
+}
+
+#[test]
+fn files_without_usable_tags_have_no_tags() {
+    let description = concat!(
+        "Input #0, wav, from 'take.wav':\n",
+        "  Metadata:\n",
+        "    encoder         : Lavf60.3.100\n",
+        "  Duration: 00:00:05.00, bitrate: 1411 kb/s\n",
+        "  Stream #0:0: Audio: pcm_s16le, 44100 Hz, 2 channels, s16, 1411 kb/s\n",
+    );
+
+    assert_eq!(parse_ffmpeg_input_tags(description), None);
+}
+
+#[test]
+fn chapters_of_the_first_input_are_read_with_their_titles() {
+    let description = concat!(
+        "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'book.m4b':\n",
+        "  Metadata:\n",
+        "    title           : Book\n",
+        "  Duration: 00:05:00.00, start: 0.000000, bitrate: 64 kb/s\n",
+        "  Chapters:\n",
+        "    Chapter #0:0: start 0.000000, end 120.000000\n",
+        "      Metadata:\n",
+        "        title           : Opening\n",
+        "    Chapter #0:1: start 120.000000, end 300.500000\n",
+        "    Chapter #0:2: start 300.500000, end 300.500000\n",
+        "  Stream #0:0[0x1](und): Audio: aac (LC), 44100 Hz, stereo, fltp, 63 kb/s (default)\n",
+        "    Metadata:\n",
+        "      title           : Stream title\n",
+    );
+
+    assert_eq!(
+        parse_ffmpeg_input_chapters(description),
+        vec![
+            AudioChapter {
+                title: Some("Opening".to_string()),
+                start_ms: 0,
+                end_ms: 120_000,
+            },
+            AudioChapter {
+                title: None,
+                start_ms: 120_000,
+                end_ms: 300_500,
+            },
+        ]
+    );
+}
+
+#[test]
+fn container_duration_is_read_only_for_inputs_with_audio() {
+    let audio = concat!(
+        "Input #0, mp3, from 'track.mp3':\n",
+        "  Duration: 01:03:12.35, start: 0.025057, bitrate: 256 kb/s\n",
+        "  Stream #0:0: Audio: mp3, 44100 Hz, stereo, fltp, 256 kb/s\n",
+    );
+    let unknown = concat!(
+        "Input #0, s16le, from 'raw.pcm':\n",
+        "  Duration: N/A, bitrate: 1411 kb/s\n",
+        "  Stream #0:0: Audio: pcm_s16le, 44100 Hz, stereo, s16, 1411 kb/s\n",
+    );
+    let image = concat!(
+        "Input #0, png_pipe, from 'cover.png':\n",
+        "  Duration: 00:00:00.04, bitrate: N/A\n",
+        "  Stream #0:0: Video: png, rgb24(pc), 600x600, 25 fps\n",
+    );
+
+    assert_eq!(parse_ffmpeg_input_duration_ms(audio), Some(3_792_350));
+    assert_eq!(parse_ffmpeg_input_duration_ms(unknown), None);
+    assert_eq!(parse_ffmpeg_input_duration_ms(image), None);
+}
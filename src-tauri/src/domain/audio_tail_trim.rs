@@ -72,7 +72,7 @@ const TAIL_DOMINANT_RETENTION: f32 = 0.90;
 const TAIL_ATTACHED_DURATION_QUANTILE: f32 = 0.25;
 const TAIL_ATTACHED_MIN_LINK_FRACTION: f32 = 0.70;
 const TAIL_CUT_REFINEMENT_LOOKBACK_MS: u32 = 3_000;
-const TAIL_CUT_SILENCE_THRESHOLD_DB: f32 = -42.0;
+pub(crate) const TAIL_CUT_SILENCE_THRESHOLD_DB: f32 = -42.0;
 const TAIL_CUT_RELATIVE_QUIET_DROP_DB: f32 = 8.0;
 const TAIL_CUT_POST_QUIET_GUARD_MS: u32 = 400;
 const TAIL_CUT_REENTRY_RISE_DB: f32 = 10.0;
@@ -287,7 +287,8 @@ pub(crate) struct TrackTailAttachment {
     pub(crate) duration_ms: u32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum AudioTailTrimEvidenceOrigin {
     FullCollection,
 }
@@ -385,7 +386,7 @@ pub(crate) fn collect_audio_tail_trim_candidates(
     collection: &Collection,
     save_root: &Path,
 ) -> Vec<AudioTailTrimCandidate> {
-    let collection_root = save_root.join(&collection.folder);
+    let collection_root = collection.dir(save_root);
     let mut seen_paths = HashSet::new();
     collection
         .musics
@@ -626,7 +627,10 @@ fn refined_tail_cut_ms(
     )
 }
 
-fn refine_tail_cut_to_quiet_boundary(signature: &TailEvidenceSignature, coarse_cut_ms: u32) -> u32 {
+pub(crate) fn refine_tail_cut_to_quiet_boundary(
+    signature: &TailEvidenceSignature,
+    coarse_cut_ms: u32,
+) -> u32 {
     let nearby_frames = signature
         .frames
         .iter()
@@ -1118,7 +1122,7 @@ fn quantile_u32(values: &[u32], quantile: f32) -> u32 {
     sorted[index]
 }
 
-fn quantile_f32(values: &[f32], quantile: f32) -> f32 {
+pub(crate) fn quantile_f32(values: &[f32], quantile: f32) -> f32 {
     if values.is_empty() {
         return 0.0;
     }
@@ -1825,7 +1829,7 @@ async fn process_audio_tail_trim_request(
                 source,
                 &collection.url,
                 &focus_plan,
-                &resolved.evidence,
+                &resolved,
                 "focus",
             )
             .await?;
@@ -1853,7 +1857,7 @@ async fn process_audio_tail_trim_request(
         );
         return Ok(());
     };
-    let evidence = resolved_evidence.evidence;
+    let evidence = &resolved_evidence.evidence;
 
     log::info!(
         target: AUDIO_TAIL_TRIM_LOG_TARGET,
@@ -1869,7 +1873,7 @@ async fn process_audio_tail_trim_request(
     );
 
     let plan = filter_unapplied_audio_tail_trim_plan(
-        build_audio_tail_trim_plan(&all_candidates, &all_signatures, &evidence),
+        build_audio_tail_trim_plan(&all_candidates, &all_signatures, evidence),
         &applied_trim_keys,
     );
     if plan.is_empty() {
@@ -1883,7 +1887,15 @@ async fn process_audio_tail_trim_request(
         return Ok(());
     }
 
-    apply_audio_tail_trim_plan(request, source, &collection.url, &plan, &evidence, "full").await?;
+    apply_audio_tail_trim_plan(
+        request,
+        source,
+        &collection.url,
+        &plan,
+        &resolved_evidence,
+        "full",
+    )
+    .await?;
 
     Ok(())
 }
@@ -1894,9 +1906,10 @@ async fn apply_audio_tail_trim_plan(
     source: AudioTailTrimSource,
     collection_url: &str,
     plan: &[MusicEndTrim],
-    evidence: &CommonTailEvidence,
+    resolved: &ResolvedAudioTailTrimEvidence,
     stage: &str,
 ) -> Result<()> {
+    let evidence = &resolved.evidence;
     let Some((updated, applied_plan)) =
         playlists_repo::trim_collection_music_ends_by_identity_with_applied_trims(
             collection_url,
@@ -1928,6 +1941,13 @@ async fn apply_audio_tail_trim_plan(
     notify_audio_style_training_for_trimmed_music(&updated, &request.save_root, &applied_plan);
     request_current_session_identity_updates_for_trimmed_music(&updated, &applied_plan);
     request_loudness_for_trimmed_music(&updated, &request.save_root, &applied_plan);
+    record_applied_tail_trims_in_manifest(
+        &request.save_root,
+        &updated,
+        applied_plan.clone(),
+        resolved.origin,
+    )
+    .await;
     log_applied_audio_tail_trim_tracks(&updated, &applied_plan);
 
     log::info!(
@@ -1945,6 +1965,30 @@ async fn apply_audio_tail_trim_plan(
     Ok(())
 }
 
+#[cfg(not(test))]
+async fn record_applied_tail_trims_in_manifest(
+    save_root: &Path,
+    collection: &Collection,
+    trims: Vec<MusicEndTrim>,
+    origin: AudioTailTrimEvidenceOrigin,
+) {
+    let collection_root = collection.dir(save_root);
+    let result = task::spawn_blocking(move || {
+        collection_import::record_collection_manifest_tail_trims(&collection_root, &trims, origin)
+    })
+    .await
+    .map_err(|error| anyhow!("tail trim manifest task failed: {error}"))
+    .and_then(|result| result);
+    if let Err(error) = result {
+        log::warn!(
+            target: AUDIO_TAIL_TRIM_LOG_TARGET,
+            "audio_tail_trim_manifest_record_failed collection=\"{}\" error=\"{}\"",
+            collection.url,
+            error
+        );
+    }
+}
+
 #[cfg(not(test))]
 fn notify_audio_style_training_for_trimmed_music(
     collection: &Collection,
@@ -1983,8 +2027,8 @@ fn audio_style_training_input_from_trimmed_music(
         alias: music.alias.clone(),
         canonical_music_id: music.canonical_music_id.clone(),
         url: music.url.clone(),
-        absolute_path: save_root
-            .join(&collection.folder)
+        absolute_path: collection
+            .dir(save_root)
             .join(relative_path)
             .to_string_lossy()
             .to_string(),
@@ -2113,7 +2157,7 @@ fn request_loudness_for_trimmed_music(
         loudness_evidence::request_audio_tail_trim_loudness_evidence(LoudnessEvidenceRequest {
             canonical_music_id: music.canonical_music_id.clone(),
             url: music.url.clone(),
-            file_path: save_root.join(&collection.folder).join(relative_path),
+            file_path: collection.dir(save_root).join(relative_path),
             start_ms: music.start_ms,
             end_ms: music.end_ms,
         });
@@ -74,6 +74,9 @@ fn music(group: &Group, name: &str, path: &str, start_ms: u32, end_ms: u32) -> M
         end_ms,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -85,6 +88,7 @@ fn collection_with_musics(musics: Vec<Music>) -> Collection {
         musics,
         last_updated: "now".to_string(),
         enable_updates: None,
+        save_root: None,
     }
 }
 
@@ -1,3 +1,6 @@
+use crate::domain::audio_tags::{AudioChapter, probe_audio_metadata};
+use crate::domain::audio_tail_trim::AudioTailTrimEvidenceOrigin;
+use crate::domain::cue_sheet::{cue_track_ranges, is_cue_sheet_path, read_cue_sheet};
 #[cfg(not(test))]
 use crate::domain::downloads::model::DownloadTaskStatus;
 use crate::domain::downloads::model::{
@@ -13,19 +16,24 @@ use crate::domain::downloads::service::{
     DownloadTaskChangeSignal, publish_download_task_change, try_claim_task,
 };
 use crate::domain::downloads::yt_dlp::{
-    LeafProbe, audio_duration_boundary_matches, probe_downloaded_audio_duration_ms,
+    LeafChapter, LeafProbe, audio_duration_boundary_matches, normalize_chapters,
+    probe_downloaded_audio_duration_ms,
+};
+use crate::domain::lyrics::{
+    find_lyrics_sidecar, is_lyrics_sidecar_of, is_lyrics_sidecar_path, lyrics_sidecar_relative_path,
 };
 #[cfg(not(test))]
 use crate::domain::playlist_playback::service as playlist_playback_service;
 use crate::domain::playlists::model::{
-    AudioStyleTrainingTrackInput, Collection, CollectionGroupOwner, Group, Music,
-    canonical_music_id_for_source,
+    AudioStyleTrainingTrackInput, Collection, CollectionGroupOwner, Group, LoudnessProfile, Music,
+    MusicTags, canonical_music_id_for_source,
 };
 use crate::domain::playlists::repo as collection_repo;
 #[cfg(not(test))]
 use crate::utils::binaries::{ManagedBinary, acquire_managed_binary_usage};
 use anyhow::{Context, Result, bail};
 use appdb::Id;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
@@ -33,8 +41,11 @@ use std::sync::LazyLock;
 #[cfg(not(test))]
 use tokio::sync::broadcast;
 use walkdir::WalkDir;
-const COLLECTION_MANIFEST_FILE_NAME: &str = ".slisic.collection.toml";
-const TEMP_DOWNLOAD_MARKER: &str = ".__slisic_tmp__";
+pub(crate) const COLLECTION_MANIFEST_FILE_NAME: &str = ".slisic.collection.toml";
+/// Version 2 adds loudness, tail trim and exclude evidence to manifest musics;
+/// version 1 manifests are still read.
+const COLLECTION_MANIFEST_VERSION: u32 = 2;
+pub(crate) const TEMP_DOWNLOAD_MARKER: &str = ".__slisic_tmp__";
 const LEAF_IDENTITY_DIRECTORY: &str = ".slisic.leaves";
 const LOCAL_AUDIO_PRECISE_DURATION_BOUNDARY_TOLERANCE_MS: u32 = 100;
 
@@ -146,18 +157,69 @@ struct CollectionManifestMusic {
     start_ms: u32,
     end_ms: u32,
     liked: bool,
+    #[serde(default)]
+    tags: Option<MusicTags>,
+    /// Size of the audio file when the entry was written; loudness and tail
+    /// trim evidence are only trusted while the file still has this size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    loudness_profile: Option<LoudnessProfile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tail_trim: Option<CollectionManifestTailTrim>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exclude: Option<CollectionManifestExclude>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct CollectionManifestTailTrim {
+    /// End of the music before its shared tail was trimmed away.
+    source_end_ms: u32,
+    origin: AudioTailTrimEvidenceOrigin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CollectionManifestExclude {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) playlists: Vec<String>,
+}
+
+/// Exclude recorded by a manifest for a music restored from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ManifestMusicExclude {
+    canonical_music_id: String,
+    exclude: CollectionManifestExclude,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct LocalAudioProbe {
     duration_ms: u32,
+    tags: Option<MusicTags>,
+    chapters: Vec<LeafChapter>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct LocalAudioFile {
-    absolute_path: PathBuf,
-    relative_path: String,
-    duration_ms: u32,
+pub(crate) struct LocalAudioFile {
+    pub(crate) absolute_path: PathBuf,
+    pub(crate) relative_path: String,
+    pub(crate) duration_ms: u32,
+    pub(crate) tags: Option<MusicTags>,
+    /// Tracks of a cue sheet next to the file; empty when no sheet splits it.
+    pub(crate) cue_tracks: Vec<LocalCueTrack>,
+    /// Embedded container chapters; a sheet's tracks win over them.
+    pub(crate) chapters: Vec<LeafChapter>,
+    /// `.lrc` sidecar with the same stem, relative like `relative_path`.
+    pub(crate) lyrics_path: Option<String>,
+}
+
+/// One cue sheet track, as a range of the local audio file it splits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LocalCueTrack {
+    pub(crate) start_ms: u32,
+    pub(crate) end_ms: u32,
+    pub(crate) tags: MusicTags,
 }
 
 pub(crate) async fn resolve_pasted_download_url(
@@ -206,6 +268,7 @@ pub(crate) fn create_collection_shell_from_plan(
         musics: vec![],
         last_updated: now_timestamp(),
         enable_updates: plan.enable_updates,
+        save_root: None,
     });
 
     collection.name = plan.collection_name.clone();
@@ -401,6 +464,11 @@ pub(crate) async fn persist_downloaded_leaf_music(
         group.url
     );
     let mut materialized = materialize_music_entries(probe, file_name, group);
+    attach_lyrics_sidecar(
+        &mut materialized,
+        &collection.dir(save_root).join(file_name),
+        file_name,
+    );
     let materialized_count = materialized.len();
     let training_scope = materialized_training_scope(&materialized);
     let (training_reason, training_inputs, changed) = {
@@ -500,6 +568,7 @@ pub(crate) async fn persist_downloaded_leaf_music_batch(
         leaves.len()
     );
 
+    let collection_path = collection.dir(save_root);
     let (training_reason, training_inputs, changed) = {
         let _collection_write = collection_repo::acquire_collection_write_composition_lock().await;
         let mut current = collection_repo::get_collection_by_url(&collection.url)
@@ -511,6 +580,11 @@ pub(crate) async fn persist_downloaded_leaf_music_batch(
             .map(|leaf| {
                 let mut materialized =
                     materialize_music_entries(&leaf.probe, &leaf.file_name, leaf.group.clone());
+                attach_lyrics_sidecar(
+                    &mut materialized,
+                    &collection_path.join(&leaf.file_name),
+                    &leaf.file_name,
+                );
                 inherit_existing_music_lifecycle(&mut materialized, &current.musics);
                 let group_url = materialized
                     .first()
@@ -629,6 +703,8 @@ fn music_entries_are_semantically_equal(left: &Music, right: &Music) -> bool {
         && left.end_ms == right.end_ms
         && left.liked == right.liked
         && left.loudness_profile == right.loudness_profile
+        && left.tags == right.tags
+        && left.lyrics_path == right.lyrics_path
 }
 
 pub(crate) async fn import_local_collection_folder(
@@ -648,15 +724,18 @@ pub(crate) async fn import_local_collection_folder(
     let collection_folder = collection_folder_from_local_path(save_root, &collection_path)?;
     let local_audio_files = collect_local_audio_files(&collection_path, ffmpeg_path)?;
     let manifest = read_collection_manifest(&collection_path)?;
-    let mut collection = match manifest {
+    let (mut collection, excludes) = match manifest {
         Some(manifest) => {
             collection_from_manifest(collection_folder, manifest, &local_audio_files)?
         }
-        None => collection_from_local_audio_files(
-            &collection_path,
-            &collection_folder,
-            &local_audio_files,
-        )?,
+        None => (
+            collection_from_local_audio_files(
+                &collection_path,
+                &collection_folder,
+                &local_audio_files,
+            )?,
+            Vec::new(),
+        ),
     };
 
     if collection.musics.is_empty() {
@@ -666,13 +745,53 @@ pub(crate) async fn import_local_collection_folder(
     normalize_music_titles_within_collection(&mut collection);
     collection.last_updated = now_timestamp();
     let saved = collection_repo::upsert_collection(&collection).await?;
+    restore_manifest_music_excludes(&saved, excludes).await?;
     notify_audio_style_inputs_changed("local_collection_imported");
     notify_playlist_playback_library_changed();
     Ok(saved)
 }
 
+/// Re-applies the excludes a manifest recorded to the imported occurrences.
+async fn restore_manifest_music_excludes(
+    collection: &Collection,
+    excludes: Vec<ManifestMusicExclude>,
+) -> Result<()> {
+    let mut restored_any = false;
+    for restored in excludes {
+        let Some(music) = collection
+            .musics
+            .iter()
+            .find(|music| music.canonical_music_id == restored.canonical_music_id)
+        else {
+            continue;
+        };
+        if collection_repo::get_exclude(music).await?.is_some()
+            || restored
+                .exclude
+                .expires_at
+                .as_deref()
+                .is_some_and(|expires_at| {
+                    collection_repo::exclude_expiry_passed(expires_at, Utc::now())
+                })
+        {
+            continue;
+        }
+        collection_repo::add_scoped_exclude(
+            music.clone(),
+            restored.exclude.expires_at,
+            restored.exclude.playlists,
+        )
+        .await?;
+        restored_any = true;
+    }
+    if restored_any {
+        notify_playlist_playback_exclude_changed();
+    }
+    Ok(())
+}
+
 #[cfg(not(test))]
-async fn import_local_collection_folder_with_task_signal(
+pub(crate) async fn import_local_collection_folder_with_task_signal(
     collection_path: &Path,
     save_root: &Path,
     ffmpeg_path: &Path,
@@ -839,6 +958,7 @@ pub(crate) fn project_local_collection_shell(
                 .last_updated
                 .unwrap_or_else(now_timestamp),
             enable_updates: manifest.collection.enable_updates,
+            save_root: None,
         });
     }
 
@@ -850,6 +970,7 @@ pub(crate) fn project_local_collection_shell(
         musics: vec![],
         last_updated: now_timestamp(),
         enable_updates: None,
+        save_root: None,
     })
 }
 
@@ -887,6 +1008,11 @@ pub async fn import_local_collection(
     app: tauri::AppHandle,
     collection_path: String,
 ) -> Result<Collection, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    crate::domain::save_root_migration::ensure_no_save_root_migration()
+        .await
+        .map_err(|error| error.to_string())?;
     let save_root = crate::domain::meta::service::resolve_save_root(&app)
         .await
         .map_err(|error| error.to_string())?;
@@ -912,6 +1038,11 @@ pub async fn create_local_collection_shell(
     app: tauri::AppHandle,
     collection_path: String,
 ) -> Result<Collection, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    crate::domain::save_root_migration::ensure_no_save_root_migration()
+        .await
+        .map_err(|error| error.to_string())?;
     let save_root = crate::domain::meta::service::resolve_save_root(&app)
         .await
         .map_err(|error| error.to_string())?;
@@ -934,6 +1065,8 @@ pub(crate) async fn list_auto_update_collection_urls() -> Result<Vec<String>> {
     collection_repo::list_auto_update_collection_urls().await
 }
 
+/// Existing collections keep their folder, which is relative to the save
+/// root they live on. New collections start on the default root.
 pub(crate) async fn resolve_collection_folder(
     collection_url: &str,
     collection_name: &str,
@@ -995,9 +1128,7 @@ fn resolve_downloaded_leaf_target(
     preferred_file_name: &str,
 ) -> Result<(String, PathBuf)> {
     let preferred_relative_path = relative_music_path(collection, preferred_file_name, group);
-    let preferred_path = save_root
-        .join(&collection.folder)
-        .join(&preferred_relative_path);
+    let preferred_path = collection.dir(save_root).join(&preferred_relative_path);
     if download_target_belongs_to_leaf_or_is_available(
         collection,
         leaf_url,
@@ -1015,7 +1146,7 @@ fn resolve_downloaded_leaf_target(
         .to_string_lossy()
         .to_string();
     let relative_path = relative_music_path(collection, &identity_relative_path, group);
-    let final_path = save_root.join(&collection.folder).join(&relative_path);
+    let final_path = collection.dir(save_root).join(&relative_path);
     if download_target_belongs_to_leaf_or_is_available(
         collection,
         leaf_url,
@@ -1136,12 +1267,7 @@ pub(crate) fn resolve_existing_leaf_file(
         .filter(|music| music.url == leaf_url && music.group.url == group.url)
         .filter_map(|music| music.path.as_deref().map(str::trim))
         .filter(|relative_path| !relative_path.is_empty())
-        .find(|relative_path| {
-            save_root
-                .join(&collection.folder)
-                .join(relative_path)
-                .is_file()
-        })
+        .find(|relative_path| collection.dir(save_root).join(relative_path).is_file())
         .map(str::to_string)
 }
 
@@ -1174,6 +1300,7 @@ pub(crate) fn materialize_music_entries(
 ) -> Vec<Music> {
     if probe.chapters.is_empty() {
         let name = probe.title.clone();
+        let tags = probe.tags.clone();
         return vec![Music {
             occurrence_id: String::new(),
             name: name.clone(),
@@ -1190,9 +1317,21 @@ pub(crate) fn materialize_music_entries(
             end_ms: probe_duration_ms(probe),
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags,
+            lyrics_path: None,
         }];
     }
 
+    let chapter_tags = probe.tags.clone().and_then(|tags| {
+        let tags = MusicTags {
+            title: None,
+            track_number: None,
+            ..tags
+        };
+        (!tags.is_empty()).then_some(tags)
+    });
+
     probe
         .chapters
         .iter()
@@ -1212,6 +1351,9 @@ pub(crate) fn materialize_music_entries(
             end_ms: chapter.end_ms,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: chapter_tags.clone(),
+            lyrics_path: None,
         })
         .collect()
 }
@@ -1231,10 +1373,7 @@ pub(crate) fn existing_leaf_identities(
                         .path
                         .as_deref()
                         .map(|relative_path| {
-                            save_root
-                                .join(&collection.folder)
-                                .join(relative_path)
-                                .is_file()
+                            collection.dir(save_root).join(relative_path).is_file()
                         })
                         .unwrap_or(false)
                 })
@@ -1254,12 +1393,7 @@ pub(crate) fn existing_planned_leaf_completions(
         let Some(relative_path) = music.path.as_deref().map(str::trim) else {
             continue;
         };
-        if relative_path.is_empty()
-            || !save_root
-                .join(&collection.folder)
-                .join(relative_path)
-                .is_file()
-        {
+        if relative_path.is_empty() || !collection.dir(save_root).join(relative_path).is_file() {
             continue;
         }
 
@@ -1308,7 +1442,7 @@ fn restore_download_manifest_evidence(
     save_root: &Path,
     local_duration_probe: &impl Fn(&Path) -> Result<Option<u32>>,
 ) -> Result<bool> {
-    let collection_path = save_root.join(&collection.folder);
+    let collection_path = collection.dir(save_root);
     let Some(manifest) = read_collection_manifest(&collection_path)? else {
         return Ok(false);
     };
@@ -1323,7 +1457,7 @@ fn restore_download_manifest_evidence(
         .collect::<Result<HashSet<_>>>()?;
     let local_audio_files =
         collect_manifest_audio_file_paths(&collection_path, &manifest_paths, local_duration_probe)?;
-    let restored =
+    let (restored, _) =
         collection_from_manifest(collection.folder.clone(), manifest, &local_audio_files)?;
     if restored.musics.is_empty() {
         return Ok(false);
@@ -1407,6 +1541,14 @@ pub(crate) fn normalize_music_titles_within_collection(collection: &mut Collecti
 }
 
 fn normalize_music_titles_for_group(musics: &mut [Music], indexes: &[usize]) {
+    let mut untagged_indexes = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        match music_tag_title(&musics[*index]) {
+            Some(title) => rename_music_source_title(&mut musics[*index], title),
+            None => untagged_indexes.push(*index),
+        }
+    }
+    let indexes = untagged_indexes.as_slice();
     if indexes.len() < 2 {
         return;
     }
@@ -1424,14 +1566,23 @@ fn normalize_music_titles_for_group(musics: &mut [Music], indexes: &[usize]) {
         .collect::<Vec<_>>();
     let normalized = normalize_music_title_batch_with_evidence(&titles, &evidence_titles);
     for (index, title) in indexes.iter().zip(normalized.into_iter()) {
-        if title == musics[*index].name {
-            continue;
-        }
+        rename_music_source_title(&mut musics[*index], title);
+    }
+}
 
-        let previous_name = std::mem::replace(&mut musics[*index].name, title.clone());
-        if musics[*index].alias == previous_name {
-            musics[*index].alias = title;
-        }
+fn music_tag_title(music: &Music) -> Option<String> {
+    let title = music.tags.as_ref()?.title.as_deref()?.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn rename_music_source_title(music: &mut Music, title: String) {
+    if title == music.name {
+        return;
+    }
+
+    let previous_name = std::mem::replace(&mut music.name, title.clone());
+    if music.alias == previous_name {
+        music.alias = title;
     }
 }
 
@@ -2551,6 +2702,16 @@ fn inherit_existing_music_lifecycle(musics: &mut [Music], existing_musics: &[Mus
     }
 }
 
+/// Points the musics materialized from one downloaded file at the `.lrc`
+/// sidecar next to it; a sidecar removed since the last download is dropped.
+pub(crate) fn attach_lyrics_sidecar(musics: &mut [Music], audio_path: &Path, relative_path: &str) {
+    let lyrics_path = find_lyrics_sidecar(audio_path)
+        .and_then(|lyrics_path| lyrics_sidecar_relative_path(relative_path, &lyrics_path));
+    for music in musics {
+        music.lyrics_path = lyrics_path.clone();
+    }
+}
+
 fn inherit_existing_music_lifecycle_fields(music: &mut Music, existing: &Music) {
     music.alias = existing.alias.clone();
     music.liked = existing.liked;
@@ -2564,6 +2725,10 @@ fn inherit_existing_music_lifecycle_fields(music: &mut Music, existing: &Music)
     if existing.canonical_music_id == music.canonical_music_id {
         music.loudness_profile = existing.loudness_profile;
     }
+
+    if music.tags.is_none() {
+        music.tags = existing.tags.clone();
+    }
 }
 
 fn relative_music_path(collection: &Collection, file_name: &str, group: &Group) -> String {
@@ -2597,7 +2762,7 @@ fn remove_existing_leaf_files(
             continue;
         }
 
-        let absolute_path = save_root.join(&collection.folder).join(relative_path);
+        let absolute_path = collection.dir(save_root).join(relative_path);
         if except.is_some_and(|except| except == absolute_path) {
             continue;
         }
@@ -2684,7 +2849,7 @@ pub(crate) fn restore_single_source_musics_from_task(
         else {
             continue;
         };
-        let absolute_path = save_root.join(&collection.folder).join(&relative_path);
+        let absolute_path = collection.dir(save_root).join(&relative_path);
         if !absolute_path.is_file() {
             continue;
         }
@@ -2712,6 +2877,9 @@ pub(crate) fn restore_single_source_musics_from_task(
             end_ms: leaf_duration_ms(leaf),
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         });
     }
 
@@ -2738,7 +2906,7 @@ fn recover_single_source_leaf_relative_path(
         .map(str::trim)
         .filter(|title| !title.is_empty())?;
     let file_name = format!("{}.m4a", sanitize_path_component(title));
-    let absolute_path = save_root.join(&collection.folder).join(&file_name);
+    let absolute_path = collection.dir(save_root).join(&file_name);
     absolute_path.is_file().then_some(file_name)
 }
 
@@ -2783,8 +2951,8 @@ fn audio_style_training_input_from_music(
         alias: music.alias.clone(),
         canonical_music_id: music.canonical_music_id.clone(),
         url: music.url.clone(),
-        absolute_path: save_root
-            .join(&collection.folder)
+        absolute_path: collection
+            .dir(save_root)
             .join(relative_path)
             .to_string_lossy()
             .to_string(),
@@ -2846,6 +3014,14 @@ fn notify_playlist_playback_library_changed() {
     playlist_playback_service::notify_playable_library_changed();
 }
 
+fn notify_playlist_playback_exclude_changed() {
+    #[cfg(not(test))]
+    {
+        crate::domain::playlist_playback::playable_index::notify_exclude_changed();
+        crate::domain::playlists::exclude_expiry::notify_exclude_expiry_changed();
+    }
+}
+
 fn seconds_to_millis(seconds: u32) -> u32 {
     seconds.saturating_mul(1_000)
 }
@@ -2876,15 +3052,25 @@ fn read_collection_manifest(collection_path: &Path) -> Result<Option<CollectionM
 }
 
 fn read_collection_manifest_file(manifest_path: &Path) -> Result<Option<CollectionManifest>> {
+    Ok(read_collection_manifest_file_snapshot(manifest_path)?.map(|(_, manifest)| manifest))
+}
+
+/// Reads a manifest together with the bytes it was parsed from, so a rewrite
+/// can tell whether another writer replaced it in between.
+fn read_collection_manifest_file_snapshot(
+    manifest_path: &Path,
+) -> Result<Option<(Vec<u8>, CollectionManifest)>> {
     if !manifest_path.is_file() {
         return Ok(None);
     }
 
-    let text = std::fs::read_to_string(&manifest_path)
+    let bytes = std::fs::read(manifest_path)
+        .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+    let text = std::str::from_utf8(&bytes)
         .with_context(|| format!("failed to read {}", manifest_path.display()))?;
-    let manifest = toml::from_str::<CollectionManifest>(&text)
+    let manifest = toml::from_str::<CollectionManifest>(text)
         .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
-    if manifest.version != 1 {
+    if !(1..=COLLECTION_MANIFEST_VERSION).contains(&manifest.version) {
         bail!(
             "unsupported collection manifest version {} in {}",
             manifest.version,
@@ -2892,14 +3078,26 @@ fn read_collection_manifest_file(manifest_path: &Path) -> Result<Option<Collecti
         );
     }
 
-    Ok(Some(manifest))
+    Ok(Some((bytes, manifest)))
 }
 
+/**
+ * Behavior:
+ *   Restore a collection from its manifest and the audio files found next to
+ *   it, together with the excludes the manifest recorded for its musics.
+ *
+ * Core invariants:
+ *   - Loudness and tail trim evidence is only restored while the audio file
+ *     still has the size recorded with it; otherwise the music is restored
+ *     as a version 1 manifest would restore it.
+ *   - A trimmed end is only restored when the end it was trimmed from still
+ *     targets the end of the local file.
+ */
 fn collection_from_manifest(
     collection_folder: String,
     manifest: CollectionManifest,
     local_audio_files: &[LocalAudioFile],
-) -> Result<Collection> {
+) -> Result<(Collection, Vec<ManifestMusicExclude>)> {
     let local_files_by_path = local_audio_files
         .iter()
         .map(|file| (file.relative_path.clone(), file))
@@ -2915,6 +3113,7 @@ fn collection_from_manifest(
             .clone()
             .unwrap_or_else(now_timestamp),
         enable_updates: manifest.collection.enable_updates,
+        save_root: None,
     };
     let collection_owner = collection_owner_group(&collection_shell);
     let groups = manifest
@@ -2940,6 +3139,7 @@ fn collection_from_manifest(
     let mut musics = Vec::new();
     let mut seen = HashSet::new();
     let mut manifest_file_paths = HashSet::new();
+    let mut excludes = Vec::new();
 
     for music in manifest.musics {
         let relative_path = normalize_manifest_relative_path(&music.path)?;
@@ -2949,7 +3149,9 @@ fn collection_from_manifest(
         if music.end_ms > local_file.duration_ms.saturating_add(1_000) {
             continue;
         }
-        let end_ms = restore_manifest_music_end_ms_from_local_file(&music, local_file);
+        let evidence_trusted = manifest_music_evidence_matches_local_file(&music, local_file);
+        let end_ms =
+            restore_manifest_music_end_ms_from_local_file(&music, local_file, evidence_trusted);
         if !seen.insert((
             music.url.clone(),
             music.group_url.clone(),
@@ -2969,9 +3171,10 @@ fn collection_from_manifest(
         let Some(group) = group else {
             continue;
         };
+        let tags = music.tags.clone().or_else(|| local_file.tags.clone());
         let name = music.name.trim();
         let name = if name.is_empty() {
-            local_music_name_from_path(Path::new(&relative_path))
+            local_music_name(Path::new(&relative_path), tags.as_ref())
         } else {
             name.to_string()
         };
@@ -2987,18 +3190,28 @@ fn collection_from_manifest(
         }
 
         manifest_file_paths.insert(relative_path.clone());
+        let canonical_music_id = canonical_music_id_for_source(&music.url, music.start_ms, end_ms);
+        if let Some(exclude) = music.exclude {
+            excludes.push(ManifestMusicExclude {
+                canonical_music_id: canonical_music_id.clone(),
+                exclude,
+            });
+        }
         musics.push(Music {
             occurrence_id: String::new(),
             name,
             alias,
             group,
-            canonical_music_id: canonical_music_id_for_source(&music.url, music.start_ms, end_ms),
+            canonical_music_id,
             url: music.url,
             path: Some(relative_path),
             start_ms: music.start_ms,
             end_ms,
             liked: music.liked,
-            loudness_profile: None,
+            loudness_profile: music.loudness_profile.filter(|_| evidence_trusted),
+            added_at: None,
+            tags,
+            lyrics_path: local_file.lyrics_path.clone(),
         });
     }
 
@@ -3007,26 +3220,31 @@ fn collection_from_manifest(
             continue;
         }
 
-        let music = local_music_from_audio_file(&collection_url, &collection_owner, local_file);
-        if seen.insert((
-            music.url.clone(),
-            music.group.url.clone(),
-            music.start_ms,
-            music.end_ms,
-            local_file.relative_path.clone(),
-        )) {
-            musics.push(music);
+        for music in local_musics_from_audio_file(&collection_url, &collection_owner, local_file) {
+            if seen.insert((
+                music.url.clone(),
+                music.group.url.clone(),
+                music.start_ms,
+                music.end_ms,
+                local_file.relative_path.clone(),
+            )) {
+                musics.push(music);
+            }
         }
     }
 
-    Ok(Collection {
-        name: collection_name,
-        url: collection_url,
-        folder: collection_folder.clone(),
-        musics,
-        last_updated: collection_last_updated,
-        enable_updates: collection_enable_updates,
-    })
+    Ok((
+        Collection {
+            name: collection_name,
+            url: collection_url,
+            folder: collection_folder.clone(),
+            musics,
+            last_updated: collection_last_updated,
+            enable_updates: collection_enable_updates,
+            save_root: None,
+        },
+        excludes,
+    ))
 }
 
 fn collection_from_local_audio_files(
@@ -3043,12 +3261,13 @@ fn collection_from_local_audio_files(
         musics: vec![],
         last_updated: now_timestamp(),
         enable_updates: None,
+        save_root: None,
     };
     let group = collection_owner_group(&collection_shell);
     let mut musics = Vec::new();
 
     for file in local_audio_files {
-        musics.push(local_music_from_audio_file(&collection_url, &group, file));
+        musics.extend(local_musics_from_audio_file(&collection_url, &group, file));
     }
 
     Ok(Collection {
@@ -3058,6 +3277,7 @@ fn collection_from_local_audio_files(
         musics,
         last_updated: collection_shell.last_updated,
         enable_updates: None,
+        save_root: None,
     })
 }
 
@@ -3071,12 +3291,22 @@ fn local_collection_name(collection_path: &Path) -> String {
         .to_string()
 }
 
-fn collect_local_audio_files(
+pub(crate) fn collect_local_audio_files(
     collection_path: &Path,
     ffmpeg_path: &Path,
 ) -> Result<Vec<LocalAudioFile>> {
     let mut files = Vec::new();
+    let mut cue_sheet_paths = Vec::new();
+    let mut lyrics_paths = Vec::new();
     for file_path in local_collection_file_candidates(collection_path) {
+        if is_cue_sheet_path(&file_path) {
+            cue_sheet_paths.push(file_path);
+            continue;
+        }
+        if is_lyrics_sidecar_path(&file_path) {
+            lyrics_paths.push(file_path);
+            continue;
+        }
         let relative_path = normalize_local_relative_path(collection_path, &file_path)?;
         let Some(probe) = probe_local_audio_file(ffmpeg_path, &file_path)? else {
             continue;
@@ -3089,12 +3319,97 @@ fn collect_local_audio_files(
             absolute_path: file_path,
             relative_path,
             duration_ms: probe.duration_ms,
+            tags: probe.tags,
+            cue_tracks: Vec::new(),
+            chapters: probe.chapters,
+            lyrics_path: None,
         });
     }
 
+    for cue_sheet_path in cue_sheet_paths {
+        attach_cue_sheet_tracks(&mut files, &cue_sheet_path);
+    }
+    for file in &mut files {
+        file.lyrics_path = lyrics_paths
+            .iter()
+            .find(|lyrics_path| is_lyrics_sidecar_of(&file.absolute_path, lyrics_path))
+            .map(|lyrics_path| normalize_local_relative_path(collection_path, lyrics_path))
+            .transpose()?;
+    }
     Ok(files)
 }
 
+/**
+ * Behavior:
+ *   Split the audio files a cue sheet points at into the tracks it lists.
+ *
+ * Core invariants:
+ *   - A sheet file matches the audio file with that name next to the sheet,
+ *     or else the only one with the same stem, since rippers often convert
+ *     the image after writing the sheet (`FILE "album.wav"` beside
+ *     `album.flac`).
+ *   - Unreadable sheets, and sheets without usable track starts, leave the
+ *     file as one music.
+ *   - Track tags fall back to the album performer and the file's own tags.
+ */
+fn attach_cue_sheet_tracks(files: &mut [LocalAudioFile], cue_sheet_path: &Path) {
+    let sheet = match read_cue_sheet(cue_sheet_path) {
+        Ok(sheet) => sheet,
+        Err(error) => {
+            log::warn!(
+                target: "collection_import",
+                "cue_sheet_skipped path=\"{}\" error=\"{}\"",
+                cue_sheet_path.display(),
+                error
+            );
+            return;
+        }
+    };
+    let sheet_dir = cue_sheet_path.parent().unwrap_or(Path::new(""));
+
+    for sheet_file in &sheet.files {
+        let target = sheet_dir.join(&sheet_file.name);
+        let target_stem = target.file_stem();
+        let Some(file) = files
+            .iter()
+            .position(|file| file.absolute_path == target)
+            .or_else(|| {
+                let mut same_stem = files.iter().enumerate().filter(|(_, file)| {
+                    file.absolute_path.parent() == Some(sheet_dir)
+                        && file.absolute_path.file_stem() == target_stem
+                });
+                match (same_stem.next(), same_stem.next()) {
+                    (Some((index, _)), None) => Some(index),
+                    _ => None,
+                }
+            })
+            .map(|index| &mut files[index])
+        else {
+            continue;
+        };
+
+        let file_tags = file.tags.clone().unwrap_or_default();
+        file.cue_tracks = cue_track_ranges(sheet_file, file.duration_ms)
+            .into_iter()
+            .map(|track| LocalCueTrack {
+                start_ms: track.start_ms,
+                end_ms: track.end_ms,
+                tags: MusicTags {
+                    title: track.title,
+                    artist: track
+                        .performer
+                        .or_else(|| sheet.performer.clone())
+                        .or_else(|| file_tags.artist.clone()),
+                    album: sheet.title.clone().or_else(|| file_tags.album.clone()),
+                    genre: sheet.genre.clone().or_else(|| file_tags.genre.clone()),
+                    year: sheet.year.or(file_tags.year),
+                    track_number: Some(track.number),
+                },
+            })
+            .collect();
+    }
+}
+
 #[cfg(test)]
 fn collect_manifest_audio_file_paths(
     collection_path: &Path,
@@ -3116,20 +3431,50 @@ fn collect_manifest_audio_file_paths(
             absolute_path: file_path,
             relative_path,
             duration_ms,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
         });
     }
 
     Ok(files)
 }
 
+fn manifest_music_evidence_matches_local_file(
+    music: &CollectionManifestMusic,
+    local_file: &LocalAudioFile,
+) -> bool {
+    music.file_size.is_some_and(|file_size| {
+        std::fs::metadata(&local_file.absolute_path)
+            .is_ok_and(|metadata| metadata.len() == file_size)
+    })
+}
+
 fn restore_manifest_music_end_ms_from_local_file(
     music: &CollectionManifestMusic,
     local_file: &LocalAudioFile,
+    evidence_trusted: bool,
 ) -> u32 {
-    if manifest_music_end_ms_targets_local_file_boundary(music.end_ms, local_file.duration_ms) {
+    if let Some(tail_trim) = music.tail_trim
+        && evidence_trusted
+        && music.end_ms < tail_trim.source_end_ms
+        && manifest_music_end_ms_targets_local_file_boundary(
+            tail_trim.source_end_ms,
+            local_file.duration_ms,
+        )
+    {
+        return music.end_ms;
+    }
+
+    // Untrusted trims fall back to the untrimmed end, so the tail is trimmed anew.
+    let end_ms = music
+        .tail_trim
+        .map_or(music.end_ms, |tail_trim| tail_trim.source_end_ms);
+    if manifest_music_end_ms_targets_local_file_boundary(end_ms, local_file.duration_ms) {
         local_file.duration_ms
     } else {
-        music.end_ms
+        end_ms
     }
 }
 
@@ -3145,7 +3490,7 @@ fn manifest_music_end_ms_targets_local_file_boundary(end_ms: u32, duration_ms: u
     end_ms.abs_diff(duration_ms) <= LOCAL_AUDIO_PRECISE_DURATION_BOUNDARY_TOLERANCE_MS
 }
 
-fn local_collection_file_candidates(collection_path: &Path) -> Vec<PathBuf> {
+pub(crate) fn local_collection_file_candidates(collection_path: &Path) -> Vec<PathBuf> {
     let mut files = WalkDir::new(collection_path)
         .into_iter()
         .filter_map(|entry| entry.ok())
@@ -3164,9 +3509,56 @@ fn local_collection_file_candidates(collection_path: &Path) -> Vec<PathBuf> {
     files
 }
 
+/**
+ * Behavior:
+ *   Read the duration, tags and embedded chapters of one local file from a
+ *   single FFmpeg input probe.
+ *
+ * Core invariants:
+ *   - Files FFmpeg cannot read as audio yield `None` and are not imported.
+ *   - Only inputs whose container reports no duration are decoded to
+ *     measure it.
+ */
 fn probe_local_audio_file(ffmpeg_path: &Path, file_path: &Path) -> Result<Option<LocalAudioProbe>> {
-    Ok(probe_downloaded_audio_duration_ms(ffmpeg_path, file_path)?
-        .map(|duration_ms| LocalAudioProbe { duration_ms }))
+    let metadata = probe_audio_metadata(ffmpeg_path, file_path)?;
+    let duration_ms = match metadata.duration_ms {
+        Some(duration_ms) => duration_ms,
+        None => match probe_downloaded_audio_duration_ms(ffmpeg_path, file_path)? {
+            Some(duration_ms) => duration_ms,
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(LocalAudioProbe {
+        duration_ms,
+        chapters: local_audio_chapters(metadata.chapters, duration_ms),
+        tags: metadata.tags,
+    }))
+}
+
+/**
+ * Behavior:
+ *   Turn embedded chapters into the chapter shape downloaded leaves split by.
+ *
+ * Core invariants:
+ *   - Chapters are cut to the measured file duration; chapters starting past
+ *     it are dropped.
+ *   - A single chapter spanning the file does not split it, as for yt-dlp.
+ *   - Untitled chapters are named by their position.
+ */
+fn local_audio_chapters(chapters: Vec<AudioChapter>, duration_ms: u32) -> Vec<LeafChapter> {
+    let chapters = chapters
+        .into_iter()
+        .filter(|chapter| chapter.start_ms < duration_ms)
+        .enumerate()
+        .map(|(index, chapter)| LeafChapter {
+            title: chapter
+                .title
+                .unwrap_or_else(|| format!("Chapter {:02}", index + 1)),
+            start_ms: chapter.start_ms,
+            end_ms: chapter.end_ms.min(duration_ms),
+        })
+        .collect();
+    normalize_chapters("", Some(duration_ms), chapters)
 }
 
 fn local_collection_url(collection_path: &Path) -> Result<String> {
@@ -3180,6 +3572,14 @@ fn local_music_url(collection_url: &str, relative_path: &str) -> String {
     format!("{collection_url}#{}", relative_path.replace('\\', "/"))
 }
 
+fn local_music_name(path: &Path, tags: Option<&MusicTags>) -> String {
+    tags.and_then(|tags| tags.title.as_deref())
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| local_music_name_from_path(path))
+}
+
 fn local_music_name_from_path(path: &Path) -> String {
     path.file_stem()
         .and_then(|value| value.to_str())
@@ -3199,7 +3599,7 @@ fn local_music_from_audio_file(
     group: &Group,
     file: &LocalAudioFile,
 ) -> Music {
-    let name = local_music_name_from_path(&file.absolute_path);
+    let name = local_music_name(&file.absolute_path, file.tags.as_ref());
     let url = local_music_url(collection_url, &file.relative_path);
     Music {
         occurrence_id: String::new(),
@@ -3213,7 +3613,71 @@ fn local_music_from_audio_file(
         end_ms: file.duration_ms,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: file.tags.clone(),
+        lyrics_path: file.lyrics_path.clone(),
+    }
+}
+
+/// Musics of one local file: one per cue sheet track or embedded chapter, or
+/// the whole file.
+fn local_musics_from_audio_file(
+    collection_url: &str,
+    group: &Group,
+    file: &LocalAudioFile,
+) -> Vec<Music> {
+    if file.cue_tracks.is_empty() && file.chapters.is_empty() {
+        return vec![local_music_from_audio_file(collection_url, group, file)];
+    }
+
+    let url = local_music_url(collection_url, &file.relative_path);
+    if file.cue_tracks.is_empty() {
+        let probe = LeafProbe {
+            title: local_music_name(&file.absolute_path, file.tags.as_ref()),
+            webpage_url: url,
+            extractor_key: None,
+            album: file.tags.as_ref().and_then(|tags| tags.album.clone()),
+            duration_ms: Some(file.duration_ms),
+            duration_seconds: Some(file.duration_ms.div_ceil(1_000)),
+            chapters: file.chapters.clone(),
+            tags: file.tags.clone(),
+        };
+        let mut musics = materialize_music_entries(&probe, &file.relative_path, group.clone());
+        for music in &mut musics {
+            music.lyrics_path = file.lyrics_path.clone();
+        }
+        return musics;
     }
+
+    file.cue_tracks
+        .iter()
+        .map(|track| {
+            let name =
+                track.tags.title.clone().unwrap_or_else(|| {
+                    format!("Track {:02}", track.tags.track_number.unwrap_or(0))
+                });
+            Music {
+                occurrence_id: String::new(),
+                name: name.clone(),
+                alias: name,
+                group: group.clone(),
+                canonical_music_id: canonical_music_id_for_source(
+                    &url,
+                    track.start_ms,
+                    track.end_ms,
+                ),
+                url: url.clone(),
+                path: Some(file.relative_path.clone()),
+                start_ms: track.start_ms,
+                end_ms: track.end_ms,
+                liked: false,
+                loudness_profile: None,
+                added_at: None,
+                tags: Some(track.tags.clone()),
+                lyrics_path: file.lyrics_path.clone(),
+            }
+        })
+        .collect()
 }
 
 fn manifest_from_raw_leaf_evidence(
@@ -3237,7 +3701,7 @@ fn manifest_from_raw_leaf_evidence(
         .collect();
 
     CollectionManifest {
-        version: 1,
+        version: COLLECTION_MANIFEST_VERSION,
         collection: CollectionManifestCollection {
             name: collection.name.clone(),
             url: collection.url.clone(),
@@ -3276,7 +3740,7 @@ fn manifest_from_raw_leaf_evidence_batch(
     }
 
     CollectionManifest {
-        version: 1,
+        version: COLLECTION_MANIFEST_VERSION,
         collection: CollectionManifestCollection {
             name: collection.name.clone(),
             url: collection.url.clone(),
@@ -3300,6 +3764,21 @@ fn collection_manifest_music_from_music(music: Music) -> CollectionManifestMusic
         start_ms: music.start_ms,
         end_ms: music.end_ms,
         liked: music.liked,
+        tags: music.tags,
+        file_size: None,
+        loudness_profile: music.loudness_profile,
+        tail_trim: None,
+        exclude: None,
+    }
+}
+
+fn record_manifest_music_file_sizes(manifest: &mut CollectionManifest, collection_root: &Path) {
+    for music in &mut manifest.musics {
+        music.file_size = normalize_manifest_relative_path(&music.path)
+            .ok()
+            .and_then(|path| std::fs::metadata(collection_root.join(path)).ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len());
     }
 }
 
@@ -3326,14 +3805,378 @@ fn write_raw_leaf_manifest_file(
     std::fs::create_dir_all(collection_root)
         .with_context(|| format!("failed to create {}", collection_root.display()))?;
     let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
-    let manifest = match read_collection_manifest_file(&manifest_path)? {
-        Some(existing) => merge_raw_leaf_manifest_evidence(existing, manifest.clone()),
-        None => manifest.clone(),
+    let previous = read_collection_manifest_file_snapshot(&manifest_path)?;
+    let (previous, mut manifest) = match previous {
+        Some((bytes, existing)) => (
+            Some(bytes),
+            merge_raw_leaf_manifest_evidence(existing, manifest.clone()),
+        ),
+        None => (None, manifest.clone()),
+    };
+    record_manifest_music_file_sizes(&mut manifest, collection_root);
+    replace_collection_manifest_file_or_fail(&manifest_path, previous.as_deref(), &manifest)
+}
+
+/**
+ * Behavior:
+ *   Rewrite the group list of an existing collection manifest and point each
+ *   manifest music at the group its stored occurrence belongs to now.
+ *
+ * Core invariants:
+ *   - Collections without a manifest are left without one.
+ *   - Manifest musics are matched by source url, range and file path; entries
+ *     without a stored occurrence keep their group.
+ */
+pub(crate) fn sync_collection_manifest_groups(
+    collection_root: &Path,
+    collection: &Collection,
+    groups: &[Group],
+) -> Result<()> {
+    let _guard = RAW_LEAF_MANIFEST_EVIDENCE_LOCK
+        .lock()
+        .map_err(|_| anyhow::anyhow!("raw leaf manifest evidence lock poisoned"))?;
+    let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
+    let Some((previous, mut manifest)) = read_collection_manifest_file_snapshot(&manifest_path)?
+    else {
+        return Ok(());
     };
+
+    manifest.groups = groups
+        .iter()
+        .filter(|group| group.url != collection.url)
+        .map(|group| CollectionManifestGroup {
+            name: group.name.clone(),
+            url: group.url.clone(),
+            folder: group.folder.clone(),
+        })
+        .collect();
+    let group_urls = collection
+        .musics
+        .iter()
+        .map(|music| {
+            (
+                (
+                    music.url.as_str(),
+                    music.start_ms,
+                    music.end_ms,
+                    normalize_path_text(music.path.as_deref().unwrap_or_default()),
+                ),
+                music.group.url.as_str(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    for music in &mut manifest.musics {
+        let key = (
+            music.url.as_str(),
+            music.start_ms,
+            music.end_ms,
+            normalize_path_text(&music.path),
+        );
+        if let Some(group_url) = group_urls.get(&key) {
+            music.group_url = group_url.to_string();
+        }
+    }
+
+    replace_collection_manifest_file_or_fail(&manifest_path, Some(&previous), &manifest)
+}
+
+pub(crate) fn has_collection_manifest(collection_root: &Path) -> bool {
+    collection_root
+        .join(COLLECTION_MANIFEST_FILE_NAME)
+        .is_file()
+}
+
+pub(crate) fn remove_collection_manifest(collection_root: &Path) -> Result<()> {
+    let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
+    if manifest_path.is_file() {
+        std::fs::remove_file(&manifest_path)
+            .with_context(|| format!("failed to remove {}", manifest_path.display()))?;
+    }
+    Ok(())
+}
+
+/**
+ * Behavior:
+ *   Rebuild a collection manifest from the stored collection and its groups,
+ *   for edits that change the collection folder layout as a whole.
+ *
+ * Core invariants:
+ *   - The source kind, tail trims and excludes recorded by the replaced
+ *     manifest are kept.
+ *   - Musics without a path below the collection folder are not recorded,
+ *     because manifests only restore files relative to it.
+ */
+pub(crate) fn rewrite_collection_manifest(
+    collection_root: &Path,
+    collection: &Collection,
+    groups: &[Group],
+) -> Result<()> {
+    let _guard = RAW_LEAF_MANIFEST_EVIDENCE_LOCK
+        .lock()
+        .map_err(|_| anyhow::anyhow!("raw leaf manifest evidence lock poisoned"))?;
+    std::fs::create_dir_all(collection_root)
+        .with_context(|| format!("failed to create {}", collection_root.display()))?;
+    let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
+    let previous = read_collection_manifest_file_snapshot(&manifest_path)?;
+
+    let manifest = build_collection_manifest(
+        collection_root,
+        collection,
+        groups,
+        previous.as_ref().map(|(_, manifest)| manifest),
+        None,
+    );
+    replace_collection_manifest_file_or_fail(
+        &manifest_path,
+        previous.as_ref().map(|(bytes, _)| bytes.as_slice()),
+        &manifest,
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CollectionManifestSync {
+    Written,
+    Unchanged,
+    /// The manifest changed on disk while it was rebuilt; nothing was written.
+    Conflict,
+}
+
+/**
+ * Behavior:
+ *   Bring the manifest of an existing collection folder in line with the
+ *   stored collection after its music changed.
+ *
+ * Core invariants:
+ *   - The new manifest is written to a temporary file and renamed over the
+ *     old one, so readers never see a half written manifest.
+ *   - When another writer replaced the manifest after it was read, the
+ *     rebuilt one is discarded and `Conflict` is returned.
+ *   - A manifest that would not change is not rewritten.
+ *   - `excludes`, keyed by canonical music id, replace the exclude state of
+ *     the previous manifest; its tail trims are kept for unchanged ranges.
+ */
+pub(crate) fn sync_collection_manifest(
+    collection_root: &Path,
+    collection: &Collection,
+    groups: &[Group],
+    excludes: &HashMap<String, CollectionManifestExclude>,
+) -> Result<CollectionManifestSync> {
+    let _guard = RAW_LEAF_MANIFEST_EVIDENCE_LOCK
+        .lock()
+        .map_err(|_| anyhow::anyhow!("raw leaf manifest evidence lock poisoned"))?;
+    let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
+    let previous = read_optional_manifest_bytes(&manifest_path)?;
+    let previous_manifest = previous
+        .as_deref()
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .and_then(|text| toml::from_str::<CollectionManifest>(text).ok());
+
+    let manifest = build_collection_manifest(
+        collection_root,
+        collection,
+        groups,
+        previous_manifest.as_ref(),
+        Some(excludes),
+    );
+    replace_collection_manifest_file(&manifest_path, previous.as_deref(), &manifest)
+}
+
+/**
+ * Behavior:
+ *   Replace a collection manifest through a staged file renamed over it,
+ *   unless it changed on disk since `previous` was read.
+ *
+ * Core invariants:
+ *   - Readers see the old or the new manifest, never a half written one.
+ *   - The manifest on disk is compared with `previous` after staging, right
+ *     before the rename; on a mismatch the staged file is removed and
+ *     `Conflict` is returned.
+ *   - A manifest that would not change is not rewritten.
+ */
+fn replace_collection_manifest_file(
+    manifest_path: &Path,
+    previous: Option<&[u8]>,
+    manifest: &CollectionManifest,
+) -> Result<CollectionManifestSync> {
     let text =
-        toml::to_string_pretty(&manifest).context("failed to serialize collection manifest")?;
-    std::fs::write(&manifest_path, text)
-        .with_context(|| format!("failed to write {}", manifest_path.display()))
+        toml::to_string_pretty(manifest).context("failed to serialize collection manifest")?;
+    if previous == Some(text.as_bytes()) {
+        return Ok(CollectionManifestSync::Unchanged);
+    }
+
+    let staged_path = manifest_path.with_extension("toml.partial");
+    std::fs::write(&staged_path, &text)
+        .with_context(|| format!("failed to write {}", staged_path.display()))?;
+    if read_optional_manifest_bytes(manifest_path)?.as_deref() != previous {
+        let _ = std::fs::remove_file(&staged_path);
+        return Ok(CollectionManifestSync::Conflict);
+    }
+    std::fs::rename(&staged_path, manifest_path)
+        .with_context(|| format!("failed to replace {}", manifest_path.display()))?;
+    Ok(CollectionManifestSync::Written)
+}
+
+/// Replaces a manifest with `contents` through a staged file, so a reader
+/// never sees it half written.
+pub(crate) fn write_collection_manifest_bytes(manifest_path: &Path, contents: &[u8]) -> Result<()> {
+    let staged_path = manifest_path.with_extension("toml.partial");
+    std::fs::write(&staged_path, contents)
+        .with_context(|| format!("failed to write {}", staged_path.display()))?;
+    std::fs::rename(&staged_path, manifest_path).with_context(|| {
+        let _ = std::fs::remove_file(&staged_path);
+        format!("failed to replace {}", manifest_path.display())
+    })
+}
+
+/// Like [`replace_collection_manifest_file`], for writers that have no retry
+/// of their own: a concurrent change is reported as an error.
+fn replace_collection_manifest_file_or_fail(
+    manifest_path: &Path,
+    previous: Option<&[u8]>,
+    manifest: &CollectionManifest,
+) -> Result<()> {
+    match replace_collection_manifest_file(manifest_path, previous, manifest)? {
+        CollectionManifestSync::Written | CollectionManifestSync::Unchanged => Ok(()),
+        CollectionManifestSync::Conflict => bail!(
+            "{} changed on disk while it was rewritten",
+            manifest_path.display()
+        ),
+    }
+}
+
+/**
+ * Behavior:
+ *   Record applied tail trims in the collection manifest, so importing the
+ *   folder again restores the trimmed ends instead of trimming anew.
+ *
+ * Core invariants:
+ *   - Collections without a manifest are left without one.
+ *   - A music trimmed more than once keeps the end it had before its first
+ *     trim as the evidence source.
+ */
+pub(crate) fn record_collection_manifest_tail_trims(
+    collection_root: &Path,
+    trims: &[collection_repo::MusicEndTrim],
+    origin: AudioTailTrimEvidenceOrigin,
+) -> Result<()> {
+    let _guard = RAW_LEAF_MANIFEST_EVIDENCE_LOCK
+        .lock()
+        .map_err(|_| anyhow::anyhow!("raw leaf manifest evidence lock poisoned"))?;
+    let manifest_path = collection_root.join(COLLECTION_MANIFEST_FILE_NAME);
+    let Some((previous, mut manifest)) = read_collection_manifest_file_snapshot(&manifest_path)?
+    else {
+        return Ok(());
+    };
+
+    let mut changed = false;
+    for music in &mut manifest.musics {
+        let Some(trim) = trims.iter().find(|trim| {
+            trim.url == music.url
+                && trim.start_ms == music.start_ms
+                && (trim.end_ms == music.end_ms
+                    || (trim.next_end_ms == music.end_ms && music.tail_trim.is_none()))
+        }) else {
+            continue;
+        };
+        let source_end_ms = music
+            .tail_trim
+            .map(|tail_trim| tail_trim.source_end_ms)
+            .unwrap_or(trim.end_ms);
+        music.end_ms = trim.next_end_ms;
+        music.tail_trim = Some(CollectionManifestTailTrim {
+            source_end_ms,
+            origin,
+        });
+        changed = true;
+    }
+    if !changed {
+        return Ok(());
+    }
+
+    manifest.version = COLLECTION_MANIFEST_VERSION;
+    record_manifest_music_file_sizes(&mut manifest, collection_root);
+    replace_collection_manifest_file_or_fail(&manifest_path, Some(&previous), &manifest)
+}
+
+fn read_optional_manifest_bytes(manifest_path: &Path) -> Result<Option<Vec<u8>>> {
+    match std::fs::read(manifest_path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => {
+            Err(error).with_context(|| format!("failed to read {}", manifest_path.display()))
+        }
+    }
+}
+
+fn build_collection_manifest(
+    collection_root: &Path,
+    collection: &Collection,
+    groups: &[Group],
+    previous: Option<&CollectionManifest>,
+    excludes: Option<&HashMap<String, CollectionManifestExclude>>,
+) -> CollectionManifest {
+    let source_kind = previous.and_then(|manifest| manifest.collection.source_kind);
+    let previous_musics = previous
+        .map(|manifest| {
+            manifest
+                .musics
+                .iter()
+                .map(|music| (collection_manifest_music_range_key(music), music))
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+    let mut manifest = CollectionManifest {
+        version: COLLECTION_MANIFEST_VERSION,
+        collection: CollectionManifestCollection {
+            name: collection.name.clone(),
+            url: collection.url.clone(),
+            folder: collection.folder.clone(),
+            source_kind,
+            enable_updates: collection.enable_updates,
+            last_updated: Some(collection.last_updated.clone()),
+        },
+        groups: groups
+            .iter()
+            .filter(|group| group.url != collection.url)
+            .map(|group| CollectionManifestGroup {
+                name: group.name.clone(),
+                url: group.url.clone(),
+                folder: group.folder.clone(),
+            })
+            .collect(),
+        musics: collection
+            .musics
+            .iter()
+            .filter(|music| {
+                music
+                    .path
+                    .as_deref()
+                    .is_some_and(|path| !path.is_empty() && Path::new(path).is_relative())
+            })
+            .map(|music| {
+                let mut entry = collection_manifest_music_from_music(music.clone());
+                let previous = previous_musics.get(&collection_manifest_music_range_key(&entry));
+                entry.tail_trim = previous.and_then(|previous| previous.tail_trim);
+                entry.exclude = match excludes {
+                    Some(excludes) => excludes.get(&music.canonical_music_id).cloned(),
+                    None => previous.and_then(|previous| previous.exclude.clone()),
+                };
+                entry
+            })
+            .collect(),
+    };
+    record_manifest_music_file_sizes(&mut manifest, collection_root);
+    manifest
+}
+
+fn collection_manifest_music_range_key(music: &CollectionManifestMusic) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        music.url,
+        normalize_path_text(&music.path),
+        music.start_ms,
+        music.end_ms
+    )
 }
 
 fn merge_raw_leaf_manifest_evidence(
@@ -3360,7 +4203,7 @@ fn merge_raw_leaf_manifest_evidence(
         .filter(|music| !next_file_scopes.contains(&collection_manifest_music_file_scope(music)));
 
     let mut merged = CollectionManifest {
-        version,
+        version: version.max(next.version),
         collection,
         groups,
         musics: retained_musics.collect(),
@@ -1,17 +1,22 @@
 use super::{
-    CollectionManifest, CollectionManifestCollection, CollectionManifestGroup,
-    CollectionManifestMusic, LocalAudioFile, collection_folder_from_local_path,
-    collection_from_manifest, finalize_downloaded_leaf, manifest_from_raw_leaf_evidence,
-    merge_raw_leaf_manifest_evidence, normalize_manifest_relative_path,
-    normalize_music_title_batch, normalize_music_titles_within_collection,
-    project_local_collection_shell,
+    CollectionManifest, CollectionManifestCollection, CollectionManifestExclude,
+    CollectionManifestGroup, CollectionManifestMusic, CollectionManifestTailTrim, LocalAudioFile,
+    attach_lyrics_sidecar, collection_folder_from_local_path, collection_from_manifest,
+    finalize_downloaded_leaf, manifest_from_raw_leaf_evidence, merge_raw_leaf_manifest_evidence,
+    normalize_manifest_relative_path, normalize_music_title_batch,
+    normalize_music_titles_within_collection, project_local_collection_shell,
+    read_collection_manifest_file, record_collection_manifest_tail_trims,
 };
+use crate::domain::audio_tags::AudioChapter;
+use crate::domain::audio_tail_trim::AudioTailTrimEvidenceOrigin;
 use crate::domain::downloads::model::CollectionSourceKind;
 use crate::domain::downloads::model::{DownloadTaskStatus, DownloadTrigger};
 use crate::domain::downloads::yt_dlp::LeafProbe;
 use crate::domain::playlists::model::{
-    Collection, CollectionGroupOwner, Group, Music, canonical_music_id_for_source,
+    Collection, CollectionGroupOwner, Group, LoudnessProfile, Music, MusicTags,
+    canonical_music_id_for_source,
 };
+use crate::domain::playlists::repo::MusicEndTrim;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -22,16 +27,28 @@ fn manifest_import_restores_download_identity_for_playable_files() {
             absolute_path: PathBuf::from("C:/library/collection/Disc 1/intro.m4a"),
             relative_path: "Disc 1/intro.m4a".to_string(),
             duration_ms: 62_000,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
         },
         LocalAudioFile {
             absolute_path: PathBuf::from("C:/library/collection/loose.flac"),
             relative_path: "loose.flac".to_string(),
             duration_ms: 30_000,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
         },
         LocalAudioFile {
             absolute_path: PathBuf::from("C:/library/collection/missing-from-manifest.ogg"),
             relative_path: "missing-from-manifest.ogg".to_string(),
             duration_ms: 44_000,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
         },
     ];
     let manifest = CollectionManifest {
@@ -59,6 +76,11 @@ fn manifest_import_restores_download_identity_for_playable_files() {
                 start_ms: 0,
                 end_ms: 60_000,
                 liked: true,
+                tags: None,
+                file_size: None,
+                loudness_profile: None,
+                tail_trim: None,
+                exclude: None,
             },
             CollectionManifestMusic {
                 name: "Missing".to_string(),
@@ -69,6 +91,11 @@ fn manifest_import_restores_download_identity_for_playable_files() {
                 start_ms: 0,
                 end_ms: 5_000,
                 liked: false,
+                tags: None,
+                file_size: None,
+                loudness_profile: None,
+                tail_trim: None,
+                exclude: None,
             },
             CollectionManifestMusic {
                 name: "Too Long".to_string(),
@@ -79,11 +106,16 @@ fn manifest_import_restores_download_identity_for_playable_files() {
                 start_ms: 0,
                 end_ms: 90_000,
                 liked: false,
+                tags: None,
+                file_size: None,
+                loudness_profile: None,
+                tail_trim: None,
+                exclude: None,
             },
         ],
     };
 
-    let collection = collection_from_manifest(
+    let (collection, _) = collection_from_manifest(
         "D:/Music/collection".to_string(),
         manifest,
         &local_audio_files,
@@ -156,6 +188,7 @@ fn raw_leaf_manifest_records_probe_text_and_measured_file_boundary_not_db_projec
         musics: vec![music_with_group(normalized_title, url, path, group.clone())],
         last_updated: "2026-06-07T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let probe = LeafProbe {
         title: raw_title.to_string(),
@@ -165,6 +198,7 @@ fn raw_leaf_manifest_records_probe_text_and_measured_file_boundary_not_db_projec
         duration_ms: Some(186_688),
         duration_seconds: Some(187),
         chapters: vec![],
+        tags: None,
     };
     let polluted_existing = CollectionManifest {
         version: 1,
@@ -179,6 +213,11 @@ fn raw_leaf_manifest_records_probe_text_and_measured_file_boundary_not_db_projec
             start_ms: 0,
             end_ms: 180_000,
             liked: false,
+            tags: None,
+            file_size: None,
+            loudness_profile: None,
+            tail_trim: None,
+            exclude: None,
         }],
     };
 
@@ -238,6 +277,7 @@ fn raw_leaf_manifest_keeps_existing_root_metadata_when_db_collection_was_edited(
         )],
         last_updated: "2026-06-07T01:00:00+00:00".to_string(),
         enable_updates: Some(true),
+        save_root: None,
     };
     let probe = LeafProbe {
         title: "Next Raw Track".to_string(),
@@ -247,6 +287,7 @@ fn raw_leaf_manifest_keeps_existing_root_metadata_when_db_collection_was_edited(
         duration_ms: Some(90_000),
         duration_seconds: Some(90),
         chapters: vec![],
+        tags: None,
     };
 
     let next = manifest_from_raw_leaf_evidence(
@@ -284,6 +325,7 @@ fn raw_leaf_manifest_does_not_materialize_collection_owner_as_group() {
         musics: vec![],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let root_probe = LeafProbe {
         title: "Root Track".to_string(),
@@ -293,6 +335,7 @@ fn raw_leaf_manifest_does_not_materialize_collection_owner_as_group() {
         duration_ms: Some(60_000),
         duration_seconds: Some(60),
         chapters: vec![],
+        tags: None,
     };
     let nested_probe = LeafProbe {
         title: "Nested Track".to_string(),
@@ -302,6 +345,7 @@ fn raw_leaf_manifest_does_not_materialize_collection_owner_as_group() {
         duration_ms: Some(60_000),
         duration_seconds: Some(60),
         chapters: vec![],
+        tags: None,
     };
     let owner_group = collection_group(
         "Collection",
@@ -343,6 +387,10 @@ fn import_ignores_manifest_music_with_missing_nested_group_and_keeps_local_file_
         absolute_path: PathBuf::from("C:/library/collection/nested.m4a"),
         relative_path: "nested.m4a".to_string(),
         duration_ms: 60_000,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
     }];
     let manifest = CollectionManifest {
         version: 1,
@@ -358,7 +406,7 @@ fn import_ignores_manifest_music_with_missing_nested_group_and_keeps_local_file_
         )],
     };
 
-    let collection = collection_from_manifest(
+    let (collection, _) = collection_from_manifest(
         "D:/Music/collection".to_string(),
         manifest,
         &local_audio_files,
@@ -380,6 +428,10 @@ fn manifest_import_restores_precise_local_duration_for_full_file_boundary() {
         absolute_path: PathBuf::from("C:/library/collection/What Now.m4a"),
         relative_path: "What Now.m4a".to_string(),
         duration_ms: 344_455,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
     }];
     let manifest = CollectionManifest {
         version: 1,
@@ -394,7 +446,7 @@ fn manifest_import_restores_precise_local_duration_for_full_file_boundary() {
         )],
     };
 
-    let collection = collection_from_manifest(
+    let (collection, _) = collection_from_manifest(
         "D:/Music/collection".to_string(),
         manifest,
         &local_audio_files,
@@ -415,6 +467,10 @@ fn manifest_import_preserves_partial_ranges_that_do_not_target_file_end() {
         absolute_path: PathBuf::from("C:/library/collection/long-track.m4a"),
         relative_path: "long-track.m4a".to_string(),
         duration_ms: 344_455,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
     }];
     let manifest = CollectionManifest {
         version: 1,
@@ -429,7 +485,7 @@ fn manifest_import_preserves_partial_ranges_that_do_not_target_file_end() {
         )],
     };
 
-    let collection = collection_from_manifest(
+    let (collection, _) = collection_from_manifest(
         "D:/Music/collection".to_string(),
         manifest,
         &local_audio_files,
@@ -484,6 +540,10 @@ fn local_collection_shell_uses_the_same_identity_projection_as_full_import() {
             absolute_path: collection.join("track.m4a"),
             relative_path: "track.m4a".to_string(),
             duration_ms: 60_000,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
         }],
     )
     .expect("local audio collection should project identity");
@@ -550,6 +610,7 @@ fn local_import_task_uses_collection_url_as_active_playback_scope() {
         musics: vec![],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: None,
+        save_root: None,
     };
 
     let task = super::create_local_import_task(&collection);
@@ -610,6 +671,7 @@ fn normalize_music_titles_restores_bracketed_title_from_downloaded_file_name_evi
         ],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -618,6 +680,311 @@ fn normalize_music_titles_restores_bracketed_title_from_downloaded_file_name_evi
     assert_eq!(collection.musics[2].alias, "[INVERTED] FULL ALBUM");
 }
 
+#[test]
+fn normalize_music_titles_prefers_embedded_tag_titles_over_file_name_heuristics() {
+    let group = collection_group(
+        "TENET Official Soundtrack",
+        "https://www.youtube.com/playlist?list=PLtenet",
+        "TENET Official Soundtrack - WaterTower Music",
+    );
+    let mut tagged = music_with_group(
+        "TENET Official Soundtrack - FAST CARS - Ludwig Göransson - WaterTower",
+        "https://www.youtube.com/watch?v=fast-cars",
+        "TENET Official Soundtrack - FAST CARS - Ludwig Göransson - WaterTower.m4a",
+        group.clone(),
+    );
+    tagged.tags = Some(MusicTags {
+        title: Some("Fast Cars".to_string()),
+        artist: Some("Ludwig Göransson".to_string()),
+        ..MusicTags::default()
+    });
+    let mut collection = Collection {
+        name: "TENET Official Soundtrack".to_string(),
+        url: "https://www.youtube.com/playlist?list=PLtenet".to_string(),
+        folder: "youtube/TENET Official Soundtrack - WaterTower Music".to_string(),
+        musics: vec![
+            tagged,
+            music_with_group(
+                "TENET Official Soundtrack - TURNSTILE - Ludwig Göransson - WaterTower",
+                "https://www.youtube.com/watch?v=turnstile",
+                "TENET Official Soundtrack - TURNSTILE - Ludwig Göransson - WaterTower.m4a",
+                group.clone(),
+            ),
+            music_with_group(
+                "TENET Official Soundtrack - RAINY NIGHT IN TALLINN - Ludwig Göransson - WaterTower",
+                "https://www.youtube.com/watch?v=tallinn",
+                "TENET Official Soundtrack - RAINY NIGHT IN TALLINN - Ludwig Göransson - WaterTower.m4a",
+                group,
+            ),
+        ],
+        last_updated: "2026-05-24T00:00:00+00:00".to_string(),
+        enable_updates: Some(false),
+        save_root: None,
+    };
+
+    normalize_music_titles_within_collection(&mut collection);
+
+    assert_eq!(collection.musics[0].name, "Fast Cars");
+    assert_eq!(collection.musics[0].alias, "Fast Cars");
+    assert_ne!(
+        collection.musics[1].name,
+        "TENET Official Soundtrack - TURNSTILE - Ludwig Göransson - WaterTower"
+    );
+}
+
+#[test]
+fn manifest_import_names_untracked_local_files_from_embedded_tags() {
+    let local_audio_files = vec![LocalAudioFile {
+        absolute_path: PathBuf::from("C:/library/collection/track01.flac"),
+        relative_path: "track01.flac".to_string(),
+        duration_ms: 60_000,
+        tags: Some(MusicTags {
+            title: Some("Opening".to_string()),
+            track_number: Some(1),
+            ..MusicTags::default()
+        }),
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
+    }];
+    let manifest = CollectionManifest {
+        version: 1,
+        collection: manifest_collection(),
+        groups: vec![],
+        musics: vec![],
+    };
+
+    let (collection, _) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        manifest,
+        &local_audio_files,
+    )
+    .expect("tagged local file should import");
+
+    assert_eq!(collection.musics.len(), 1);
+    assert_eq!(collection.musics[0].name, "Opening");
+    assert_eq!(
+        collection.musics[0]
+            .tags
+            .as_ref()
+            .and_then(|tags| tags.track_number),
+        Some(1)
+    );
+}
+
+#[test]
+fn cue_sheet_splits_a_local_file_into_ranged_musics() {
+    let collection_root = unique_temp_path("cue-sheet");
+    std::fs::create_dir_all(&collection_root).expect("collection root should be creatable");
+    let cue_sheet_path = collection_root.join("album.cue");
+    std::fs::write(
+        &cue_sheet_path,
+        "PERFORMER \"Album Artist\"\nTITLE \"Album\"\nFILE \"album.wav\" WAVE\n\
+         TRACK 01 AUDIO\nTITLE \"Opening\"\nINDEX 01 00:00:00\n\
+         TRACK 02 AUDIO\nPERFORMER \"Guest\"\nINDEX 01 01:00:00\n",
+    )
+    .expect("cue sheet should be writable");
+    let mut local_audio_files = vec![LocalAudioFile {
+        absolute_path: collection_root.join("album.flac"),
+        relative_path: "album.flac".to_string(),
+        duration_ms: 150_000,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
+    }];
+
+    super::attach_cue_sheet_tracks(&mut local_audio_files, &cue_sheet_path);
+    let (collection, _) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        CollectionManifest {
+            version: 1,
+            collection: manifest_collection(),
+            groups: vec![],
+            musics: vec![],
+        },
+        &local_audio_files,
+    )
+    .expect("cue split local file should import");
+
+    let musics = collection
+        .musics
+        .iter()
+        .map(|music| {
+            (
+                music.name.as_str(),
+                music.start_ms,
+                music.end_ms,
+                music.tags.as_ref().and_then(|tags| tags.artist.as_deref()),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        musics,
+        vec![
+            ("Opening", 0, 60_000, Some("Album Artist")),
+            ("Track 02", 60_000, 150_000, Some("Guest")),
+        ]
+    );
+    assert!(collection.musics.iter().all(|music| {
+        music.canonical_music_id
+            == canonical_music_id_for_source(&music.url, music.start_ms, music.end_ms)
+    }));
+    assert_ne!(
+        collection.musics[0].canonical_music_id,
+        collection.musics[1].canonical_music_id
+    );
+
+    let _ = std::fs::remove_dir_all(&collection_root);
+}
+
+#[test]
+fn lyrics_sidecar_is_attached_to_the_musics_of_its_audio_file() {
+    let collection_root = unique_temp_path("lyrics-sidecar");
+    let disc_root = collection_root.join("Disc 1");
+    std::fs::create_dir_all(&disc_root).expect("collection root should be creatable");
+    std::fs::write(disc_root.join("song.flac"), b"audio").expect("audio should be writable");
+    std::fs::write(disc_root.join("song.lrc"), "[00:01.00]Line\n")
+        .expect("lyrics should be writable");
+    std::fs::write(disc_root.join("other.lrc"), "[00:01.00]Other\n")
+        .expect("lyrics should be writable");
+    let sidecar_path = std::path::Path::new("Disc 1")
+        .join("song.lrc")
+        .to_string_lossy()
+        .to_string();
+    let local_audio_files = vec![LocalAudioFile {
+        absolute_path: disc_root.join("song.flac"),
+        relative_path: "Disc 1/song.flac".to_string(),
+        duration_ms: 150_000,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: Some(sidecar_path.clone()),
+    }];
+
+    let (collection, _) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        CollectionManifest {
+            version: 1,
+            collection: manifest_collection(),
+            groups: vec![],
+            musics: vec![],
+        },
+        &local_audio_files,
+    )
+    .expect("local file with lyrics should import");
+    assert_eq!(
+        collection.musics[0].lyrics_path.as_deref(),
+        Some(sidecar_path.as_str())
+    );
+
+    let mut musics = collection.musics.clone();
+    musics[0].lyrics_path = None;
+    attach_lyrics_sidecar(
+        &mut musics,
+        &disc_root.join("song.flac"),
+        "Disc 1/song.flac",
+    );
+    assert_eq!(
+        musics[0].lyrics_path.as_deref(),
+        Some(sidecar_path.as_str())
+    );
+
+    std::fs::remove_file(disc_root.join("song.lrc")).expect("lyrics should be removable");
+    attach_lyrics_sidecar(
+        &mut musics,
+        &disc_root.join("song.flac"),
+        "Disc 1/song.flac",
+    );
+    assert_eq!(musics[0].lyrics_path, None);
+
+    let _ = std::fs::remove_dir_all(&collection_root);
+}
+
+#[test]
+fn embedded_chapters_split_a_local_file_like_downloaded_chapters() {
+    let chapters = super::local_audio_chapters(
+        vec![
+            AudioChapter {
+                title: Some("Opening".to_string()),
+                start_ms: 0,
+                end_ms: 90_000,
+            },
+            AudioChapter {
+                title: None,
+                start_ms: 90_000,
+                end_ms: 200_000,
+            },
+            AudioChapter {
+                title: Some("Past the end".to_string()),
+                start_ms: 180_000,
+                end_ms: 240_000,
+            },
+        ],
+        180_000,
+    );
+    let local_audio_files = vec![LocalAudioFile {
+        absolute_path: PathBuf::from("C:/library/collection/book.m4b"),
+        relative_path: "book.m4b".to_string(),
+        duration_ms: 180_000,
+        tags: Some(MusicTags {
+            title: Some("Book".to_string()),
+            artist: Some("Narrator".to_string()),
+            ..MusicTags::default()
+        }),
+        cue_tracks: Vec::new(),
+        chapters,
+        lyrics_path: None,
+    }];
+
+    let (collection, _) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        CollectionManifest {
+            version: 1,
+            collection: manifest_collection(),
+            groups: vec![],
+            musics: vec![],
+        },
+        &local_audio_files,
+    )
+    .expect("chaptered local file should import");
+
+    let musics = collection
+        .musics
+        .iter()
+        .map(|music| (music.name.as_str(), music.start_ms, music.end_ms))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        musics,
+        vec![("Opening", 0, 90_000), ("Chapter 02", 90_000, 180_000)]
+    );
+    assert!(collection.musics.iter().all(|music| {
+        music.path.as_deref() == Some("book.m4b")
+            && music.canonical_music_id
+                == canonical_music_id_for_source(&music.url, music.start_ms, music.end_ms)
+            && music.tags.as_ref().and_then(|tags| tags.artist.as_deref()) == Some("Narrator")
+            && music
+                .tags
+                .as_ref()
+                .and_then(|tags| tags.title.as_deref())
+                .is_none()
+    }));
+}
+
+#[test]
+fn a_single_embedded_chapter_spanning_the_file_does_not_split_it() {
+    let chapters = super::local_audio_chapters(
+        vec![AudioChapter {
+            title: Some("Whole".to_string()),
+            start_ms: 0,
+            end_ms: 60_000,
+        }],
+        60_000,
+    );
+
+    assert!(chapters.is_empty());
+}
+
 #[test]
 fn normalize_music_titles_deletes_separator_suffix_as_one_semantic_block() {
     let group = collection_group(
@@ -657,6 +1024,7 @@ fn normalize_music_titles_deletes_separator_suffix_as_one_semantic_block() {
         ],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -698,6 +1066,7 @@ fn normalize_music_titles_never_projects_complete_suffix_to_partial_suffix() {
         ],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -758,6 +1127,7 @@ fn normalize_music_titles_removes_download_source_notes_without_destroying_title
         ],
         last_updated: "2026-06-07T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -805,6 +1175,7 @@ fn normalize_music_titles_repairs_bracketed_variant_from_file_name_evidence() {
         ],
         last_updated: "2026-06-07T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -864,6 +1235,7 @@ fn normalize_music_titles_handles_catalog_prefixes_without_cutting_title_apostro
         ],
         last_updated: "2026-06-07T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -914,6 +1286,7 @@ fn normalize_music_titles_rejects_noise_deletions_that_leave_only_numbers() {
         ],
         last_updated: "2026-06-07T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -970,6 +1343,7 @@ fn finalize_downloaded_leaf_commits_the_actual_downloaded_file_name_without_temp
         musics: vec![],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let group = Group {
         name: collection.name.clone(),
@@ -1018,6 +1392,7 @@ fn finalize_downloaded_leaf_rejects_partial_download_fragments() {
         musics: vec![],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let group = Group {
         name: collection.name.clone(),
@@ -1071,6 +1446,7 @@ fn finalize_downloaded_leaf_is_idempotent_after_temp_file_was_already_committed(
         )],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let group = Group {
         name: collection.name.clone(),
@@ -1122,6 +1498,7 @@ fn finalize_downloaded_leaf_preserves_another_leaf_with_the_same_title() {
         )],
         last_updated: "2026-08-02T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     let relative_path = finalize_downloaded_leaf(
@@ -1176,6 +1553,7 @@ fn finalize_downloaded_leaf_does_not_delete_stable_file_when_source_is_missing()
         )],
         last_updated: "2026-08-02T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     let error = finalize_downloaded_leaf(
@@ -1223,6 +1601,7 @@ fn materialized_training_scope_limits_inputs_to_committed_leafs() {
         musics: vec![unrelated, committed.clone()],
         last_updated: "2026-05-24T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     let scope = super::materialized_training_scope(&[committed]);
@@ -1234,6 +1613,136 @@ fn materialized_training_scope_limits_inputs_to_committed_leafs() {
     assert!(inputs[0].absolute_path.contains("committed.m4a"));
 }
 
+#[test]
+fn v2_manifest_evidence_is_trusted_only_while_the_file_size_matches() {
+    let collection_root = unique_temp_path("v2_evidence");
+    let file_path = collection_root.join("Outro.m4a");
+    std::fs::create_dir_all(&collection_root).expect("collection folder should be created");
+    std::fs::write(&file_path, b"audio").expect("audio fixture should be written");
+    let local_audio_files = vec![LocalAudioFile {
+        absolute_path: file_path,
+        relative_path: "Outro.m4a".to_string(),
+        duration_ms: 200_000,
+        tags: None,
+        cue_tracks: Vec::new(),
+        chapters: Vec::new(),
+        lyrics_path: None,
+    }];
+    let url = "https://example.com/watch?v=outro";
+    let mut music = manifest_music("Outro", url, "Outro.m4a", 0, 190_000);
+    music.file_size = Some(5);
+    music.loudness_profile = Some(LoudnessProfile {
+        integrated_lufs: -9.5,
+        true_peak_dbtp: Some(-0.8),
+        lra: None,
+        short_lufs_p50: None,
+        short_lufs_p80: None,
+        short_lufs_p95: None,
+        short_lufs_max: None,
+        presence_db: None,
+        model_adjustment_db: None,
+    });
+    music.tail_trim = Some(CollectionManifestTailTrim {
+        source_end_ms: 200_000,
+        origin: AudioTailTrimEvidenceOrigin::FullCollection,
+    });
+    music.exclude = Some(CollectionManifestExclude {
+        expires_at: None,
+        playlists: vec!["Workout".to_string()],
+    });
+    let manifest = |music: CollectionManifestMusic| CollectionManifest {
+        version: 2,
+        collection: manifest_collection(),
+        groups: vec![],
+        musics: vec![music],
+    };
+
+    let (collection, excludes) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        manifest(music.clone()),
+        &local_audio_files,
+    )
+    .expect("v2 manifest should restore");
+    assert_eq!(collection.musics[0].end_ms, 190_000);
+    assert_eq!(
+        collection.musics[0]
+            .loudness_profile
+            .map(|profile| profile.integrated_lufs),
+        Some(-9.5)
+    );
+    assert_eq!(excludes.len(), 1);
+    assert_eq!(
+        excludes[0].canonical_music_id,
+        canonical_music_id_for_source(url, 0, 190_000)
+    );
+    assert_eq!(excludes[0].exclude.playlists, vec!["Workout".to_string()]);
+
+    music.file_size = Some(6);
+    let (collection, _) = collection_from_manifest(
+        "D:/Music/collection".to_string(),
+        manifest(music),
+        &local_audio_files,
+    )
+    .expect("v2 manifest with a changed file should restore");
+    assert_eq!(collection.musics[0].end_ms, 200_000);
+    assert_eq!(collection.musics[0].loudness_profile, None);
+
+    let _ = std::fs::remove_dir_all(collection_root);
+}
+
+#[test]
+fn applied_tail_trims_are_recorded_in_the_manifest() {
+    let collection_root = unique_temp_path("tail_trim_record");
+    std::fs::create_dir_all(&collection_root).expect("collection folder should be created");
+    std::fs::write(collection_root.join("Outro.m4a"), b"audio")
+        .expect("audio fixture should be written");
+    let url = "https://example.com/watch?v=outro";
+    write_manifest_fixture(
+        &collection_root,
+        &CollectionManifest {
+            version: 1,
+            collection: manifest_collection(),
+            groups: vec![],
+            musics: vec![manifest_music("Outro", url, "Outro.m4a", 0, 200_000)],
+        },
+    )
+    .expect("v1 manifest fixture should be written");
+
+    record_collection_manifest_tail_trims(
+        &collection_root,
+        &[MusicEndTrim {
+            url: url.to_string(),
+            start_ms: 0,
+            end_ms: 200_000,
+            next_end_ms: 190_000,
+        }],
+        AudioTailTrimEvidenceOrigin::FullCollection,
+    )
+    .expect("tail trims should be recorded");
+
+    let manifest_path = collection_root.join(".slisic.collection.toml");
+    let manifest = read_collection_manifest_file(&manifest_path)
+        .expect("recorded manifest should be readable")
+        .expect("manifest should exist");
+    assert_eq!(manifest.version, 2);
+    assert_eq!(manifest.musics[0].end_ms, 190_000);
+    assert_eq!(manifest.musics[0].file_size, Some(5));
+    assert_eq!(
+        manifest.musics[0].tail_trim,
+        Some(CollectionManifestTailTrim {
+            source_end_ms: 200_000,
+            origin: AudioTailTrimEvidenceOrigin::FullCollection,
+        })
+    );
+    assert!(
+        std::fs::read_to_string(&manifest_path)
+            .expect("manifest should be readable")
+            .contains("origin = \"full_collection\"")
+    );
+
+    let _ = std::fs::remove_dir_all(collection_root);
+}
+
 fn manifest_collection() -> CollectionManifestCollection {
     CollectionManifestCollection {
         name: "Collection".to_string(),
@@ -1261,6 +1770,11 @@ fn manifest_music(
         start_ms,
         end_ms,
         liked: false,
+        tags: None,
+        file_size: None,
+        loudness_profile: None,
+        tail_trim: None,
+        exclude: None,
     }
 }
 
@@ -1281,6 +1795,11 @@ fn manifest_music_with_group(
         start_ms,
         end_ms,
         liked: false,
+        tags: None,
+        file_size: None,
+        loudness_profile: None,
+        tail_trim: None,
+        exclude: None,
     }
 }
 
@@ -1310,6 +1829,9 @@ fn music_with_group(name: &str, url: &str, path: &str, group: Group) -> Music {
         end_ms: 60_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -0,0 +1,193 @@
+use crate::domain::collection_import::{
+    CollectionManifestExclude, CollectionManifestSync, sync_collection_manifest,
+};
+use crate::domain::playlists::model::Collection;
+use crate::domain::playlists::repo as collection_repo;
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+#[cfg(not(test))]
+use std::collections::HashSet;
+use std::path::Path;
+#[cfg(not(test))]
+use std::sync::{LazyLock, Mutex, OnceLock};
+#[cfg(not(test))]
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+#[path = "collection_manifest_sync.test.rs"]
+mod tests;
+
+#[cfg(not(test))]
+const COLLECTION_MANIFEST_SYNC_DEBOUNCE: Duration = Duration::from_millis(1_500);
+
+#[cfg(not(test))]
+static MANIFEST_SYNC_APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+#[cfg(not(test))]
+static MANIFEST_SYNC_QUEUE: LazyLock<Mutex<ManifestSyncQueue>> =
+    LazyLock::new(|| Mutex::new(ManifestSyncQueue::default()));
+
+#[cfg(not(test))]
+#[derive(Default)]
+struct ManifestSyncQueue {
+    pending: HashSet<String>,
+    last_change: Option<Instant>,
+    worker_running: bool,
+}
+
+/**
+ * Behavior:
+ *   Rewrite the manifest of `collection` from its stored music and groups,
+ *   in its folder on its own save root; `save_root` is the default root.
+ *
+ * Core invariants:
+ *   - Collections whose folder is gone are skipped, because a manifest
+ *     without its files restores nothing.
+ *   - The manifest records the current exclude of each music, so it
+ *     survives importing the folder into another library.
+ */
+pub(crate) async fn sync_stored_collection_manifest(
+    save_root: &Path,
+    collection: &Collection,
+) -> Result<Option<CollectionManifestSync>> {
+    let collection_root = collection.dir(save_root);
+    if !collection_root.is_dir() {
+        return Ok(None);
+    }
+
+    let groups = collection_repo::list_collection_groups(&collection.url).await?;
+    let mut excludes = HashMap::new();
+    for music in &collection.musics {
+        if let Some(exclude) = collection_repo::get_exclude(music).await? {
+            excludes.insert(
+                music.canonical_music_id.clone(),
+                CollectionManifestExclude {
+                    expires_at: exclude.expires_at,
+                    playlists: exclude.playlists,
+                },
+            );
+        }
+    }
+    let collection = collection.clone();
+    tokio::task::spawn_blocking(move || {
+        sync_collection_manifest(&collection_root, &collection, &groups, &excludes)
+    })
+    .await
+    .map_err(|error| anyhow!("collection manifest sync task failed: {error}"))?
+    .map(Some)
+}
+
+#[cfg(not(test))]
+pub(crate) fn initialize_runtime(app: tauri::AppHandle) {
+    let _ = MANIFEST_SYNC_APP.set(app);
+}
+
+/// Queues a manifest rewrite for each collection; rewrites run once the
+/// library was quiet for a moment, so bursts of edits write each manifest once.
+#[cfg(not(test))]
+pub(crate) fn schedule_collection_manifest_sync(collection_urls: impl IntoIterator<Item = String>) {
+    let Ok(mut queue) = MANIFEST_SYNC_QUEUE.lock() else {
+        log::error!(
+            target: "collection_manifest_sync",
+            "manifest_sync_schedule_failed error=\"lock_poisoned\""
+        );
+        return;
+    };
+    queue.pending.extend(collection_urls);
+    if queue.pending.is_empty() {
+        return;
+    }
+    queue.last_change = Some(Instant::now());
+    if queue.worker_running {
+        return;
+    }
+    queue.worker_running = true;
+    drop(queue);
+
+    tauri::async_runtime::spawn(run_manifest_sync_worker());
+}
+
+#[cfg(not(test))]
+async fn run_manifest_sync_worker() {
+    loop {
+        tokio::time::sleep(COLLECTION_MANIFEST_SYNC_DEBOUNCE).await;
+        let urls = {
+            let Ok(mut queue) = MANIFEST_SYNC_QUEUE.lock() else {
+                return;
+            };
+            if queue
+                .last_change
+                .is_some_and(|changed| changed.elapsed() < COLLECTION_MANIFEST_SYNC_DEBOUNCE)
+            {
+                continue;
+            }
+            if queue.pending.is_empty() {
+                queue.worker_running = false;
+                return;
+            }
+            std::mem::take(&mut queue.pending)
+        };
+
+        let mut conflicts = Vec::new();
+        let _restore_guard = crate::domain::library_backup::acquire_library_write_guard().await;
+        for url in urls {
+            match sync_collection_manifest_for_app(&url).await {
+                Ok(Some(CollectionManifestSync::Conflict)) => {
+                    log::warn!(
+                        target: "collection_manifest_sync",
+                        "manifest_sync_conflict collection_url=\"{}\"",
+                        url
+                    );
+                    conflicts.push(url);
+                }
+                Ok(_) => {}
+                Err(error) => log::error!(
+                    target: "collection_manifest_sync",
+                    "manifest_sync_failed collection_url=\"{}\" error=\"{}\"",
+                    url,
+                    error
+                ),
+            }
+        }
+        // A conflicting writer has finished by the next round; rebuild then.
+        if !conflicts.is_empty()
+            && let Ok(mut queue) = MANIFEST_SYNC_QUEUE.lock()
+        {
+            queue.pending.extend(conflicts);
+            queue.last_change = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(not(test))]
+async fn sync_collection_manifest_for_app(
+    collection_url: &str,
+) -> Result<Option<CollectionManifestSync>> {
+    let app = MANIFEST_SYNC_APP
+        .get()
+        .ok_or_else(|| anyhow!("collection manifest sync has not been initialized"))?;
+    let Some(collection) = collection_repo::get_collection_by_url(collection_url).await? else {
+        return Ok(None);
+    };
+    let save_root = crate::domain::meta::service::resolve_save_root(app).await?;
+
+    sync_stored_collection_manifest(&save_root, &collection).await
+}
+
+/// Queues manifest rewrites for every collection holding the music identity.
+#[cfg(not(test))]
+pub(crate) async fn schedule_music_identity_manifest_sync(url: &str, start_ms: u32, end_ms: u32) {
+    match collection_repo::list_music_occurrences_by_identity(url, start_ms, end_ms).await {
+        Ok(occurrences) => schedule_collection_manifest_sync(
+            occurrences
+                .into_iter()
+                .map(|(collection_url, _)| collection_url),
+        ),
+        Err(error) => log::error!(
+            target: "collection_manifest_sync",
+            "manifest_sync_lookup_failed music_url=\"{}\" error=\"{}\"",
+            url,
+            error
+        ),
+    }
+}
@@ -0,0 +1,77 @@
+use super::sync_stored_collection_manifest;
+use crate::domain::collection_import::CollectionManifestSync;
+use crate::domain::playlists::model::Collection;
+use crate::domain::playlists::repo::{
+    get_collection_by_url, set_music_liked_by_identity, upsert_collection,
+};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, music, owner_group, run_async, temp_path,
+};
+use appdb::connection::reset_db;
+
+fn collection(url: &str, folder: &str) -> Collection {
+    crate::domain::test_support::collection(
+        "Singles",
+        url,
+        folder,
+        vec![music("Single", &owner_group("Singles", url, folder))],
+    )
+}
+
+#[test]
+fn manifest_follows_music_edits() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let save_root = temp_path("manifest_sync_root");
+        let collection_url = "https://example.com/singles";
+        let collection_dir = save_root.join("example/Singles");
+        let manifest_path = collection_dir.join(".slisic.collection.toml");
+        std::fs::create_dir_all(&collection_dir).expect("collection folder should be created");
+        upsert_collection(&collection(collection_url, "example/Singles"))
+            .await
+            .expect("collection should save");
+
+        set_music_liked_by_identity("https://example.com/watch/Single", 0, 60_000, true)
+            .await
+            .expect("liked update should succeed")
+            .expect("music should exist");
+        let stored = get_collection_by_url(collection_url)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+
+        assert_eq!(
+            sync_stored_collection_manifest(&save_root, &stored)
+                .await
+                .expect("manifest sync should succeed"),
+            Some(CollectionManifestSync::Written)
+        );
+        let manifest = std::fs::read_to_string(&manifest_path).expect("manifest should exist");
+        assert!(manifest.contains("path = \"Single.m4a\""));
+        assert!(manifest.contains("liked = true"));
+        assert!(
+            !collection_dir
+                .join(".slisic.collection.toml.partial")
+                .exists()
+        );
+
+        assert_eq!(
+            sync_stored_collection_manifest(&save_root, &stored)
+                .await
+                .expect("repeated manifest sync should succeed"),
+            Some(CollectionManifestSync::Unchanged)
+        );
+
+        std::fs::remove_dir_all(&save_root).expect("save root should be removed");
+        assert_eq!(
+            sync_stored_collection_manifest(&save_root, &stored)
+                .await
+                .expect("manifest sync without a folder should succeed"),
+            None
+        );
+
+        reset_db();
+    });
+}
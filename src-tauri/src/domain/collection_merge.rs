@@ -0,0 +1,449 @@
+use crate::domain::collection_import::{
+    has_collection_manifest, remove_collection_manifest, rewrite_collection_manifest,
+};
+use crate::domain::collection_relocation::{
+    copy_collection_tree, copy_verified_file, discard_partial_copy, ensure_empty_relocation_target,
+};
+use crate::domain::downloads::model::now_timestamp;
+use crate::domain::downloads::naming::sanitize_path_component;
+use crate::domain::downloads::repo as download_repo;
+use crate::domain::meta::model::save_root_dir;
+use crate::domain::playlists::model::{Collection, CollectionGroupOwner, Group, Music};
+use crate::domain::playlists::repo as collection_repo;
+use crate::domain::save_root_migration::ensure_no_save_root_migration;
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+#[path = "collection_merge.test.rs"]
+mod tests;
+
+/**
+ * Behavior:
+ *   Merge collection `source_url` into `target_url`. The source becomes a
+ *   group of the target named after it, its files move into a subfolder of
+ *   the target folder and its own groups follow below that subfolder.
+ *
+ * Core invariants:
+ *   - Group urls are kept, so music occurrences, liked state and excludes
+ *     stay attached to the same records.
+ *   - Playlists that selected the source collection select its group.
+ *   - Nothing starts while a save path migration is running or unfinished.
+ *   - Records are only changed after every file was copied and verified.
+ *   - The record changes succeed or fail together: on a failure the target,
+ *     the source, its groups and the playlist selections are restored from
+ *     snapshots and the copied files are discarded.
+ *   - The source folder is only removed after every record change succeeded.
+ */
+pub(crate) async fn merge_collections(
+    save_root: &Path,
+    target_url: &str,
+    source_url: &str,
+) -> Result<Collection> {
+    if target_url == source_url {
+        bail!("a collection cannot be merged into itself");
+    }
+    let Some(target) = collection_repo::get_collection_by_url(target_url).await? else {
+        bail!("collection `{target_url}` was not found");
+    };
+    let Some(source) = collection_repo::get_collection_by_url(source_url).await? else {
+        bail!("collection `{source_url}` was not found");
+    };
+    ensure_no_save_root_migration().await?;
+    ensure_not_downloading(&[target_url, source_url]).await?;
+    if collection_repo::list_collection_groups(target_url)
+        .await?
+        .iter()
+        .any(|group| group.name == source.name)
+    {
+        bail!(
+            "collection `{target_url}` already has a group named `{}`",
+            source.name
+        );
+    }
+
+    let target_dir = target.dir(save_root);
+    let source_dir = source.dir(save_root);
+    let subfolder = sanitize_path_component(&source.name);
+    let merged_dir = target_dir.join(&subfolder);
+    ensure_empty_relocation_target(&merged_dir)?;
+    let write_manifest =
+        has_collection_manifest(&target_dir) || has_collection_manifest(&source_dir);
+
+    let copy_source = source_dir.clone();
+    let copy_target = merged_dir.clone();
+    let url = source_url.to_string();
+    tokio::task::spawn_blocking(move || {
+        copy_collection_tree(&url, &copy_source, &copy_target, &mut |_| {})
+            .and_then(|_| remove_collection_manifest(&copy_target))
+            .inspect_err(|_| discard_partial_copy(&copy_target))
+    })
+    .await??;
+
+    let source_groups = collection_repo::list_collection_groups(source_url).await?;
+    let mut merged = target.clone();
+    merged.musics.extend(source.musics.iter().map(|music| {
+        let mut music = music.clone();
+        music.group = merged_group(&music.group, &source, &target, &subfolder);
+        music.path = music
+            .path
+            .as_deref()
+            .map(|path| merged_music_path(path, &subfolder, &source_dir, &merged_dir));
+        music
+    }));
+    let selections = collection_repo::snapshot_playlist_selections(source_url).await?;
+    let merge_records = async {
+        collection_repo::upsert_collection(&merged).await?;
+        for group in &source_groups {
+            collection_repo::rebind_group(&merged_group(group, &source, &target, &subfolder))
+                .await?;
+        }
+        collection_repo::replace_playlist_collection_with_group(source_url, source_url).await?;
+        collection_repo::delete_absorbed_collection(source_url).await?;
+        Ok::<_, anyhow::Error>(())
+    };
+    if let Err(error) = merge_records.await {
+        log::warn!(
+            target: "collection_merge",
+            "merge_rolled_back target_url=\"{}\" source_url=\"{}\" error=\"{:#}\"",
+            target_url,
+            source_url,
+            error
+        );
+        collection_repo::restore_collection_snapshots(&[source.clone(), target.clone()]).await;
+        for group in &source_groups {
+            if let Err(error) = collection_repo::rebind_group(group).await {
+                log::error!(
+                    target: "collection_merge",
+                    "group_restore_failed group_url=\"{}\" error=\"{:#}\"",
+                    group.url,
+                    error
+                );
+            }
+        }
+        collection_repo::restore_playlist_selections(&selections).await;
+        discard_partial_copy(&merged_dir);
+        return Err(error);
+    }
+    if source_dir.is_dir()
+        && let Err(error) = std::fs::remove_dir_all(&source_dir)
+    {
+        log::warn!(
+            target: "collection_merge",
+            "source_cleanup_failed collection_url=\"{}\" path=\"{}\" error=\"{}\"",
+            source_url,
+            source_dir.display(),
+            error
+        );
+    }
+
+    let Some(saved) = collection_repo::get_collection_by_url(target_url).await? else {
+        bail!("collection `{target_url}` disappeared during the merge");
+    };
+    if write_manifest {
+        let groups = collection_repo::list_collection_groups(target_url).await?;
+        rewrite_collection_manifest(&target_dir, &saved, &groups)?;
+    }
+    Ok(saved)
+}
+
+/**
+ * Behavior:
+ *   Split one group out of a collection into a collection of its own, next
+ *   to the original folder and named after the group.
+ *
+ * Core invariants:
+ *   - The new collection takes the group url, so the group record becomes
+ *     its own group and occurrences, liked state and excludes are kept.
+ *   - Playlists that selected the original collection also select the new
+ *     one; playlists that selected the group keep it.
+ *   - Files still used by music left in the original collection are copied
+ *     instead of moved.
+ *   - Nothing starts while a save path migration is running or unfinished.
+ *   - The record changes succeed or fail together: on a failure the original
+ *     collection and group are restored, the new collection is dropped again
+ *     and the copied files are discarded.
+ *   - Source files are only removed after every record change succeeded.
+ */
+pub(crate) async fn split_group_into_collection(
+    save_root: &Path,
+    collection_url: &str,
+    group_url: &str,
+) -> Result<Collection> {
+    if group_url == collection_url {
+        bail!("the collection group cannot be split off");
+    }
+    let Some(collection) = collection_repo::get_collection_by_url(collection_url).await? else {
+        bail!("collection `{collection_url}` was not found");
+    };
+    let Some(group) = collection_repo::list_collection_groups(collection_url)
+        .await?
+        .into_iter()
+        .find(|group| group.url == group_url)
+    else {
+        bail!("group `{group_url}` does not belong to `{collection_url}`");
+    };
+    if collection_repo::get_collection_by_url(group_url)
+        .await?
+        .is_some()
+    {
+        bail!("collection `{group_url}` already exists");
+    }
+    ensure_no_save_root_migration().await?;
+    ensure_not_downloading(&[collection_url]).await?;
+
+    let collection_dir = collection.dir(save_root);
+    let folder = sibling_collection_folder(&collection.folder, &group.name);
+    let split_dir = save_root_dir(save_root, collection.save_root.as_deref()).join(&folder);
+    ensure_empty_relocation_target(&split_dir)?;
+    let write_manifest = has_collection_manifest(&collection_dir);
+
+    let (moved, kept): (Vec<Music>, Vec<Music>) = collection
+        .musics
+        .iter()
+        .cloned()
+        .partition(|music| music.group.url == group_url);
+    let mut split = Collection {
+        name: group.name.clone(),
+        url: group.url.clone(),
+        folder: folder.clone(),
+        musics: vec![],
+        last_updated: now_timestamp(),
+        enable_updates: None,
+        save_root: collection.save_root.clone(),
+    };
+    let split_group = Group {
+        name: split.name.clone(),
+        url: split.url.clone(),
+        collection: CollectionGroupOwner::from(&split),
+        folder: folder.clone(),
+    };
+    let mut copies = Vec::<(PathBuf, PathBuf)>::new();
+    split.musics = moved
+        .into_iter()
+        .map(|mut music| {
+            if let Some(path) = music.path.clone()
+                && Path::new(&path).is_relative()
+            {
+                let relative = Path::new(&path)
+                    .strip_prefix(&group.folder)
+                    .unwrap_or(Path::new(&path))
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                copies.push((collection_dir.join(&path), split_dir.join(&relative)));
+                music.path = Some(relative);
+            }
+            music.group = split_group.clone();
+            music
+        })
+        .collect();
+
+    let copy_target = split_dir.clone();
+    let copied = copies.clone();
+    tokio::task::spawn_blocking(move || {
+        copy_split_files(&copied, &copy_target).inspect_err(|_| discard_partial_copy(&copy_target))
+    })
+    .await??;
+
+    let mut remaining = collection.clone();
+    remaining.musics = kept;
+    let split_records = async {
+        let saved = collection_repo::upsert_collection(&split).await?;
+        collection_repo::rebind_group(&split_group.bind_collection(&saved)).await?;
+        let remaining = collection_repo::upsert_collection(&remaining).await?;
+        collection_repo::add_playlist_collection_alongside(collection_url, group_url).await?;
+        Ok::<_, anyhow::Error>((saved, remaining))
+    };
+    let (saved, remaining) = match split_records.await {
+        Ok(records) => records,
+        Err(error) => {
+            log::warn!(
+                target: "collection_merge",
+                "split_rolled_back collection_url=\"{}\" group_url=\"{}\" error=\"{:#}\"",
+                collection_url,
+                group_url,
+                error
+            );
+            collection_repo::restore_collection_snapshots(std::slice::from_ref(&collection)).await;
+            let restored = async {
+                collection_repo::rebind_group(&group).await?;
+                collection_repo::delete_absorbed_collection(group_url).await
+            };
+            if let Err(error) = restored.await {
+                log::error!(
+                    target: "collection_merge",
+                    "split_restore_failed group_url=\"{}\" error=\"{:#}\"",
+                    group_url,
+                    error
+                );
+            }
+            discard_partial_copy(&split_dir);
+            return Err(error);
+        }
+    };
+
+    let still_used = remaining
+        .musics
+        .iter()
+        .filter_map(|music| music.path.as_deref())
+        .map(|path| collection_dir.join(path))
+        .collect::<HashSet<_>>();
+    for (source, _) in &copies {
+        if !still_used.contains(source)
+            && let Err(error) = std::fs::remove_file(source)
+        {
+            log::warn!(
+                target: "collection_merge",
+                "source_cleanup_failed collection_url=\"{}\" path=\"{}\" error=\"{}\"",
+                collection_url,
+                source.display(),
+                error
+            );
+        }
+    }
+    let group_dir = collection_dir.join(&group.folder);
+    if group.folder != collection.folder
+        && Path::new(&group.folder).is_relative()
+        && group_dir.is_dir()
+        && let Err(error) = std::fs::remove_dir(&group_dir)
+        && error.kind() != std::io::ErrorKind::DirectoryNotEmpty
+    {
+        log::warn!(
+            target: "collection_merge",
+            "group_folder_cleanup_failed collection_url=\"{}\" path=\"{}\" error=\"{}\"",
+            collection_url,
+            group_dir.display(),
+            error
+        );
+    }
+
+    if write_manifest {
+        let groups = collection_repo::list_collection_groups(collection_url).await?;
+        rewrite_collection_manifest(&collection_dir, &remaining, &groups)?;
+        let groups = collection_repo::list_collection_groups(group_url).await?;
+        rewrite_collection_manifest(&split_dir, &saved, &groups)?;
+    }
+    Ok(saved)
+}
+
+async fn ensure_not_downloading(collection_urls: &[&str]) -> Result<()> {
+    if let Some(task) = download_repo::list_tasks().await?.iter().find(|task| {
+        task.status.is_active()
+            && task
+                .collection_url
+                .as_deref()
+                .is_some_and(|url| collection_urls.contains(&url))
+    }) {
+        bail!(
+            "collection `{}` cannot change while it is downloading",
+            task.collection_url.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// The group a source collection group becomes inside the target: the
+/// source's own group is named after it and owns the subfolder, and the
+/// other groups move below that subfolder.
+fn merged_group(group: &Group, source: &Collection, target: &Collection, subfolder: &str) -> Group {
+    let mut merged = group.clone().bind_collection(target);
+    if group.url == source.url {
+        merged.name = source.name.clone();
+        merged.folder = subfolder.to_string();
+    } else if group.folder == source.folder {
+        merged.folder = subfolder.to_string();
+    } else if Path::new(&group.folder).is_relative() {
+        merged.folder = format!("{subfolder}/{}", group.folder);
+    }
+    merged
+}
+
+fn merged_music_path(path: &str, subfolder: &str, source_dir: &Path, merged_dir: &Path) -> String {
+    let current = Path::new(path);
+    if current.is_relative() {
+        return format!("{subfolder}/{}", path.replace('\\', "/"));
+    }
+    match current.strip_prefix(source_dir) {
+        Ok(relative) => merged_dir.join(relative).to_string_lossy().to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// A folder next to `folder`, named after `name`; relative folders stay
+/// relative to the save root.
+fn sibling_collection_folder(folder: &str, name: &str) -> String {
+    let sibling = Path::new(folder)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(sanitize_path_component(name));
+    if sibling.is_relative() {
+        sibling.to_string_lossy().replace('\\', "/")
+    } else {
+        sibling.to_string_lossy().to_string()
+    }
+}
+
+fn copy_split_files(copies: &[(PathBuf, PathBuf)], target_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(target_dir)?;
+    let mut copied = HashSet::new();
+    for (source, destination) in copies {
+        if !copied.insert(destination) || !source.is_file() {
+            continue;
+        }
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        copy_verified_file(source, destination)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn merge_collection_into(
+    app: tauri::AppHandle,
+    target_url: String,
+    source_url: String,
+) -> Result<Collection, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let collection = merge_collections(&save_root, &target_url, &source_url)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    notify_collections_reshaped("collection_merge");
+    Ok(collection)
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn split_group_to_collection(
+    app: tauri::AppHandle,
+    collection_url: String,
+    group_url: String,
+) -> Result<Collection, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let collection = split_group_into_collection(&save_root, &collection_url, &group_url)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    notify_collections_reshaped("collection_split");
+    Ok(collection)
+}
+
+#[cfg(not(test))]
+fn notify_collections_reshaped(reason: &'static str) {
+    crate::domain::playlists::search::notify_library_changed();
+    crate::domain::playlist_playback::service::notify_music_library_inputs_changed(reason);
+    crate::domain::playlist_playback::service::notify_playable_library_changed();
+}
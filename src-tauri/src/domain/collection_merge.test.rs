@@ -0,0 +1,229 @@
+use super::{merge_collections, split_group_into_collection};
+use crate::domain::playlists::model::{Collection, Group, Music, PlayList, PlayListWriteRequest};
+use crate::domain::playlists::repo::{
+    get_collection_by_url, get_playlist_by_name, list_collection_groups, upsert_collection,
+    upsert_playlist_surface,
+};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, collection, owner, owner_group, run_async, temp_path,
+    write_file,
+};
+use appdb::AutoFill;
+use appdb::connection::reset_db;
+
+fn music(name: &str, group: &Group, path: &str, liked: bool) -> Music {
+    let mut music = crate::domain::test_support::music(name, group);
+    music.path = Some(path.to_string());
+    music.liked = liked;
+    music
+}
+
+async fn save_playlist(name: &str, collections: Vec<Collection>, groups: Vec<Group>) {
+    let playlist = PlayList {
+        name: name.to_string(),
+        collections,
+        groups,
+        extra: vec![],
+        playlists: None,
+        rules: None,
+        created_at: AutoFill::pending(),
+    };
+    upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+        .await
+        .expect("playlist should save");
+}
+
+#[test]
+fn merging_moves_a_collection_into_a_group_of_another() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let save_root = temp_path("merge");
+        let target_url = "https://example.com/albums";
+        let source_url = "https://example.com/singles";
+        let target_group = owner_group("Albums", target_url, "example/Albums");
+        let source_group = owner_group("Singles", source_url, "example/Singles");
+        write_file(&save_root.join("example/Albums/Album.m4a"), b"album");
+        write_file(
+            &save_root.join("example/Albums/.slisic.collection.toml"),
+            format!(
+                "version = 1\ngroups = []\n\n[collection]\nname = \"Albums\"\nurl = \"{target_url}\"\nfolder = \"example/Albums\"\n\n[[music]]\nname = \"Album\"\nalias = \"Album\"\nurl = \"https://example.com/watch/Album\"\npath = \"Album.m4a\"\ngroup_url = \"{target_url}\"\nstart_ms = 0\nend_ms = 60000\nliked = false\n"
+            )
+            .as_bytes(),
+        );
+        write_file(&save_root.join("example/Singles/Single.m4a"), b"single");
+        write_file(
+            &save_root.join("example/Singles/.slisic.collection.toml"),
+            b"version = 1",
+        );
+        upsert_collection(&collection(
+            "Albums",
+            target_url,
+            "example/Albums",
+            vec![music("Album", &target_group, "Album.m4a", false)],
+        ))
+        .await
+        .expect("target should save");
+        let source = upsert_collection(&collection(
+            "Singles",
+            source_url,
+            "example/Singles",
+            vec![music("Single", &source_group, "Single.m4a", true)],
+        ))
+        .await
+        .expect("source should save");
+        save_playlist("Mix", vec![source.clone()], vec![]).await;
+
+        assert!(
+            merge_collections(&save_root, target_url, target_url)
+                .await
+                .is_err()
+        );
+        let merged = merge_collections(&save_root, target_url, source_url)
+            .await
+            .expect("merge should succeed");
+
+        let single = merged
+            .musics
+            .iter()
+            .find(|music| music.name == "Single")
+            .expect("merged music should join the target");
+        assert_eq!(single.occurrence_id, source.musics[0].occurrence_id);
+        assert!(single.liked);
+        assert_eq!(single.group.url, source_url);
+        assert_eq!(single.group.folder, "Singles");
+        assert_eq!(single.path.as_deref(), Some("Singles/Single.m4a"));
+        assert!(
+            list_collection_groups(target_url)
+                .await
+                .expect("groups should list")
+                .iter()
+                .any(|group| group.url == source_url && group.name == "Singles")
+        );
+        assert!(
+            get_collection_by_url(source_url)
+                .await
+                .expect("collection lookup should succeed")
+                .is_none()
+        );
+
+        let target_dir = save_root.join("example/Albums");
+        assert!(target_dir.join("Singles/Single.m4a").is_file());
+        assert!(!target_dir.join("Singles/.slisic.collection.toml").exists());
+        assert!(!save_root.join("example/Singles").exists());
+        let manifest = std::fs::read_to_string(target_dir.join(".slisic.collection.toml"))
+            .expect("target manifest should exist");
+        assert!(manifest.contains("path = \"Singles/Single.m4a\""));
+        assert!(manifest.contains("folder = \"Singles\""));
+
+        let mix = get_playlist_by_name("Mix")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should exist");
+        assert!(mix.collections.is_empty());
+        assert_eq!(mix.groups.len(), 1);
+        assert_eq!(mix.groups[0].url, source_url);
+
+        let _ = std::fs::remove_dir_all(save_root);
+        reset_db();
+    });
+}
+
+#[test]
+fn splitting_turns_a_group_into_its_own_collection() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let save_root = temp_path("split");
+        let collection_url = "https://example.com/concerts";
+        let live_url = "https://example.com/concerts#group/Live";
+        let studio = owner_group("Concerts", collection_url, "example/Concerts");
+        let live = Group {
+            name: "Live".to_string(),
+            url: live_url.to_string(),
+            collection: owner("Concerts", collection_url, "example/Concerts"),
+            folder: "Live".to_string(),
+        };
+        write_file(&save_root.join("example/Concerts/Studio.m4a"), b"studio");
+        write_file(
+            &save_root.join("example/Concerts/Live/Encore.m4a"),
+            b"encore",
+        );
+        let stored = upsert_collection(&collection(
+            "Concerts",
+            collection_url,
+            "example/Concerts",
+            vec![
+                music("Studio", &studio, "Studio.m4a", false),
+                music("Encore", &live, "Live/Encore.m4a", true),
+            ],
+        ))
+        .await
+        .expect("collection should save");
+        let encore = stored
+            .musics
+            .iter()
+            .find(|music| music.name == "Encore")
+            .expect("encore should be stored")
+            .clone();
+        save_playlist("Everything", vec![stored.clone()], vec![]).await;
+        save_playlist("Only Live", vec![], vec![encore.group.clone()]).await;
+
+        assert!(
+            split_group_into_collection(&save_root, collection_url, collection_url)
+                .await
+                .is_err()
+        );
+        let split = split_group_into_collection(&save_root, collection_url, live_url)
+            .await
+            .expect("split should succeed");
+
+        assert_eq!(split.url, live_url);
+        assert_eq!(split.name, "Live");
+        assert_eq!(split.folder, "example/Live");
+        assert_eq!(split.musics.len(), 1);
+        assert_eq!(split.musics[0].occurrence_id, encore.occurrence_id);
+        assert!(split.musics[0].liked);
+        assert_eq!(split.musics[0].path.as_deref(), Some("Encore.m4a"));
+        assert!(save_root.join("example/Live/Encore.m4a").is_file());
+        assert!(!save_root.join("example/Concerts/Live").exists());
+        assert!(save_root.join("example/Concerts/Studio.m4a").is_file());
+
+        let remaining = get_collection_by_url(collection_url)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("original collection should remain");
+        assert_eq!(remaining.musics.len(), 1);
+        assert_eq!(remaining.musics[0].name, "Studio");
+        assert!(
+            list_collection_groups(collection_url)
+                .await
+                .expect("groups should list")
+                .iter()
+                .all(|group| group.url != live_url)
+        );
+
+        let everything = get_playlist_by_name("Everything")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should exist");
+        let mut urls = everything
+            .collections
+            .iter()
+            .map(|collection| collection.url.as_str())
+            .collect::<Vec<_>>();
+        urls.sort();
+        assert_eq!(urls, vec![collection_url, live_url]);
+        let only_live = get_playlist_by_name("Only Live")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should exist");
+        assert_eq!(only_live.groups.len(), 1);
+        assert_eq!(only_live.groups[0].url, live_url);
+
+        let _ = std::fs::remove_dir_all(save_root);
+        reset_db();
+    });
+}
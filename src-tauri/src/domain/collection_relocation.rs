@@ -0,0 +1,353 @@
+use crate::domain::collection_import::TEMP_DOWNLOAD_MARKER;
+use crate::domain::downloads::repo as download_repo;
+use crate::domain::meta::model::{DEFAULT_SAVE_ROOT_NAME, MetaInfo};
+use crate::domain::playlists::model::Collection;
+use crate::domain::playlists::repo as collection_repo;
+use crate::domain::save_root_migration::ensure_no_save_root_migration;
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri_specta::Event;
+use walkdir::WalkDir;
+
+#[cfg(test)]
+#[path = "collection_relocation.test.rs"]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionRelocationStage {
+    Copying,
+    Updating,
+    Cleaning,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct CollectionRelocationProgress {
+    pub collection_url: String,
+    pub stage: CollectionRelocationStage,
+    pub copied_files: u32,
+    pub total_files: u32,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/**
+ * Behavior:
+ *   Move one collection folder to another save root, then point the
+ *   collection at its new location and drop the old folder.
+ *
+ * Core invariants:
+ *   - Records are only updated after every file was copied and verified, so
+ *     an interrupted move leaves the collection playable from its old root.
+ *   - A collection is never merged into an existing non-empty folder.
+ *   - Collections with an active download are not moved, and nothing moves
+ *     while a save path migration is running or unfinished.
+ */
+pub(crate) async fn move_collection_to_save_root(
+    meta: &MetaInfo,
+    collection_url: &str,
+    target_root_name: &str,
+    mut on_progress: impl FnMut(&CollectionRelocationProgress) + Send + 'static,
+) -> Result<Collection> {
+    let Some(collection) = collection_repo::get_collection_by_url(collection_url).await? else {
+        bail!("collection `{collection_url}` was not found");
+    };
+    let target_root_name = Some(target_root_name).filter(|name| *name != DEFAULT_SAVE_ROOT_NAME);
+    if collection.save_root.as_deref() == target_root_name {
+        return Ok(collection);
+    }
+
+    let current_root = meta
+        .save_root_path(collection.save_root.as_deref())
+        .ok_or_else(|| {
+            anyhow!(
+                "collection `{collection_url}` lives on unknown save root `{}`",
+                collection
+                    .save_root
+                    .as_deref()
+                    .unwrap_or(DEFAULT_SAVE_ROOT_NAME)
+            )
+        })?;
+    let target_root = meta.save_root_path(target_root_name).ok_or_else(|| {
+        anyhow!(
+            "save root `{}` is not configured",
+            target_root_name.unwrap_or_default()
+        )
+    })?;
+    ensure_no_save_root_migration().await?;
+    if download_repo::list_tasks().await?.iter().any(|task| {
+        task.status.is_active() && task.collection_url.as_deref() == Some(collection_url)
+    }) {
+        bail!("collection `{collection_url}` cannot move while it is downloading");
+    }
+
+    let source_dir = current_root.join(&collection.folder);
+    let relative_folder = collection_folder_within_root(&collection.folder, &current_root);
+    let target_dir = target_root.join(&relative_folder);
+    let target_folder = relative_folder.to_string_lossy().replace('\\', "/");
+
+    ensure_empty_relocation_target(&target_dir)?;
+
+    let copy_source = source_dir.clone();
+    let copy_target = target_dir.clone();
+    let url = collection_url.to_string();
+    let (mut progress, mut on_progress) = tokio::task::spawn_blocking(move || {
+        match copy_collection_tree(&url, &copy_source, &copy_target, &mut on_progress) {
+            Ok(progress) => Ok((progress, on_progress)),
+            Err(error) => {
+                discard_partial_copy(&copy_target);
+                Err(error)
+            }
+        }
+    })
+    .await??;
+
+    progress.stage = CollectionRelocationStage::Updating;
+    on_progress(&progress);
+    let relocated = relocate_collection_record(
+        &collection,
+        target_folder,
+        target_root_name.map(ToOwned::to_owned),
+        &source_dir,
+        &target_dir,
+    );
+    let saved = match collection_repo::upsert_collection(&relocated).await {
+        Ok(saved) => saved,
+        Err(error) => {
+            discard_partial_copy(&target_dir);
+            return Err(error);
+        }
+    };
+
+    progress.stage = CollectionRelocationStage::Cleaning;
+    on_progress(&progress);
+    if source_dir.is_dir()
+        && let Err(error) = std::fs::remove_dir_all(&source_dir)
+    {
+        log::warn!(
+            target: "collection_relocation",
+            "source_cleanup_failed collection_url=\"{}\" path=\"{}\" error=\"{}\"",
+            collection_url,
+            source_dir.display(),
+            error
+        );
+    }
+
+    progress.stage = CollectionRelocationStage::Done;
+    on_progress(&progress);
+    Ok(saved)
+}
+
+pub(crate) fn discard_partial_copy(target_dir: &Path) {
+    if target_dir.exists()
+        && let Err(error) = std::fs::remove_dir_all(target_dir)
+    {
+        log::warn!(
+            target: "collection_relocation",
+            "partial_copy_cleanup_failed path=\"{}\" error=\"{}\"",
+            target_dir.display(),
+            error
+        );
+    }
+}
+
+/// The collection folder relative to the root it lives on. Folders outside
+/// every root, like local imports from elsewhere, keep only their last name.
+pub(crate) fn collection_folder_within_root(folder: &str, root: &Path) -> PathBuf {
+    let folder = Path::new(folder);
+    if folder.is_relative() {
+        return folder.to_path_buf();
+    }
+
+    match folder.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative.to_path_buf(),
+        _ => folder
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("collection")),
+    }
+}
+
+/**
+ * Behavior:
+ *   Copy every file below `source` into `target`, keeping the layout, and
+ *   verify each copy by size and SHA-256 before reporting it.
+ *
+ * Core invariants:
+ *   - Unfinished download temp files are not copied.
+ */
+pub(crate) fn copy_collection_tree(
+    collection_url: &str,
+    source: &Path,
+    target: &Path,
+    on_progress: &mut impl FnMut(&CollectionRelocationProgress),
+) -> Result<CollectionRelocationProgress> {
+    let files = if source.is_dir() {
+        WalkDir::new(source)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_none_or(|name| !name.contains(TEMP_DOWNLOAD_MARKER))
+            })
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    let mut progress = CollectionRelocationProgress {
+        collection_url: collection_url.to_string(),
+        stage: CollectionRelocationStage::Copying,
+        copied_files: 0,
+        total_files: files.len() as u32,
+        copied_bytes: 0,
+        total_bytes: files
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+    };
+    on_progress(&progress);
+
+    std::fs::create_dir_all(target)
+        .with_context(|| format!("failed to create {}", target.display()))?;
+    for file in files {
+        let relative = file.strip_prefix(source)?;
+        let destination = target.join(relative);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let copied = copy_verified_file(&file, &destination)?;
+
+        progress.copied_files += 1;
+        progress.copied_bytes += copied;
+        on_progress(&progress);
+    }
+
+    Ok(progress)
+}
+
+/// Copies one file and checks the copy by size and SHA-256, returning the
+/// number of bytes copied.
+pub(crate) fn copy_verified_file(source: &Path, destination: &Path) -> Result<u64> {
+    let copied = std::fs::copy(source, destination).with_context(|| {
+        format!(
+            "failed to copy {} to {}",
+            source.display(),
+            destination.display()
+        )
+    })?;
+    let expected = std::fs::metadata(source)?.len();
+    if copied != expected
+        || std::fs::metadata(destination)?.len() != expected
+        || file_sha256(source)? != file_sha256(destination)?
+    {
+        bail!("copy of {} could not be verified", source.display());
+    }
+
+    Ok(copied)
+}
+
+fn file_sha256(path: &Path) -> Result<Vec<u8>> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0_u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+pub(crate) fn ensure_empty_relocation_target(target: &Path) -> Result<()> {
+    if target.is_dir()
+        && std::fs::read_dir(target)
+            .with_context(|| format!("failed to read {}", target.display()))?
+            .next()
+            .is_some()
+    {
+        bail!("relocation target {} is not empty", target.display());
+    }
+
+    Ok(())
+}
+
+/// Points a collection, its root groups and any absolute music paths inside
+/// the old folder at the new location.
+pub(crate) fn relocate_collection_record(
+    collection: &Collection,
+    target_folder: String,
+    target_root_name: Option<String>,
+    source_dir: &Path,
+    target_dir: &Path,
+) -> Collection {
+    let mut relocated = collection.clone();
+    for music in &mut relocated.musics {
+        if music.group.folder == collection.folder {
+            music.group.folder = target_folder.clone();
+        }
+        if let Some(path) = music.path.as_deref()
+            && let Ok(relative) = Path::new(path).strip_prefix(source_dir)
+        {
+            music.path = Some(target_dir.join(relative).to_string_lossy().to_string());
+        }
+    }
+    relocated.folder = target_folder;
+    relocated.save_root = target_root_name;
+    relocated
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn move_collection_to_root(
+    app: tauri::AppHandle,
+    collection_url: String,
+    root_name: String,
+) -> Result<Collection, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    crate::domain::meta::service::resolve_named_save_root(&app, Some(&root_name))
+        .await
+        .map_err(|error| error.to_string())?;
+    let meta = crate::domain::meta::repo::get_meta_info()
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| "save path should always be configured".to_string())?;
+
+    let progress_app = app.clone();
+    let collection =
+        move_collection_to_save_root(&meta, &collection_url, &root_name, move |progress| {
+            if let Err(error) = progress.clone().emit(&progress_app) {
+                log::warn!(
+                    target: "collection_relocation",
+                    "progress_emit_failed collection_url=\"{}\" error=\"{}\"",
+                    progress.collection_url,
+                    error
+                );
+            }
+        })
+        .await
+        .map_err(|error| error.to_string())?;
+
+    crate::domain::playlist_playback::service::notify_music_library_inputs_changed(
+        "collection_relocate",
+    );
+    crate::domain::playlist_playback::service::notify_playable_library_changed();
+    Ok(collection)
+}
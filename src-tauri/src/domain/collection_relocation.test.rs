@@ -0,0 +1,174 @@
+use super::{
+    CollectionRelocationStage, collection_folder_within_root, copy_collection_tree,
+    ensure_empty_relocation_target, move_collection_to_save_root,
+};
+use crate::domain::meta::model::{MetaInfo, SaveRoot};
+use crate::domain::playlists::model::Collection;
+use crate::domain::playlists::repo::{get_collection_by_url, upsert_collection};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, music, owner_group, run_async, temp_path, write_file,
+};
+use appdb::connection::reset_db;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const COLLECTION_URL: &str = "https://example.com/relocate";
+const COLLECTION_FOLDER: &str = "example/Relocate";
+
+fn collection() -> Collection {
+    let mut music = music(
+        "Relocated",
+        &owner_group("Relocate", COLLECTION_URL, COLLECTION_FOLDER),
+    );
+    music.liked = true;
+    crate::domain::test_support::collection(
+        "Relocate",
+        COLLECTION_URL,
+        COLLECTION_FOLDER,
+        vec![music],
+    )
+}
+
+#[test]
+fn copy_collection_tree_keeps_layout_and_skips_temp_downloads() {
+    let source = temp_path("copy_source");
+    let target = temp_path("copy_target");
+    write_file(&source.join("Track.m4a"), b"track");
+    write_file(&source.join("Disc 2/Other.m4a"), b"other track");
+    write_file(&source.join(".slisic.collection.toml"), b"version = 1");
+    write_file(&source.join(".slisic.leaves/leaf.toml"), b"leaf");
+    write_file(&source.join("Next.__slisic_tmp__leaf.m4a"), b"partial");
+
+    let mut reports = Vec::new();
+    let progress = copy_collection_tree(COLLECTION_URL, &source, &target, &mut |progress| {
+        reports.push(progress.clone())
+    })
+    .expect("copy should succeed");
+
+    assert_eq!(progress.stage, CollectionRelocationStage::Copying);
+    assert_eq!((progress.copied_files, progress.total_files), (4, 4));
+    assert_eq!(progress.copied_bytes, progress.total_bytes);
+    assert_eq!(reports.len(), 5);
+    assert_eq!(
+        std::fs::read(target.join("Disc 2/Other.m4a")).expect("copy should exist"),
+        b"other track"
+    );
+    assert!(target.join(".slisic.leaves/leaf.toml").is_file());
+    assert!(target.join(".slisic.collection.toml").is_file());
+    assert!(!target.join("Next.__slisic_tmp__leaf.m4a").exists());
+    assert!(ensure_empty_relocation_target(&target).is_err());
+    assert!(ensure_empty_relocation_target(&target.join("missing")).is_ok());
+
+    let _ = std::fs::remove_dir_all(source);
+    let _ = std::fs::remove_dir_all(target);
+}
+
+#[test]
+fn collection_folder_within_root_strips_the_current_root() {
+    let root = std::env::temp_dir().join("hdd").join("slisic");
+    let elsewhere = std::env::temp_dir().join("elsewhere").join("Mix");
+
+    assert_eq!(
+        collection_folder_within_root("youtube/Mix", &root),
+        PathBuf::from("youtube/Mix")
+    );
+    assert_eq!(
+        collection_folder_within_root(&root.join("youtube/Mix").to_string_lossy(), &root),
+        PathBuf::from("youtube/Mix")
+    );
+    assert_eq!(
+        collection_folder_within_root(&elsewhere.to_string_lossy(), &root),
+        PathBuf::from("Mix")
+    );
+}
+
+#[test]
+fn moving_a_collection_between_roots_updates_folder_root_and_files() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let default_root = temp_path("ssd");
+        let hdd_root = temp_path("hdd");
+        write_file(
+            &default_root.join(COLLECTION_FOLDER).join("Relocated.m4a"),
+            b"audio",
+        );
+        upsert_collection(&collection())
+            .await
+            .expect("collection should save");
+        let meta = MetaInfo {
+            save_path: Some(default_root.to_string_lossy().to_string()),
+            save_roots: Some(vec![SaveRoot {
+                name: "hdd".to_string(),
+                path: hdd_root.to_string_lossy().to_string(),
+            }]),
+        };
+        meta.publish_save_roots();
+
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&stages);
+        let moved = move_collection_to_save_root(&meta, COLLECTION_URL, "hdd", move |progress| {
+            recorded
+                .lock()
+                .expect("stage log should lock")
+                .push(progress.stage)
+        })
+        .await
+        .expect("move to the named root should succeed");
+
+        let moved_folder = hdd_root.join(COLLECTION_FOLDER);
+        assert_eq!(moved.save_root.as_deref(), Some("hdd"));
+        assert_eq!(moved.folder, COLLECTION_FOLDER);
+        assert_eq!(moved.dir(&default_root), moved_folder);
+        assert!(moved_folder.join("Relocated.m4a").is_file());
+        assert!(!default_root.join(COLLECTION_FOLDER).exists());
+        assert_eq!(
+            stages.lock().expect("stage log should lock").last(),
+            Some(&CollectionRelocationStage::Done)
+        );
+        let stored = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(stored.musics[0].group.folder, moved.folder);
+        assert!(stored.musics[0].liked);
+
+        let remounted_root = temp_path("hdd-remounted");
+        MetaInfo {
+            save_path: meta.save_path.clone(),
+            save_roots: Some(vec![SaveRoot {
+                name: "hdd".to_string(),
+                path: remounted_root.to_string_lossy().to_string(),
+            }]),
+        }
+        .publish_save_roots();
+        assert_eq!(
+            stored.dir(&default_root),
+            remounted_root.join(COLLECTION_FOLDER)
+        );
+        meta.publish_save_roots();
+
+        let back = move_collection_to_save_root(&meta, COLLECTION_URL, "default", |_| {})
+            .await
+            .expect("move back to the default root should succeed");
+        assert_eq!(back.save_root, None);
+        assert_eq!(back.folder, COLLECTION_FOLDER);
+        assert!(
+            default_root
+                .join(COLLECTION_FOLDER)
+                .join("Relocated.m4a")
+                .is_file()
+        );
+        assert!(
+            move_collection_to_save_root(&meta, COLLECTION_URL, "usb", |_| {})
+                .await
+                .is_err()
+        );
+
+        let _ = std::fs::remove_dir_all(default_root);
+        let _ = std::fs::remove_dir_all(hdd_root);
+        reset_db();
+    });
+}
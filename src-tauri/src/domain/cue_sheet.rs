@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "cue_sheet.test.rs"]
+mod tests;
+
+const CUE_FRAMES_PER_SECOND: u32 = 75;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CueSheet {
+    pub(crate) title: Option<String>,
+    pub(crate) performer: Option<String>,
+    pub(crate) genre: Option<String>,
+    pub(crate) year: Option<u32>,
+    pub(crate) files: Vec<CueSheetFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CueSheetFile {
+    /// File name as written in the sheet, relative to the sheet's folder.
+    pub(crate) name: String,
+    pub(crate) tracks: Vec<CueSheetTrack>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CueSheetTrack {
+    pub(crate) number: u32,
+    pub(crate) title: Option<String>,
+    pub(crate) performer: Option<String>,
+    /// `INDEX 01` of the track; `None` when the sheet left it out.
+    pub(crate) start_ms: Option<u32>,
+}
+
+/// One track of a sheet file, cut to the range it covers in the audio file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CueTrackRange {
+    pub(crate) number: u32,
+    pub(crate) title: Option<String>,
+    pub(crate) performer: Option<String>,
+    pub(crate) start_ms: u32,
+    pub(crate) end_ms: u32,
+}
+
+pub(crate) fn is_cue_sheet_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("cue"))
+}
+
+/// Reads a cue sheet, accepting UTF-8 with or without BOM and falling back to
+/// Latin-1 for the legacy sheets most rippers still write.
+pub(crate) fn read_cue_sheet(path: &Path) -> Result<CueSheet> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|byte| char::from(*byte)).collect(),
+    };
+    Ok(parse_cue_sheet(&text))
+}
+
+/**
+ * Behavior:
+ *   Parse the commands of a cue sheet that describe tracks: `FILE`, `TRACK`,
+ *   `TITLE`, `PERFORMER`, `INDEX 01` and the `REM GENRE` / `REM DATE`
+ *   comments rippers write.
+ *
+ * Core invariants:
+ *   - Commands are matched case-insensitively; unknown commands are skipped.
+ *   - `TITLE` and `PERFORMER` before the first `TRACK` describe the album.
+ *   - Tracks before the first `FILE` have no audio to point at and are
+ *     dropped.
+ */
+pub(crate) fn parse_cue_sheet(text: &str) -> CueSheet {
+    let mut sheet = CueSheet::default();
+    for line in text.lines() {
+        let (command, rest) = split_cue_command(line.trim());
+        match command.to_ascii_uppercase().as_str() {
+            "FILE" => sheet.files.push(CueSheetFile {
+                name: parse_cue_file_name(rest),
+                tracks: Vec::new(),
+            }),
+            "TRACK" => {
+                let Some(file) = sheet.files.last_mut() else {
+                    continue;
+                };
+                let Some(number) = rest
+                    .split_whitespace()
+                    .next()
+                    .and_then(|number| number.parse().ok())
+                else {
+                    continue;
+                };
+                file.tracks.push(CueSheetTrack {
+                    number,
+                    title: None,
+                    performer: None,
+                    start_ms: None,
+                });
+            }
+            "TITLE" | "PERFORMER" => {
+                let value = parse_cue_value(rest);
+                let current_track = sheet
+                    .files
+                    .last_mut()
+                    .and_then(|file| file.tracks.last_mut());
+                let target = match (current_track, command.eq_ignore_ascii_case("TITLE")) {
+                    (Some(track), true) => &mut track.title,
+                    (Some(track), false) => &mut track.performer,
+                    (None, true) => &mut sheet.title,
+                    (None, false) => &mut sheet.performer,
+                };
+                *target = value;
+            }
+            "INDEX" => {
+                let mut parts = rest.split_whitespace();
+                if parts.next().and_then(|index| index.parse::<u32>().ok()) != Some(1) {
+                    continue;
+                }
+                let start_ms = parts.next().and_then(parse_cue_timestamp_ms);
+                if let Some(track) = sheet
+                    .files
+                    .last_mut()
+                    .and_then(|file| file.tracks.last_mut())
+                {
+                    track.start_ms = start_ms;
+                }
+            }
+            "REM" => {
+                let (comment, value) = split_cue_command(rest);
+                match comment.to_ascii_uppercase().as_str() {
+                    "GENRE" => sheet.genre = parse_cue_value(value),
+                    "DATE" => {
+                        sheet.year = value
+                            .trim()
+                            .trim_matches('"')
+                            .get(..4)
+                            .and_then(|year| year.parse().ok());
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    sheet
+}
+
+/**
+ * Behavior:
+ *   Cut the tracks of one sheet file into ranges of an audio file that is
+ *   `duration_ms` long. Each track ends where the next one starts; the last
+ *   one ends with the file.
+ *
+ * Core invariants:
+ *   - Tracks without `INDEX 01`, or whose start does not move forward, make
+ *     the whole sheet file unusable, so an empty list is returned.
+ *   - Tracks starting at or past the end of the file are dropped.
+ */
+pub(crate) fn cue_track_ranges(file: &CueSheetFile, duration_ms: u32) -> Vec<CueTrackRange> {
+    let mut starts = Vec::with_capacity(file.tracks.len());
+    for track in &file.tracks {
+        let Some(start_ms) = track.start_ms else {
+            return Vec::new();
+        };
+        if starts.last().is_some_and(|previous| *previous >= start_ms) {
+            return Vec::new();
+        }
+        starts.push(start_ms);
+    }
+
+    file.tracks
+        .iter()
+        .zip(&starts)
+        .enumerate()
+        .filter(|(_, (_, start_ms))| **start_ms < duration_ms)
+        .map(|(index, (track, start_ms))| CueTrackRange {
+            number: track.number,
+            title: track.title.clone(),
+            performer: track.performer.clone(),
+            start_ms: *start_ms,
+            end_ms: starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(duration_ms)
+                .min(duration_ms),
+        })
+        .collect()
+}
+
+fn split_cue_command(line: &str) -> (&str, &str) {
+    match line.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    }
+}
+
+fn parse_cue_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+        .trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// `FILE "name with spaces.flac" WAVE`: the name is quoted, or the text
+/// before the trailing file type.
+fn parse_cue_file_name(rest: &str) -> String {
+    if let Some(quoted) = rest.strip_prefix('"')
+        && let Some((name, _)) = quoted.split_once('"')
+    {
+        return name.to_string();
+    }
+    match rest.rsplit_once(char::is_whitespace) {
+        Some((name, _)) => name.trim().to_string(),
+        None => rest.to_string(),
+    }
+}
+
+/// `mm:ss:ff`, where frames are 1/75 of a second.
+fn parse_cue_timestamp_ms(value: &str) -> Option<u32> {
+    let mut parts = value.split(':');
+    let minutes = parts.next()?.parse::<u32>().ok()?;
+    let seconds = parts.next()?.parse::<u32>().ok()?;
+    let frames = parts.next()?.parse::<u32>().ok()?;
+    if parts.next().is_some() || seconds >= 60 || frames >= CUE_FRAMES_PER_SECOND {
+        return None;
+    }
+    let whole_ms = minutes
+        .checked_mul(60)?
+        .checked_add(seconds)?
+        .checked_mul(1_000)?;
+    whole_ms.checked_add(frames * 1_000 / CUE_FRAMES_PER_SECOND)
+}
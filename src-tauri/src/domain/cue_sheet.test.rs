@@ -0,0 +1,98 @@
+use super::{CueSheetFile, CueSheetTrack, CueTrackRange, cue_track_ranges, parse_cue_sheet};
+
+const ALBUM_SHEET: &str = "\
+REM GENRE \"Ambient\"
+REM DATE 1998/05
+PERFORMER \"Album Artist\"
+TITLE \"Album Title\"
+FILE \"Album Title.flac\" WAVE
+  TRACK 01 AUDIO
+    TITLE \"Opening\"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE \"Guest Song\"
+    PERFORMER \"Guest\"
+    INDEX 00 03:58:00
+    INDEX 01 04:00:37
+";
+
+#[test]
+fn parses_album_fields_and_track_starts() {
+    let sheet = parse_cue_sheet(ALBUM_SHEET);
+
+    assert_eq!(sheet.title.as_deref(), Some("Album Title"));
+    assert_eq!(sheet.performer.as_deref(), Some("Album Artist"));
+    assert_eq!(sheet.genre.as_deref(), Some("Ambient"));
+    assert_eq!(sheet.year, Some(1998));
+    assert_eq!(
+        sheet.files,
+        vec![CueSheetFile {
+            name: "Album Title.flac".to_string(),
+            tracks: vec![
+                CueSheetTrack {
+                    number: 1,
+                    title: Some("Opening".to_string()),
+                    performer: None,
+                    start_ms: Some(0),
+                },
+                CueSheetTrack {
+                    number: 2,
+                    title: Some("Guest Song".to_string()),
+                    performer: Some("Guest".to_string()),
+                    start_ms: Some(240_493),
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn unquoted_file_names_drop_the_trailing_file_type() {
+    let sheet = parse_cue_sheet("file side a.wav WAVE\ntrack 1 audio\nindex 01 00:00:00\n");
+
+    assert_eq!(sheet.files[0].name, "side a.wav");
+    assert_eq!(sheet.files[0].tracks[0].start_ms, Some(0));
+}
+
+#[test]
+fn track_ranges_end_at_the_next_start_and_the_last_at_the_file_end() {
+    let sheet = parse_cue_sheet(ALBUM_SHEET);
+
+    assert_eq!(
+        cue_track_ranges(&sheet.files[0], 420_000),
+        vec![
+            CueTrackRange {
+                number: 1,
+                title: Some("Opening".to_string()),
+                performer: None,
+                start_ms: 0,
+                end_ms: 240_493,
+            },
+            CueTrackRange {
+                number: 2,
+                title: Some("Guest Song".to_string()),
+                performer: Some("Guest".to_string()),
+                start_ms: 240_493,
+                end_ms: 420_000,
+            },
+        ]
+    );
+    assert_eq!(cue_track_ranges(&sheet.files[0], 240_000).len(), 1);
+    assert_eq!(
+        cue_track_ranges(&sheet.files[0], 240_000)[0].end_ms,
+        240_000
+    );
+}
+
+#[test]
+fn track_ranges_reject_missing_or_backward_starts() {
+    let missing = parse_cue_sheet(
+        "FILE \"a.flac\" WAVE\nTRACK 01 AUDIO\nTRACK 02 AUDIO\nINDEX 01 01:00:00\n",
+    );
+    let backward = parse_cue_sheet(
+        "FILE \"a.flac\" WAVE\nTRACK 01 AUDIO\nINDEX 01 02:00:00\nTRACK 02 AUDIO\nINDEX 01 01:00:00\n",
+    );
+
+    assert!(cue_track_ranges(&missing.files[0], 300_000).is_empty());
+    assert!(cue_track_ranges(&backward.files[0], 300_000).is_empty());
+}
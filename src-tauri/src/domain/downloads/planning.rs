@@ -147,6 +147,7 @@ async fn resolve_collection_plan_from_root_probe(
                     .as_ref()
                     .and_then(|collection| collection.enable_updates)
                     .or(Some(false)),
+                save_root: None,
             };
             let discovered_leaf_count = list.entries.len();
             let partial_reason =
@@ -245,6 +246,7 @@ fn residual_task_collection_owner(task: &DownloadTask) -> Option<Collection> {
         musics: vec![],
         last_updated: now_timestamp(),
         enable_updates: None,
+        save_root: None,
     })
 }
 
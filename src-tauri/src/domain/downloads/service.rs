@@ -23,6 +23,8 @@ use super::yt_dlp::{
     classify_root_preference,
 };
 #[cfg(not(test))]
+use crate::domain::audio_tags::probe_audio_tags_best_effort;
+#[cfg(not(test))]
 use crate::domain::audio_tail_trim::{self, AudioTailTrimRequest};
 use crate::domain::collection_import;
 use crate::domain::collection_import::PlannedLeaf;
@@ -30,10 +32,12 @@ use crate::domain::collection_import::{CollectionShellPlan, CollectionSyncPlan};
 #[cfg(not(test))]
 use crate::domain::loudness_evidence::{self, LoudnessEvidenceRequest};
 #[cfg(not(test))]
+use crate::domain::meta::model::collection_dir;
+#[cfg(not(test))]
 use crate::domain::meta::service as meta_service;
 #[cfg(not(test))]
 use crate::domain::player::event::{PlaybackDiagnosticTraceDetail, PlaybackDiagnosticTraceEvent};
-use crate::domain::playlists::model::{Collection, Group};
+use crate::domain::playlists::model::{Collection, Group, MusicTags};
 #[cfg(not(test))]
 use crate::utils::binaries::acquire_managed_binary_usage;
 #[cfg(not(test))]
@@ -766,6 +770,7 @@ pub async fn enqueue_collection_download(url: String) -> Result<EnqueuedCollecti
 }
 
 pub async fn resume_download_task(task_id: String) -> Result<DownloadTask> {
+    ensure_downloads_may_start().await?;
     let mut task = repo::get_task(&task_id).await?;
     if task.status == DownloadTaskStatus::Completed {
         bail!("completed download tasks cannot be resumed");
@@ -782,6 +787,7 @@ pub async fn submit_youtube_cookies_and_resume_download_task(
     cookies: String,
     path: PathBuf,
 ) -> Result<DownloadTask> {
+    ensure_downloads_may_start().await?;
     let normalized = normalize_youtube_cookies_text(&cookies)?;
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
@@ -1179,6 +1185,7 @@ async fn enqueue_collection_download_with_trigger(
         trigger.as_str(),
         url
     );
+    ensure_downloads_may_start().await?;
     let (task, collection) = match prepare_task_enqueue_outcome(url, trigger).await? {
         PreparedTaskEnqueue::Existing(task) => {
             log::info!(
@@ -1522,6 +1529,7 @@ fn spawn_task(task_id: String, root_probe: Option<RootProbe>) -> Result<()> {
         let _claim = ActiveDownloadTaskClaim::new(task_id.clone());
         let task_id_for_worker = task_id.clone();
         let handle = tauri::async_runtime::spawn(async move {
+            let _restore_guard = crate::domain::library_backup::acquire_library_write_guard().await;
             run_task(task_id_for_worker, app, root_probe).await
         });
         match handle.await {
@@ -1547,6 +1555,20 @@ fn spawn_task(_task_id: String, _root_probe: Option<RootProbe>) -> Result<()> {
     Ok(())
 }
 
+/// Downloads write into collection folders, so none starts while a save path
+/// migration is running or unfinished, or while a backup is restored.
+#[cfg(not(test))]
+async fn ensure_downloads_may_start() -> Result<()> {
+    crate::domain::save_root_migration::ensure_no_save_root_migration().await?;
+    drop(crate::domain::library_backup::try_acquire_library_write_guard()?);
+    Ok(())
+}
+
+#[cfg(test)]
+async fn ensure_downloads_may_start() -> Result<()> {
+    Ok(())
+}
+
 #[cfg(not(test))]
 async fn bootstrap_enqueued_collection_with_deps(
     task: DownloadTask,
@@ -1936,6 +1958,7 @@ async fn fill_leaf_pipeline(
     spawn_ready_leaf_downloads(
         pipeline,
         task_snapshot,
+        collection,
         source_kind,
         save_root,
         client.clone(),
@@ -2319,7 +2342,7 @@ async fn finalize_existing_leaf_files(
         persist_outcome.changed
     );
     if let Err(error) = collection_import::write_raw_leaf_manifest_evidence_batch(
-        &save_root.join(&collection.folder),
+        &collection.dir(&save_root),
         &collection,
         source_kind,
         &materializations,
@@ -2365,6 +2388,7 @@ async fn handle_leaf_pipeline_event(
         spawn_ready_leaf_downloads(
             pipeline,
             task_snapshot,
+            collection,
             source_kind,
             save_root,
             client,
@@ -2728,7 +2752,7 @@ async fn handle_prepared_leaf_download(
     let file_stem = sanitize_path_component(&prepared.probe.title);
     let music_group = resolve_music_group(group.clone(), collection);
     leaf_snapshot.group = Some(DownloadLeafGroupContext::from(music_group.clone()));
-    let target_dir = save_root.join(&collection.folder);
+    let target_dir = collection.dir(save_root);
     let temp_file_stem = temporary_download_stem(&file_stem, &leaf_snapshot.url, &music_group.url);
 
     if let Some(relative_path) = collection_import::resolve_existing_leaf_file(
@@ -2764,6 +2788,9 @@ async fn handle_prepared_leaf_download(
                 downloaded_path.clone(),
             )
             .await?;
+            let tags =
+                completed_local_audio_tags(ffmpeg_path.to_path_buf(), downloaded_path.clone())
+                    .await;
             log::debug!(
                 target: "downloads",
                 "leaf_found_existing_temp_file leaf={} path=\"{}\"",
@@ -2780,6 +2807,7 @@ async fn handle_prepared_leaf_download(
                     downloaded: super::yt_dlp::DownloadedLeaf {
                         absolute_path: downloaded_path,
                         duration_ms: Some(duration_ms),
+                        tags,
                     },
                     progress: DownloadProgress::default(),
                     retry_failures: 0,
@@ -2856,7 +2884,7 @@ pub(crate) fn existing_file_completions_from_task_leaves(
             leaf: work_item.leaf.clone(),
             music_probe,
             group,
-            absolute_path: save_root.join(&collection.folder).join(&relative_path),
+            absolute_path: collection.dir(save_root).join(&relative_path),
             relative_path,
             readiness: work_item.readiness,
         });
@@ -2922,6 +2950,11 @@ fn sync_existing_leaf_completion_into_collection(
         &completion.relative_path,
         completion.group.clone(),
     );
+    collection_import::attach_lyrics_sidecar(
+        &mut materialized,
+        &completion.absolute_path,
+        &completion.relative_path,
+    );
     let replacement_group_url = materialized.first().map(|music| music.group.url.clone());
     let mut replaced = false;
     let mut next_musics = Vec::with_capacity(collection.musics.len() + materialized.len());
@@ -2972,10 +3005,17 @@ async fn probe_existing_file_batch_durations(
                     completion.relative_path
                 );
                 let duration_result = completed_local_audio_duration_ms(
-                    ffmpeg_path,
+                    ffmpeg_path.clone(),
                     completion.absolute_path.clone(),
                 )
                 .await;
+                let tags = match duration_result {
+                    Ok(_) => {
+                        completed_local_audio_tags(ffmpeg_path, completion.absolute_path.clone())
+                            .await
+                    }
+                    Err(_) => None,
+                };
                 log::debug!(
                     target: "downloads",
                     "leaf_existing_file_duration_probe_finished leaf={} relative_path=\"{}\" status={} elapsed_ms={}",
@@ -2984,19 +3024,20 @@ async fn probe_existing_file_batch_durations(
                     if duration_result.is_ok() { "ok" } else { "failed" },
                     started.elapsed().as_millis()
                 );
-                (completion, duration_result)
+                (completion, duration_result, tags)
             });
         }
 
         let Some(joined) = workers.join_next().await else {
             break;
         };
-        let Ok((mut completion, duration_result)) = joined else {
+        let Ok((mut completion, duration_result, tags)) = joined else {
             continue;
         };
         match duration_result {
             Ok(duration_ms) => {
                 apply_completed_audio_duration_evidence(&mut completion.music_probe, duration_ms);
+                apply_completed_audio_tag_evidence(&mut completion.music_probe, tags);
                 completed.push(completion);
             }
             Err(error) => {
@@ -3070,6 +3111,7 @@ async fn pause_task_for_youtube_cookie_challenge_without_leaf(
 async fn spawn_ready_leaf_downloads(
     pipeline: &mut LeafPipelineState,
     task_snapshot: &mut DownloadTask,
+    collection: &Collection,
     source_kind: CollectionSourceKind,
     save_root: &Path,
     client: Arc<dyn YtDlpClient>,
@@ -3086,7 +3128,9 @@ async fn spawn_ready_leaf_downloads(
         task_snapshot.replace_leaf(leaf_snapshot.clone());
 
         let file_stem = sanitize_path_component(&prepared.probe.title);
-        let target_dir = save_root.join(
+        let target_dir = collection_dir(
+            save_root,
+            collection.save_root.as_deref(),
             task_snapshot
                 .collection_folder
                 .as_deref()
@@ -3238,6 +3282,7 @@ async fn handle_finished_leaf_download(
         leaf_snapshot.duration_ms = music_probe.duration_ms;
         leaf_snapshot.duration_seconds = music_probe.duration_seconds;
     }
+    apply_completed_audio_tag_evidence(&mut music_probe, downloaded.tags);
     let (file_name, persist_changed) = match persist_completed_leaf_download(
         collection,
         source_kind,
@@ -3323,6 +3368,7 @@ async fn handle_finished_leaf_download_for_test(
         leaf_snapshot.duration_ms = music_probe.duration_ms;
         leaf_snapshot.duration_seconds = music_probe.duration_seconds;
     }
+    apply_completed_audio_tag_evidence(&mut music_probe, downloaded.tags);
     let (file_name, _persist_changed) = match persist_completed_leaf_download(
         collection,
         source_kind,
@@ -3476,7 +3522,7 @@ fn request_committed_leaf_loudness_evidence_batch(
         let Some(relative_path) = music.path.as_deref() else {
             continue;
         };
-        let file_path = save_root.join(&collection.folder).join(relative_path);
+        let file_path = collection.dir(save_root).join(relative_path);
         if !file_path.is_file() {
             skipped_missing_file += 1;
             continue;
@@ -3612,7 +3658,7 @@ fn write_collection_manifest_after_download(
     probe: &LeafProbe,
     relative_path: &str,
 ) -> Result<()> {
-    let collection_root = save_root.join(&collection.folder);
+    let collection_root = collection.dir(save_root);
     let Some(group) = collection
         .musics
         .iter()
@@ -3860,6 +3906,7 @@ fn spawn_recovery(app: AppHandle) {
 
 #[cfg(not(test))]
 pub(crate) async fn recover_incomplete_download_tasks() -> Result<usize> {
+    ensure_downloads_may_start().await?;
     let tasks = repo::list_tasks().await?;
     let mut recovered = 0_usize;
 
@@ -3932,6 +3979,12 @@ async fn run_auto_update_cycle() -> Result<()> {
     Ok(())
 }
 
+pub(crate) fn apply_completed_audio_tag_evidence(probe: &mut LeafProbe, tags: Option<MusicTags>) {
+    if tags.is_some() {
+        probe.tags = tags;
+    }
+}
+
 pub(crate) fn apply_completed_audio_duration_evidence(probe: &mut LeafProbe, duration_ms: u32) {
     let previous_duration_ms = probe.duration_ms.or_else(|| {
         probe
@@ -3971,6 +4024,18 @@ async fn completed_local_audio_duration_ms(
     .await
 }
 
+#[cfg(not(test))]
+async fn completed_local_audio_tags(ffmpeg_path: PathBuf, file_path: PathBuf) -> Option<MusicTags> {
+    let usage = acquire_downloads_ffmpeg_probe_usage();
+    run_blocking(move || {
+        let _usage = usage;
+        Ok(probe_audio_tags_best_effort(&ffmpeg_path, &file_path))
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
 #[cfg(not(test))]
 fn build_client(app: &AppHandle, ffmpeg_path: &Path) -> Result<Arc<dyn YtDlpClient>> {
     let ytdlp_path =
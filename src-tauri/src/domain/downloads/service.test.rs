@@ -525,6 +525,7 @@ fn list_download_marks_finalize_failures_on_leaf_without_aborting_task() {
             musics: vec![],
             last_updated: "2026-05-24T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -562,6 +563,7 @@ fn list_download_marks_finalize_failures_on_leaf_without_aborting_task() {
                 downloaded: DownloadedLeaf {
                     absolute_path: downloaded_path,
                     duration_ms: None,
+                    tags: None,
                 },
                 progress: DownloadProgress::default(),
                 retry_failures: 0,
@@ -596,6 +598,7 @@ fn list_download_marks_finalize_failures_on_leaf_without_aborting_task() {
                 downloaded: DownloadedLeaf {
                     absolute_path: downloaded_path,
                     duration_ms: None,
+                    tags: None,
                 },
                 progress: DownloadProgress::default(),
                 retry_failures: 0,
@@ -639,6 +642,7 @@ fn materialize_music_entries_expands_chapters_without_splitting_files() {
                 end_ms: 180_000,
             },
         ],
+        tags: None,
     };
 
     let group = collection_group(
@@ -675,6 +679,7 @@ fn materialize_music_entries_falls_back_to_single_full_track_when_no_chapters_ex
         duration_ms: Some(245_500),
         duration_seconds: Some(245),
         chapters: vec![],
+        tags: None,
     };
 
     let group = collection_group("Singles", "https://example.com/singles", "youtube/singles");
@@ -702,6 +707,7 @@ fn materialize_music_entries_uses_precise_full_track_duration_evidence() {
         duration_ms: Some(257_499),
         duration_seconds: Some(257),
         chapters: vec![],
+        tags: None,
     };
 
     let group = collection_group(
@@ -734,6 +740,7 @@ fn completed_leaf_download_duration_evidence_overrides_probe_metadata() {
             musics: vec![],
             last_updated: "2026-05-31T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         let collection_owner = collection_group(
             "C418 - Releases",
@@ -765,6 +772,7 @@ fn completed_leaf_download_duration_evidence_overrides_probe_metadata() {
             duration_ms: Some(257_000),
             duration_seconds: Some(257),
             chapters: vec![],
+            tags: None,
         };
 
         handle_finished_leaf_download(
@@ -780,6 +788,7 @@ fn completed_leaf_download_duration_evidence_overrides_probe_metadata() {
                 downloaded: DownloadedLeaf {
                     absolute_path: downloaded_path,
                     duration_ms: Some(257_499),
+                    tags: None,
                 },
                 progress: DownloadProgress::default(),
                 retry_failures: 0,
@@ -816,6 +825,7 @@ fn single_leaf_download_completion_commits_music_and_manifest() {
             musics: vec![],
             last_updated: "2026-06-10T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -851,6 +861,7 @@ fn single_leaf_download_completion_commits_music_and_manifest() {
             duration_ms: Some(5_733_000),
             duration_seconds: Some(5_733),
             chapters: vec![],
+            tags: None,
         };
         let owner = collection_group(&collection.name, collection_url, collection_folder);
 
@@ -867,6 +878,7 @@ fn single_leaf_download_completion_commits_music_and_manifest() {
                 downloaded: DownloadedLeaf {
                     absolute_path: downloaded_path.clone(),
                     duration_ms: Some(5_733_000),
+                    tags: None,
                 },
                 progress: DownloadProgress::default(),
                 retry_failures: 0,
@@ -915,6 +927,7 @@ fn completed_audio_duration_evidence_replaces_integer_metadata_boundary() {
         duration_ms: Some(257_000),
         duration_seconds: Some(257),
         chapters: vec![],
+        tags: None,
     };
 
     apply_completed_audio_duration_evidence(&mut probe, 257_520);
@@ -937,6 +950,7 @@ fn completed_audio_duration_evidence_replaces_full_span_chapter_boundary() {
             start_ms: 0,
             end_ms: 344_437,
         }],
+        tags: None,
     };
 
     apply_completed_audio_duration_evidence(&mut probe, 344_455);
@@ -960,6 +974,7 @@ fn materialize_music_entries_uses_completed_duration_evidence_for_chapter_end()
             start_ms: 0,
             end_ms: 344_437,
         }],
+        tags: None,
     };
     apply_completed_audio_duration_evidence(&mut probe, 344_455);
 
@@ -1008,6 +1023,9 @@ fn persist_downloaded_leaf_music_replaces_only_the_matching_group_copy() {
                     end_ms: 10_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -1025,10 +1043,14 @@ fn persist_downloaded_leaf_music_replaces_only_the_matching_group_copy() {
                     end_ms: 10_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
             ],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
 
         let probe = LeafProbe {
@@ -1039,6 +1061,7 @@ fn persist_downloaded_leaf_music_replaces_only_the_matching_group_copy() {
             duration_ms: Some(10_000),
             duration_seconds: Some(10),
             chapters: vec![],
+            tags: None,
         };
         let save_root = temp_test_dir();
 
@@ -1111,6 +1134,9 @@ fn persist_downloaded_leaf_music_batch_replaces_only_matching_group_copies() {
                     end_ms: 10_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -1128,10 +1154,14 @@ fn persist_downloaded_leaf_music_batch_replaces_only_matching_group_copies() {
                     end_ms: 10_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
             ],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -1145,6 +1175,7 @@ fn persist_downloaded_leaf_music_batch_replaces_only_matching_group_copies() {
             duration_ms: Some(10_000),
             duration_seconds: Some(10),
             chapters: vec![],
+            tags: None,
         };
         let save_root = temp_test_dir();
 
@@ -1217,9 +1248,13 @@ fn persist_downloaded_leaf_music_reports_unchanged_for_repeated_leaf_materializa
                 end_ms: 10_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         let probe = LeafProbe {
             title: "Repeated Leaf".to_string(),
@@ -1229,6 +1264,7 @@ fn persist_downloaded_leaf_music_reports_unchanged_for_repeated_leaf_materializa
             duration_ms: Some(10_000),
             duration_seconds: Some(10),
             chapters: vec![],
+            tags: None,
         };
         let save_root = temp_test_dir();
 
@@ -1260,6 +1296,9 @@ fn persist_downloaded_leaf_music_reports_unchanged_for_repeated_leaf_materializa
             end_ms: 10_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         });
         let saved_with_neighbors = upsert_collection(&collection)
             .await
@@ -1320,9 +1359,13 @@ fn persist_downloaded_leaf_music_preserves_current_leaf_lifecycle_facts() {
                 end_ms: raw_end_ms,
                 liked: true,
                 loudness_profile: Some(profile),
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
 
         let saved = upsert_collection(&collection)
@@ -1353,6 +1396,7 @@ fn persist_downloaded_leaf_music_preserves_current_leaf_lifecycle_facts() {
             duration_ms: Some(raw_end_ms),
             duration_seconds: Some(raw_end_ms / 1_000),
             chapters: vec![],
+            tags: None,
         };
         let save_root = temp_test_dir();
 
@@ -1401,6 +1445,7 @@ fn persist_downloaded_leaf_music_rejects_partial_download_paths() {
             musics: vec![],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         let probe = LeafProbe {
             title: "Partial".to_string(),
@@ -1410,6 +1455,7 @@ fn persist_downloaded_leaf_music_rejects_partial_download_paths() {
             duration_ms: Some(10_000),
             duration_seconds: Some(10),
             chapters: vec![],
+            tags: None,
         };
         let save_root = temp_test_dir();
 
@@ -1460,6 +1506,9 @@ fn normalize_music_titles_removes_common_group_affixes_inside_collection() {
                 end_ms: 79_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
             Music {
     occurrence_id: String::new(),
@@ -1473,6 +1522,9 @@ fn normalize_music_titles_removes_common_group_affixes_inside_collection() {
                 end_ms: 152_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
             Music {
     occurrence_id: String::new(),
@@ -1486,6 +1538,9 @@ fn normalize_music_titles_removes_common_group_affixes_inside_collection() {
                 end_ms: 213_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
             Music {
     occurrence_id: String::new(),
@@ -1499,10 +1554,14 @@ fn normalize_music_titles_removes_common_group_affixes_inside_collection() {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -1541,6 +1600,9 @@ fn normalize_music_titles_keeps_multi_part_track_title_language_core() {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
             Music {
     occurrence_id: String::new(),
@@ -1554,6 +1616,9 @@ fn normalize_music_titles_keeps_multi_part_track_title_language_core() {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
             Music {
     occurrence_id: String::new(),
@@ -1567,10 +1632,14 @@ fn normalize_music_titles_keeps_multi_part_track_title_language_core() {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+    added_at: None,
+    tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -1614,6 +1683,9 @@ fn normalize_music_titles_does_not_compare_across_download_groups() {
                 end_ms: 120_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
             Music {
                 occurrence_id: String::new(),
@@ -1631,10 +1703,14 @@ fn normalize_music_titles_does_not_compare_across_download_groups() {
                 end_ms: 120_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -1671,6 +1747,9 @@ fn normalize_music_titles_uses_file_name_evidence_without_renaming_paths() {
                 end_ms: 137_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
             Music {
                 occurrence_id: String::new(),
@@ -1688,10 +1767,14 @@ fn normalize_music_titles_uses_file_name_evidence_without_renaming_paths() {
                 end_ms: 136_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-05-26T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     normalize_music_titles_within_collection(&mut collection);
@@ -1751,6 +1834,7 @@ fn expansion_owner() -> Collection {
         musics: vec![],
         last_updated: "2026-05-27T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     }
 }
 
@@ -1797,6 +1881,7 @@ fn leaf_probe(title: &str, url: &str, duration_seconds: u32) -> LeafProbe {
         duration_ms: Some(duration_seconds.saturating_mul(1_000)),
         duration_seconds: Some(duration_seconds),
         chapters: vec![],
+        tags: None,
     }
 }
 
@@ -2112,6 +2197,7 @@ fn resolve_pasted_download_url_returns_new_url_for_empty_collection_shell() {
             musics: vec![],
             last_updated: "2026-04-24T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -2157,9 +2243,13 @@ fn resolve_pasted_download_url_returns_existing_collection_for_materialized_know
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
             last_updated: "2026-04-24T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -2217,6 +2307,9 @@ fn existing_leaf_identities_only_count_entries_with_present_files() {
                 end_ms: 60_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
             Music {
                 occurrence_id: String::new(),
@@ -2234,10 +2327,14 @@ fn existing_leaf_identities_only_count_entries_with_present_files() {
                 end_ms: 60_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     let identities = existing_leaf_identities(Some(&collection), &root);
@@ -2306,9 +2403,13 @@ fn existing_leaf_identities_keep_same_video_distinct_across_playlist_groups() {
             end_ms: 60_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
 
     let identities = existing_leaf_identities(Some(&collection), &root);
@@ -2418,9 +2519,13 @@ fn existing_planned_leaf_completions_match_manifest_identity_with_group_context(
             end_ms: 120_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }],
         last_updated: "2026-05-27T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let plan = CollectionSyncPlan {
         source_kind: CollectionSourceKind::List,
@@ -2561,9 +2666,13 @@ fn apply_collection_plan_to_task_consumes_existing_music_before_pipeline_schedul
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let plan = CollectionSyncPlan {
         source_kind: CollectionSourceKind::List,
@@ -2624,6 +2733,7 @@ fn existing_file_completions_derive_from_task_leafs_not_plan_leafs() {
         musics: vec![],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     collection.musics.extend(materialize_music_entries(
         &leaf_probe("Task Track", "https://example.com/watch?v=task", 180),
@@ -2652,6 +2762,7 @@ fn existing_file_completions_derive_from_task_leafs_not_plan_leafs() {
                     duration_ms: Some(180_000),
                     duration_seconds: Some(180),
                     chapters: vec![],
+                    tags: None,
                 }),
                 music_title: Some("Task Track".to_string()),
                 group_hint: Some(group.clone()),
@@ -2668,6 +2779,7 @@ fn existing_file_completions_derive_from_task_leafs_not_plan_leafs() {
                     duration_ms: Some(180_000),
                     duration_seconds: Some(180),
                     chapters: vec![],
+                    tags: None,
                 }),
                 music_title: Some("Task Track".to_string()),
                 group_hint: Some(group),
@@ -2930,6 +3042,7 @@ fn collection_plan_for_youtube_watch_url_uses_probe_title_not_url_fallback_ident
                 duration_ms: Some(7_200_000),
                 duration_seconds: Some(7_200),
                 chapters: vec![],
+                tags: None,
             }),
         )])));
 
@@ -3014,6 +3127,7 @@ fn resolve_collection_plan_reuses_existing_collection_when_probe_title_already_e
             musics: vec![],
             last_updated: "2026-04-24T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         upsert_collection(&existing)
             .await
@@ -3119,6 +3233,7 @@ fn materialize_music_entries_preserves_group_and_nested_relative_path() {
             start_ms: 0,
             end_ms: 180_000,
         }],
+        tags: None,
     };
     let group = Group {
         name: "Compilation".to_string(),
@@ -3157,6 +3272,7 @@ fn resolve_existing_leaf_file_matches_owned_final_m4a_path() {
         musics: vec![],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let group = collection_group("Recovered", &collection.url, &collection.folder);
     let mut collection = collection;
@@ -3192,6 +3308,7 @@ fn resolve_existing_leaf_file_matches_nested_group_path() {
         musics: vec![],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let group = collection_group("Disc 1", "https://example.com/group", "Disc 1");
     let mut collection = collection;
@@ -3235,6 +3352,7 @@ fn resolve_existing_leaf_file_rejects_same_title_file_owned_by_another_leaf() {
         ),
         last_updated: "2026-08-02T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let collection_dir = root.join(&collection.folder);
     std::fs::create_dir_all(&collection_dir).expect("test collection dir should be created");
@@ -3336,6 +3454,7 @@ fn residual_temp_file_completion_moves_file_and_persists_music_once() {
             musics: vec![],
             last_updated: "2026-05-24T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         upsert_collection(&collection)
             .await
@@ -3370,6 +3489,7 @@ fn residual_temp_file_completion_moves_file_and_persists_music_once() {
                 downloaded: DownloadedLeaf {
                     absolute_path: downloaded_path.clone(),
                     duration_ms: None,
+                    tags: None,
                 },
                 progress: DownloadProgress::default(),
                 retry_failures: 0,
@@ -3836,6 +3956,7 @@ fn probe_download_root_title_reuses_existing_collection_when_probe_title_already
             musics: vec![],
             last_updated: "2026-04-24T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         };
         upsert_collection(&existing)
             .await
@@ -4740,9 +4861,13 @@ fn create_collection_shell_reuses_existing_music_and_updates_collection_metadata
             end_ms: 120_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     };
     let plan = CollectionSyncPlan {
         source_kind: CollectionSourceKind::List,
@@ -4815,6 +4940,7 @@ liked = false
             musics: vec![],
             last_updated: "2026-05-27T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         })
         .await
         .expect("collection shell should be saved");
@@ -4905,6 +5031,7 @@ liked = false
             musics: vec![],
             last_updated: "2026-05-27T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         })
         .await
         .expect("collection shell should be saved");
@@ -5015,9 +5142,13 @@ liked = false
                 end_ms: 344_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
             last_updated: "2026-05-27T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         })
         .await
         .expect("collection shell should be saved");
@@ -5110,6 +5241,7 @@ liked = false
             musics: vec![],
             last_updated: "2026-06-07T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         })
         .await
         .expect("collection shell should be saved");
@@ -5153,6 +5285,7 @@ liked = false
             musics: vec![],
             last_updated: "2026-06-07T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         })
         .await
         .expect("collection shell should be reset before transaction load");
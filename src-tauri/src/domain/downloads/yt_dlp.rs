@@ -1,4 +1,6 @@
 use super::model::CollectionSourceKind;
+use crate::domain::audio_tags::probe_audio_tags_best_effort;
+use crate::domain::playlists::model::MusicTags;
 use anyhow::{Context, Result, bail};
 use reqwest::Url;
 use serde_json::Value;
@@ -52,6 +54,7 @@ pub struct LeafProbe {
     pub duration_ms: Option<u32>,
     pub duration_seconds: Option<u32>,
     pub chapters: Vec<LeafChapter>,
+    pub tags: Option<MusicTags>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,6 +74,7 @@ pub enum RootProbe {
 pub struct DownloadedLeaf {
     pub absolute_path: PathBuf,
     pub duration_ms: Option<u32>,
+    pub tags: Option<MusicTags>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -264,6 +268,7 @@ impl YtDlpClient for CliYtDlpClient {
                     absolute_path.display()
                 )
             })?;
+        let tags = probe_audio_tags_best_effort(&self.ffmpeg_path(), &absolute_path);
         log::info!(
             target: "downloads::yt_dlp",
             "resolved_audio url={} path={}",
@@ -274,6 +279,7 @@ impl YtDlpClient for CliYtDlpClient {
         Ok(DownloadedLeaf {
             absolute_path,
             duration_ms: Some(duration_ms),
+            tags,
         })
     }
 }
@@ -557,6 +563,7 @@ pub fn parse_leaf_probe(value: Value) -> Result<LeafProbe> {
         duration_ms,
         duration_seconds,
         chapters,
+        tags: None,
     })
 }
 
@@ -597,7 +604,7 @@ fn parse_chapter(value: &Value) -> Option<LeafChapter> {
     })
 }
 
-fn normalize_chapters(
+pub(crate) fn normalize_chapters(
     video_title: &str,
     duration_ms: Option<u32>,
     mut chapters: Vec<LeafChapter>,
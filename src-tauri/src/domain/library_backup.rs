@@ -0,0 +1,608 @@
+use crate::domain::collection_import::{
+    COLLECTION_MANIFEST_FILE_NAME, write_collection_manifest_bytes,
+};
+use crate::domain::downloads::model::now_timestamp;
+use anyhow::{Context, Result, anyhow, bail};
+use appdb::connection::get_db;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+#[cfg(test)]
+#[path = "library_backup.test.rs"]
+mod tests;
+
+pub(crate) const LIBRARY_BACKUP_VERSION: u32 = 1;
+const LIBRARY_BACKUP_INDEX_ENTRY: &str = "backup.json";
+const LIBRARY_BACKUP_DATABASE_ENTRY: &str = "database.surql";
+const LIBRARY_BACKUP_MANIFEST_PREFIX: &str = "manifests/";
+const LIBRARY_BACKUP_CACHE_PREFIX: &str = "caches/";
+
+/// Held exclusively while a backup is restored. Library writers hold it
+/// shared: background ones pause instead of writing into a half-restored
+/// library, and commands the user started are turned away.
+static LIBRARY_RESTORE_LOCK: tokio::sync::RwLock<()> = tokio::sync::RwLock::const_new(());
+
+/// The last entry of every backup archive, written once everything it lists
+/// is in place, describing what else the archive holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LibraryBackupIndex {
+    pub version: u32,
+    pub created_at: String,
+    pub manifests: Vec<LibraryBackupManifestEntry>,
+    pub caches: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct LibraryBackupManifestEntry {
+    pub collection_url: String,
+    pub entry: String,
+}
+
+/// Where a collection keeps its files, so its manifest can be read or
+/// written back.
+#[derive(Debug, Clone)]
+pub(crate) struct LibraryBackupCollectionDir {
+    pub url: String,
+    pub dir: PathBuf,
+}
+
+/// A derived cache folder that may travel with a backup. Caches are only
+/// restored when the archive holds them.
+#[derive(Debug, Clone)]
+pub(crate) struct LibraryBackupCacheDir {
+    pub name: &'static str,
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LibraryBackupSummary {
+    pub path: String,
+    pub manifest_count: u32,
+    pub cache_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LibraryRestoreSummary {
+    pub restored_manifests: u32,
+    pub skipped_manifests: u32,
+    pub restored_caches: u32,
+}
+
+/// Waits out a running restore. Background writers hold the guard for their
+/// whole write.
+pub(crate) async fn acquire_library_write_guard() -> tokio::sync::RwLockReadGuard<'static, ()> {
+    LIBRARY_RESTORE_LOCK.read().await
+}
+
+/// Shared guard for a command the user started. It fails right away while a
+/// restore runs instead of leaving the UI waiting on it.
+pub(crate) fn try_acquire_library_write_guard() -> Result<tokio::sync::RwLockReadGuard<'static, ()>>
+{
+    LIBRARY_RESTORE_LOCK.try_read().map_err(|_| {
+        anyhow!("the library is being restored from a backup; try again when it is done")
+    })
+}
+
+/**
+ * Behavior:
+ *   Write one archive holding the whole database, every collection manifest
+ *   found in `collections` and the given cache folders.
+ *
+ * Core invariants:
+ *   - The archive is written beside `archive_path` and only renamed into
+ *     place once complete, so a failed backup never leaves a broken archive.
+ *   - Collections without a manifest are skipped; the database still
+ *     describes them.
+ */
+pub(crate) async fn write_library_backup(
+    archive_path: &Path,
+    collections: &[LibraryBackupCollectionDir],
+    caches: &[LibraryBackupCacheDir],
+) -> Result<LibraryBackupSummary> {
+    let export_path = scratch_path("export");
+    export_database(&export_path).await?;
+
+    let archive_path_for_task = archive_path.to_path_buf();
+    let collections = collections.to_vec();
+    let caches = caches.to_vec();
+    let export_path_for_task = export_path.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        write_library_backup_archive(
+            &archive_path_for_task,
+            &export_path_for_task,
+            &collections,
+            &caches,
+        )
+    })
+    .await
+    .map_err(|error| anyhow!("library backup task failed: {error}"));
+    let _ = std::fs::remove_file(&export_path);
+    let index = written??;
+
+    Ok(LibraryBackupSummary {
+        path: archive_path.to_string_lossy().to_string(),
+        manifest_count: index.manifests.len() as u32,
+        cache_count: index.caches.len() as u32,
+    })
+}
+
+fn write_library_backup_archive(
+    archive_path: &Path,
+    database_export: &Path,
+    collections: &[LibraryBackupCollectionDir],
+    caches: &[LibraryBackupCacheDir],
+) -> Result<LibraryBackupIndex> {
+    if let Some(parent) = archive_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let partial_path = archive_path.with_extension("partial");
+    let written = write_archive_entries(&partial_path, database_export, collections, caches);
+    let index = match written {
+        Ok(index) => index,
+        Err(error) => {
+            let _ = std::fs::remove_file(&partial_path);
+            return Err(error);
+        }
+    };
+    std::fs::rename(&partial_path, archive_path)
+        .with_context(|| format!("failed to write {}", archive_path.display()))?;
+
+    Ok(index)
+}
+
+fn write_archive_entries(
+    partial_path: &Path,
+    database_export: &Path,
+    collections: &[LibraryBackupCollectionDir],
+    caches: &[LibraryBackupCacheDir],
+) -> Result<LibraryBackupIndex> {
+    let file = std::fs::File::create(partial_path)
+        .with_context(|| format!("failed to create {}", partial_path.display()))?;
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut manifests = Vec::new();
+    for collection in collections {
+        let manifest_path = collection.dir.join(COLLECTION_MANIFEST_FILE_NAME);
+        if !manifest_path.is_file() {
+            continue;
+        }
+        let entry = format!("{LIBRARY_BACKUP_MANIFEST_PREFIX}{}.toml", manifests.len());
+        append_file_entry(&mut writer, &entry, &manifest_path, options)?;
+        manifests.push(LibraryBackupManifestEntry {
+            collection_url: collection.url.clone(),
+            entry,
+        });
+    }
+
+    let mut cache_names = Vec::new();
+    for cache in caches {
+        if !cache.dir.is_dir() {
+            continue;
+        }
+        for file in WalkDir::new(&cache.dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let relative = file
+                .path()
+                .strip_prefix(&cache.dir)
+                .context("cache file should be below its cache folder")?;
+            let entry = format!(
+                "{LIBRARY_BACKUP_CACHE_PREFIX}{}/{}",
+                cache.name,
+                archive_entry_path(relative)
+            );
+            append_file_entry(&mut writer, &entry, file.path(), options)?;
+        }
+        cache_names.push(cache.name.to_string());
+    }
+
+    append_file_entry(
+        &mut writer,
+        LIBRARY_BACKUP_DATABASE_ENTRY,
+        database_export,
+        options,
+    )?;
+    let index = LibraryBackupIndex {
+        version: LIBRARY_BACKUP_VERSION,
+        created_at: now_timestamp(),
+        manifests,
+        caches: cache_names,
+    };
+    writer.start_file(LIBRARY_BACKUP_INDEX_ENTRY, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&index)?)?;
+    writer.finish()?;
+
+    Ok(index)
+}
+
+fn append_file_entry(
+    writer: &mut ZipWriter<std::fs::File>,
+    entry: &str,
+    source: &Path,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    let mut file = std::fs::File::open(source)
+        .with_context(|| format!("failed to open {}", source.display()))?;
+    writer.start_file(entry, options)?;
+    std::io::copy(&mut file, writer)
+        .with_context(|| format!("failed to archive {}", source.display()))?;
+    Ok(())
+}
+
+fn archive_entry_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Reads the index of a backup archive and refuses archives written by an
+/// unknown backup version or missing their database.
+pub(crate) fn read_library_backup_index(archive_path: &Path) -> Result<LibraryBackupIndex> {
+    let mut archive = open_archive(archive_path)?;
+    let index: LibraryBackupIndex = serde_json::from_slice(&read_archive_entry(
+        &mut archive,
+        LIBRARY_BACKUP_INDEX_ENTRY,
+    )?)
+    .context("backup index is not valid")?;
+    if index.version != LIBRARY_BACKUP_VERSION {
+        bail!(
+            "backup version {} is not supported; expected version {LIBRARY_BACKUP_VERSION}",
+            index.version
+        );
+    }
+    if archive.by_name(LIBRARY_BACKUP_DATABASE_ENTRY).is_err() {
+        bail!("backup archive has no database");
+    }
+
+    Ok(index)
+}
+
+/**
+ * Behavior:
+ *   Replace every database table with the ones stored in the backup.
+ *
+ * Core invariants:
+ *   - The archive is validated before the database is touched.
+ *   - A failed import puts the previous database contents back.
+ */
+pub(crate) async fn restore_library_database(archive_path: &Path) -> Result<LibraryBackupIndex> {
+    let index = read_library_backup_index(archive_path)?;
+    let import_path = scratch_path("import");
+    let archive_path_for_task = archive_path.to_path_buf();
+    let import_path_for_task = import_path.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut archive = open_archive(&archive_path_for_task)?;
+        let database = read_archive_entry(&mut archive, LIBRARY_BACKUP_DATABASE_ENTRY)?;
+        std::fs::write(&import_path_for_task, database)
+            .with_context(|| format!("failed to write {}", import_path_for_task.display()))
+    })
+    .await
+    .map_err(|error| anyhow!("library restore task failed: {error}"))??;
+
+    let rollback_path = scratch_path("rollback");
+    let restored = replace_database(&import_path, &rollback_path).await;
+    let _ = std::fs::remove_file(&import_path);
+    restored?;
+
+    Ok(index)
+}
+
+/// Swaps the database for the one at `import_path`, exporting the current
+/// one to `rollback_path` first. The export is removed once it is no longer
+/// needed, and kept when putting it back failed so nothing is lost.
+async fn replace_database(import_path: &Path, rollback_path: &Path) -> Result<()> {
+    if let Err(error) = export_database(rollback_path).await {
+        let _ = std::fs::remove_file(rollback_path);
+        return Err(error);
+    }
+    let Err(error) = import_database(import_path).await else {
+        let _ = std::fs::remove_file(rollback_path);
+        return Ok(());
+    };
+
+    log::error!(
+        target: "library_backup",
+        "library_restore_import_failed error=\"{:#}\"",
+        error
+    );
+    if let Err(rollback_error) = import_database(rollback_path).await {
+        log::error!(
+            target: "library_backup",
+            "library_restore_rollback_failed path=\"{}\" error=\"{:#}\"",
+            rollback_path.display(),
+            rollback_error
+        );
+        bail!(
+            "failed to put the previous database back after a failed restore ({rollback_error:#}); \
+             it was kept at {}",
+            rollback_path.display()
+        );
+    }
+    let _ = std::fs::remove_file(rollback_path);
+    Err(anyhow!("failed to import backup database: {error:#}"))
+}
+
+/// Replaces every table with the contents of one exported database file.
+async fn import_database(path: &Path) -> Result<()> {
+    clear_database_tables().await?;
+    get_db()?
+        .import(path)
+        .await
+        .with_context(|| format!("failed to import {}", path.display()))?;
+    Ok(())
+}
+
+async fn export_database(path: &Path) -> Result<()> {
+    get_db()?
+        .export(path)
+        .with_config()
+        .users(false)
+        .accesses(false)
+        .params(false)
+        .functions(false)
+        .analyzers(false)
+        .await
+        .context("failed to export the database")?;
+    Ok(())
+}
+
+async fn clear_database_tables() -> Result<()> {
+    let db = get_db()?;
+    let mut result = db
+        .query("RETURN object::keys((INFO FOR DB).tables);")
+        .await?
+        .check()?;
+    let tables: Vec<String> = result.take(0)?;
+    for table in tables {
+        db.query("REMOVE TABLE IF EXISTS $table;")
+            .bind(("table", table))
+            .await?
+            .check()?;
+    }
+    Ok(())
+}
+
+/**
+ * Behavior:
+ *   Put the collection manifests and caches of a restored backup back in
+ *   place.
+ *
+ * Core invariants:
+ *   - A manifest is only written into a collection folder that exists; the
+ *     others are counted as skipped.
+ *   - Manifests are replaced through a staged file, never written in place.
+ *   - A restored cache replaces the whole cache folder.
+ */
+pub(crate) fn restore_library_files(
+    archive_path: &Path,
+    index: &LibraryBackupIndex,
+    collections: &[LibraryBackupCollectionDir],
+    caches: &[LibraryBackupCacheDir],
+) -> Result<LibraryRestoreSummary> {
+    let mut archive = open_archive(archive_path)?;
+    let mut summary = LibraryRestoreSummary {
+        restored_manifests: 0,
+        skipped_manifests: 0,
+        restored_caches: 0,
+    };
+
+    for manifest in &index.manifests {
+        let Some(collection) = collections
+            .iter()
+            .find(|collection| collection.url == manifest.collection_url)
+            .filter(|collection| collection.dir.is_dir())
+        else {
+            summary.skipped_manifests += 1;
+            continue;
+        };
+        let contents = read_archive_entry(&mut archive, &manifest.entry)?;
+        let manifest_path = collection.dir.join(COLLECTION_MANIFEST_FILE_NAME);
+        write_collection_manifest_bytes(&manifest_path, &contents)?;
+        summary.restored_manifests += 1;
+    }
+
+    for cache in caches
+        .iter()
+        .filter(|cache| index.caches.iter().any(|name| name == cache.name))
+    {
+        restore_cache_dir(&mut archive, cache)?;
+        summary.restored_caches += 1;
+    }
+
+    Ok(summary)
+}
+
+fn restore_cache_dir(
+    archive: &mut ZipArchive<std::fs::File>,
+    cache: &LibraryBackupCacheDir,
+) -> Result<()> {
+    if cache.dir.exists() {
+        std::fs::remove_dir_all(&cache.dir)
+            .with_context(|| format!("failed to clear {}", cache.dir.display()))?;
+    }
+    std::fs::create_dir_all(&cache.dir)
+        .with_context(|| format!("failed to create {}", cache.dir.display()))?;
+
+    let prefix = format!("{LIBRARY_BACKUP_CACHE_PREFIX}{}/", cache.name);
+    for position in 0..archive.len() {
+        let mut file = archive.by_index(position)?;
+        let Some(relative) = file
+            .name()
+            .strip_prefix(&prefix)
+            .filter(|relative| !relative.is_empty())
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+        // Entries that would escape the cache folder are ignored.
+        if file.enclosed_name().is_none()
+            || relative
+                .components()
+                .any(|component| !matches!(component, std::path::Component::Normal(_)))
+        {
+            continue;
+        }
+
+        let destination = cache.dir.join(relative);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let mut output = std::fs::File::create(&destination)
+            .with_context(|| format!("failed to create {}", destination.display()))?;
+        std::io::copy(&mut file, &mut output)
+            .with_context(|| format!("failed to write {}", destination.display()))?;
+    }
+
+    Ok(())
+}
+
+fn open_archive(archive_path: &Path) -> Result<ZipArchive<std::fs::File>> {
+    let file = std::fs::File::open(archive_path)
+        .with_context(|| format!("failed to open {}", archive_path.display()))?;
+    ZipArchive::new(file)
+        .with_context(|| format!("{} is not a backup archive", archive_path.display()))
+}
+
+fn read_archive_entry(archive: &mut ZipArchive<std::fs::File>, entry: &str) -> Result<Vec<u8>> {
+    let mut file = archive
+        .by_name(entry)
+        .with_context(|| format!("backup archive has no `{entry}`"))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)
+        .with_context(|| format!("failed to read `{entry}` from the backup archive"))?;
+    Ok(contents)
+}
+
+fn scratch_path(label: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!(
+        "slisic_library_{label}_{}_{nanos}.surql",
+        std::process::id()
+    ))
+}
+
+#[cfg(not(test))]
+async fn library_collection_dirs(
+    app: &tauri::AppHandle,
+) -> Result<Vec<LibraryBackupCollectionDir>> {
+    let save_root = crate::domain::meta::service::resolve_save_root(app).await?;
+    Ok(crate::domain::playlists::repo::list_collections()
+        .await?
+        .into_iter()
+        .map(|collection| LibraryBackupCollectionDir {
+            dir: collection.dir(&save_root),
+            url: collection.url,
+        })
+        .collect())
+}
+
+#[cfg(not(test))]
+fn library_cache_dirs(app: &tauri::AppHandle) -> Result<Vec<LibraryBackupCacheDir>> {
+    use tauri::Manager;
+
+    let local_data_dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|error| anyhow!("failed to resolve app local data directory: {error}"))?;
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|error| anyhow!("failed to resolve app cache directory: {error}"))?;
+    Ok(vec![
+        LibraryBackupCacheDir {
+            name: "audio-style-stable-model",
+            dir: local_data_dir.join(
+                crate::domain::playlist_playback::recommendation::AUDIO_STYLE_STABLE_MODEL_DIR_NAME,
+            ),
+        },
+        LibraryBackupCacheDir {
+            name: "audio-style-embeddings",
+            dir: cache_dir.join("audio-style-embeddings"),
+        },
+        LibraryBackupCacheDir {
+            name: "waveforms",
+            dir: cache_dir.join("waveforms"),
+        },
+    ])
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn backup_library(
+    app: tauri::AppHandle,
+    path: String,
+    include_caches: bool,
+) -> Result<LibraryBackupSummary, String> {
+    let collections = library_collection_dirs(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let caches = if include_caches {
+        library_cache_dirs(&app).map_err(|error| error.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    write_library_backup(Path::new(&path), &collections, &caches)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_library(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<LibraryRestoreSummary, String> {
+    let archive_path = PathBuf::from(path);
+    if crate::domain::downloads::repo::list_tasks()
+        .await
+        .map_err(|error| error.to_string())?
+        .iter()
+        .any(|task| task.status.is_active())
+    {
+        return Err("a library cannot be restored while downloads are running".to_string());
+    }
+    let _ = crate::domain::player::service::stop_playback().await;
+    let _restore_guard = LIBRARY_RESTORE_LOCK.write().await;
+
+    let index = restore_library_database(&archive_path)
+        .await
+        .map_err(|error| error.to_string())?;
+    let collections = library_collection_dirs(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let caches = library_cache_dirs(&app).map_err(|error| error.to_string())?;
+    let summary = tokio::task::spawn_blocking(move || {
+        restore_library_files(&archive_path, &index, &collections, &caches)
+    })
+    .await
+    .map_err(|error| error.to_string())?
+    .map_err(|error| error.to_string())?;
+
+    crate::domain::playlists::search::notify_library_changed();
+    crate::domain::playlist_playback::service::notify_music_library_inputs_changed(
+        "library_restore",
+    );
+    crate::domain::playlist_playback::service::notify_playable_library_changed();
+    crate::domain::playlist_playback::playable_index::notify_exclude_changed();
+    crate::domain::playlists::exclude_expiry::notify_exclude_expiry_changed();
+    Ok(summary)
+}
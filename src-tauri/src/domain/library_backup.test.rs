@@ -0,0 +1,111 @@
+use super::{
+    LibraryBackupCacheDir, LibraryBackupCollectionDir, read_library_backup_index,
+    restore_library_database, restore_library_files, write_library_backup,
+};
+use crate::domain::playlists::model::Collection;
+use crate::domain::playlists::repo::{
+    delete_absorbed_collection, get_collection_by_url, upsert_collection,
+};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, music, owner_group, run_async, temp_path, write_file,
+};
+use appdb::connection::reset_db;
+use std::io::Write;
+
+fn collection(url: &str, folder: &str) -> Collection {
+    let mut music = music("Album", &owner_group("Albums", url, folder));
+    music.liked = true;
+    crate::domain::test_support::collection("Albums", url, folder, vec![music])
+}
+
+#[test]
+fn restoring_a_backup_rebuilds_the_library() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let workspace = temp_path("restore");
+        let collection_url = "https://example.com/albums";
+        let collection_dir = workspace.join("library/example/Albums");
+        let manifest_path = collection_dir.join(".slisic.collection.toml");
+        write_file(&manifest_path, b"version = 1");
+        let cache_dir = workspace.join("cache/waveforms");
+        write_file(&cache_dir.join("track/manifest.json"), b"{}");
+        upsert_collection(&collection(collection_url, "example/Albums"))
+            .await
+            .expect("collection should save");
+
+        let archive_path = workspace.join("backup.zip");
+        let collections = vec![LibraryBackupCollectionDir {
+            url: collection_url.to_string(),
+            dir: collection_dir.clone(),
+        }];
+        let caches = vec![LibraryBackupCacheDir {
+            name: "waveforms",
+            dir: cache_dir.clone(),
+        }];
+        let summary = write_library_backup(&archive_path, &collections, &caches)
+            .await
+            .expect("backup should be written");
+        assert_eq!(summary.manifest_count, 1);
+        assert_eq!(summary.cache_count, 1);
+        assert!(archive_path.is_file());
+
+        delete_absorbed_collection(collection_url)
+            .await
+            .expect("collection should be deleted");
+        std::fs::remove_file(&manifest_path).expect("manifest should be removed");
+        std::fs::remove_dir_all(&cache_dir).expect("cache should be removed");
+
+        let index = restore_library_database(&archive_path)
+            .await
+            .expect("database should be restored");
+        let restored = get_collection_by_url(collection_url)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should be restored");
+        assert_eq!(restored.musics.len(), 1);
+        assert!(restored.musics[0].liked);
+
+        let files = restore_library_files(&archive_path, &index, &collections, &caches)
+            .expect("files should be restored");
+        assert_eq!(files.restored_manifests, 1);
+        assert_eq!(files.skipped_manifests, 0);
+        assert_eq!(files.restored_caches, 1);
+        assert_eq!(
+            std::fs::read(&manifest_path).expect("manifest should be restored"),
+            b"version = 1"
+        );
+        assert!(!manifest_path.with_extension("toml.partial").exists());
+        assert!(cache_dir.join("track/manifest.json").is_file());
+
+        let _ = std::fs::remove_dir_all(workspace);
+        reset_db();
+    });
+}
+
+#[test]
+fn backup_archives_from_another_version_are_refused() {
+    let workspace = temp_path("version");
+    let archive_path = workspace.join("backup.zip");
+    std::fs::create_dir_all(&workspace).expect("workspace should be created");
+    let file = std::fs::File::create(&archive_path).expect("archive should be created");
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+    writer
+        .start_file("database.surql", options)
+        .expect("database entry should start");
+    writer
+        .start_file("backup.json", options)
+        .expect("index entry should start");
+    writer
+        .write_all(br#"{"version":99,"created_at":"","manifests":[],"caches":[]}"#)
+        .expect("index should be written");
+    writer.finish().expect("archive should finish");
+
+    let error = read_library_backup_index(&archive_path)
+        .expect_err("unknown backup versions should be refused");
+    assert!(error.to_string().contains("version 99"));
+
+    let _ = std::fs::remove_dir_all(workspace);
+}
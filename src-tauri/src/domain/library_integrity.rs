@@ -0,0 +1,405 @@
+#[cfg(not(test))]
+use crate::domain::collection_import::collect_local_audio_files;
+use crate::domain::collection_import::{
+    LocalAudioFile, TEMP_DOWNLOAD_MARKER, local_collection_file_candidates,
+};
+use crate::domain::downloads::yt_dlp::audio_duration_boundary_matches;
+use crate::domain::playlists::model::{Collection, Music};
+use crate::domain::playlists::repo as collection_repo;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "library_integrity.test.rs"]
+mod tests;
+
+const RELINK_CANDIDATE_LIMIT: usize = 3;
+const RELINK_MIN_SCORE: f64 = 0.5;
+const RELINK_DURATION_WEIGHT: f64 = 0.45;
+const RELINK_NAME_WEIGHT: f64 = 0.4;
+const RELINK_SIZE_WEIGHT: f64 = 0.15;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
+pub struct LibraryIntegrityReport {
+    pub missing_files: Vec<MissingMusicFile>,
+    pub orphan_files: Vec<OrphanAudioFile>,
+    pub temp_residue: Vec<TempResidueFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MissingMusicFile {
+    pub collection_url: String,
+    pub music: Music,
+    pub expected_path: String,
+    pub relink_candidates: Vec<RelinkCandidate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct OrphanAudioFile {
+    pub collection_url: String,
+    pub path: String,
+    pub relative_path: String,
+    pub duration_ms: u32,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct TempResidueFile {
+    pub collection_url: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct RelinkCandidate {
+    pub path: String,
+    pub duration_ms: u32,
+    pub size_bytes: u64,
+    pub score: f64,
+}
+
+/**
+ * Behavior:
+ *   Compare the persisted library with what is on disk under every collection
+ *   folder and report missing music files, unreferenced audio files and
+ *   leftover temporary download files.
+ *
+ * Core invariants:
+ *   - Each collection folder is listed once, even when several collections
+ *     share it.
+ *   - Temporary download files are reported as residue, never as orphans or
+ *     relink candidates.
+ *   - Only music whose stored path no longer resolves to a file is missing;
+ *     music without a path has not been downloaded yet.
+ */
+pub(crate) fn scan_library_integrity(
+    save_root: &Path,
+    collections: &[Collection],
+    list_audio_files: &impl Fn(&Path) -> Result<Vec<LocalAudioFile>>,
+) -> Result<LibraryIntegrityReport> {
+    let mut report = LibraryIntegrityReport::default();
+    let mut referenced = HashSet::new();
+    let mut missing = Vec::new();
+    for collection in collections {
+        for music in &collection.musics {
+            let Some(path) = collection_repo::resolve_music_file_path(
+                save_root,
+                collection.save_root.as_deref(),
+                &collection.folder,
+                music.path.as_deref(),
+            ) else {
+                continue;
+            };
+            if path.is_file() {
+                referenced.insert(path_key(&path));
+            } else {
+                missing.push((collection, music, path));
+            }
+        }
+    }
+
+    let mut listed_folders = HashSet::new();
+    let mut listed_files = HashSet::new();
+    let mut byte_rates: HashMap<&str, Vec<f64>> = HashMap::new();
+    for collection in collections {
+        let collection_path = collection.dir(save_root);
+        if !collection_path.is_dir() || !listed_folders.insert(path_key(&collection_path)) {
+            continue;
+        }
+
+        for path in local_collection_file_candidates(&collection_path) {
+            if is_temp_download_file(&path) && listed_files.insert(path_key(&path)) {
+                report.temp_residue.push(TempResidueFile {
+                    collection_url: collection.url.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    size_bytes: file_size(&path),
+                });
+            }
+        }
+
+        for file in list_audio_files(&collection_path)? {
+            let key = path_key(&file.absolute_path);
+            if is_temp_download_file(&file.absolute_path) || !listed_files.insert(key.clone()) {
+                continue;
+            }
+            let size_bytes = file_size(&file.absolute_path);
+            if referenced.contains(&key) {
+                if size_bytes > 0 {
+                    byte_rates
+                        .entry(collection.url.as_str())
+                        .or_default()
+                        .push(size_bytes as f64 / f64::from(file.duration_ms));
+                }
+                continue;
+            }
+            report.orphan_files.push(OrphanAudioFile {
+                collection_url: collection.url.clone(),
+                path: file.absolute_path.to_string_lossy().to_string(),
+                relative_path: file.relative_path,
+                duration_ms: file.duration_ms,
+                size_bytes,
+            });
+        }
+    }
+
+    for (collection, music, expected_path) in missing {
+        let byte_rate = byte_rates
+            .get_mut(collection.url.as_str())
+            .and_then(|rates| median(rates));
+        report.missing_files.push(MissingMusicFile {
+            collection_url: collection.url.clone(),
+            music: music.clone(),
+            expected_path: expected_path.to_string_lossy().to_string(),
+            relink_candidates: rank_relink_candidates(
+                music,
+                &expected_path,
+                byte_rate,
+                &report.orphan_files,
+            ),
+        });
+    }
+
+    Ok(report)
+}
+
+/**
+ * Behavior:
+ *   Point one collection's music occurrence at an existing audio file.
+ *
+ * Core invariants:
+ *   - Files inside the collection folder are stored relative to it, like
+ *     imported files; anything else is stored as an absolute path.
+ */
+pub(crate) async fn relink_collection_music_file(
+    save_root: &Path,
+    collection_url: &str,
+    music_url: &str,
+    start_ms: u32,
+    end_ms: u32,
+    file_path: &Path,
+) -> Result<Option<Music>> {
+    if !file_path.is_file() {
+        bail!("relink target {} is not a file", file_path.display());
+    }
+    let Some(collection) = collection_repo::get_collection_by_url(collection_url).await? else {
+        return Ok(None);
+    };
+
+    let collection_path = collection.dir(save_root);
+    let stored_path = match file_path.strip_prefix(&collection_path) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => file_path
+            .canonicalize()
+            .with_context(|| format!("failed to resolve {}", file_path.display()))?
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    collection_repo::set_collection_music_path_by_identity(
+        collection_url,
+        music_url,
+        start_ms,
+        end_ms,
+        &stored_path,
+    )
+    .await
+}
+
+fn rank_relink_candidates(
+    music: &Music,
+    expected_path: &Path,
+    byte_rate: Option<f64>,
+    orphans: &[OrphanAudioFile],
+) -> Vec<RelinkCandidate> {
+    let expected_stem = expected_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let expected_size = byte_rate.map(|rate| rate * f64::from(music.end_ms));
+
+    let mut candidates = orphans
+        .iter()
+        .filter_map(|orphan| {
+            let score = relink_score(music, &expected_stem, expected_size, orphan)?;
+            (score >= RELINK_MIN_SCORE).then(|| RelinkCandidate {
+                path: orphan.path.clone(),
+                duration_ms: orphan.duration_ms,
+                size_bytes: orphan.size_bytes,
+                score,
+            })
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+    candidates.truncate(RELINK_CANDIDATE_LIMIT);
+    candidates
+}
+
+/// Scores how likely `orphan` is the moved or renamed file of `music`.
+/// Files too short to contain the music range are never candidates.
+fn relink_score(
+    music: &Music,
+    expected_stem: &str,
+    expected_size: Option<f64>,
+    orphan: &OrphanAudioFile,
+) -> Option<f64> {
+    let duration_score = if audio_duration_boundary_matches(music.end_ms, orphan.duration_ms) {
+        1.0
+    } else if orphan.duration_ms >= music.end_ms {
+        0.5
+    } else {
+        return None;
+    };
+
+    let orphan_stem = Path::new(&orphan.relative_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name_score = name_similarity(&orphan_stem, expected_stem)
+        .max(name_similarity(&orphan_stem, &music.name))
+        .max(name_similarity(&orphan_stem, &music.alias));
+
+    let size_score = expected_size
+        .filter(|expected| *expected > 0.0 && orphan.size_bytes > 0)
+        .map(|expected| 1.0 - ((orphan.size_bytes as f64 - expected).abs() / expected).min(1.0));
+
+    Some(match size_score {
+        Some(size_score) => {
+            RELINK_DURATION_WEIGHT * duration_score
+                + RELINK_NAME_WEIGHT * name_score
+                + RELINK_SIZE_WEIGHT * size_score
+        }
+        None => {
+            (RELINK_DURATION_WEIGHT * duration_score + RELINK_NAME_WEIGHT * name_score)
+                / (RELINK_DURATION_WEIGHT + RELINK_NAME_WEIGHT)
+        }
+    })
+}
+
+/// Dice coefficient over character bigrams of the lowercase alphanumerics.
+fn name_similarity(left: &str, right: &str) -> f64 {
+    let left = name_bigrams(left);
+    let right = name_bigrams(right);
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+
+    let mut remaining = right.clone();
+    let mut shared = 0usize;
+    for bigram in &left {
+        if let Some(index) = remaining.iter().position(|candidate| candidate == bigram) {
+            remaining.swap_remove(index);
+            shared += 1;
+        }
+    }
+    (2 * shared) as f64 / (left.len() + right.len()) as f64
+}
+
+fn name_bigrams(value: &str) -> Vec<(char, char)> {
+    let chars = value
+        .chars()
+        .filter(|value| value.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    Some(values[values.len() / 2])
+}
+
+fn is_temp_download_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.contains(TEMP_DOWNLOAD_MARKER))
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/").to_lowercase()
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn check_library_integrity(
+    app: tauri::AppHandle,
+) -> Result<LibraryIntegrityReport, String> {
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let ffmpeg_path = crate::utils::binaries::ensure_managed_binary(
+        &app,
+        crate::utils::binaries::ManagedBinary::Ffmpeg,
+    )
+    .map_err(|error| error.to_string())?;
+    let collections = collection_repo::list_collections()
+        .await
+        .map_err(|error| error.to_string())?;
+
+    tokio::task::spawn_blocking(move || {
+        let _usage = crate::utils::binaries::acquire_managed_binary_usage(
+            crate::utils::binaries::ManagedBinary::Ffmpeg,
+            "library_integrity",
+        );
+        scan_library_integrity(&save_root, &collections, &|collection_path: &Path| {
+            collect_local_audio_files(collection_path, &ffmpeg_path)
+        })
+    })
+    .await
+    .map_err(|error| error.to_string())?
+    .map_err(|error| error.to_string())
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn relink_music_file(
+    app: tauri::AppHandle,
+    collection_url: String,
+    music_url: String,
+    start_ms: u32,
+    end_ms: u32,
+    file_path: String,
+) -> Result<Option<Music>, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let music = relink_collection_music_file(
+        &save_root,
+        &collection_url,
+        &music_url,
+        start_ms,
+        end_ms,
+        Path::new(&file_path),
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+
+    if let Some(music) = &music {
+        crate::domain::playlist_playback::service::notify_music_input_changed(
+            "music_relink",
+            music,
+        );
+        crate::domain::playlist_playback::service::notify_playable_library_changed();
+    }
+    Ok(music)
+}
@@ -0,0 +1,209 @@
+use super::{
+    LocalAudioFile, OrphanAudioFile, name_similarity, relink_collection_music_file, relink_score,
+    scan_library_integrity,
+};
+use crate::domain::playlists::model::{Collection, Music, canonical_music_id_for_source};
+use crate::domain::playlists::repo::{get_collection_by_url, upsert_collection};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, owner_group, ranged_music, run_async, temp_path, write_file,
+};
+use anyhow::Result;
+use appdb::connection::reset_db;
+use std::path::Path;
+
+const COLLECTION_URL: &str = "local://collection/integrity";
+const COLLECTION_FOLDER: &str = "local/integrity";
+
+fn music(name: &str, path: Option<&str>, end_ms: u32) -> Music {
+    let mut music = ranged_music(
+        name,
+        &owner_group("Integrity", COLLECTION_URL, COLLECTION_FOLDER),
+        0,
+        end_ms,
+    );
+    music.url = format!("{COLLECTION_URL}#{name}");
+    music.canonical_music_id = canonical_music_id_for_source(&music.url, 0, end_ms);
+    music.path = path.map(ToOwned::to_owned);
+    music
+}
+
+fn collection(musics: Vec<Music>) -> Collection {
+    crate::domain::test_support::collection("Integrity", COLLECTION_URL, COLLECTION_FOLDER, musics)
+}
+
+fn write_sized_file(path: &Path, size: usize) {
+    write_file(path, &vec![0u8; size]);
+}
+
+/// Lists every file with a duration derived from its size, so the scan can
+/// run without ffmpeg.
+fn list_fake_audio_files(collection_path: &Path) -> Result<Vec<LocalAudioFile>> {
+    Ok(super::local_collection_file_candidates(collection_path)
+        .into_iter()
+        .map(|path| LocalAudioFile {
+            relative_path: path
+                .strip_prefix(collection_path)
+                .expect("candidate should be inside the collection")
+                .to_string_lossy()
+                .replace('\\', "/"),
+            duration_ms: std::fs::metadata(&path).expect("file should exist").len() as u32 * 10,
+            absolute_path: path,
+            tags: None,
+            cue_tracks: Vec::new(),
+            chapters: Vec::new(),
+            lyrics_path: None,
+        })
+        .collect())
+}
+
+fn orphan(relative_path: &str, duration_ms: u32, size_bytes: u64) -> OrphanAudioFile {
+    OrphanAudioFile {
+        collection_url: COLLECTION_URL.to_string(),
+        path: format!("/library/{relative_path}"),
+        relative_path: relative_path.to_string(),
+        duration_ms,
+        size_bytes,
+    }
+}
+
+#[test]
+fn scan_reports_missing_orphan_and_residue_files_with_relink_candidates() {
+    let save_root = temp_path("integrity_scan");
+    let folder = save_root.join(COLLECTION_FOLDER);
+    write_sized_file(&folder.join("Kept.m4a"), 6_000);
+    write_sized_file(&folder.join("renamed/moved song (remaster).m4a"), 9_000);
+    write_sized_file(&folder.join("Unrelated Jingle.m4a"), 9_000);
+    write_sized_file(&folder.join("Too Short.m4a"), 1_000);
+    write_sized_file(&folder.join("Next.__slisic_tmp__leaf.m4a"), 500);
+
+    let collections = vec![collection(vec![
+        music("Kept", Some("Kept.m4a"), 60_000),
+        music("Moved Song", Some("Moved Song.m4a"), 90_000),
+        music("Pending", None, 30_000),
+    ])];
+    let report = scan_library_integrity(&save_root, &collections, &list_fake_audio_files)
+        .expect("scan should succeed");
+
+    assert_eq!(report.missing_files.len(), 1);
+    let missing = &report.missing_files[0];
+    assert_eq!(missing.music.name, "Moved Song");
+    assert!(missing.expected_path.ends_with("Moved Song.m4a"));
+    let best = missing
+        .relink_candidates
+        .first()
+        .expect("the renamed file should be offered");
+    assert!(best.path.ends_with("moved song (remaster).m4a"));
+    assert!(
+        missing
+            .relink_candidates
+            .iter()
+            .all(|candidate| !candidate.path.ends_with("Too Short.m4a"))
+    );
+
+    let mut orphans = report
+        .orphan_files
+        .iter()
+        .map(|orphan| orphan.relative_path.as_str())
+        .collect::<Vec<_>>();
+    orphans.sort();
+    assert_eq!(
+        orphans,
+        vec![
+            "Too Short.m4a",
+            "Unrelated Jingle.m4a",
+            "renamed/moved song (remaster).m4a"
+        ]
+    );
+    assert_eq!(report.temp_residue.len(), 1);
+    assert!(
+        report.temp_residue[0]
+            .path
+            .ends_with("Next.__slisic_tmp__leaf.m4a")
+    );
+
+    let _ = std::fs::remove_dir_all(save_root);
+}
+
+#[test]
+fn relink_score_prefers_matching_duration_name_and_size() {
+    let target = music("Moved Song", Some("Moved Song.m4a"), 90_000);
+
+    let exact = relink_score(
+        &target,
+        "Moved Song",
+        Some(9_000.0),
+        &orphan("moved-song.m4a", 90_000, 9_000),
+    )
+    .expect("matching file should score");
+    let longer = relink_score(
+        &target,
+        "Moved Song",
+        Some(9_000.0),
+        &orphan("moved-song.m4a", 240_000, 24_000),
+    )
+    .expect("longer file may still contain the range");
+    assert!(exact > longer);
+    assert!((exact - 1.0).abs() < 1e-9);
+    assert!(
+        relink_score(
+            &target,
+            "Moved Song",
+            None,
+            &orphan("moved-song.m4a", 30_000, 3_000)
+        )
+        .is_none()
+    );
+
+    assert!((name_similarity("Moved Song", "moved_song") - 1.0).abs() < 1e-9);
+    assert_eq!(name_similarity("Moved Song", "xyz"), 0.0);
+}
+
+#[test]
+fn relink_stores_collection_relative_path_for_files_inside_the_folder() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let save_root = temp_path("integrity_relink");
+        let moved = save_root.join(COLLECTION_FOLDER).join("renamed/Moved.m4a");
+        write_sized_file(&moved, 100);
+        upsert_collection(&collection(vec![music("Moved", Some("Moved.m4a"), 60_000)]))
+            .await
+            .expect("collection should save");
+
+        let relinked = relink_collection_music_file(
+            &save_root,
+            COLLECTION_URL,
+            &format!("{COLLECTION_URL}#Moved"),
+            0,
+            60_000,
+            &moved,
+        )
+        .await
+        .expect("relink should succeed")
+        .expect("existing music should be relinked");
+        assert_eq!(relinked.path.as_deref(), Some("renamed/Moved.m4a"));
+
+        let stored = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(stored.musics[0].path.as_deref(), Some("renamed/Moved.m4a"));
+        assert!(
+            relink_collection_music_file(
+                &save_root,
+                COLLECTION_URL,
+                &format!("{COLLECTION_URL}#Moved"),
+                0,
+                60_000,
+                &save_root.join("missing.m4a"),
+            )
+            .await
+            .is_err()
+        );
+
+        let _ = std::fs::remove_dir_all(save_root);
+        reset_db();
+    });
+}
@@ -1176,6 +1176,12 @@ async fn measure_and_persist_loudness(
             publish_persisted_loudness_evidence(request, persisted);
         }
         runtime.completion_notify.notify_waiters();
+        crate::domain::collection_manifest_sync::schedule_music_identity_manifest_sync(
+            &commit_request.url,
+            commit_request.start_ms,
+            commit_request.end_ms,
+        )
+        .await;
         log::info!(
             target: LOUDNESS_EVIDENCE_LOG_TARGET,
             "loudness_evidence_persisted source={} canonical_music_id=\"{}\" integrated={:.3} true_peak={} lra={}",
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+#[path = "lyrics.test.rs"]
+mod tests;
+
+/// One timed line of an `.lrc` file, at its position in the audio file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LyricLine {
+    pub(crate) start_ms: u32,
+    pub(crate) text: String,
+}
+
+/// The lyric line under the playhead, timed relative to the music's start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct LyricLineCue {
+    pub index: u32,
+    pub text: String,
+    pub start_ms: u32,
+    /// Start of the next line; `None` for the last line.
+    pub end_ms: Option<u32>,
+}
+
+pub(crate) fn is_lyrics_sidecar_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("lrc"))
+}
+
+/// A sidecar belongs to the audio file next to it with the same stem.
+pub(crate) fn is_lyrics_sidecar_of(audio_path: &Path, lyrics_path: &Path) -> bool {
+    is_lyrics_sidecar_path(lyrics_path)
+        && lyrics_path.parent() == audio_path.parent()
+        && lyrics_path.file_stem() == audio_path.file_stem()
+}
+
+/// Finds the `.lrc` sidecar of an audio file, picking the first by name when
+/// several spellings of the extension exist.
+pub(crate) fn find_lyrics_sidecar(audio_path: &Path) -> Option<PathBuf> {
+    let mut sidecars = std::fs::read_dir(audio_path.parent()?)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_lyrics_sidecar_of(audio_path, path))
+        .collect::<Vec<_>>();
+    sidecars.sort();
+    sidecars.into_iter().next()
+}
+
+/// Path of a sidecar in the same form as the relative path of its audio file.
+pub(crate) fn lyrics_sidecar_relative_path(
+    relative_audio_path: &str,
+    lyrics_path: &Path,
+) -> Option<String> {
+    Some(
+        Path::new(relative_audio_path)
+            .with_file_name(lyrics_path.file_name()?)
+            .to_string_lossy()
+            .to_string(),
+    )
+}
+
+/// Reads an `.lrc` file, accepting UTF-8 with or without BOM and falling back
+/// to Latin-1 like cue sheets do.
+#[cfg_attr(test, allow(dead_code))]
+pub(crate) fn read_lyrics(path: &Path) -> Result<Vec<LyricLine>> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|byte| char::from(*byte)).collect(),
+    };
+    Ok(parse_lrc(&text))
+}
+
+/**
+ * Behavior:
+ *   Parse the timed lines of an `.lrc` file, in playback order.
+ *
+ * Core invariants:
+ *   - A line may carry several time tags (`[00:12.30][01:40.00]chorus`) and
+ *     is repeated at each of them.
+ *   - `[offset:+/-ms]` moves every line earlier by that many milliseconds, as
+ *     the format defines; other ID tags are skipped.
+ *   - Lines with an empty text are kept: they end the previous line during
+ *     instrumental breaks.
+ */
+pub(crate) fn parse_lrc(text: &str) -> Vec<LyricLine> {
+    let mut offset_ms = 0i64;
+    let mut lines = Vec::new();
+    for raw_line in text.lines() {
+        let mut rest = raw_line.trim();
+        let mut starts = Vec::new();
+        while let Some(tag_end) = rest.strip_prefix('[').and_then(|tag| tag.find(']')) {
+            let tag = &rest[1..=tag_end];
+            rest = rest[tag_end + 2..].trim_start();
+            if let Some(start_ms) = parse_lrc_timestamp_ms(tag) {
+                starts.push(start_ms);
+            } else if let Some((key, value)) = tag.split_once(':')
+                && key.trim().eq_ignore_ascii_case("offset")
+                && let Ok(value) = value.trim().parse::<i64>()
+            {
+                offset_ms = value;
+            }
+        }
+        lines.extend(
+            starts
+                .into_iter()
+                .map(|start_ms| (start_ms, rest.trim_end())),
+        );
+    }
+
+    let mut lines = lines
+        .into_iter()
+        .map(|(start_ms, text)| LyricLine {
+            start_ms: (start_ms - offset_ms).clamp(0, i64::from(u32::MAX)) as u32,
+            text: text.to_string(),
+        })
+        .collect::<Vec<_>>();
+    lines.sort_by_key(|line| line.start_ms);
+    lines
+}
+
+/**
+ * Behavior:
+ *   Resolve the lyric line playing at a position of the audio file.
+ *
+ * Core invariants:
+ *   - Lyric timestamps describe the audio file, so a music whose range
+ *     starts at `music_start_ms` sees them shifted back by that much.
+ *   - A line that began before the music's range is current from its start
+ *     and reported at 0.
+ *   - Before the first line nothing is current.
+ */
+pub(crate) fn resolve_lyric_line(
+    lines: &[LyricLine],
+    music_start_ms: u32,
+    position_ms: u32,
+) -> Option<LyricLineCue> {
+    let index = lines
+        .partition_point(|line| line.start_ms <= position_ms)
+        .checked_sub(1)?;
+    let line = &lines[index];
+    Some(LyricLineCue {
+        index: index as u32,
+        text: line.text.clone(),
+        start_ms: line.start_ms.saturating_sub(music_start_ms),
+        end_ms: lines
+            .get(index + 1)
+            .map(|next| next.start_ms.saturating_sub(music_start_ms)),
+    })
+}
+
+/// Parses `mm:ss`, `mm:ss.xx`, `mm:ss.xxx` and the `mm:ss:xx` some editors
+/// write.
+fn parse_lrc_timestamp_ms(value: &str) -> Option<i64> {
+    let (minutes, rest) = value.trim().split_once(':')?;
+    let (seconds, fraction) = rest
+        .split_once(['.', ':'])
+        .map_or((rest, None), |(seconds, fraction)| {
+            (seconds, Some(fraction))
+        });
+    if [minutes, seconds, fraction.unwrap_or("0")]
+        .iter()
+        .any(|part| part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()))
+    {
+        return None;
+    }
+    let minutes = minutes.parse::<i64>().ok()?;
+    let seconds = seconds.parse::<i64>().ok()?;
+    let fraction_ms = match fraction {
+        None => 0,
+        Some(fraction) => {
+            let digits = &fraction[..fraction.len().min(3)];
+            digits.parse::<i64>().ok()? * 10i64.pow(3 - digits.len() as u32)
+        }
+    };
+    Some((minutes * 60 + seconds) * 1_000 + fraction_ms)
+}
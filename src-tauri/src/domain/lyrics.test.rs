@@ -0,0 +1,87 @@
+use super::{LyricLine, LyricLineCue, lyrics_sidecar_relative_path, parse_lrc, resolve_lyric_line};
+use std::path::Path;
+
+fn line(start_ms: u32, text: &str) -> LyricLine {
+    LyricLine {
+        start_ms,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn parses_time_tags_in_playback_order_and_skips_id_tags() {
+    let lines = parse_lrc(
+        "[ti:Song]\n[ar:Artist]\n[00:12.30][01:40.00]Chorus\n[00:05.5]Verse\n[00:20:25]\n\nno tag\n",
+    );
+
+    assert_eq!(
+        lines,
+        vec![
+            line(5_500, "Verse"),
+            line(12_300, "Chorus"),
+            line(20_250, ""),
+            line(100_000, "Chorus"),
+        ]
+    );
+}
+
+#[test]
+fn offset_tag_moves_every_line_earlier() {
+    let lines = parse_lrc("[offset:+500]\n[00:00.20]Intro\n[00:10.000]Verse\n");
+
+    assert_eq!(lines, vec![line(0, "Intro"), line(9_500, "Verse")]);
+}
+
+#[test]
+fn resolved_lines_are_shifted_to_the_music_start() {
+    let lines = vec![
+        line(5_000, "One"),
+        line(65_000, "Two"),
+        line(70_000, "Three"),
+    ];
+
+    assert_eq!(resolve_lyric_line(&lines, 0, 4_999), None);
+    assert_eq!(
+        resolve_lyric_line(&lines, 0, 65_000),
+        Some(LyricLineCue {
+            index: 1,
+            text: "Two".to_string(),
+            start_ms: 65_000,
+            end_ms: Some(70_000),
+        })
+    );
+    assert_eq!(
+        resolve_lyric_line(&lines, 60_000, 62_000),
+        Some(LyricLineCue {
+            index: 0,
+            text: "One".to_string(),
+            start_ms: 0,
+            end_ms: Some(5_000),
+        })
+    );
+    assert_eq!(
+        resolve_lyric_line(&lines, 60_000, 90_000),
+        Some(LyricLineCue {
+            index: 2,
+            text: "Three".to_string(),
+            start_ms: 10_000,
+            end_ms: None,
+        })
+    );
+}
+
+#[test]
+fn sidecar_relative_path_follows_the_audio_folder() {
+    assert_eq!(
+        lyrics_sidecar_relative_path(
+            "Disc 1/01 Song.flac",
+            Path::new("/music/Disc 1/01 Song.LRC")
+        ),
+        Some(
+            Path::new("Disc 1")
+                .join("01 Song.LRC")
+                .to_string_lossy()
+                .to_string()
+        )
+    );
+}
@@ -20,10 +20,16 @@ pub async fn get_meta_info(app: AppHandle) -> Result<Option<MetaInfo>, String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn save_meta_info(app: AppHandle, meta: MetaInfo) -> Result<MetaInfo, String> {
-    super::repo::save_meta_info(super::repo::resolve_meta_info(
-        Some(meta),
-        default_save_path(&app)?,
-    ))
-    .await
-    .map_err(|error| error.to_string())
+    let meta = super::repo::resolve_meta_info(Some(meta), default_save_path(&app)?);
+    super::repo::validate_save_roots(&meta).map_err(|error| error.to_string())?;
+    super::service::ensure_save_roots_keep_collections(&meta)
+        .await
+        .map_err(|error| error.to_string())?;
+    crate::domain::save_root_migration::ensure_save_path_change_allowed(&meta)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    super::repo::save_meta_info(meta)
+        .await
+        .map_err(|error| error.to_string())
 }
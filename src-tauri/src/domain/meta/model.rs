@@ -1,11 +1,90 @@
 use appdb::Store;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
 use surrealdb_types::SurrealValue;
 
+/// Name under which `MetaInfo::save_path` is addressed next to the named
+/// save roots.
+pub const DEFAULT_SAVE_ROOT_NAME: &str = "default";
+
+/// Named save roots as last read from or written to the database, so
+/// collection folders resolve without a database round trip.
+static NAMED_SAVE_ROOTS: LazyLock<RwLock<Vec<SaveRoot>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
 #[derive(Debug, Serialize, Deserialize, Clone, SurrealValue, Store, Type)]
 pub struct MetaInfo {
     // pub ffmpeg_path: Option<String>,
     // pub ytdlp_path: Option<String>,
     pub save_path: Option<String>,
+    #[serde(default)]
+    pub save_roots: Option<Vec<SaveRoot>>,
+}
+
+/// An additional library location, such as a second drive, that collections
+/// can live on instead of the default `save_path`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, SurrealValue, Type)]
+pub struct SaveRoot {
+    pub name: String,
+    pub path: String,
+}
+
+impl MetaInfo {
+    /// Resolves a save root by name; `None` and `DEFAULT_SAVE_ROOT_NAME` both
+    /// address the default `save_path`.
+    pub fn save_root_path(&self, name: Option<&str>) -> Option<PathBuf> {
+        match name {
+            None | Some(DEFAULT_SAVE_ROOT_NAME) => self.save_path.as_ref().map(PathBuf::from),
+            Some(name) => self
+                .save_roots
+                .iter()
+                .flatten()
+                .find(|root| root.name == name)
+                .map(|root| PathBuf::from(&root.path)),
+        }
+    }
+
+    /// Makes these named roots the ones `collection_dir` resolves against.
+    pub(crate) fn publish_save_roots(&self) {
+        *NAMED_SAVE_ROOTS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            self.save_roots.clone().unwrap_or_default();
+    }
+}
+
+/// Path of the save root named `save_root`, `None` being the default root at
+/// `default_root`; a root that is not configured falls back to the default one.
+pub fn save_root_dir(default_root: &Path, save_root: Option<&str>) -> PathBuf {
+    let Some(name) = save_root.filter(|name| *name != DEFAULT_SAVE_ROOT_NAME) else {
+        return default_root.to_path_buf();
+    };
+    NAMED_SAVE_ROOTS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .find(|root| root.name == name)
+        .map(|root| PathBuf::from(&root.path))
+        .unwrap_or_else(|| {
+            log::warn!(target: "meta", "unknown_save_root name=\"{}\"", name);
+            default_root.to_path_buf()
+        })
+}
+
+/**
+ * Behavior:
+ *   Resolve the folder of a collection on `save_root`, `None` being the
+ *   default root at `default_root`.
+ *
+ * Core invariants:
+ *   - Folders are relative to their root, so a named root can change its path
+ *     without touching its collections.
+ *   - Folders stored absolute by older versions resolve to themselves.
+ *   - A root that is not configured falls back to the default root, where the
+ *     collection files are then reported missing instead of guessed.
+ */
+pub fn collection_dir(default_root: &Path, save_root: Option<&str>, folder: &str) -> PathBuf {
+    save_root_dir(default_root, save_root).join(folder)
 }
@@ -1,8 +1,10 @@
-use super::model::MetaInfo;
-use anyhow::Result;
+use super::model::{DEFAULT_SAVE_ROOT_NAME, MetaInfo};
+use anyhow::{Result, bail};
 use appdb::error::{DBError, classify_db_error};
 use appdb::model::meta::ModelMeta;
 use appdb::repository::Repo;
+use std::collections::HashSet;
+use std::path::Path;
 use std::time::Duration;
 use surrealdb::types::RecordId;
 
@@ -11,7 +13,10 @@ const ENSURE_META_INFO_RETRY_ATTEMPTS: usize = 6;
 
 pub async fn get_meta_info() -> Result<Option<MetaInfo>> {
     match Repo::<MetaInfo>::get_record(meta_info_record_id()).await {
-        Ok(meta) => Ok(Some(meta)),
+        Ok(meta) => {
+            meta.publish_save_roots();
+            Ok(Some(meta))
+        }
         Err(error) => match classify_db_error(&error) {
             DBError::NotFound | DBError::MissingTable(_) => Ok(None),
             other => Err(other.into()),
@@ -20,11 +25,16 @@ pub async fn get_meta_info() -> Result<Option<MetaInfo>> {
 }
 
 pub async fn save_meta_info(meta: MetaInfo) -> Result<MetaInfo> {
-    Repo::<MetaInfo>::upsert_at(meta_info_record_id(), meta).await
+    let saved = Repo::<MetaInfo>::upsert_at(meta_info_record_id(), meta).await?;
+    saved.publish_save_roots();
+    Ok(saved)
 }
 
 pub fn resolve_meta_info(meta: Option<MetaInfo>, default_save_path: String) -> MetaInfo {
-    let mut meta = meta.unwrap_or(MetaInfo { save_path: None });
+    let mut meta = meta.unwrap_or(MetaInfo {
+        save_path: None,
+        save_roots: None,
+    });
 
     if meta.save_path.is_none() {
         meta.save_path = Some(default_save_path);
@@ -33,6 +43,52 @@ pub fn resolve_meta_info(meta: Option<MetaInfo>, default_save_path: String) -> M
     meta
 }
 
+/**
+ * Behavior:
+ *   Reject save root lists that cannot be addressed unambiguously.
+ *
+ * Core invariants:
+ *   - Root names are unique, non-empty and never shadow the default root.
+ *   - Root paths are absolute and never equal to another root's path.
+ */
+pub fn validate_save_roots(meta: &MetaInfo) -> Result<()> {
+    let mut names = HashSet::new();
+    let mut paths = meta
+        .save_path
+        .iter()
+        .map(|path| normalize_save_root_path(path))
+        .collect::<HashSet<_>>();
+    for root in meta.save_roots.iter().flatten() {
+        let name = root.name.trim();
+        if name.is_empty() || name != root.name {
+            bail!(
+                "save root name `{}` must be non-empty and trimmed",
+                root.name
+            );
+        }
+        if name == DEFAULT_SAVE_ROOT_NAME {
+            bail!(
+                "save root name `{DEFAULT_SAVE_ROOT_NAME}` is reserved for the default save path"
+            );
+        }
+        if !names.insert(name) {
+            bail!("save root name `{name}` is used more than once");
+        }
+        if !Path::new(&root.path).is_absolute() {
+            bail!("save root `{name}` must use an absolute path");
+        }
+        if !paths.insert(normalize_save_root_path(&root.path)) {
+            bail!("save root `{name}` points at a path that is already a save root");
+        }
+    }
+
+    Ok(())
+}
+
+fn normalize_save_root_path(path: &str) -> String {
+    path.replace('\\', "/").trim_end_matches('/').to_lowercase()
+}
+
 pub async fn ensure_meta_info(default_save_path: String) -> Result<MetaInfo> {
     let mut backoff = Duration::from_millis(5);
 
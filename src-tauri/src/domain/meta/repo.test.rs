@@ -1,7 +1,7 @@
-use super::model::MetaInfo;
+use super::model::{MetaInfo, SaveRoot};
 use super::repo::{
     ensure_meta_info, get_meta_info, is_retryable_transaction_conflict, resolve_meta_info,
-    save_meta_info,
+    save_meta_info, validate_save_roots,
 };
 use crate::domain::playlists::PLAYLIST_DB_TEST_LOCK;
 use appdb::connection::{reinit_db, reset_db};
@@ -58,6 +58,7 @@ fn saves_and_loads_singleton_meta_info() {
 
         save_meta_info(MetaInfo {
             save_path: Some("D:\\MediaLibrary".to_string()),
+            save_roots: None,
         })
         .await
         .expect("meta info should save");
@@ -78,12 +79,16 @@ fn resolve_meta_info_backfills_the_default_save_path() {
     let resolved_missing =
         resolve_meta_info(None, "C:\\Users\\admin\\Documents\\slisic".to_string());
     let resolved_null = resolve_meta_info(
-        Some(MetaInfo { save_path: None }),
+        Some(MetaInfo {
+            save_path: None,
+            save_roots: None,
+        }),
         "C:\\Users\\admin\\Documents\\slisic".to_string(),
     );
     let resolved_existing = resolve_meta_info(
         Some(MetaInfo {
             save_path: Some("D:\\MediaLibrary".to_string()),
+            save_roots: None,
         }),
         "C:\\Users\\admin\\Documents\\slisic".to_string(),
     );
@@ -127,9 +132,8 @@ fn ensure_meta_info_persists_the_default_save_path_when_missing() {
 
 #[test]
 fn retryable_transaction_conflicts_are_classified_for_startup_meta_resolution() {
-    let failed_transaction = anyhow::anyhow!(
-        "SurrealDB error: The query was not executed due to a failed transaction"
-    );
+    let failed_transaction =
+        anyhow::anyhow!("SurrealDB error: The query was not executed due to a failed transaction");
     let write_conflict = anyhow::anyhow!(
         "Transaction conflict: Transaction write conflict. This transaction can be retried"
     );
@@ -139,3 +143,39 @@ fn retryable_transaction_conflicts_are_classified_for_startup_meta_resolution()
     assert!(is_retryable_transaction_conflict(&write_conflict));
     assert!(!is_retryable_transaction_conflict(&ordinary_error));
 }
+
+#[test]
+fn named_save_roots_resolve_by_name_and_reject_ambiguous_entries() {
+    let base = std::env::temp_dir();
+    let path = |name: &str| base.join(name).to_string_lossy().to_string();
+    let root = |name: &str, path: String| SaveRoot {
+        name: name.to_string(),
+        path,
+    };
+    let meta = |roots: Vec<SaveRoot>| MetaInfo {
+        save_path: Some(path("ssd")),
+        save_roots: Some(roots),
+    };
+
+    let valid = meta(vec![root("hdd", path("hdd"))]);
+    assert!(validate_save_roots(&valid).is_ok());
+    assert_eq!(valid.save_root_path(None), Some(base.join("ssd")));
+    assert_eq!(
+        valid.save_root_path(Some("default")),
+        Some(base.join("ssd"))
+    );
+    assert_eq!(valid.save_root_path(Some("hdd")), Some(base.join("hdd")));
+    assert_eq!(valid.save_root_path(Some("usb")), None);
+
+    assert!(validate_save_roots(&meta(vec![root("default", path("hdd"))])).is_err());
+    assert!(validate_save_roots(&meta(vec![root(" ", path("hdd"))])).is_err());
+    assert!(validate_save_roots(&meta(vec![root("hdd", "relative".to_string())])).is_err());
+    assert!(validate_save_roots(&meta(vec![root("hdd", format!("{}/", path("ssd")))])).is_err());
+    assert!(
+        validate_save_roots(&meta(vec![
+            root("hdd", path("hdd")),
+            root("hdd", path("usb"))
+        ]))
+        .is_err()
+    );
+}
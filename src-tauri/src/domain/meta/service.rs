@@ -1,4 +1,5 @@
-use anyhow::{Context, Result, anyhow};
+use super::model::{DEFAULT_SAVE_ROOT_NAME, MetaInfo};
+use anyhow::{Context, Result, anyhow, bail};
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
@@ -20,3 +21,46 @@ pub async fn resolve_save_root(app: &AppHandle) -> Result<PathBuf> {
         .with_context(|| format!("failed to create {}", root.display()))?;
     Ok(root)
 }
+
+/**
+ * Behavior:
+ *   Refuse a save root change that would strand collections.
+ *
+ * Core invariants:
+ *   - A named root holding collections keeps its name until its collections
+ *     were moved to another root; its path may change, since collection
+ *     folders are relative to their root.
+ */
+pub async fn ensure_save_roots_keep_collections(next: &MetaInfo) -> Result<()> {
+    let current = super::repo::get_meta_info().await?;
+    for name in crate::domain::playlists::repo::list_collection_save_root_names().await? {
+        let configured = current
+            .as_ref()
+            .is_some_and(|meta| meta.save_root_path(Some(&name)).is_some());
+        if configured && next.save_root_path(Some(&name)).is_none() {
+            bail!(
+                "save root `{name}` still holds collections; move them to another root before removing or renaming it"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves a named save root, or the default one for `None`, and makes sure
+/// the folder exists.
+pub async fn resolve_named_save_root(app: &AppHandle, name: Option<&str>) -> Result<PathBuf> {
+    let default_root = resolve_save_root(app).await?;
+    let Some(name) = name.filter(|name| *name != DEFAULT_SAVE_ROOT_NAME) else {
+        return Ok(default_root);
+    };
+    let meta = super::repo::get_meta_info()
+        .await?
+        .ok_or_else(|| anyhow!("save path should always be configured"))?;
+    let root = meta
+        .save_root_path(Some(name))
+        .ok_or_else(|| anyhow!("save root `{name}` is not configured"))?;
+    std::fs::create_dir_all(&root)
+        .with_context(|| format!("failed to create {}", root.display()))?;
+    Ok(root)
+}
@@ -1,7 +1,16 @@
+pub mod audio_segmentation;
+pub mod audio_tags;
 pub mod audio_tail_trim;
 pub mod collection_import;
+pub mod collection_manifest_sync;
+pub mod collection_merge;
+pub mod collection_relocation;
+pub mod cue_sheet;
 pub mod downloads;
+pub mod library_backup;
+pub mod library_integrity;
 pub mod loudness_evidence;
+pub mod lyrics;
 pub mod meta;
 pub mod player;
 pub mod playlist_playback;
@@ -10,3 +19,7 @@ mod remote_host_identity;
 mod remote_p2p_hls;
 mod remote_p2p_transport;
 pub mod remote_share;
+pub mod save_root_migration;
+/// Fixtures shared by the persistence tests of the domain modules.
+#[cfg(test)]
+pub(crate) mod test_support;
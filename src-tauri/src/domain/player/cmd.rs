@@ -1,6 +1,6 @@
 use super::model::{
-    PlaybackContinuationMode, PlaybackStatusPayload, PlaybackTrackPayload,
-    SpectrumPlaybackLoopSignalPayload,
+    ListeningHistoryEntry, PlaybackContinuationMode, PlaybackStatusPayload, PlaybackTrackPayload,
+    PlaylistPlayStats, SpectrumPlaybackLoopSignalPayload, TrackPlayStats,
 };
 use super::waveform::{TrackWaveform, TrackWaveformSummary, TrackWaveformTile};
 use tauri::AppHandle;
@@ -207,3 +207,27 @@ pub async fn get_track_waveform_tile(
     .await
     .map_err(|error| error.to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_recent_plays(limit: Option<u32>) -> Result<Vec<ListeningHistoryEntry>, String> {
+    super::service::list_recent_plays(limit)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_track_play_stats(limit: Option<u32>) -> Result<Vec<TrackPlayStats>, String> {
+    super::service::list_track_play_stats(limit)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_playlist_play_stats() -> Result<Vec<PlaylistPlayStats>, String> {
+    super::service::list_playlist_play_stats()
+        .await
+        .map_err(|error| error.to_string())
+}
@@ -1,4 +1,5 @@
 use super::model::{ActivePlaybackRange, PlaybackTrack, PlaybackTrackPayload};
+use crate::domain::lyrics::LyricLineCue;
 use crate::domain::playlists::model::{Exclude, ExcludeAvailability};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -80,6 +81,18 @@ pub struct PlaybackAudioVisualizationFrameEvent {
     pub dynamics: f32,
 }
 
+/// Sent when the lyric line under the playhead changes, including to no line;
+/// times are relative to the start of the music's range.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct PlaybackLyricLineChangedEvent {
+    pub session_generation: u64,
+    pub playlist_name: String,
+    pub canonical_music_id: String,
+    pub music_url: String,
+    pub position_ms: u32,
+    pub line: Option<LyricLineCue>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
 pub struct PlaybackExcludeCommittedEvent {
     pub exclude: Exclude,
@@ -0,0 +1,87 @@
+use super::model::{
+    ListeningHistoryEntry, ListeningOutcome, PlaybackContinuationMode, PlaybackTrack,
+};
+use std::time::{Duration, Instant};
+
+/**
+ * Behavior:
+ *   Track the single play that is currently audible and turn it into a
+ *   `ListeningHistoryEntry` once the player moves away from it.
+ *
+ * Core invariants:
+ *   - At most one play is open; beginning a new play closes the previous one
+ *     as `Completed`, because the player only advances by itself once a range
+ *     ends. A play that anything else replaces is finished as `Stopped` first.
+ *   - Heard time only accumulates while the play is not paused and is capped
+ *     at the played range length.
+ */
+#[derive(Debug, Default)]
+pub(crate) struct ListeningHistoryRecorder {
+    open: Option<OpenListeningPlay>,
+}
+
+#[derive(Debug)]
+struct OpenListeningPlay {
+    entry: ListeningHistoryEntry,
+    heard: Duration,
+    resumed_at: Option<Instant>,
+}
+
+impl ListeningHistoryRecorder {
+    pub(crate) fn begin(
+        &mut self,
+        track: &PlaybackTrack,
+        continuation_mode: PlaybackContinuationMode,
+        started_at_ms: u64,
+        now: Instant,
+    ) -> Option<ListeningHistoryEntry> {
+        let previous = self.finish(ListeningOutcome::Completed, now);
+        self.open = Some(OpenListeningPlay {
+            entry: ListeningHistoryEntry {
+                canonical_music_id: track.canonical_music_id.clone(),
+                music_url: track.music_url.clone(),
+                music_name: track.music_name.clone(),
+                playlist_name: track.playlist_name.clone(),
+                start_ms: track.start_ms,
+                end_ms: track.end_ms,
+                started_at_ms,
+                listened_ms: 0,
+                outcome: ListeningOutcome::Completed,
+                continuation_mode,
+            },
+            heard: Duration::ZERO,
+            resumed_at: Some(now),
+        });
+        previous
+    }
+
+    pub(crate) fn pause(&mut self, now: Instant) {
+        if let Some(open) = self.open.as_mut()
+            && let Some(resumed_at) = open.resumed_at.take()
+        {
+            open.heard += now.saturating_duration_since(resumed_at);
+        }
+    }
+
+    pub(crate) fn resume(&mut self, now: Instant) {
+        if let Some(open) = self.open.as_mut()
+            && open.resumed_at.is_none()
+        {
+            open.resumed_at = Some(now);
+        }
+    }
+
+    pub(crate) fn finish(
+        &mut self,
+        outcome: ListeningOutcome,
+        now: Instant,
+    ) -> Option<ListeningHistoryEntry> {
+        self.pause(now);
+        let open = self.open.take()?;
+        let mut entry = open.entry;
+        let range_ms = u128::from(entry.end_ms.saturating_sub(entry.start_ms));
+        entry.listened_ms = open.heard.as_millis().min(range_ms) as u32;
+        entry.outcome = outcome;
+        Some(entry)
+    }
+}
@@ -0,0 +1,92 @@
+use super::history::ListeningHistoryRecorder;
+use super::model::{ListeningOutcome, PlaybackContinuationMode, PlaybackTrack};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn track(name: &str, playlist_name: &str) -> PlaybackTrack {
+    PlaybackTrack {
+        playlist_name: playlist_name.to_string(),
+        music_name: name.to_string(),
+        canonical_music_id: format!("source:https://example.com/{name}:0:60000"),
+        music_url: format!("https://example.com/{name}"),
+        file_path: PathBuf::from(format!("{name}.m4a")),
+        source_music: None,
+        start_ms: 0,
+        end_ms: 60_000,
+        liked: false,
+        loudness_profile: None,
+    }
+}
+
+#[test]
+fn beginning_the_next_play_closes_the_previous_one_as_completed() {
+    let mut recorder = ListeningHistoryRecorder::default();
+    let start = Instant::now();
+
+    assert!(
+        recorder
+            .begin(
+                &track("one", "Focus"),
+                PlaybackContinuationMode::Random,
+                1_000,
+                start,
+            )
+            .is_none()
+    );
+    let finished = recorder
+        .begin(
+            &track("two", "Focus"),
+            PlaybackContinuationMode::RepeatCurrent,
+            2_000,
+            start + Duration::from_secs(90),
+        )
+        .expect("previous play should close");
+
+    assert_eq!(finished.music_name, "one");
+    assert_eq!(finished.started_at_ms, 1_000);
+    assert_eq!(finished.outcome, ListeningOutcome::Completed);
+    assert_eq!(
+        finished.listened_ms, 60_000,
+        "heard time is capped at the range"
+    );
+    assert_eq!(finished.continuation_mode, PlaybackContinuationMode::Random);
+
+    let skipped = recorder
+        .finish(ListeningOutcome::Skipped, start + Duration::from_secs(95))
+        .expect("open play should finish");
+    assert_eq!(skipped.music_name, "two");
+    assert_eq!(skipped.outcome, ListeningOutcome::Skipped);
+    assert_eq!(skipped.listened_ms, 5_000);
+    assert_eq!(
+        skipped.continuation_mode,
+        PlaybackContinuationMode::RepeatCurrent
+    );
+    assert!(
+        recorder
+            .finish(ListeningOutcome::Stopped, start + Duration::from_secs(96))
+            .is_none()
+    );
+}
+
+#[test]
+fn paused_time_is_not_counted_as_heard() {
+    let mut recorder = ListeningHistoryRecorder::default();
+    let start = Instant::now();
+    recorder.begin(
+        &track("one", "Focus"),
+        PlaybackContinuationMode::Random,
+        1_000,
+        start,
+    );
+
+    recorder.pause(start + Duration::from_secs(10));
+    recorder.pause(start + Duration::from_secs(20));
+    recorder.resume(start + Duration::from_secs(40));
+    recorder.resume(start + Duration::from_secs(45));
+    let finished = recorder
+        .finish(ListeningOutcome::Excluded, start + Duration::from_secs(43))
+        .expect("open play should finish");
+
+    assert_eq!(finished.listened_ms, 13_000);
+    assert_eq!(finished.outcome, ListeningOutcome::Excluded);
+}
@@ -3,7 +3,9 @@ pub mod cmd;
 
 #[cfg(not(test))]
 pub mod event;
+pub mod history;
 pub mod model;
+pub mod repo;
 pub mod service;
 pub mod strategy;
 pub mod track_identity_substitution;
@@ -20,6 +22,14 @@ mod strategy_test;
 #[path = "track_identity_substitution.test.rs"]
 mod track_identity_substitution_test;
 
+#[cfg(test)]
+#[path = "history.test.rs"]
+mod history_test;
+
+#[cfg(test)]
+#[path = "repo.test.rs"]
+mod repo_test;
+
 #[cfg(test)]
 #[path = "service.test.rs"]
 mod service_test;
@@ -1,11 +1,14 @@
+use appdb::Store;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::path::PathBuf;
+use surrealdb_types::SurrealValue;
 
 use crate::domain::playlists::model::{LoudnessProfile, Music};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SurrealValue, Type)]
 #[serde(rename_all = "camelCase")]
+#[surreal(rename_all = "camelCase")]
 pub enum PlaybackContinuationMode {
     Random,
     RepeatCurrent,
@@ -136,3 +139,57 @@ impl PlaybackTrack {
         }
     }
 }
+
+/// How a listening-history play ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SurrealValue, Type)]
+#[serde(rename_all = "snake_case")]
+#[surreal(rename_all = "snake_case")]
+pub enum ListeningOutcome {
+    /// The range played through and the player moved on by itself.
+    Completed,
+    /// The user skipped to the next track.
+    Skipped,
+    /// The track was excluded while playing and skipped as a consequence.
+    Excluded,
+    /// Playback was stopped or the session was replaced before the range ended.
+    Stopped,
+}
+
+/// One durable play in the listening history.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, Store, Type)]
+pub struct ListeningHistoryEntry {
+    pub canonical_music_id: String,
+    pub music_url: String,
+    pub music_name: String,
+    pub playlist_name: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub started_at_ms: u64,
+    pub listened_ms: u32,
+    pub outcome: ListeningOutcome,
+    pub continuation_mode: PlaybackContinuationMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, Type)]
+pub struct TrackPlayStats {
+    pub canonical_music_id: String,
+    pub music_url: String,
+    pub music_name: String,
+    pub play_count: u32,
+    pub completed_count: u32,
+    pub skip_count: u32,
+    pub listened_ms: u64,
+    pub last_played_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, Type)]
+pub struct PlaylistPlayStats {
+    pub playlist_name: String,
+    pub play_count: u32,
+    pub completed_count: u32,
+    pub skip_count: u32,
+    pub excluded_count: u32,
+    pub distinct_track_count: u32,
+    pub listened_ms: u64,
+    pub last_played_at_ms: u64,
+}
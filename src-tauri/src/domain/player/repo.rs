@@ -0,0 +1,134 @@
+use super::model::{ListeningHistoryEntry, PlaylistPlayStats, TrackPlayStats};
+use anyhow::Result;
+use appdb::connection::get_db;
+use appdb::error::{DBError, classify_db_error};
+use appdb::model::meta::ModelMeta;
+use appdb::repository::Repo;
+use surrealdb::types::Table;
+use surrealdb_types::SurrealValue;
+
+pub async fn save_listening_history_entry(
+    entry: ListeningHistoryEntry,
+) -> Result<ListeningHistoryEntry> {
+    Repo::<ListeningHistoryEntry>::create(entry).await
+}
+
+pub async fn list_recent_listening_history(limit: u32) -> Result<Vec<ListeningHistoryEntry>> {
+    query_listening_history(
+        "SELECT * OMIT id FROM $table
+         ORDER BY started_at_ms DESC
+         LIMIT $limit;",
+        Some(limit),
+    )
+    .await
+}
+
+/// Play stats per track, most played first, aggregated by the database. The
+/// name is the one of the latest play.
+pub async fn list_track_play_stats(limit: Option<u32>) -> Result<Vec<TrackPlayStats>> {
+    let limit_clause = if limit.is_some() { "LIMIT $limit" } else { "" };
+    query_listening_history(
+        &format!(
+            "SELECT
+                 canonical_music_id,
+                 music_url,
+                 (SELECT VALUE music_name FROM $table
+                  WHERE canonical_music_id = $parent.canonical_music_id
+                  ORDER BY started_at_ms DESC
+                  LIMIT 1)[0] AS music_name,
+                 play_count,
+                 completed_count,
+                 skip_count,
+                 listened_ms,
+                 last_played_at_ms
+             FROM (
+                 SELECT
+                     canonical_music_id,
+                     array::first(music_url) AS music_url,
+                     count() AS play_count,
+                     count(outcome = 'completed') AS completed_count,
+                     count(outcome = 'skipped') AS skip_count,
+                     math::sum(listened_ms) AS listened_ms,
+                     math::max(started_at_ms) AS last_played_at_ms
+                 FROM $table
+                 GROUP BY canonical_music_id
+             )
+             ORDER BY play_count DESC, last_played_at_ms DESC, canonical_music_id ASC
+             {limit_clause};"
+        ),
+        limit,
+    )
+    .await
+}
+
+/// Play stats per playlist, most recently played first, aggregated by the
+/// database.
+pub async fn list_playlist_play_stats() -> Result<Vec<PlaylistPlayStats>> {
+    query_listening_history(
+        "SELECT
+             playlist_name,
+             play_count,
+             completed_count,
+             skip_count,
+             excluded_count,
+             array::len(tracks) AS distinct_track_count,
+             listened_ms,
+             last_played_at_ms
+         FROM (
+             SELECT
+                 playlist_name,
+                 count() AS play_count,
+                 count(outcome = 'completed') AS completed_count,
+                 count(outcome = 'skipped') AS skip_count,
+                 count(outcome = 'excluded') AS excluded_count,
+                 array::distinct(canonical_music_id) AS tracks,
+                 math::sum(listened_ms) AS listened_ms,
+                 math::max(started_at_ms) AS last_played_at_ms
+             FROM $table
+             GROUP BY playlist_name
+         )
+         ORDER BY last_played_at_ms DESC, playlist_name ASC;",
+        None,
+    )
+    .await
+}
+
+/// Runs one query over the history table, reading a missing table as empty.
+async fn query_listening_history<T: SurrealValue>(
+    query: &str,
+    limit: Option<u32>,
+) -> Result<Vec<T>> {
+    let db = get_db()?;
+    let mut query = db
+        .query(query)
+        .bind(("table", Table::from(ListeningHistoryEntry::table_name())));
+    if let Some(limit) = limit {
+        query = query.bind(("limit", i64::from(limit)));
+    }
+    let mut result = match query.await {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(vec![]),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    Ok(result.take(0)?)
+}
+
+#[cfg(test)]
+pub(crate) async fn list_listening_history() -> Result<Vec<ListeningHistoryEntry>> {
+    match Repo::<ListeningHistoryEntry>::list().await {
+        Ok(entries) => Ok(entries),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => Ok(vec![]),
+            other => Err(other.into()),
+        },
+    }
+}
@@ -0,0 +1,148 @@
+use super::model::{ListeningHistoryEntry, ListeningOutcome, PlaybackContinuationMode};
+use super::repo::{
+    list_listening_history, list_playlist_play_stats, list_recent_listening_history,
+    list_track_play_stats, save_listening_history_entry,
+};
+use crate::domain::test_support::{acquire_db_test_lock, run_async, temp_path};
+use appdb::connection::reinit_db;
+
+fn play(name: &str, started_at_ms: u64, outcome: ListeningOutcome) -> ListeningHistoryEntry {
+    ListeningHistoryEntry {
+        canonical_music_id: format!("source:https://example.com/{name}:0:60000"),
+        music_url: format!("https://example.com/{name}"),
+        music_name: name.to_string(),
+        playlist_name: "Focus".to_string(),
+        start_ms: 0,
+        end_ms: 60_000,
+        started_at_ms,
+        listened_ms: 12_000,
+        outcome,
+        continuation_mode: PlaybackContinuationMode::RepeatCurrent,
+    }
+}
+
+#[test]
+fn listening_history_lists_recent_plays_newest_first() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        reinit_db(temp_path("player_repo_db"))
+            .await
+            .expect("player repo database should initialize");
+
+        assert!(
+            list_recent_listening_history(10)
+                .await
+                .expect("missing history table should list as empty")
+                .is_empty()
+        );
+
+        save_listening_history_entry(play("one", 10, ListeningOutcome::Completed))
+            .await
+            .expect("first play should save");
+        save_listening_history_entry(play("two", 30, ListeningOutcome::Skipped))
+            .await
+            .expect("second play should save");
+        save_listening_history_entry(play("one", 20, ListeningOutcome::Excluded))
+            .await
+            .expect("repeated play should save as its own entry");
+
+        let recent = list_recent_listening_history(2)
+            .await
+            .expect("recent plays should list");
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].music_name, "two");
+        assert_eq!(recent[0].outcome, ListeningOutcome::Skipped);
+        assert_eq!(recent[1].started_at_ms, 20);
+        assert_eq!(recent[1].outcome, ListeningOutcome::Excluded);
+        assert_eq!(
+            recent[1].continuation_mode,
+            PlaybackContinuationMode::RepeatCurrent
+        );
+
+        assert_eq!(
+            list_listening_history()
+                .await
+                .expect("full history should list")
+                .len(),
+            3
+        );
+    });
+}
+
+fn play_in(
+    name: &str,
+    playlist_name: &str,
+    started_at_ms: u64,
+    listened_ms: u32,
+    outcome: ListeningOutcome,
+) -> ListeningHistoryEntry {
+    ListeningHistoryEntry {
+        playlist_name: playlist_name.to_string(),
+        listened_ms,
+        ..play(name, started_at_ms, outcome)
+    }
+}
+
+#[test]
+fn play_stats_are_aggregated_per_track_and_per_playlist() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        reinit_db(temp_path("player_stats_db"))
+            .await
+            .expect("player repo database should initialize");
+
+        assert!(
+            list_track_play_stats(None)
+                .await
+                .expect("missing history table should list as empty")
+                .is_empty()
+        );
+
+        for entry in [
+            play_in("one", "Focus", 10, 60_000, ListeningOutcome::Completed),
+            play_in("two", "Focus", 20, 4_000, ListeningOutcome::Excluded),
+            play_in("one", "Drive", 30, 30_000, ListeningOutcome::Skipped),
+            play_in("one", "Focus", 40, 1_000, ListeningOutcome::Stopped),
+        ] {
+            save_listening_history_entry(entry)
+                .await
+                .expect("play should save");
+        }
+
+        let tracks = list_track_play_stats(None)
+            .await
+            .expect("track stats should list");
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].music_name, "one");
+        assert_eq!(tracks[0].play_count, 3);
+        assert_eq!(tracks[0].completed_count, 1);
+        assert_eq!(tracks[0].skip_count, 1);
+        assert_eq!(tracks[0].listened_ms, 91_000);
+        assert_eq!(tracks[0].last_played_at_ms, 40);
+        assert_eq!(tracks[1].music_name, "two");
+        assert_eq!(tracks[1].play_count, 1);
+        assert_eq!(
+            list_track_play_stats(Some(1))
+                .await
+                .expect("limited track stats should list")
+                .len(),
+            1
+        );
+
+        let playlists = list_playlist_play_stats()
+            .await
+            .expect("playlist stats should list");
+        assert_eq!(playlists.len(), 2);
+        assert_eq!(playlists[0].playlist_name, "Focus");
+        assert_eq!(playlists[0].play_count, 3);
+        assert_eq!(playlists[0].completed_count, 1);
+        assert_eq!(playlists[0].excluded_count, 1);
+        assert_eq!(playlists[0].distinct_track_count, 2);
+        assert_eq!(playlists[0].listened_ms, 65_000);
+        assert_eq!(playlists[0].last_played_at_ms, 40);
+        assert_eq!(playlists[1].playlist_name, "Drive");
+        assert_eq!(playlists[1].skip_count, 1);
+    });
+}
@@ -2,14 +2,20 @@
 use super::event::{
     NowPlayingTrackChangedEvent, NowPlayingTrackLikedChangedEvent,
     PlaybackAudioVisualizationFrameEvent, PlaybackDiagnosticTraceDetail,
-    PlaybackDiagnosticTraceEvent, PlaybackSurfaceStatus, PlaybackSurfaceStatusChangedEvent,
+    PlaybackDiagnosticTraceEvent, PlaybackLyricLineChangedEvent, PlaybackSurfaceStatus,
+    PlaybackSurfaceStatusChangedEvent,
 };
-pub(crate) use super::model::ActivePlaybackRange;
 #[cfg(not(test))]
-use super::model::PlaybackContinuationMode;
+use super::history::ListeningHistoryRecorder;
+pub(crate) use super::model::ActivePlaybackRange;
 #[cfg(not(test))]
 use super::model::PlaybackStatusPayload;
 use super::model::PlaybackTrack;
+#[cfg(not(test))]
+use super::model::{
+    ListeningHistoryEntry, ListeningOutcome, PlaybackContinuationMode, PlaylistPlayStats,
+    TrackPlayStats,
+};
 use super::strategy::PlaybackQueueMode;
 #[cfg(not(test))]
 use super::strategy::PlaybackStrategySet;
@@ -22,6 +28,8 @@ use super::track_identity_substitution::{
 use super::waveform::{self, TrackWaveform, TrackWaveformSummary, TrackWaveformTile};
 #[cfg(not(test))]
 use crate::domain::loudness_evidence::{self, LoudnessEvidenceRequest};
+#[cfg(not(test))]
+use crate::domain::lyrics::{LyricLine, find_lyrics_sidecar, read_lyrics, resolve_lyric_line};
 use crate::domain::playlists::model::LoudnessProfile;
 #[cfg(not(test))]
 use crate::utils::binaries::{
@@ -34,14 +42,14 @@ use anyhow::{Context, Result, anyhow, bail};
 use ffplayr::Playback;
 use ffplayr::{PlaybackNormalization, PlaybackRequest, PlaybackTimeRange};
 #[cfg(not(test))]
-use std::path::Path;
+use std::path::{Path, PathBuf};
 #[cfg(not(test))]
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::{AtomicU64, Ordering};
 #[cfg(not(test))]
 use std::sync::{Arc, Mutex, OnceLock, RwLock};
 #[cfg(not(test))]
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 #[cfg(not(test))]
 use tauri::{AppHandle, Manager};
 #[cfg(not(test))]
@@ -107,6 +115,7 @@ pub struct PlayerRuntime {
     spectrum_playback_loop_signal: RwLock<Option<SpectrumPlaybackLoopSignal>>,
     temporary_playback_pause: RwLock<bool>,
     continuation_mode: RwLock<PlaybackContinuationMode>,
+    listening_history: Mutex<ListeningHistoryRecorder>,
     playback_run_generation: AtomicU64,
     spectrum_playback_scope_generation: AtomicU64,
 }
@@ -249,6 +258,7 @@ pub fn initialize_runtime(app: AppHandle) {
             spectrum_playback_loop_signal: RwLock::new(None),
             temporary_playback_pause: RwLock::new(false),
             continuation_mode: RwLock::new(PlaybackContinuationMode::Random),
+            listening_history: Mutex::new(ListeningHistoryRecorder::default()),
             playback_run_generation: AtomicU64::new(0),
             spectrum_playback_scope_generation: AtomicU64::new(0),
         })
@@ -400,6 +410,94 @@ fn emit_playback_audio_visualization_frame(
     }
 }
 
+/// Lyric line last sent for the track the playback loop is watching.
+#[cfg(not(test))]
+#[derive(Default)]
+struct PlaybackLyricsWatch {
+    track_key: Option<(String, PathBuf)>,
+    lines: Vec<LyricLine>,
+    emitted_index: Option<Option<u32>>,
+}
+
+/// Lyrics of a track, from the sidecar its music points at or, for tracks
+/// restored without their music, the sidecar next to the file.
+#[cfg(not(test))]
+fn load_playback_track_lyrics(track: &PlaybackTrack) -> Vec<LyricLine> {
+    let lyrics_path = match track.source_music.as_deref() {
+        Some(music) => music.lyrics_path.as_deref().and_then(|lyrics_path| {
+            Some(
+                track
+                    .file_path
+                    .with_file_name(Path::new(lyrics_path).file_name()?),
+            )
+        }),
+        None => find_lyrics_sidecar(&track.file_path),
+    };
+    let Some(lyrics_path) = lyrics_path else {
+        return Vec::new();
+    };
+    read_lyrics(&lyrics_path).unwrap_or_else(|error| {
+        log::warn!(
+            target: "player",
+            "playback_lyrics_unreadable title=\"{}\" path=\"{}\" error=\"{}\"",
+            track.music_name,
+            lyrics_path.display(),
+            error
+        );
+        Vec::new()
+    })
+}
+
+/**
+ * Behavior:
+ *   Emit the lyric line under the playhead whenever it changes.
+ *
+ * Core invariants:
+ *   - Lyrics are loaded once per track; a track without lyrics emits a
+ *     single empty line so stale lyrics are cleared.
+ *   - `current_position_ms` is a position in the audio file; the emitted
+ *     times are shifted to the music's `start_ms`.
+ */
+#[cfg(not(test))]
+fn emit_playback_lyric_line_if_changed(
+    app: &AppHandle,
+    generation: u64,
+    watch: &mut PlaybackLyricsWatch,
+    track: &PlaybackTrack,
+    current_position_ms: u32,
+) {
+    let track_key = (track.canonical_music_id.clone(), track.file_path.clone());
+    if watch.track_key.as_ref() != Some(&track_key) {
+        watch.lines = load_playback_track_lyrics(track);
+        watch.track_key = Some(track_key);
+        watch.emitted_index = None;
+    }
+    let line = resolve_lyric_line(&watch.lines, track.start_ms, current_position_ms);
+    let index = line.as_ref().map(|line| line.index);
+    if watch.emitted_index == Some(index) {
+        return;
+    }
+    watch.emitted_index = Some(index);
+
+    let event = PlaybackLyricLineChangedEvent {
+        session_generation: generation,
+        playlist_name: track.playlist_name.clone(),
+        canonical_music_id: track.canonical_music_id.clone(),
+        music_url: track.music_url.clone(),
+        position_ms: current_position_ms.saturating_sub(track.start_ms),
+        line,
+    };
+    if let Err(error) = event.emit(app) {
+        log::warn!(
+            target: "player",
+            "failed to emit playback lyric line playlist=\"{}\" title=\"{}\" error=\"{}\"",
+            track.playlist_name,
+            track.music_name,
+            error
+        );
+    }
+}
+
 #[cfg(not(test))]
 pub(crate) async fn play_tracks_from_initial_track_for_request_with_queue_mode(
     request: &PlaybackStartRequestHandle,
@@ -519,6 +617,7 @@ async fn play_tracks_with_initial_track(
                 end_ms: track.end_ms,
             });
             log_player_stop_done(track, active_range, "session_restart");
+            runtime.finish_listening_play(ListeningOutcome::Stopped);
             emit_playback_audio_visualization_frame(
                 &runtime.app,
                 playback_run_generation,
@@ -728,6 +827,27 @@ pub(crate) fn active_request_track_snapshot() -> Result<Option<PlaybackTrack>> {
     runtime()?.active_request_track_snapshot()
 }
 
+/// The active library track with the playhead position in its audio file, or
+/// `None` while the playing file is not a library track.
+#[cfg(not(test))]
+pub(crate) async fn active_request_track_position_snapshot() -> Result<Option<(PlaybackTrack, u32)>>
+{
+    let runtime = runtime()?;
+    let Some(playback) = runtime.current_playback()? else {
+        return Ok(None);
+    };
+    let status = playback
+        .status()
+        .await
+        .map_err(|error| anyhow!("failed to read playback status: {error}"))?;
+    let Some(track) = runtime.active_request_track_for_status_path(status.path.as_deref())? else {
+        return Ok(None);
+    };
+    let position_ms =
+        resolve_playback_absolute_position_ms(&status, runtime.active_playback_range_snapshot()?);
+    Ok(Some((track, position_ms)))
+}
+
 #[cfg(not(test))]
 pub(crate) async fn update_current_session_track_identity(
     update: &PlaybackTrackIdentityUpdate,
@@ -951,6 +1071,7 @@ pub async fn stop_playback() -> Result<bool> {
     runtime.clear_active_session()?;
     runtime.set_temporary_playback_pause(false)?;
     runtime.clear_spectrum_playback_loop_signal()?;
+    runtime.finish_listening_play(ListeningOutcome::Stopped);
     let Some(playback) = runtime.current_playback()? else {
         return Ok(false);
     };
@@ -975,6 +1096,7 @@ pub async fn pause_playback() -> Result<bool> {
         .pause()
         .await
         .map_err(|error| anyhow!("failed to pause playback: {error}"))?;
+    runtime.with_listening_history(|history| history.pause(Instant::now()));
 
     Ok(true)
 }
@@ -991,16 +1113,30 @@ pub async fn resume_playback() -> Result<bool> {
         .resume()
         .await
         .map_err(|error| anyhow!("failed to resume playback: {error}"))?;
+    runtime.with_listening_history(|history| history.resume(Instant::now()));
 
     Ok(true)
 }
 
 #[cfg(not(test))]
 pub async fn skip_current_track() -> Result<bool> {
+    skip_current_track_with_outcome(ListeningOutcome::Skipped).await
+}
+
+/// Skips the current track after it was excluded, so the listening history
+/// records the play as `Excluded` rather than as a user skip.
+#[cfg(not(test))]
+pub(crate) async fn skip_excluded_current_track() -> Result<bool> {
+    skip_current_track_with_outcome(ListeningOutcome::Excluded).await
+}
+
+#[cfg(not(test))]
+async fn skip_current_track_with_outcome(outcome: ListeningOutcome) -> Result<bool> {
     let runtime = runtime()?;
     runtime.set_temporary_playback_pause(false)?;
     runtime.clear_spectrum_playback_loop_signal()?;
     runtime.clear_active_playback_range()?;
+    runtime.finish_listening_play(outcome);
     let Some(playback) = runtime.current_playback()? else {
         return Ok(false);
     };
@@ -1438,6 +1574,25 @@ pub async fn get_track_waveform_tile(
     .map_err(|error| anyhow!(error))
 }
 
+#[cfg(not(test))]
+const DEFAULT_RECENT_PLAYS_LIMIT: u32 = 50;
+
+#[cfg(not(test))]
+pub async fn list_recent_plays(limit: Option<u32>) -> Result<Vec<ListeningHistoryEntry>> {
+    super::repo::list_recent_listening_history(limit.unwrap_or(DEFAULT_RECENT_PLAYS_LIMIT).max(1))
+        .await
+}
+
+#[cfg(not(test))]
+pub async fn list_track_play_stats(limit: Option<u32>) -> Result<Vec<TrackPlayStats>> {
+    super::repo::list_track_play_stats(limit).await
+}
+
+#[cfg(not(test))]
+pub async fn list_playlist_play_stats() -> Result<Vec<PlaylistPlayStats>> {
+    super::repo::list_playlist_play_stats().await
+}
+
 #[cfg(not(test))]
 pub fn set_playback_continuation_mode(mode: PlaybackContinuationMode) -> Result<()> {
     let runtime = runtime()?;
@@ -2208,6 +2363,38 @@ impl PlayerRuntime {
         Ok(())
     }
 
+    fn with_listening_history<T>(
+        &self,
+        update: impl FnOnce(&mut ListeningHistoryRecorder) -> T,
+    ) -> T {
+        let mut history = self
+            .listening_history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        update(&mut history)
+    }
+
+    fn begin_listening_play(&self, track: &PlaybackTrack, mode: PlaybackContinuationMode) {
+        let started_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        let finished = self.with_listening_history(|history| {
+            history.begin(track, mode, started_at_ms, Instant::now())
+        });
+        if let Some(entry) = finished {
+            spawn_save_listening_history_entry(entry);
+        }
+    }
+
+    fn finish_listening_play(&self, outcome: ListeningOutcome) {
+        let finished =
+            self.with_listening_history(|history| history.finish(outcome, Instant::now()));
+        if let Some(entry) = finished {
+            spawn_save_listening_history_entry(entry);
+        }
+    }
+
     fn continuation_mode(&self) -> Result<PlaybackContinuationMode> {
         self.continuation_mode
             .read()
@@ -2356,6 +2543,7 @@ async fn run_playback_session(
                 has_completed_track,
                 producer_terminal,
             ) {
+                runtime.finish_listening_play(ListeningOutcome::Completed);
                 if runtime.clear_active_session_for_generation(session.session_generation)? {
                     emit_playback_surface_status(
                         &runtime,
@@ -2469,6 +2657,12 @@ async fn run_playback_session(
             Ok(_) => {
                 runtime.set_active_request_track(track.clone())?;
                 runtime.set_active_playback_range(Some(active_range))?;
+                if runtime.spectrum_playback_scope_snapshot()?.is_none() {
+                    runtime.begin_listening_play(&track, mode);
+                } else {
+                    // Spectrum playback took over before the range ended.
+                    runtime.finish_listening_play(ListeningOutcome::Stopped);
+                }
                 NowPlayingTrackChangedEvent::from_session_track(
                     session.session_generation,
                     track.to_payload(),
@@ -3531,6 +3725,27 @@ fn log_player_end_signal(
     );
 }
 
+#[cfg(not(test))]
+fn spawn_save_listening_history_entry(entry: ListeningHistoryEntry) {
+    log::info!(
+        target: "player",
+        "listening_history_recorded title=\"{}\" playlist=\"{}\" listened_ms={} outcome={:?}",
+        entry.music_name,
+        entry.playlist_name,
+        entry.listened_ms,
+        entry.outcome,
+    );
+    tauri::async_runtime::spawn(async move {
+        let _restore_guard = crate::domain::library_backup::acquire_library_write_guard().await;
+        if let Err(error) = super::repo::save_listening_history_entry(entry).await {
+            log::warn!(
+                target: "player",
+                "listening_history_save_failed error={error}"
+            );
+        }
+    });
+}
+
 #[cfg(not(test))]
 fn log_player_stop_done(track: &PlaybackTrack, active_range: ActivePlaybackRange, reason: &str) {
     log::info!(
@@ -3586,6 +3801,7 @@ async fn wait_until_track_finishes(
     let mut clock_running = true;
     let mut force_status_sample = false;
     let mut last_visualization_frame_at: Option<Instant> = None;
+    let mut lyrics_watch = PlaybackLyricsWatch::default();
     loop {
         if runtime.playback_run_generation.load(Ordering::SeqCst) != generation {
             emit_player_trace(
@@ -3849,6 +4065,15 @@ async fn wait_until_track_finishes(
                 last_visualization_frame_at = Some(Instant::now());
             }
         }
+        if let Some(track) = active_track.as_ref() {
+            emit_playback_lyric_line_if_changed(
+                &runtime.app,
+                generation,
+                &mut lyrics_watch,
+                track,
+                current_position_ms,
+            );
+        }
 
         match completion {
             PlaybackRangeCompletion::Continue => {
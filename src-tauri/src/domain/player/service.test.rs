@@ -491,6 +491,9 @@ fn resolve_session_track_liked_update_changes_only_liked_field() {
         canonical_music_id: current.canonical_music_id.clone(),
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }));
     let other = track("b");
 
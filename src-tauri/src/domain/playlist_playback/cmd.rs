@@ -27,8 +27,11 @@ pub async fn play_playlist(app: AppHandle, name: String) -> Result<PlayPlaylistS
 #[specta::specta]
 pub async fn exclude_current_music_and_skip(
     app: AppHandle,
+    playlist_only: Option<bool>,
 ) -> Result<ExcludeCurrentMusicAndSkipResult, String> {
-    super::service::exclude_current_music_and_skip(&app)
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    super::service::exclude_current_music_and_skip(&app, playlist_only.unwrap_or(false))
         .await
         .map_err(|error| error.to_string())
 }
@@ -3,6 +3,7 @@ pub mod cmd;
 pub mod model;
 pub mod playable_index;
 pub mod recommendation;
+pub mod repo;
 pub mod service;
 pub(crate) mod symbolic_program;
 pub(crate) mod temporal_memory;
@@ -1,5 +1,7 @@
+use appdb::Store;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use surrealdb_types::SurrealValue;
 
 use crate::domain::player::model::PlaybackTrackPayload;
 use crate::domain::playlists::model::{Exclude, ExcludeAvailability};
@@ -36,3 +38,12 @@ pub enum ExcludeCurrentMusicAndSkipResult {
     NoActiveTrack,
     MissingMusic,
 }
+
+/// Persisted form of one temporal-memory exposure, stored at a record keyed
+/// by `canonical_music_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, Store)]
+pub struct PlaylistPlaybackTemporalExposureRecord {
+    pub canonical_music_id: String,
+    pub last_played_at_ms: u64,
+    pub stability_ms: u64,
+}
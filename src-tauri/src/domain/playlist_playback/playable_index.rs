@@ -176,6 +176,8 @@ struct FirstSlotCachePlaylist {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FirstSlotCacheSource {
     collection_folder: String,
+    #[serde(default)]
+    collection_save_root: Option<String>,
     music: crate::domain::playlists::model::Music,
     track: PlaybackTrackPayload,
     source_kind: PlaylistPlayableIndexSourceKind,
@@ -198,6 +200,7 @@ impl From<&PreparedPlaylistSourceCredential> for FirstSlotCacheSource {
     fn from(value: &PreparedPlaylistSourceCredential) -> Self {
         Self {
             collection_folder: value.source.collection_folder.clone(),
+            collection_save_root: value.source.collection_save_root.clone(),
             music: value.source.music.clone(),
             track: value.track.to_payload(),
             source_kind: value.source_kind,
@@ -213,6 +216,7 @@ impl FirstSlotCacheSource {
         Ok(PreparedPlaylistSource {
             source: PlaylistPlaybackTrackSource {
                 collection_folder: self.collection_folder,
+                collection_save_root: self.collection_save_root,
                 music: self.music,
             },
             track,
@@ -488,6 +492,22 @@ pub(crate) fn notify_playlist_changed(playlist_name: &str) {
     notify_playlist_changed_impl(playlist_name);
 }
 
+/**
+ * Behavior:
+ *   Invalidate and refresh playlists whose selection includes a changed
+ *   playlist.
+ *
+ * Core invariants:
+ *   - Callers pass every transitive dependent; this does not cascade further,
+ *     so one edit refreshes each dependent once.
+ */
+pub(crate) fn notify_dependent_playlists_changed(playlist_names: &[String]) {
+    for playlist_name in playlist_names {
+        invalidate_playlist_scope_revision(playlist_name);
+        notify_dependent_playlist_changed_impl(playlist_name);
+    }
+}
+
 #[cfg(not(test))]
 pub(crate) fn request_playlist_slot_refill(playlist_name: &str) {
     spawn_refresh_playlist(
@@ -654,11 +674,35 @@ fn notify_playlist_changed_impl(playlist_name: &str) {
         playlist_name.to_string(),
         PlayableIndexRefreshReason::PlaylistChanged,
     );
+    let playlist_name = playlist_name.to_string();
+    tauri::async_runtime::spawn(async move {
+        match playlist_repo::list_dependent_playlist_names(&playlist_name).await {
+            Ok(dependents) => notify_dependent_playlists_changed(&dependents),
+            Err(error) => log::error!(
+                target: PLAYABLE_INDEX_LOG_TARGET,
+                "first_slot_dependent_playlists_lookup_failed playlist=\"{}\" error=\"{}\"",
+                escape_log_value(&playlist_name),
+                escape_log_value(&error.to_string())
+            ),
+        }
+    });
 }
 
 #[cfg(test)]
 fn notify_playlist_changed_impl(_playlist_name: &str) {}
 
+#[cfg(not(test))]
+fn notify_dependent_playlist_changed_impl(playlist_name: &str) {
+    spawn_refresh_playlist(
+        None,
+        playlist_name.to_string(),
+        PlayableIndexRefreshReason::PlaylistChanged,
+    );
+}
+
+#[cfg(test)]
+fn notify_dependent_playlist_changed_impl(_playlist_name: &str) {}
+
 fn rename_playlist_source_pool(pool: &mut PlaylistPlayableIndexPool, next_name: &str) {
     pool.playlist_name = next_name.to_string();
     for source in &mut pool.sources {
@@ -5,8 +5,8 @@ use super::playable_index::{
     current_index_revision, current_playlist_scope_revision, defer_global_refresh_for_test,
     discard_playlist_source, first_slot_loudness_request_order_for_test,
     initialize_runtime_for_test, mark_playlist_source_kind_for_test,
-    mark_startup_cache_restore_finished_for_test, notify_playlist_renamed,
-    pending_global_refresh_for_test, playlist_bootstrap_ready_for_test,
+    mark_startup_cache_restore_finished_for_test, notify_dependent_playlists_changed,
+    notify_playlist_renamed, pending_global_refresh_for_test, playlist_bootstrap_ready_for_test,
     publish_first_slot_loudness_evidence, queue_global_refresh_for_test, read_playlist_source,
     record_playlist_bootstrap_ready, refresh_playlist_now_for_reason_for_test,
     refresh_playlist_now_for_test, request_global_refresh_while_active_for_test, reset_for_test,
@@ -66,12 +66,16 @@ fn music_with_loudness(index: usize, loudness_profile: Option<f32>) -> Music {
         end_ms: 180_000,
         liked: false,
         loudness_profile: loudness_profile.and_then(LoudnessProfile::from_integrated_lufs),
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
 fn source(index: usize) -> PlaylistPlaybackTrackSource {
     PlaylistPlaybackTrackSource {
         collection_folder: "youtube/index".to_string(),
+        collection_save_root: None,
         music: music(index),
     }
 }
@@ -79,6 +83,7 @@ fn source(index: usize) -> PlaylistPlaybackTrackSource {
 fn source_without_loudness(index: usize) -> PlaylistPlaybackTrackSource {
     PlaylistPlaybackTrackSource {
         collection_folder: "youtube/index".to_string(),
+        collection_save_root: None,
         music: music_with_loudness(index, None),
     }
 }
@@ -121,6 +126,7 @@ fn selection(name: &str) -> PlaylistPlaybackSelection {
         )],
         groups: vec![],
         extra: vec![],
+        rules: vec![],
         download_scopes: vec!["https://example.com/collection".to_string()],
     }
 }
@@ -199,6 +205,32 @@ async fn playlist_candidate_change_advances_symbolic_scope_revision() {
     assert_ne!(scope_after, scope_before);
 }
 
+#[tokio::test]
+async fn dependent_playlist_change_advances_dependent_scope_revisions_only() {
+    let _guard = setup_playable_index_test();
+    refresh_playlist_now_for_test(selection("Focus"), Some(source(3)))
+        .await
+        .expect("base snapshot should commit");
+    refresh_playlist_now_for_test(selection("Mix"), Some(source(4)))
+        .await
+        .expect("dependent snapshot should commit");
+    let focus_before =
+        current_playlist_scope_revision("Focus").expect("scope revision should be readable");
+    let mix_before =
+        current_playlist_scope_revision("Mix").expect("scope revision should be readable");
+
+    notify_dependent_playlists_changed(&["Mix".to_string()]);
+
+    assert_eq!(
+        current_playlist_scope_revision("Focus").expect("scope revision should be readable"),
+        focus_before
+    );
+    assert_ne!(
+        current_playlist_scope_revision("Mix").expect("scope revision should be readable"),
+        mix_before
+    );
+}
+
 #[tokio::test]
 async fn playable_index_loudness_evidence_updates_prepared_first_slot_cargo_without_consuming_it() {
     let _guard = setup_playable_index_test();
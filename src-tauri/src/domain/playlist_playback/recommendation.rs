@@ -492,6 +492,8 @@ struct CachedPlaybackTrack {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CachedPlaylistPlaybackTrackSource {
     collection_folder: String,
+    #[serde(default)]
+    collection_save_root: Option<String>,
     music: CachedMusic,
 }
 
@@ -859,6 +861,7 @@ impl From<&PlaylistPlaybackTrackSource> for CachedPlaylistPlaybackTrackSource {
     fn from(source: &PlaylistPlaybackTrackSource) -> Self {
         Self {
             collection_folder: source.collection_folder.clone(),
+            collection_save_root: source.collection_save_root.clone(),
             music: CachedMusic::from(&source.music),
         }
     }
@@ -868,6 +871,7 @@ impl From<CachedPlaylistPlaybackTrackSource> for PlaylistPlaybackTrackSource {
     fn from(source: CachedPlaylistPlaybackTrackSource) -> Self {
         Self {
             collection_folder: source.collection_folder,
+            collection_save_root: source.collection_save_root,
             music: Music::from(source.music),
         }
     }
@@ -904,6 +908,9 @@ impl From<CachedMusic> for Music {
             end_ms: music.end_ms,
             liked: music.liked,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }
     }
 }
@@ -5498,6 +5505,7 @@ impl AudioStyleModelSnapshot {
             .map(|track| AudioStyleIndexedTrack {
                 source: PlaylistPlaybackTrackSource {
                     collection_folder: String::new(),
+                    collection_save_root: None,
                     music: track
                         .source_music
                         .as_deref()
@@ -5624,6 +5632,7 @@ impl AudioStyleModelSnapshot {
                 AudioStyleIndexedTrack {
                     source: PlaylistPlaybackTrackSource {
                         collection_folder,
+                        collection_save_root: None,
                         music: playback_track_source_music_from_track(&track),
                     },
                     track,
@@ -5661,6 +5670,7 @@ impl AudioStyleModelSnapshot {
             .map(|track| AudioStyleIndexedTrack {
                 source: PlaylistPlaybackTrackSource {
                     collection_folder: String::new(),
+                    collection_save_root: None,
                     music: track
                         .source_music
                         .as_deref()
@@ -7290,6 +7300,7 @@ fn resolve_audio_style_training_track(
     };
     let source = PlaylistPlaybackTrackSource {
         collection_folder: String::new(),
+        collection_save_root: None,
         music: playback_track_source_music_from_track(&track),
     };
     AudioStyleTrainingTrackProjection::Indexed(AudioStyleIndexedTrack { track, source })
@@ -7334,6 +7345,9 @@ fn playback_track_source_music_from_track(track: &PlaybackTrack) -> Music {
         end_ms: track.end_ms,
         liked: track.liked,
         loudness_profile: track.loudness_profile,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -0,0 +1,50 @@
+use super::model::PlaylistPlaybackTemporalExposureRecord;
+use anyhow::Result;
+use appdb::error::{DBError, classify_db_error};
+use appdb::model::meta::ModelMeta;
+use appdb::repository::Repo;
+use surrealdb::types::RecordId;
+
+pub async fn list_temporal_exposures() -> Result<Vec<PlaylistPlaybackTemporalExposureRecord>> {
+    match Repo::<PlaylistPlaybackTemporalExposureRecord>::list().await {
+        Ok(records) => Ok(records),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => Ok(vec![]),
+            other => Err(other.into()),
+        },
+    }
+}
+
+pub async fn save_temporal_exposure(
+    record: PlaylistPlaybackTemporalExposureRecord,
+) -> Result<PlaylistPlaybackTemporalExposureRecord> {
+    Repo::<PlaylistPlaybackTemporalExposureRecord>::upsert_at(
+        temporal_exposure_record_id(&record.canonical_music_id),
+        record,
+    )
+    .await
+}
+
+pub async fn delete_temporal_exposures(canonical_music_ids: &[String]) -> Result<()> {
+    for canonical_music_id in canonical_music_ids {
+        match Repo::<PlaylistPlaybackTemporalExposureRecord>::delete_record(
+            temporal_exposure_record_id(canonical_music_id),
+        )
+        .await
+        {
+            Ok(()) => {}
+            Err(error) => match classify_db_error(&error) {
+                DBError::NotFound | DBError::MissingTable(_) => {}
+                other => return Err(other.into()),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn temporal_exposure_record_id(canonical_music_id: &str) -> RecordId {
+    RecordId::new(
+        PlaylistPlaybackTemporalExposureRecord::table_name(),
+        canonical_music_id.to_string(),
+    )
+}
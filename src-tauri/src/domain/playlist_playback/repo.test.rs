@@ -0,0 +1,63 @@
+use super::model::PlaylistPlaybackTemporalExposureRecord;
+use super::repo::{delete_temporal_exposures, list_temporal_exposures, save_temporal_exposure};
+use crate::domain::test_support::{acquire_db_test_lock, run_async, temp_path};
+use appdb::connection::reinit_db;
+
+fn exposure(
+    canonical_music_id: &str,
+    last_played_at_ms: u64,
+) -> PlaylistPlaybackTemporalExposureRecord {
+    PlaylistPlaybackTemporalExposureRecord {
+        canonical_music_id: canonical_music_id.to_string(),
+        last_played_at_ms,
+        stability_ms: 72_000_000,
+    }
+}
+
+#[test]
+fn temporal_exposures_are_keyed_by_canonical_music_id() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        reinit_db(temp_path("playlist_playback_repo_db"))
+            .await
+            .expect("playlist playback repo database should initialize");
+
+        assert!(
+            list_temporal_exposures()
+                .await
+                .expect("missing exposure table should list as empty")
+                .is_empty()
+        );
+
+        let first = "source:https://example.com/watch?v=one:0:60000";
+        let second = "source:https://example.com/watch?v=two:0:90000";
+        save_temporal_exposure(exposure(first, 10))
+            .await
+            .expect("first exposure should save");
+        save_temporal_exposure(exposure(second, 20))
+            .await
+            .expect("second exposure should save");
+        save_temporal_exposure(exposure(first, 30))
+            .await
+            .expect("repeated exposure should replace the first record");
+
+        let mut records = list_temporal_exposures()
+            .await
+            .expect("exposures should list");
+        records.sort_by(|left, right| left.canonical_music_id.cmp(&right.canonical_music_id));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].canonical_music_id, first);
+        assert_eq!(records[0].last_played_at_ms, 30);
+
+        delete_temporal_exposures(&[first.to_string(), "source:missing:0:1".to_string()])
+            .await
+            .expect("pruned exposures should delete");
+
+        let records = list_temporal_exposures()
+            .await
+            .expect("exposures should list after prune");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].canonical_music_id, second);
+    });
+}
@@ -1,7 +1,10 @@
 #[cfg(not(test))]
 use super::model::{
     ExcludeCurrentMusicAndSkipResult, PlayPlaylistSession, PlayPlaylistSessionStatus,
+    PlaylistPlaybackTemporalExposureRecord,
 };
+#[cfg(not(test))]
+use super::repo as playlist_playback_repo;
 use crate::domain::downloads::model::DownloadTask;
 #[cfg(not(test))]
 use crate::domain::downloads::repo as download_repo;
@@ -34,7 +37,9 @@ use crate::domain::playlist_playback::recommendation::{
     published_audio_style_model_snapshots_for_anchor,
 };
 #[cfg(not(test))]
-use crate::domain::playlist_playback::temporal_memory::PlaylistPlaybackTemporalMemory;
+use crate::domain::playlist_playback::temporal_memory::{
+    PlaylistPlaybackTemporalExposure, PlaylistPlaybackTemporalMemory,
+};
 #[cfg(not(test))]
 use crate::domain::playlists::model::AudioStyleTrainingTrackInput;
 #[cfg(not(test))]
@@ -43,6 +48,8 @@ use crate::domain::playlists::model::Music;
 use crate::domain::playlists::repo as playlist_repo;
 use crate::domain::playlists::repo::{PlaylistPlaybackSelection, PlaylistPlaybackTrackSource};
 #[cfg(not(test))]
+use crate::domain::playlists::search as library_search;
+#[cfg(not(test))]
 use anyhow::{Result, anyhow, bail};
 use rand::RngExt;
 #[cfg(not(test))]
@@ -201,7 +208,71 @@ pub(crate) fn observe_playlist_playback_temporal_memory(track: &PlaybackTrack) {
         return;
     };
     memory.observe(music_id, now_ms);
-    memory.prune_expired(now_ms);
+    let exposure = memory.exposure(music_id);
+    let pruned = memory.prune_expired(now_ms);
+    drop(memory);
+
+    let record = exposure.map(|exposure| PlaylistPlaybackTemporalExposureRecord {
+        canonical_music_id: music_id.to_string(),
+        last_played_at_ms: exposure.last_played_at_ms,
+        stability_ms: exposure.stability_ms,
+    });
+    tauri::async_runtime::spawn(async move {
+        let _restore_guard = crate::domain::library_backup::acquire_library_write_guard().await;
+        if let Some(record) = record
+            && let Err(error) = playlist_playback_repo::save_temporal_exposure(record).await
+        {
+            log::warn!(
+                target: PLAYLIST_PLAYBACK_LOG_TARGET,
+                "playlist_playback_temporal_memory_persist_failed error=\"{error:#}\""
+            );
+        }
+        if let Err(error) = playlist_playback_repo::delete_temporal_exposures(&pruned).await {
+            log::warn!(
+                target: PLAYLIST_PLAYBACK_LOG_TARGET,
+                "playlist_playback_temporal_memory_prune_failed error=\"{error:#}\""
+            );
+        }
+    });
+}
+
+/**
+ * Behavior:
+ *   Reload persisted temporal-memory exposures so tracks played before a
+ *   relaunch stay suppressed by anti-FSRS history.
+ *
+ * Core invariants:
+ *   - Exposures already below the prune retrievability are deleted instead
+ *     of restored.
+ *   - An exposure observed in this process before the restore finished wins
+ *     over an older persisted one.
+ */
+#[cfg(not(test))]
+async fn restore_playlist_playback_temporal_memory() -> Result<()> {
+    let records = playlist_playback_repo::list_temporal_exposures().await?;
+    let restored_count = records.len();
+    let now_ms = current_playback_time_ms();
+    let pruned = {
+        let mut memory = playlist_playback_temporal_memory()
+            .lock()
+            .map_err(|_| anyhow!("playlist playback temporal memory lock poisoned"))?;
+        for record in records {
+            memory.restore(
+                record.canonical_music_id,
+                PlaylistPlaybackTemporalExposure {
+                    last_played_at_ms: record.last_played_at_ms,
+                    stability_ms: record.stability_ms,
+                },
+            );
+        }
+        memory.prune_expired(now_ms)
+    };
+    log::info!(
+        target: PLAYLIST_PLAYBACK_LOG_TARGET,
+        "playlist_playback_temporal_memory_restored records={restored_count} pruned={}",
+        pruned.len()
+    );
+    playlist_playback_repo::delete_temporal_exposures(&pruned).await
 }
 
 #[cfg(not(test))]
@@ -243,17 +314,27 @@ fn recently_played_tracks_with_anti_fsrs(
 
 #[cfg(not(test))]
 pub fn initialize_runtime(app: AppHandle) {
+    tauri::async_runtime::spawn(async {
+        if let Err(error) = restore_playlist_playback_temporal_memory().await {
+            log::warn!(
+                target: PLAYLIST_PLAYBACK_LOG_TARGET,
+                "playlist_playback_temporal_memory_restore_failed error=\"{error:#}\""
+            );
+        }
+    });
     initialize_audio_style_recommendation_runtime(app.clone());
     playable_index::initialize_runtime(app);
 }
 
 #[cfg(not(test))]
 pub(crate) fn notify_music_library_inputs_changed(reason: &'static str) {
+    library_search::notify_library_changed();
     notify_audio_style_library_inputs_changed(reason);
 }
 
 #[cfg(not(test))]
 pub(crate) fn notify_music_input_changed(reason: &'static str, music: &Music) {
+    library_search::notify_music_changed(music);
     notify_audio_style_music_input_changed(reason, music);
 }
 
@@ -562,6 +643,7 @@ pub async fn play_playlist(app: &AppHandle, name: String) -> Result<PlayPlaylist
 #[cfg(not(test))]
 pub async fn exclude_current_music_and_skip(
     app: &AppHandle,
+    playlist_only: bool,
 ) -> Result<ExcludeCurrentMusicAndSkipResult> {
     let Some(track) = player_service::active_request_track_snapshot()? else {
         return Ok(ExcludeCurrentMusicAndSkipResult::NoActiveTrack);
@@ -576,7 +658,11 @@ pub async fn exclude_current_music_and_skip(
     }
     spawn_exclude_current_playback_skip(track.clone());
 
-    let exclude_result = playlist_repo::add_exclude(excluded_music).await?;
+    let exclude_result = if playlist_only {
+        playlist_repo::add_playlist_exclude(excluded_music, &track.playlist_name, None).await?
+    } else {
+        playlist_repo::add_exclude(excluded_music, None).await?
+    };
     PlaybackExcludeCommittedEvent {
         exclude: exclude_result.exclude.clone(),
         exclude_availability: exclude_result.exclude_availability.clone(),
@@ -592,7 +678,7 @@ pub async fn exclude_current_music_and_skip(
 #[cfg(not(test))]
 fn spawn_exclude_current_playback_skip(track: PlaybackTrack) {
     tauri::async_runtime::spawn(async move {
-        if let Err(error) = player_service::skip_current_track().await {
+        if let Err(error) = player_service::skip_excluded_current_track().await {
             eprintln!(
                 "[playlist_playback] failed to skip excluded current music `{}`: {error}",
                 track.music_name
@@ -2306,10 +2392,11 @@ async fn resolve_playlist_playback_queue_anchor(
         return Ok(initial_track.clone());
     };
 
-    if playlist_repo::is_music_identity_excluded_for_playback(
+    if playlist_repo::is_music_identity_excluded_for_playlist(
         &active_track.music_url,
         active_track.start_ms,
         active_track.end_ms,
+        &active_track.playlist_name,
     )
     .await?
     {
@@ -2925,11 +3012,12 @@ pub(crate) fn resolve_playlist_playback_source_resolution(
 
     #[cfg(test)]
     let failure_description = format!(
-        "playlist `{}` does not contain any playable tracks [selected_collection_refs={}, selected_group_refs={}, selected_extra_refs={}, checked_sources={}, playable={}, missing_path={}, missing_file={}, save_root={}]",
+        "playlist `{}` does not contain any playable tracks [selected_collection_refs={}, selected_group_refs={}, selected_extra_refs={}, selected_rule_sets={}, checked_sources={}, playable={}, missing_path={}, missing_file={}, save_root={}]",
         selection.playlist_name,
         selection.collections.len(),
         selection.groups.len(),
         selection.extra.len(),
+        selection.rules.len(),
         stats.source_count,
         stats.playable,
         stats.missing_path,
@@ -3011,12 +3099,12 @@ pub(crate) fn resolve_source_music_file_path(
     save_root: &Path,
     source: &PlaylistPlaybackTrackSource,
 ) -> Option<PathBuf> {
-    let path = PathBuf::from(source.music.path.as_deref()?);
-    if path.is_absolute() {
-        return Some(path);
-    }
-
-    Some(save_root.join(&source.collection_folder).join(path))
+    playlist_repo::resolve_music_file_path(
+        save_root,
+        source.collection_save_root.as_deref(),
+        &source.collection_folder,
+        source.music.path.as_deref(),
+    )
 }
 
 #[derive(Clone)]
@@ -95,6 +95,9 @@ fn music(name: &str, url: &str, path: &str, group: Group) -> Music {
         end_ms: 180_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -111,6 +114,9 @@ fn music_with_alias(name: &str, alias: &str, url: &str, path: &str, group: Group
         end_ms: 180_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -149,6 +155,7 @@ fn playback_selection(
             })
             .unwrap_or_default(),
         extra: vec![],
+        rules: vec![],
 
         download_scopes: std::iter::once(collection_url.to_string())
             .chain(group_url.map(str::to_string))
@@ -163,6 +170,7 @@ fn playback_source(
 ) -> PlaylistPlaybackTrackSource {
     PlaylistPlaybackTrackSource {
         collection_folder: collection_folder.to_string(),
+        collection_save_root: None,
         music,
     }
 }
@@ -243,6 +251,7 @@ fn playlist_selection_active_downloads_match_explicit_parent_download_scope() {
             "disc-1",
         )],
         extra: vec![],
+        rules: vec![],
 
         download_scopes: vec![
             "https://example.com/album#disc-1".to_string(),
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
 const TEMPORAL_MEMORY_FSRS_DECAY: f32 = 0.5;
 const TEMPORAL_MEMORY_TRACK_STABILITY_MS: u64 = 20 * 60 * 60 * 1_000;
@@ -49,6 +50,35 @@ impl PlaylistPlaybackTemporalMemory {
         );
     }
 
+    pub(crate) fn exposure(
+        &self,
+        canonical_music_id: &str,
+    ) -> Option<PlaylistPlaybackTemporalExposure> {
+        self.exposures.get(canonical_music_id).copied()
+    }
+
+    /// Merge a persisted exposure; an exposure observed in this process
+    /// before the restore finished stays authoritative when it is newer.
+    pub(crate) fn restore(
+        &mut self,
+        canonical_music_id: String,
+        exposure: PlaylistPlaybackTemporalExposure,
+    ) {
+        if canonical_music_id.is_empty() {
+            return;
+        }
+        match self.exposures.entry(canonical_music_id) {
+            Entry::Occupied(mut current) => {
+                if current.get().last_played_at_ms < exposure.last_played_at_ms {
+                    current.insert(exposure);
+                }
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(exposure);
+            }
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn retrievability_for(&self, canonical_music_id: &str, now_ms: u64) -> f32 {
         self.exposures
@@ -98,11 +128,19 @@ impl PlaylistPlaybackTemporalMemory {
             .fold(0.0_f32, f32::max)
     }
 
-    pub(crate) fn prune_expired(&mut self, now_ms: u64) {
-        self.exposures.retain(|_, exposure| {
-            temporal_memory_retrievability(now_ms, *exposure)
-                >= TEMPORAL_MEMORY_PRUNE_RETRIEVABILITY
+    /// Drop exposures below the prune retrievability and return their ids so
+    /// persisted copies can be removed too.
+    pub(crate) fn prune_expired(&mut self, now_ms: u64) -> Vec<String> {
+        let mut pruned = Vec::new();
+        self.exposures.retain(|music_id, exposure| {
+            let keep = temporal_memory_retrievability(now_ms, *exposure)
+                >= TEMPORAL_MEMORY_PRUNE_RETRIEVABILITY;
+            if !keep {
+                pruned.push(music_id.clone());
+            }
+            keep
         });
+        pruned
     }
 }
 
@@ -86,3 +86,35 @@ fn basin_pressure_is_rebuilt_from_the_current_model_projection() {
     );
     assert!(memory.basin_retrievability("new", HOUR_MS, promoted_model) > 0.9);
 }
+
+#[test]
+fn restored_exposures_keep_newer_in_process_observations_and_report_pruned_ids() {
+    let mut memory = PlaylistPlaybackTemporalMemory::default();
+    memory.observe("source:fresh:0:60000", 10 * HOUR_MS);
+    memory.restore(
+        "source:fresh:0:60000".to_string(),
+        PlaylistPlaybackTemporalExposure {
+            last_played_at_ms: 0,
+            stability_ms: 20 * HOUR_MS,
+        },
+    );
+    memory.restore(
+        "source:stale:0:60000".to_string(),
+        PlaylistPlaybackTemporalExposure {
+            last_played_at_ms: 0,
+            stability_ms: 1_000,
+        },
+    );
+
+    assert_eq!(
+        memory
+            .exposure("source:fresh:0:60000")
+            .map(|exposure| exposure.last_played_at_ms),
+        Some(10 * HOUR_MS)
+    );
+    assert_eq!(
+        memory.prune_expired(10 * HOUR_MS),
+        vec!["source:stale:0:60000".to_string()]
+    );
+    assert!(memory.exposure("source:fresh:0:60000").is_some());
+}
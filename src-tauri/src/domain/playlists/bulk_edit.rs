@@ -0,0 +1,321 @@
+use super::model::{
+    Exclude, Music, MusicBulkEdit, MusicBulkEditAction, MusicBulkEditResult, MusicBulkEditStatus,
+    MusicRangeRef,
+};
+use super::{repo, trash};
+use anyhow::{Result, bail};
+use chrono::Utc;
+use std::collections::BTreeSet;
+
+pub struct MusicBulkEditOutcome {
+    /// One result per edit, in batch order.
+    pub results: Vec<MusicBulkEditResult>,
+    /// Whether anything was written, including edits that were rolled back.
+    pub changed: bool,
+    pub excludes_changed: bool,
+    /// Collections holding an edited music, for manifest rewrites.
+    pub collection_urls: BTreeSet<String>,
+}
+
+struct AppliedMusicEdit {
+    music: Option<Music>,
+    collection_urls: Vec<String>,
+}
+
+/// What a rollback puts back besides the snapshotted collections.
+#[derive(Default)]
+struct MusicEditJournal {
+    excludes: Vec<(Music, Option<Exclude>)>,
+    trash_ids: Vec<String>,
+}
+
+/**
+ * Behavior:
+ *   Apply a batch of music edits in order and report one result per edit.
+ *
+ * Core invariants:
+ *   - The batch runs under the composition lock, so no other collection
+ *     write interleaves with it.
+ *   - The batch applies as a whole: every collection an edit can touch is
+ *     snapshotted first, and when an edit fails the snapshots, excludes and
+ *     playlist extras are put back and the rest of the batch is skipped.
+ *   - An edit whose music is not in the library is reported as `NotFound`
+ *     and does not fail the batch.
+ *   - Trash entries of deleted music are dropped again on a rollback.
+ *   - Edits address the identity as it is when they run; an edit after a
+ *     range change of the same music must use the new range.
+ */
+pub async fn apply_music_bulk_edits(edits: &[MusicBulkEdit]) -> Result<MusicBulkEditOutcome> {
+    let mut outcome = MusicBulkEditOutcome {
+        results: Vec::with_capacity(edits.len()),
+        changed: false,
+        excludes_changed: false,
+        collection_urls: BTreeSet::new(),
+    };
+    let _collection_write = repo::acquire_collection_write_composition_lock().await;
+    let snapshot = snapshot_edited_collections(edits).await?;
+    let mut journal = MusicEditJournal::default();
+    let mut applied = Vec::new();
+
+    for (index, edit) in edits.iter().enumerate() {
+        match apply_music_edit(edit, &mut journal).await {
+            Ok(Some(edit_applied)) => {
+                outcome.changed = true;
+                outcome.excludes_changed |=
+                    matches!(edit.action, MusicBulkEditAction::Exclude { .. });
+                outcome.collection_urls.extend(edit_applied.collection_urls);
+                outcome.results.push(bulk_edit_result(
+                    edit,
+                    MusicBulkEditStatus::Applied,
+                    None,
+                    edit_applied.music,
+                ));
+                applied.push(index);
+            }
+            Ok(None) => outcome.results.push(bulk_edit_result(
+                edit,
+                MusicBulkEditStatus::NotFound,
+                None,
+                None,
+            )),
+            Err(error) => {
+                outcome.results.push(bulk_edit_result(
+                    edit,
+                    MusicBulkEditStatus::Failed,
+                    Some(error.to_string()),
+                    None,
+                ));
+                outcome.results.extend(
+                    edits[index + 1..].iter().map(|edit| {
+                        bulk_edit_result(edit, MusicBulkEditStatus::Skipped, None, None)
+                    }),
+                );
+                roll_back_music_edits(&mut outcome.results, &applied, &snapshot, journal).await;
+                return Ok(outcome);
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Snapshots every collection holding a music the batch addresses, and the
+/// target collections of group moves.
+async fn snapshot_edited_collections(edits: &[MusicBulkEdit]) -> Result<repo::CollectionSnapshot> {
+    let mut collection_urls = Vec::new();
+    let mut identities = Vec::new();
+    for edit in edits {
+        for (collection_url, _) in
+            repo::list_music_occurrences_by_identity(&edit.url, edit.start_ms, edit.end_ms).await?
+        {
+            if !collection_urls.contains(&collection_url) {
+                collection_urls.push(collection_url);
+            }
+        }
+        if let MusicBulkEditAction::MoveToGroup { collection_url, .. } = &edit.action
+            && !collection_urls.contains(collection_url)
+        {
+            collection_urls.push(collection_url.clone());
+        }
+        let range = MusicRangeRef {
+            url: edit.url.clone(),
+            start_ms: edit.start_ms,
+            end_ms: edit.end_ms,
+        };
+        if !identities.contains(&range) {
+            identities.push(range);
+        }
+    }
+    repo::snapshot_collections(&collection_urls, &identities).await
+}
+
+async fn apply_music_edit(
+    edit: &MusicBulkEdit,
+    journal: &mut MusicEditJournal,
+) -> Result<Option<AppliedMusicEdit>> {
+    let occurrences =
+        repo::list_music_occurrences_by_identity(&edit.url, edit.start_ms, edit.end_ms).await?;
+    let Some((_, first)) = occurrences.first() else {
+        return Ok(None);
+    };
+    let first = first.clone();
+    let collection_urls = occurrences
+        .iter()
+        .map(|(collection_url, _)| collection_url.clone())
+        .collect::<Vec<_>>();
+
+    let music = match &edit.action {
+        MusicBulkEditAction::SetLiked { liked } => {
+            let Some(music) =
+                repo::set_music_liked_by_identity(&edit.url, edit.start_ms, edit.end_ms, *liked)
+                    .await?
+            else {
+                return Ok(None);
+            };
+            Some(music)
+        }
+        MusicBulkEditAction::Exclude {
+            expires_at,
+            playlists,
+        } => {
+            let previous = repo::get_exclude(&first).await?;
+            journal.excludes.push((first.clone(), previous));
+            repo::add_scoped_exclude(first.clone(), expires_at.clone(), playlists.clone()).await?;
+            Some(first)
+        }
+        MusicBulkEditAction::SetAlias { alias } => {
+            let Some(music) = repo::update_music(
+                &edit.url,
+                edit.start_ms,
+                edit.end_ms,
+                alias,
+                edit.start_ms,
+                edit.end_ms,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+            Some(music)
+        }
+        MusicBulkEditAction::SetRange { start_ms, end_ms } => {
+            if start_ms >= end_ms {
+                bail!("music range {start_ms}..{end_ms} is empty");
+            }
+            let Some(music) = repo::update_music(
+                &edit.url,
+                edit.start_ms,
+                edit.end_ms,
+                &first.alias,
+                *start_ms,
+                *end_ms,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+            Some(music)
+        }
+        MusicBulkEditAction::MoveToGroup {
+            collection_url,
+            group_url,
+        } => {
+            if !occurrences
+                .iter()
+                .any(|(owner_url, _)| owner_url == collection_url)
+            {
+                return Ok(None);
+            }
+            let range = MusicRangeRef {
+                url: edit.url.clone(),
+                start_ms: edit.start_ms,
+                end_ms: edit.end_ms,
+            };
+            let Some(collection) = repo::move_collection_musics_to_group_locked(
+                collection_url,
+                std::slice::from_ref(&range),
+                group_url,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+            let music = collection
+                .musics
+                .into_iter()
+                .find(|music| range.matches(music) && &music.group.url == group_url);
+            return Ok(Some(AppliedMusicEdit {
+                music,
+                collection_urls: vec![collection_url.clone()],
+            }));
+        }
+        MusicBulkEditAction::Delete => {
+            let Some(item) = trash::trash_music(&edit.url, edit.start_ms, edit.end_ms).await?
+            else {
+                return Ok(None);
+            };
+            journal.trash_ids.push(item.id);
+            None
+        }
+    };
+
+    Ok(Some(AppliedMusicEdit {
+        music,
+        collection_urls,
+    }))
+}
+
+/// Puts the snapshotted collections back, then the excludes in reverse order,
+/// and drops the trash entries of deleted music that came back with them.
+async fn roll_back_music_edits(
+    results: &mut [MusicBulkEditResult],
+    applied: &[usize],
+    snapshot: &repo::CollectionSnapshot,
+    journal: MusicEditJournal,
+) {
+    let mut failure = repo::restore_collections(snapshot)
+        .await
+        .err()
+        .map(|error| error.to_string());
+    for (music, previous) in journal.excludes.into_iter().rev() {
+        let restored = match previous.filter(|previous| {
+            !previous
+                .expires_at
+                .as_deref()
+                .is_some_and(|expires_at| repo::exclude_expiry_passed(expires_at, Utc::now()))
+        }) {
+            Some(previous) => {
+                repo::add_scoped_exclude(music.clone(), previous.expires_at, previous.playlists)
+                    .await
+                    .map(|_| ())
+            }
+            None => repo::remove_exclude(&music).await.map(|_| ()),
+        };
+        if let Err(error) = restored {
+            log::error!(
+                target: "playlists",
+                "music_bulk_edit_rollback_failed music_url=\"{}\" error=\"{}\"",
+                music.url,
+                error
+            );
+            failure.get_or_insert_with(|| error.to_string());
+        }
+    }
+    for trash_id in journal.trash_ids {
+        if let Err(error) = trash::purge_trash_item(&trash_id).await {
+            log::warn!(
+                target: "playlists",
+                "music_bulk_edit_trash_cleanup_failed trash_id=\"{}\" error=\"{}\"",
+                trash_id,
+                error
+            );
+        }
+    }
+
+    for index in applied {
+        let result = &mut results[*index];
+        match &failure {
+            None => {
+                result.status = MusicBulkEditStatus::RolledBack;
+                result.music = None;
+            }
+            Some(error) => result.error = Some(format!("rollback failed: {error}")),
+        }
+    }
+}
+
+fn bulk_edit_result(
+    edit: &MusicBulkEdit,
+    status: MusicBulkEditStatus,
+    error: Option<String>,
+    music: Option<Music>,
+) -> MusicBulkEditResult {
+    MusicBulkEditResult {
+        url: edit.url.clone(),
+        start_ms: edit.start_ms,
+        end_ms: edit.end_ms,
+        status,
+        error,
+        music,
+    }
+}
@@ -0,0 +1,199 @@
+use super::bulk_edit::apply_music_bulk_edits;
+use super::model::{Collection, Music, MusicBulkEdit, MusicBulkEditAction, MusicBulkEditStatus};
+use super::repo::{
+    get_collection_by_url, is_music_identity_excluded_for_playback,
+    list_music_occurrences_by_identity, upsert_collection,
+};
+use super::trash::list_trash;
+use crate::domain::test_support::{acquire_db_test_lock, bootstrap_db, owner_group, run_async};
+use appdb::connection::reset_db;
+
+const COLLECTION_URL: &str = "https://example.com/bulk";
+const COLLECTION_FOLDER: &str = "youtube/bulk-demo";
+
+fn collection(musics: Vec<Music>) -> Collection {
+    crate::domain::test_support::collection("Bulk Demo", COLLECTION_URL, COLLECTION_FOLDER, musics)
+}
+
+fn music(name: &str) -> Music {
+    crate::domain::test_support::music(
+        name,
+        &owner_group("Bulk Demo", COLLECTION_URL, COLLECTION_FOLDER),
+    )
+}
+
+fn music_url(name: &str) -> String {
+    format!("https://example.com/watch/{name}")
+}
+
+fn edit(name: &str, action: MusicBulkEditAction) -> MusicBulkEdit {
+    MusicBulkEdit {
+        url: music_url(name),
+        start_ms: 0,
+        end_ms: 60_000,
+        action,
+    }
+}
+
+#[test]
+fn bulk_edits_apply_in_order_and_report_each_item() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        upsert_collection(&collection(vec![
+            music("Liked"),
+            music("Renamed"),
+            music("Ranged"),
+            music("Excluded"),
+            music("Deleted"),
+        ]))
+        .await
+        .expect("collection should save");
+
+        let outcome = apply_music_bulk_edits(&[
+            edit("Liked", MusicBulkEditAction::SetLiked { liked: true }),
+            edit(
+                "Renamed",
+                MusicBulkEditAction::SetAlias {
+                    alias: "New Name".to_string(),
+                },
+            ),
+            edit(
+                "Ranged",
+                MusicBulkEditAction::SetRange {
+                    start_ms: 1_000,
+                    end_ms: 50_000,
+                },
+            ),
+            edit(
+                "Excluded",
+                MusicBulkEditAction::Exclude {
+                    expires_at: None,
+                    playlists: vec![],
+                },
+            ),
+            edit("Deleted", MusicBulkEditAction::Delete),
+            edit("Missing", MusicBulkEditAction::Delete),
+        ])
+        .await
+        .expect("bulk edit should run");
+
+        let statuses = outcome
+            .results
+            .iter()
+            .map(|result| result.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                MusicBulkEditStatus::Applied,
+                MusicBulkEditStatus::Applied,
+                MusicBulkEditStatus::Applied,
+                MusicBulkEditStatus::Applied,
+                MusicBulkEditStatus::Applied,
+                MusicBulkEditStatus::NotFound,
+            ]
+        );
+        assert!(outcome.changed);
+        assert!(outcome.excludes_changed);
+        assert_eq!(
+            outcome.collection_urls.into_iter().collect::<Vec<_>>(),
+            vec![COLLECTION_URL.to_string()]
+        );
+
+        let stored = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        let by_url = |name: &str| {
+            stored
+                .musics
+                .iter()
+                .find(|music| music.url == music_url(name))
+                .cloned()
+        };
+        assert!(by_url("Liked").expect("liked music should exist").liked);
+        assert_eq!(
+            by_url("Renamed").expect("renamed music should exist").alias,
+            "New Name"
+        );
+        let ranged = by_url("Ranged").expect("ranged music should exist");
+        assert_eq!((ranged.start_ms, ranged.end_ms), (1_000, 50_000));
+        assert!(
+            is_music_identity_excluded_for_playback(&music_url("Excluded"), 0, 60_000)
+                .await
+                .expect("exclude lookup should succeed")
+        );
+        assert!(by_url("Deleted").is_none());
+
+        reset_db();
+    });
+}
+
+#[test]
+fn failed_bulk_edit_rolls_back_the_edits_before_it() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        upsert_collection(&collection(vec![music("Liked"), music("Deleted")]))
+            .await
+            .expect("collection should save");
+
+        let outcome = apply_music_bulk_edits(&[
+            edit("Liked", MusicBulkEditAction::SetLiked { liked: true }),
+            edit("Deleted", MusicBulkEditAction::Delete),
+            edit(
+                "Liked",
+                MusicBulkEditAction::MoveToGroup {
+                    collection_url: COLLECTION_URL.to_string(),
+                    group_url: format!("{COLLECTION_URL}#group/missing"),
+                },
+            ),
+            edit("Liked", MusicBulkEditAction::SetLiked { liked: false }),
+        ])
+        .await
+        .expect("bulk edit should run");
+
+        let statuses = outcome
+            .results
+            .iter()
+            .map(|result| result.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                MusicBulkEditStatus::RolledBack,
+                MusicBulkEditStatus::RolledBack,
+                MusicBulkEditStatus::Failed,
+                MusicBulkEditStatus::Skipped,
+            ]
+        );
+        assert!(outcome.results[2].error.is_some());
+
+        let liked = list_music_occurrences_by_identity(&music_url("Liked"), 0, 60_000)
+            .await
+            .expect("occurrence lookup should succeed");
+        assert!(!liked[0].1.liked);
+        let restored = list_music_occurrences_by_identity(&music_url("Deleted"), 0, 60_000)
+            .await
+            .expect("occurrence lookup should succeed");
+        assert_eq!(restored.len(), 1);
+        let collection = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(
+            collection
+                .musics
+                .iter()
+                .map(|music| music.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Liked", "Deleted"]
+        );
+        assert!(list_trash().await.expect("trash should list").is_empty());
+
+        reset_db();
+    });
+}
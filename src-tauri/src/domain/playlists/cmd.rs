@@ -1,10 +1,19 @@
 use super::model::{
-    AddExcludeResult, Collection, ConfigLibraryView, Music, PlayList, PlayListConfigView,
-    PlayListListView, PlayListWriteRequest, RemoveExcludeResult, SpectrumMusicContext,
+    AddExcludeResult, Collection, ConfigLibraryView, Group, LibrarySearchHit, Music, MusicBulkEdit,
+    MusicBulkEditAction, MusicBulkEditResult, MusicBulkEditStatus, MusicRangeRef, PlayList,
+    PlayListConfigView, PlayListListView, PlayListWriteRequest, PlaylistExportFormat,
+    PlaylistExportResult, PlaylistImportResult, RemoveExcludeResult, SpectrumMusicContext,
+    TrashItem, canonical_music_id_for_source,
 };
+use super::trash::RestoredTrashItem;
+use crate::domain::collection_manifest_sync::{
+    schedule_collection_manifest_sync, schedule_music_identity_manifest_sync,
+};
+use crate::domain::library_backup::try_acquire_library_write_guard;
 use crate::domain::player::service::{
-    PlaybackTrackLikedUpdate, active_request_track_snapshot,
-    request_current_session_track_identity_update, update_current_session_track_liked,
+    PlaybackTrackLikedUpdate, active_request_track_position_snapshot,
+    active_request_track_snapshot, request_current_session_track_identity_update,
+    update_current_session_track_liked,
 };
 use crate::domain::player::track_identity_substitution::PlaybackTrackIdentityUpdate;
 use crate::domain::playlist_playback::playable_index;
@@ -75,14 +84,62 @@ pub async fn get_playlist_config(name: String) -> Result<Option<PlayListConfigVi
         .map_err(|error| error.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn export_playlist(
+    app: AppHandle,
+    name: String,
+    format: PlaylistExportFormat,
+    target_path: String,
+) -> Result<Option<PlaylistExportResult>, String> {
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    super::export::export_playlist(
+        &name,
+        format,
+        &save_root,
+        std::path::Path::new(&target_path),
+    )
+    .await
+    .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_playlist_file(
+    app: AppHandle,
+    file_path: String,
+    name: Option<String>,
+) -> Result<PlaylistImportResult, String> {
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    super::import::import_playlist_file(
+        std::path::Path::new(&file_path),
+        name.as_deref(),
+        &save_root,
+    )
+    .await
+    .map_err(|error| error.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_playlist(name: String) -> Result<bool, String> {
-    let deleted = super::repo::delete_playlist_by_name(&name)
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let dependents = super::repo::list_dependent_playlist_names(&name)
         .await
         .map_err(|error| error.to_string())?;
+    let deleted = super::trash::trash_playlist(&name)
+        .await
+        .map_err(|error| error.to_string())?
+        .is_some();
     if deleted {
         playable_index::notify_playlist_deleted(&name);
+        playable_index::notify_dependent_playlists_changed(&dependents);
     }
     Ok(deleted)
 }
@@ -93,6 +150,7 @@ pub async fn upsert_playlist(
     previous_name: Option<String>,
     playlist: PlayListWriteRequest,
 ) -> Result<PlayListListView, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let upsert = super::repo::upsert_playlist_surface(&playlist, previous_name.as_deref())
         .await
         .map_err(|error| error.to_string())?;
@@ -111,6 +169,7 @@ pub async fn upsert_playlist(
 #[tauri::command]
 #[specta::specta]
 pub async fn push_extra(name: String, music: Music) -> Result<Option<PlayListConfigView>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let playlist = super::repo::push_extra(&name, music)
         .await
         .map_err(|error| error.to_string())?;
@@ -126,6 +185,7 @@ pub async fn remove_extra(
     name: String,
     music: Music,
 ) -> Result<Option<PlayListConfigView>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let playlist = super::repo::remove_extra(&name, &music)
         .await
         .map_err(|error| error.to_string())?;
@@ -141,6 +201,7 @@ pub async fn set_collection_updates(
     url: String,
     enabled: bool,
 ) -> Result<Option<Collection>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     super::repo::set_collection_updates(&url, enabled)
         .await
         .map_err(|error| error.to_string())
@@ -156,14 +217,22 @@ pub async fn update_music(
     next_start_ms: u32,
     next_end_ms: u32,
 ) -> Result<Option<Music>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let updated =
         super::repo::update_music(&url, start_ms, end_ms, &alias, next_start_ms, next_end_ms)
             .await
             .map_err(|error| error.to_string())?;
 
     if let Some(music) = updated.as_ref() {
-        playlist_playback_service::notify_music_input_changed("music_identity_update", music);
+        if music.start_ms != start_ms || music.end_ms != end_ms {
+            // Every record sharing the old range moved; only the first is
+            // returned, so reload the inputs instead of patching one entry.
+            playlist_playback_service::notify_music_library_inputs_changed("music_identity_update");
+        } else {
+            playlist_playback_service::notify_music_input_changed("music_identity_update", music);
+        }
         playlist_playback_service::notify_playable_library_changed();
+        schedule_music_identity_manifest_sync(&music.url, music.start_ms, music.end_ms).await;
         request_current_session_track_identity_update(PlaybackTrackIdentityUpdate {
             music_name: music.alias.clone(),
             music_url: url,
@@ -177,9 +246,57 @@ pub async fn update_music(
     Ok(updated)
 }
 
+/// Splits one music at the given cut points; a session playing the original
+/// continues on the piece under the playhead.
+#[tauri::command]
+#[specta::specta]
+pub async fn split_music(
+    url: String,
+    start_ms: u32,
+    end_ms: u32,
+    cut_points: Vec<u32>,
+) -> Result<Option<Vec<Music>>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let pieces = super::repo::split_music(&url, start_ms, end_ms, &cut_points)
+        .await
+        .map_err(|error| error.to_string())?;
+
+    if let Some(pieces) = pieces.as_ref().filter(|pieces| !pieces.is_empty()) {
+        let playhead_ms = active_request_track_position_snapshot()
+            .await
+            .ok()
+            .flatten()
+            .filter(|(track, _)| {
+                track.music_url == url && track.start_ms == start_ms && track.end_ms == end_ms
+            })
+            .map(|(_, position_ms)| position_ms);
+        let next = playhead_ms
+            .and_then(|position_ms| {
+                pieces
+                    .iter()
+                    .find(|piece| piece.start_ms <= position_ms && position_ms < piece.end_ms)
+            })
+            .unwrap_or(&pieces[0]);
+        playlist_playback_service::notify_music_library_inputs_changed("music_split");
+        playlist_playback_service::notify_playable_library_changed();
+        schedule_music_identity_manifest_sync(&next.url, next.start_ms, next.end_ms).await;
+        request_current_session_track_identity_update(PlaybackTrackIdentityUpdate {
+            music_name: next.alias.clone(),
+            music_url: url,
+            start_ms,
+            end_ms,
+            next_start_ms: next.start_ms,
+            next_end_ms: next.end_ms,
+        });
+    }
+
+    Ok(pieces)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn set_current_music_liked(liked: bool) -> Result<Option<Music>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let Some(track) = active_request_track_snapshot().map_err(|error| error.to_string())? else {
         return Ok(None);
     };
@@ -195,6 +312,7 @@ pub async fn set_current_music_liked(liked: bool) -> Result<Option<Music>, Strin
 
     if updated.is_some() {
         playlist_playback_service::notify_playable_library_changed();
+        schedule_music_identity_manifest_sync(&track.music_url, track.start_ms, track.end_ms).await;
         update_current_session_track_liked(&PlaybackTrackLikedUpdate {
             canonical_music_id: track.canonical_music_id,
             liked,
@@ -208,26 +326,100 @@ pub async fn set_current_music_liked(liked: bool) -> Result<Option<Music>, Strin
 #[tauri::command]
 #[specta::specta]
 pub async fn create_music(source_collection_url: String, music: Music) -> Result<Music, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
     let created = super::repo::create_music(&source_collection_url, &music)
         .await
         .map_err(|error| error.to_string())?;
     playlist_playback_service::notify_music_input_changed("music_create", &created);
     playlist_playback_service::notify_playable_library_changed();
+    schedule_collection_manifest_sync([source_collection_url]);
     Ok(created)
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_music(url: String, start_ms: u32, end_ms: u32) -> Result<bool, String> {
-    let deleted = super::repo::delete_music(&url, start_ms, end_ms)
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let owner_urls = super::repo::list_music_occurrences_by_identity(&url, start_ms, end_ms)
         .await
-        .map_err(|error| error.to_string())?;
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .map(|(collection_url, _)| collection_url)
+        .collect::<Vec<_>>();
+    let deleted = super::trash::trash_music(&url, start_ms, end_ms)
+        .await
+        .map_err(|error| error.to_string())?
+        .is_some();
     if deleted {
+        playlist_playback_service::notify_music_library_inputs_changed("music_delete");
         playlist_playback_service::notify_playable_library_changed();
+        schedule_collection_manifest_sync(owner_urls);
     }
     Ok(deleted)
 }
 
+/// Applies a batch of music edits; library notifications fire once for the
+/// whole batch instead of once per edit.
+#[tauri::command]
+#[specta::specta]
+pub async fn bulk_edit_musics(
+    edits: Vec<MusicBulkEdit>,
+) -> Result<Vec<MusicBulkEditResult>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let outcome = super::bulk_edit::apply_music_bulk_edits(&edits)
+        .await
+        .map_err(|error| error.to_string())?;
+    if !outcome.changed {
+        return Ok(outcome.results);
+    }
+
+    for (edit, result) in edits.iter().zip(&outcome.results) {
+        if result.status != MusicBulkEditStatus::Applied {
+            continue;
+        }
+        match (&edit.action, result.music.as_ref()) {
+            (MusicBulkEditAction::SetLiked { liked }, Some(_)) => {
+                let update = PlaybackTrackLikedUpdate {
+                    canonical_music_id: canonical_music_id_for_source(
+                        &edit.url,
+                        edit.start_ms,
+                        edit.end_ms,
+                    ),
+                    liked: *liked,
+                };
+                if let Err(error) = update_current_session_track_liked(&update) {
+                    log::warn!(
+                        target: "playlists",
+                        "music_bulk_edit_session_liked_failed music_url=\"{}\" error=\"{}\"",
+                        edit.url,
+                        error
+                    );
+                }
+            }
+            (
+                MusicBulkEditAction::SetAlias { .. } | MusicBulkEditAction::SetRange { .. },
+                Some(music),
+            ) => request_current_session_track_identity_update(PlaybackTrackIdentityUpdate {
+                music_name: music.alias.clone(),
+                music_url: edit.url.clone(),
+                start_ms: edit.start_ms,
+                end_ms: edit.end_ms,
+                next_start_ms: music.start_ms,
+                next_end_ms: music.end_ms,
+            }),
+            _ => {}
+        }
+    }
+    if outcome.excludes_changed {
+        playable_index::notify_exclude_changed();
+        super::exclude_expiry::notify_exclude_expiry_changed();
+    }
+    playlist_playback_service::notify_music_library_inputs_changed("music_bulk_edit");
+    playlist_playback_service::notify_playable_library_changed();
+    schedule_collection_manifest_sync(outcome.collection_urls);
+    Ok(outcome.results)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn list_musics_by_file_path(
@@ -272,22 +464,195 @@ pub async fn load_spectrum_music_context(
 
 #[tauri::command]
 #[specta::specta]
-pub async fn add_exclude(music: Music) -> Result<AddExcludeResult, String> {
-    let result = super::repo::add_exclude(music)
+pub async fn search_library(
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<LibrarySearchHit>, String> {
+    super::search::search_library(&query, limit)
         .await
-        .map_err(|error| error.to_string())?;
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_exclude(
+    music: Music,
+    expires_at: Option<String>,
+    playlist_name: Option<String>,
+) -> Result<AddExcludeResult, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let (url, start_ms, end_ms) = (music.url.clone(), music.start_ms, music.end_ms);
+    let result = match playlist_name {
+        Some(playlist_name) => {
+            super::repo::add_playlist_exclude(music, &playlist_name, expires_at).await
+        }
+        None => super::repo::add_exclude(music, expires_at).await,
+    }
+    .map_err(|error| error.to_string())?;
     playable_index::notify_exclude_changed();
+    super::exclude_expiry::notify_exclude_expiry_changed();
+    schedule_music_identity_manifest_sync(&url, start_ms, end_ms).await;
     Ok(result)
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn remove_exclude(music: Music) -> Result<RemoveExcludeResult, String> {
-    let result = super::repo::remove_exclude(&music)
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let result = super::trash::trash_exclude(&music)
         .await
         .map_err(|error| error.to_string())?;
     if result.removed {
         playable_index::notify_exclude_changed();
+        schedule_music_identity_manifest_sync(&music.url, music.start_ms, music.end_ms).await;
     }
     Ok(result)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_trash() -> Result<Vec<TrashItem>, String> {
+    super::trash::list_trash()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_trash_item(id: String) -> Result<bool, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let Some(restored) = super::trash::restore_trash_item(&id)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(false);
+    };
+
+    match restored {
+        RestoredTrashItem::Music(musics) => {
+            for music in &musics {
+                playlist_playback_service::notify_music_input_changed("music_restore", music);
+            }
+            if let Some(music) = musics.first() {
+                schedule_music_identity_manifest_sync(&music.url, music.start_ms, music.end_ms)
+                    .await;
+            }
+        }
+        RestoredTrashItem::Playlist(playlist) => {
+            let dependents = super::repo::list_dependent_playlist_names(&playlist.name)
+                .await
+                .map_err(|error| error.to_string())?;
+            playable_index::notify_playlist_changed(&playlist.name);
+            playable_index::notify_dependent_playlists_changed(&dependents);
+        }
+        RestoredTrashItem::Exclude(result) => {
+            playable_index::notify_exclude_changed();
+            super::exclude_expiry::notify_exclude_expiry_changed();
+            let music = &result.exclude.music;
+            schedule_music_identity_manifest_sync(&music.url, music.start_ms, music.end_ms).await;
+        }
+    }
+    playlist_playback_service::notify_playable_library_changed();
+    Ok(true)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn purge_trash_item(id: String) -> Result<bool, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    super::trash::purge_trash_item(&id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_collection_groups(collection_url: String) -> Result<Vec<Group>, String> {
+    super::repo::list_collection_groups(&collection_url)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_collection_group(
+    app: AppHandle,
+    collection_url: String,
+    name: String,
+) -> Result<Group, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let group = super::groups::create_group(&save_root, &collection_url, &name)
+        .await
+        .map_err(|error| error.to_string())?;
+    playlist_playback_service::notify_music_library_inputs_changed("group_create");
+    playlist_playback_service::notify_playable_library_changed();
+    Ok(group)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_collection_group(
+    app: AppHandle,
+    collection_url: String,
+    group_url: String,
+    name: String,
+) -> Result<Option<Group>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let renamed = super::groups::rename_group(&save_root, &collection_url, &group_url, &name)
+        .await
+        .map_err(|error| error.to_string())?;
+    if renamed.is_some() {
+        playlist_playback_service::notify_music_library_inputs_changed("group_rename");
+    }
+    Ok(renamed)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn move_musics_to_group(
+    app: AppHandle,
+    collection_url: String,
+    musics: Vec<MusicRangeRef>,
+    group_url: String,
+) -> Result<Option<Collection>, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let moved =
+        super::groups::move_musics_to_group(&save_root, &collection_url, &musics, &group_url)
+            .await
+            .map_err(|error| error.to_string())?;
+    if moved.is_some() {
+        playlist_playback_service::notify_music_library_inputs_changed("group_move");
+        playlist_playback_service::notify_playable_library_changed();
+    }
+    Ok(moved)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_collection_group(
+    app: AppHandle,
+    collection_url: String,
+    group_url: String,
+) -> Result<bool, String> {
+    let _restore_guard = try_acquire_library_write_guard().map_err(|error| error.to_string())?;
+    let save_root = crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let deleted = super::groups::delete_group(&save_root, &collection_url, &group_url)
+        .await
+        .map_err(|error| error.to_string())?;
+    if deleted {
+        playlist_playback_service::notify_music_library_inputs_changed("group_delete");
+        playlist_playback_service::notify_playable_library_changed();
+    }
+    Ok(deleted)
+}
@@ -0,0 +1,94 @@
+use super::repo;
+use crate::domain::collection_manifest_sync::schedule_music_identity_manifest_sync;
+use crate::domain::library_backup::acquire_library_write_guard;
+use crate::domain::playlist_playback::playable_index;
+use chrono::Utc;
+use std::sync::OnceLock;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+const EXCLUDE_EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 15);
+/// Floor between sweeps, so an expiry a failing sweep cannot lift does not
+/// spin the thread.
+const EXCLUDE_EXPIRY_MIN_DELAY: Duration = Duration::from_secs(1);
+
+static EXCLUDE_EXPIRY_WAKE: OnceLock<Sender<()>> = OnceLock::new();
+
+/**
+ * Behavior:
+ *   Lift timed excludes once they expire: at startup, right when the next
+ *   expiry passes, and periodically as a fallback.
+ *
+ * Core invariants:
+ *   - An expired exclude stops counting toward exclude availability within a
+ *     moment of its expiry, not at the next periodic sweep.
+ *   - `notify_exclude_expiry_changed` reschedules the sweep, so an exclude
+ *     added with an earlier expiry is not lifted late.
+ */
+pub(crate) fn initialize_runtime() {
+    let (sender, receiver) = mpsc::channel();
+    if EXCLUDE_EXPIRY_WAKE.set(sender).is_err() {
+        return;
+    }
+    let _ = thread::Builder::new()
+        .name("exclude-expiry-sweep".to_string())
+        .spawn(move || {
+            loop {
+                tauri::async_runtime::block_on(sweep_expired_excludes());
+                let wait = tauri::async_runtime::block_on(next_sweep_delay());
+                if let Err(RecvTimeoutError::Disconnected) = receiver.recv_timeout(wait) {
+                    return;
+                }
+            }
+        });
+}
+
+/// Wakes the sweep so it reschedules itself for the earliest expiry.
+pub(crate) fn notify_exclude_expiry_changed() {
+    if let Some(sender) = EXCLUDE_EXPIRY_WAKE.get() {
+        let _ = sender.send(());
+    }
+}
+
+async fn next_sweep_delay() -> Duration {
+    match repo::next_exclude_expiry().await {
+        Ok(Some(expires_at)) => (expires_at - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+            .clamp(EXCLUDE_EXPIRY_MIN_DELAY, EXCLUDE_EXPIRY_SWEEP_INTERVAL),
+        Ok(None) => EXCLUDE_EXPIRY_SWEEP_INTERVAL,
+        Err(error) => {
+            log::warn!(
+                target: "playlists",
+                "exclude_expiry_schedule_failed error=\"{}\"",
+                error
+            );
+            EXCLUDE_EXPIRY_SWEEP_INTERVAL
+        }
+    }
+}
+
+async fn sweep_expired_excludes() {
+    let _restore_guard = acquire_library_write_guard().await;
+    match repo::lift_expired_excludes(Utc::now()).await {
+        Ok(lifted) if lifted.is_empty() => {}
+        Ok(lifted) => {
+            log::info!(
+                target: "playlists",
+                "expired_excludes_lifted count={}",
+                lifted.len()
+            );
+            playable_index::notify_exclude_changed();
+            for music in &lifted {
+                schedule_music_identity_manifest_sync(&music.url, music.start_ms, music.end_ms)
+                    .await;
+            }
+        }
+        Err(error) => log::error!(
+            target: "playlists",
+            "exclude_expiry_sweep_failed error=\"{}\"",
+            error
+        ),
+    }
+}
@@ -0,0 +1,223 @@
+use super::model::{PlaylistExportFormat, PlaylistExportResult};
+use super::repo::{self, PlaylistPlaybackTrackSource};
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+const PLAYLIST_EXPORT_SOURCE_LIMIT: usize = 20_000;
+const XSPF_VLC_EXTENSION_APPLICATION: &str = "http://www.videolan.org/vlc/playlist/0";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlaylistExportTrack {
+    pub(crate) file_path: PathBuf,
+    pub(crate) title: String,
+    pub(crate) start_ms: u32,
+    pub(crate) end_ms: u32,
+}
+
+/**
+ * Behavior:
+ *   Write the playable tracks of a stored playlist to `target` as M3U8 or
+ *   XSPF.
+ *
+ * Core invariants:
+ *   - Membership is the playback selection (refs, included playlists, smart
+ *     rules and excludes), so the file lists what Slisic itself would play.
+ *   - Paths are absolute, resolved from `Music.path` under the save root;
+ *     music without a downloaded path is not exported.
+ *   - Each track keeps its alias and `start_ms`/`end_ms` edit range as VLC
+ *     start/stop options, so other players respect trims and splits.
+ *   - A playlist with more than `PLAYLIST_EXPORT_SOURCE_LIMIT` playable
+ *     tracks is refused instead of exported incomplete.
+ *   - The file is staged beside `target` and renamed into place, so an
+ *     existing export is never left half written.
+ *   - A missing playlist returns `None` and writes nothing.
+ */
+pub async fn export_playlist(
+    name: &str,
+    format: PlaylistExportFormat,
+    save_root: &Path,
+    target: &Path,
+) -> Result<Option<PlaylistExportResult>> {
+    let Some(selection) = repo::get_playlist_playback_selection_by_name(name).await? else {
+        return Ok(None);
+    };
+    let sources =
+        repo::load_playlist_playback_track_sources(&selection, PLAYLIST_EXPORT_SOURCE_LIMIT + 1)
+            .await?;
+    if sources.len() > PLAYLIST_EXPORT_SOURCE_LIMIT {
+        bail!(
+            "playlist `{}` has more than {PLAYLIST_EXPORT_SOURCE_LIMIT} tracks and cannot be exported",
+            selection.playlist_name
+        );
+    }
+    let tracks = sources
+        .iter()
+        .filter_map(|source| playlist_export_track(save_root, source))
+        .collect::<Vec<_>>();
+    let contents = render_playlist_export(format, &selection.playlist_name, &tracks);
+    write_playlist_export(target, &contents)?;
+
+    Ok(Some(PlaylistExportResult {
+        path: target.to_string_lossy().to_string(),
+        track_count: u32::try_from(tracks.len()).unwrap_or(u32::MAX),
+    }))
+}
+
+pub(crate) fn write_playlist_export(target: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = target.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut staged_name = target
+        .file_name()
+        .with_context(|| format!("{} is not a file path", target.display()))?
+        .to_os_string();
+    staged_name.push(".partial");
+    let staged_path = target.with_file_name(staged_name);
+    std::fs::write(&staged_path, contents)
+        .with_context(|| format!("failed to write playlist export {}", staged_path.display()))?;
+    std::fs::rename(&staged_path, target).with_context(|| {
+        let _ = std::fs::remove_file(&staged_path);
+        format!("failed to write playlist export {}", target.display())
+    })
+}
+
+fn playlist_export_track(
+    save_root: &Path,
+    source: &PlaylistPlaybackTrackSource,
+) -> Option<PlaylistExportTrack> {
+    let file_path = repo::resolve_music_file_path(
+        save_root,
+        source.collection_save_root.as_deref(),
+        &source.collection_folder,
+        source.music.path.as_deref(),
+    )?;
+    let title = if source.music.alias.trim().is_empty() {
+        source.music.name.clone()
+    } else {
+        source.music.alias.clone()
+    };
+
+    Some(PlaylistExportTrack {
+        file_path,
+        title,
+        start_ms: source.music.start_ms,
+        end_ms: source.music.end_ms,
+    })
+}
+
+pub(crate) fn render_playlist_export(
+    format: PlaylistExportFormat,
+    playlist_name: &str,
+    tracks: &[PlaylistExportTrack],
+) -> String {
+    match format {
+        PlaylistExportFormat::M3u8 => render_m3u8(playlist_name, tracks),
+        PlaylistExportFormat::Xspf => render_xspf(playlist_name, tracks),
+    }
+}
+
+fn render_m3u8(playlist_name: &str, tracks: &[PlaylistExportTrack]) -> String {
+    let mut contents = String::from("#EXTM3U\n");
+    contents.push_str(&format!("#PLAYLIST:{}\n", single_line(playlist_name)));
+    for track in tracks {
+        let duration_secs = track.end_ms.saturating_sub(track.start_ms).div_ceil(1000);
+        contents.push_str(&format!(
+            "#EXTINF:{duration_secs},{}\n",
+            single_line(&track.title)
+        ));
+        contents.push_str(&format!(
+            "#EXTVLCOPT:start-time={}\n",
+            format_seconds(track.start_ms)
+        ));
+        contents.push_str(&format!(
+            "#EXTVLCOPT:stop-time={}\n",
+            format_seconds(track.end_ms)
+        ));
+        contents.push_str(&format!("{}\n", track.file_path.display()));
+    }
+    contents
+}
+
+fn render_xspf(playlist_name: &str, tracks: &[PlaylistExportTrack]) -> String {
+    let mut contents = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\" ",
+        "xmlns:vlc=\"http://www.videolan.org/vlc/playlist/ns/0/\">\n"
+    ));
+    contents.push_str(&format!("  <title>{}</title>\n", xml_escape(playlist_name)));
+    contents.push_str("  <trackList>\n");
+    for track in tracks {
+        contents.push_str("    <track>\n");
+        contents.push_str(&format!(
+            "      <location>{}</location>\n",
+            xml_escape(&file_uri(&track.file_path))
+        ));
+        contents.push_str(&format!(
+            "      <title>{}</title>\n",
+            xml_escape(&track.title)
+        ));
+        contents.push_str(&format!(
+            "      <duration>{}</duration>\n",
+            track.end_ms.saturating_sub(track.start_ms)
+        ));
+        contents.push_str(&format!(
+            "      <extension application=\"{XSPF_VLC_EXTENSION_APPLICATION}\">\n"
+        ));
+        contents.push_str(&format!(
+            "        <vlc:option>start-time={}</vlc:option>\n",
+            format_seconds(track.start_ms)
+        ));
+        contents.push_str(&format!(
+            "        <vlc:option>stop-time={}</vlc:option>\n",
+            format_seconds(track.end_ms)
+        ));
+        contents.push_str("      </extension>\n");
+        contents.push_str("    </track>\n");
+    }
+    contents.push_str("  </trackList>\n");
+    contents.push_str("</playlist>\n");
+    contents
+}
+
+fn format_seconds(ms: u32) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(char::from(byte))
+            }
+            other => uri.push_str(&format!("%{other:02X}")),
+        }
+    }
+    uri
+}
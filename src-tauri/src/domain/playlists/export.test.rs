@@ -0,0 +1,79 @@
+use super::export::{PlaylistExportTrack, render_playlist_export, write_playlist_export};
+use super::model::PlaylistExportFormat;
+use crate::domain::test_support::{temp_path, write_file};
+use std::path::PathBuf;
+
+fn trimmed_track() -> PlaylistExportTrack {
+    PlaylistExportTrack {
+        file_path: PathBuf::from("/music/slisic/youtube/focus & calm/Intro.m4a"),
+        title: "Intro <live>".to_string(),
+        start_ms: 12_345,
+        end_ms: 67_890,
+    }
+}
+
+#[test]
+fn m3u8_export_keeps_alias_absolute_path_and_edit_range() {
+    let contents = render_playlist_export(PlaylistExportFormat::M3u8, "Focus", &[trimmed_track()]);
+
+    assert_eq!(
+        contents,
+        concat!(
+            "#EXTM3U\n",
+            "#PLAYLIST:Focus\n",
+            "#EXTINF:56,Intro <live>\n",
+            "#EXTVLCOPT:start-time=12.345\n",
+            "#EXTVLCOPT:stop-time=67.890\n",
+            "/music/slisic/youtube/focus & calm/Intro.m4a\n",
+        )
+    );
+}
+
+#[test]
+fn xspf_export_escapes_titles_and_encodes_file_locations() {
+    let contents = render_playlist_export(
+        PlaylistExportFormat::Xspf,
+        "Focus & Ambient",
+        &[trimmed_track()],
+    );
+
+    assert!(contents.contains("<title>Focus &amp; Ambient</title>"));
+    assert!(contents.contains(
+        "<location>file:///music/slisic/youtube/focus%20%26%20calm/Intro.m4a</location>"
+    ));
+    assert!(contents.contains("<title>Intro &lt;live&gt;</title>"));
+    assert!(contents.contains("<duration>55545</duration>"));
+    assert!(contents.contains("<vlc:option>start-time=12.345</vlc:option>"));
+    assert!(contents.contains("<vlc:option>stop-time=67.890</vlc:option>"));
+}
+
+#[test]
+fn xspf_export_writes_windows_paths_as_file_uris() {
+    let track = PlaylistExportTrack {
+        file_path: PathBuf::from(r"C:\Users\me\Documents\slisic\Intro.m4a"),
+        ..trimmed_track()
+    };
+
+    let contents = render_playlist_export(PlaylistExportFormat::Xspf, "Focus", &[track]);
+
+    assert!(
+        contents.contains("<location>file:///C:/Users/me/Documents/slisic/Intro.m4a</location>")
+    );
+}
+
+#[test]
+fn export_replaces_an_existing_file_without_leaving_a_staged_copy() {
+    let workspace = temp_path("playlist_export");
+    let target = workspace.join("exports/Focus.m3u8");
+    write_file(&target, b"#EXTM3U\nold\n");
+
+    write_playlist_export(&target, "#EXTM3U\nnew\n").expect("export should be written");
+
+    assert_eq!(
+        std::fs::read_to_string(&target).expect("export should exist"),
+        "#EXTM3U\nnew\n"
+    );
+    assert!(!workspace.join("exports/Focus.m3u8.partial").exists());
+
+    let _ = std::fs::remove_dir_all(workspace);
+}
@@ -0,0 +1,152 @@
+use super::model::{Collection, CollectionGroupOwner, Group, MusicRangeRef};
+use super::repo;
+use crate::domain::collection_import::sync_collection_manifest_groups;
+use crate::domain::downloads::naming::sanitize_path_component;
+use anyhow::{Result, bail};
+use std::path::Path;
+
+/**
+ * Behavior:
+ *   Create an empty group under a collection. The group gets a stable url
+ *   below the collection url and a folder named after it.
+ *
+ * Core invariants:
+ *   - Group names are trimmed, non-empty and unique within the collection.
+ *   - Folder and url get a ` (n)` counter when the sanitized name is taken,
+ *     e.g. by a renamed group that kept its folder or by a name that
+ *     sanitizes the same way.
+ */
+pub async fn create_group(save_root: &Path, collection_url: &str, name: &str) -> Result<Group> {
+    let name = validated_group_name(name)?;
+    let Some(collection) = repo::get_collection_by_url(collection_url).await? else {
+        bail!("collection `{collection_url}` not found");
+    };
+    ensure_group_name_available(&collection, name, None).await?;
+
+    let (folder, url) = available_group_folder(collection_url, name).await?;
+    let group = repo::create_collection_group(
+        collection_url,
+        Group {
+            name: name.to_string(),
+            url,
+            collection: CollectionGroupOwner::from(&collection),
+            folder,
+        },
+    )
+    .await?;
+    sync_manifest(save_root, collection_url).await?;
+    Ok(group)
+}
+
+/// Renames a group of the collection; the collection's own group follows the
+/// collection name and cannot be renamed here.
+pub async fn rename_group(
+    save_root: &Path,
+    collection_url: &str,
+    group_url: &str,
+    name: &str,
+) -> Result<Option<Group>> {
+    let name = validated_group_name(name)?;
+    let Some(collection) = repo::get_collection_by_url(collection_url).await? else {
+        return Ok(None);
+    };
+    if group_url == collection.url {
+        bail!("the collection group is renamed with its collection");
+    }
+    ensure_collection_owns_group(collection_url, group_url).await?;
+    ensure_group_name_available(&collection, name, Some(group_url)).await?;
+
+    let renamed = repo::rename_group(group_url, name).await?;
+    sync_manifest(save_root, collection_url).await?;
+    Ok(renamed)
+}
+
+/// Moves music occurrences between groups of one collection.
+pub async fn move_musics_to_group(
+    save_root: &Path,
+    collection_url: &str,
+    musics: &[MusicRangeRef],
+    group_url: &str,
+) -> Result<Option<Collection>> {
+    let moved = repo::move_collection_musics_to_group(collection_url, musics, group_url).await?;
+    if moved.is_some() {
+        sync_manifest(save_root, collection_url).await?;
+    }
+    Ok(moved)
+}
+
+/// Deletes a group of the collection once it holds no music.
+pub async fn delete_group(save_root: &Path, collection_url: &str, group_url: &str) -> Result<bool> {
+    if group_url == collection_url {
+        bail!("the collection group cannot be deleted");
+    }
+    ensure_collection_owns_group(collection_url, group_url).await?;
+
+    let deleted = repo::delete_empty_group(group_url).await?;
+    if deleted {
+        sync_manifest(save_root, collection_url).await?;
+    }
+    Ok(deleted)
+}
+
+fn validated_group_name(name: &str) -> Result<&str> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("group name must not be empty");
+    }
+    Ok(name)
+}
+
+async fn ensure_collection_owns_group(collection_url: &str, group_url: &str) -> Result<()> {
+    if !repo::list_collection_groups(collection_url)
+        .await?
+        .iter()
+        .any(|group| group.url == group_url)
+    {
+        bail!("group `{group_url}` does not belong to `{collection_url}`");
+    }
+    Ok(())
+}
+
+async fn ensure_group_name_available(
+    collection: &Collection,
+    name: &str,
+    except_url: Option<&str>,
+) -> Result<()> {
+    if repo::list_collection_groups(&collection.url)
+        .await?
+        .iter()
+        .any(|group| Some(group.url.as_str()) != except_url && group.name == name)
+    {
+        bail!(
+            "collection `{}` already has a group named `{name}`",
+            collection.url
+        );
+    }
+    Ok(())
+}
+
+async fn available_group_folder(collection_url: &str, name: &str) -> Result<(String, String)> {
+    let base = sanitize_path_component(name);
+    let groups = repo::list_collection_groups(collection_url).await?;
+    let mut folder = base.clone();
+    let mut counter = 1;
+    loop {
+        let url = format!("{collection_url}#group/{folder}");
+        if !groups.iter().any(|group| group.folder == folder)
+            && !repo::group_url_exists(&url).await?
+        {
+            return Ok((folder, url));
+        }
+        counter += 1;
+        folder = format!("{base} ({counter})");
+    }
+}
+
+async fn sync_manifest(save_root: &Path, collection_url: &str) -> Result<()> {
+    let Some(collection) = repo::get_collection_by_url(collection_url).await? else {
+        return Ok(());
+    };
+    let groups = repo::list_collection_groups(collection_url).await?;
+    sync_collection_manifest_groups(&collection.dir(save_root), &collection, &groups)
+}
@@ -0,0 +1,227 @@
+use super::groups::{create_group, delete_group, move_musics_to_group, rename_group};
+use super::model::{Collection, Music, MusicRangeRef, PlayList, PlayListWriteRequest};
+use super::repo::{
+    get_collection_by_url, get_playlist_by_name, list_collection_groups, push_extra,
+    upsert_collection, upsert_playlist_surface,
+};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, owner_group, run_async, temp_path, write_file,
+};
+use appdb::AutoFill;
+use appdb::connection::reset_db;
+use std::path::PathBuf;
+
+const COLLECTION_URL: &str = "https://example.com/groups";
+const COLLECTION_FOLDER: &str = "youtube/groups-demo";
+
+fn music(name: &str) -> Music {
+    crate::domain::test_support::music(
+        name,
+        &owner_group("Groups Demo", COLLECTION_URL, COLLECTION_FOLDER),
+    )
+}
+
+fn collection() -> Collection {
+    crate::domain::test_support::collection(
+        "Groups Demo",
+        COLLECTION_URL,
+        COLLECTION_FOLDER,
+        vec![music("Opener"), music("Encore")],
+    )
+}
+
+fn range(music: &Music) -> MusicRangeRef {
+    MusicRangeRef {
+        url: music.url.clone(),
+        start_ms: music.start_ms,
+        end_ms: music.end_ms,
+    }
+}
+
+fn write_manifest(save_root: &std::path::Path) -> PathBuf {
+    let manifest_path = save_root
+        .join(COLLECTION_FOLDER)
+        .join(".slisic.collection.toml");
+    let musics = collection()
+        .musics
+        .iter()
+        .map(|music| {
+            format!(
+                "[[music]]\nname = \"{name}\"\nalias = \"{name}\"\nurl = \"{url}\"\npath = \"{name}.m4a\"\ngroup_url = \"{COLLECTION_URL}\"\nstart_ms = 0\nend_ms = 60000\nliked = false\n",
+                name = music.name,
+                url = music.url
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    write_file(
+        &manifest_path,
+        format!(
+            "version = 1\ngroups = []\n\n[collection]\nname = \"Groups Demo\"\nurl = \"{COLLECTION_URL}\"\nfolder = \"{COLLECTION_FOLDER}\"\n\n{musics}"
+        )
+        .as_bytes(),
+    );
+    manifest_path
+}
+
+#[test]
+fn groups_can_be_created_renamed_filled_and_deleted() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let save_root = temp_path("groups_root");
+        let manifest_path = write_manifest(&save_root);
+        upsert_collection(&collection())
+            .await
+            .expect("collection should save");
+
+        let live = create_group(&save_root, COLLECTION_URL, " Live ")
+            .await
+            .expect("group should be created");
+        assert_eq!(live.name, "Live");
+        assert_eq!(live.folder, "Live");
+        assert!(
+            create_group(&save_root, COLLECTION_URL, "Live")
+                .await
+                .is_err()
+        );
+        assert!(
+            create_group(&save_root, COLLECTION_URL, "  ")
+                .await
+                .is_err()
+        );
+        assert!(
+            list_collection_groups(COLLECTION_URL)
+                .await
+                .expect("groups should list")
+                .iter()
+                .any(|group| group.url == live.url)
+        );
+
+        let renamed = rename_group(&save_root, COLLECTION_URL, &live.url, "Live Sets")
+            .await
+            .expect("rename should succeed")
+            .expect("group should exist");
+        assert_eq!(renamed.name, "Live Sets");
+        assert_eq!(renamed.url, live.url);
+        let live_again = create_group(&save_root, COLLECTION_URL, "Live")
+            .await
+            .expect("the freed name should be usable again");
+        assert_ne!(live_again.url, live.url);
+        assert_eq!(live_again.folder, "Live (2)");
+        assert!(
+            delete_group(&save_root, COLLECTION_URL, &live_again.url)
+                .await
+                .expect("empty group should delete")
+        );
+        assert!(
+            rename_group(&save_root, COLLECTION_URL, COLLECTION_URL, "Other")
+                .await
+                .is_err()
+        );
+
+        let encore = music("Encore");
+        let moved = move_musics_to_group(&save_root, COLLECTION_URL, &[range(&encore)], &live.url)
+            .await
+            .expect("move should succeed")
+            .expect("collection should exist");
+        let moved_encore = moved
+            .musics
+            .iter()
+            .find(|music| music.url == encore.url)
+            .expect("moved music should stay in the collection");
+        assert_eq!(moved_encore.group.url, live.url);
+        assert_eq!(moved_encore.group.name, "Live Sets");
+        assert_eq!(moved_encore.path.as_deref(), Some("Encore.m4a"));
+
+        let manifest = std::fs::read_to_string(&manifest_path).expect("manifest should exist");
+        assert!(manifest.contains("name = \"Live Sets\""));
+        assert!(manifest.contains(&format!("group_url = \"{}\"", live.url)));
+
+        assert!(
+            delete_group(&save_root, COLLECTION_URL, &live.url)
+                .await
+                .is_err()
+        );
+        move_musics_to_group(
+            &save_root,
+            COLLECTION_URL,
+            &[range(&encore)],
+            COLLECTION_URL,
+        )
+        .await
+        .expect("move back should succeed");
+        assert!(
+            delete_group(&save_root, COLLECTION_URL, &live.url)
+                .await
+                .expect("empty group should delete")
+        );
+        assert!(
+            list_collection_groups(COLLECTION_URL)
+                .await
+                .expect("groups should list")
+                .iter()
+                .all(|group| group.url != live.url)
+        );
+        let manifest = std::fs::read_to_string(&manifest_path).expect("manifest should exist");
+        assert!(!manifest.contains("Live Sets"));
+        assert!(!manifest.contains(&live.url));
+
+        let _ = std::fs::remove_dir_all(save_root);
+        reset_db();
+    });
+}
+
+#[test]
+fn moving_music_keeps_playlist_extras_pointing_at_it() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+        let save_root = temp_path("groups_extras");
+        upsert_collection(&collection())
+            .await
+            .expect("collection should save");
+        let opener = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist")
+            .musics
+            .into_iter()
+            .find(|music| music.name == "Opener")
+            .expect("opener should exist");
+        let playlist = PlayList {
+            name: "Pinned".to_string(),
+            collections: vec![],
+            groups: vec![],
+            extra: vec![],
+            playlists: None,
+            rules: None,
+            created_at: AutoFill::pending(),
+        };
+        upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+            .await
+            .expect("playlist should save");
+        push_extra("Pinned", opener.clone())
+            .await
+            .expect("extra should be pinned");
+
+        let live = create_group(&save_root, COLLECTION_URL, "Live")
+            .await
+            .expect("group should be created");
+        move_musics_to_group(&save_root, COLLECTION_URL, &[range(&opener)], &live.url)
+            .await
+            .expect("move should succeed");
+
+        let pinned = get_playlist_by_name("Pinned")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should exist");
+        assert_eq!(pinned.extra.len(), 1);
+        assert_eq!(pinned.extra[0].url, opener.url);
+        assert_eq!(pinned.extra[0].group.url, live.url);
+
+        reset_db();
+    });
+}
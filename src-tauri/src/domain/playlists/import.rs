@@ -0,0 +1,480 @@
+use super::model::{
+    Music, PlayListWriteRequest, PlaylistImportResult, PlaylistImportUnresolvedEntry,
+    PlaylistImportUnresolvedReason,
+};
+use super::repo;
+use anyhow::{Context, Result, bail};
+use appdb::AutoFill;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+const IMPORTED_PLAYLIST_FALLBACK_NAME: &str = "Imported playlist";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlaylistFileFormat {
+    M3u,
+    Pls,
+    Xspf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlaylistFileEntry {
+    pub(crate) location: String,
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) start_ms: Option<u32>,
+    pub(crate) end_ms: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ParsedPlaylistFile {
+    pub(crate) title: Option<String>,
+    pub(crate) entries: Vec<PlaylistFileEntry>,
+}
+
+/**
+ * Behavior:
+ *   Parse an M3U/M3U8/PLS/XSPF file from another player and project its
+ *   entries onto existing library `Music` rows as a playlist draft.
+ *
+ * Core invariants:
+ *   - Entries resolve through the same file lookup as
+ *     `list_musics_by_file_path`, so a file maps to every stored segment of it.
+ *   - An entry carrying VLC start/stop options (as written by the exporter)
+ *     narrows to the segment with exactly that range when one exists.
+ *   - Nothing is imported into the library: the folders of files not yet in
+ *     it are only reported as candidates, for the caller to import once the
+ *     user confirms and then run the import again.
+ *   - Nothing is persisted as a playlist: the caller receives a write request
+ *     with `extra` tracks plus every entry that could not be resolved.
+ */
+pub async fn import_playlist_file(
+    file_path: &Path,
+    name: Option<&str>,
+    save_root: &Path,
+) -> Result<PlaylistImportResult> {
+    let bytes = std::fs::read(file_path)
+        .with_context(|| format!("failed to read playlist file {}", file_path.display()))?;
+    let contents = String::from_utf8_lossy(&bytes);
+    let contents = contents.trim_start_matches('\u{feff}');
+    let Some(format) = playlist_file_format(file_path, contents) else {
+        bail!("unsupported playlist file {}", file_path.display());
+    };
+    let base_dir = file_path.parent().unwrap_or_else(|| Path::new(""));
+    let parsed = parse_playlist_file(format, contents, base_dir);
+
+    let mut resolved = HashMap::<usize, Vec<Music>>::new();
+    let mut unresolved = Vec::<PlaylistImportUnresolvedEntry>::new();
+    let mut candidate_folders = Vec::<String>::new();
+
+    for (index, entry) in parsed.entries.iter().enumerate() {
+        let Some(path) = entry.path.as_deref() else {
+            unresolved.push(unresolved_entry(
+                entry,
+                PlaylistImportUnresolvedReason::RemoteLocation,
+            ));
+            continue;
+        };
+        if !path.is_file() {
+            unresolved.push(unresolved_entry(
+                entry,
+                PlaylistImportUnresolvedReason::MissingFile,
+            ));
+            continue;
+        }
+
+        let musics = repo::list_musics_by_file_path(path, save_root).await?;
+        if musics.is_empty() {
+            if let Some(folder) = path.parent() {
+                let folder = folder.to_string_lossy().to_string();
+                if !candidate_folders.contains(&folder) {
+                    candidate_folders.push(folder);
+                }
+            }
+            unresolved.push(unresolved_entry(
+                entry,
+                PlaylistImportUnresolvedReason::NotInLibrary,
+            ));
+        } else {
+            resolved.insert(index, musics);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut extra = Vec::new();
+    for (index, entry) in parsed.entries.iter().enumerate() {
+        let Some(musics) = resolved.remove(&index) else {
+            continue;
+        };
+        for music in select_entry_musics(entry, musics) {
+            if seen.insert((music.url.clone(), music.start_ms, music.end_ms)) {
+                extra.push(music);
+            }
+        }
+    }
+
+    Ok(PlaylistImportResult {
+        playlist: PlayListWriteRequest {
+            name: imported_playlist_name(name, parsed.title.as_deref(), file_path),
+            collections: vec![],
+            groups: vec![],
+            extra,
+            playlists: vec![],
+            rules: None,
+            created_at: AutoFill::pending(),
+        },
+        unresolved,
+        candidate_folders,
+    })
+}
+
+fn unresolved_entry(
+    entry: &PlaylistFileEntry,
+    reason: PlaylistImportUnresolvedReason,
+) -> PlaylistImportUnresolvedEntry {
+    PlaylistImportUnresolvedEntry {
+        location: entry.location.clone(),
+        reason,
+    }
+}
+
+fn select_entry_musics(entry: &PlaylistFileEntry, musics: Vec<Music>) -> Vec<Music> {
+    let (Some(start_ms), Some(end_ms)) = (entry.start_ms, entry.end_ms) else {
+        return musics;
+    };
+    match musics
+        .iter()
+        .find(|music| music.start_ms == start_ms && music.end_ms == end_ms)
+    {
+        Some(music) => vec![music.clone()],
+        None => musics,
+    }
+}
+
+pub(crate) fn imported_playlist_name(
+    name: Option<&str>,
+    title: Option<&str>,
+    file_path: &Path,
+) -> String {
+    let stem = file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string());
+    [name, title, stem.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|candidate| !candidate.is_empty())
+        .unwrap_or(IMPORTED_PLAYLIST_FALLBACK_NAME)
+        .to_string()
+}
+
+pub(crate) fn playlist_file_format(path: &Path, contents: &str) -> Option<PlaylistFileFormat> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("m3u" | "m3u8") => return Some(PlaylistFileFormat::M3u),
+        Some("pls") => return Some(PlaylistFileFormat::Pls),
+        Some("xspf") => return Some(PlaylistFileFormat::Xspf),
+        _ => {}
+    }
+
+    let head = contents.trim_start();
+    if head.starts_with("#EXTM3U") {
+        Some(PlaylistFileFormat::M3u)
+    } else if head.to_lowercase().starts_with("[playlist]") {
+        Some(PlaylistFileFormat::Pls)
+    } else if head.contains("http://xspf.org/ns/0/") {
+        Some(PlaylistFileFormat::Xspf)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn parse_playlist_file(
+    format: PlaylistFileFormat,
+    contents: &str,
+    base_dir: &Path,
+) -> ParsedPlaylistFile {
+    match format {
+        PlaylistFileFormat::M3u => parse_m3u(contents, base_dir),
+        PlaylistFileFormat::Pls => parse_pls(contents, base_dir),
+        PlaylistFileFormat::Xspf => parse_xspf(contents, base_dir),
+    }
+}
+
+fn parse_m3u(contents: &str, base_dir: &Path) -> ParsedPlaylistFile {
+    let mut parsed = ParsedPlaylistFile::default();
+    let mut start_ms = None;
+    let mut end_ms = None;
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(title) = line.strip_prefix("#PLAYLIST:") {
+            parsed.title = Some(title.trim().to_string());
+            continue;
+        }
+        if let Some(option) = line.strip_prefix("#EXTVLCOPT:") {
+            apply_vlc_option(option, &mut start_ms, &mut end_ms);
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        parsed.entries.push(playlist_file_entry(
+            line,
+            base_dir,
+            start_ms.take(),
+            end_ms.take(),
+        ));
+    }
+
+    parsed
+}
+
+fn parse_pls(contents: &str, base_dir: &Path) -> ParsedPlaylistFile {
+    let mut files = BTreeMap::<u32, String>::new();
+    for line in contents.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.len() <= 4 || !key[..4].eq_ignore_ascii_case("file") {
+            continue;
+        }
+        let Ok(number) = key[4..].parse::<u32>() else {
+            continue;
+        };
+        let value = value.trim();
+        if !value.is_empty() {
+            files.insert(number, value.to_string());
+        }
+    }
+
+    ParsedPlaylistFile {
+        title: None,
+        entries: files
+            .into_values()
+            .map(|location| playlist_file_entry(&location, base_dir, None, None))
+            .collect(),
+    }
+}
+
+fn parse_xspf(contents: &str, base_dir: &Path) -> ParsedPlaylistFile {
+    let (head, track_list) = match contents.find("<trackList") {
+        Some(offset) => contents.split_at(offset),
+        None => (contents, ""),
+    };
+    let mut parsed = ParsedPlaylistFile {
+        title: xml_element_texts(head, "title")
+            .into_iter()
+            .next()
+            .map(|title| xml_unescape(title.trim())),
+        entries: vec![],
+    };
+
+    for track in xml_element_texts(track_list, "track") {
+        let Some(location) = xml_element_texts(track, "location").into_iter().next() else {
+            continue;
+        };
+        let location = xml_unescape(location.trim());
+        if location.is_empty() {
+            continue;
+        }
+
+        let mut start_ms = None;
+        let mut end_ms = None;
+        for option in xml_element_texts(track, "vlc:option") {
+            apply_vlc_option(&xml_unescape(option.trim()), &mut start_ms, &mut end_ms);
+        }
+        parsed
+            .entries
+            .push(playlist_file_entry(&location, base_dir, start_ms, end_ms));
+    }
+
+    parsed
+}
+
+fn xml_element_texts<'a>(contents: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut texts = Vec::new();
+    let mut rest = contents;
+
+    while let Some(start) = rest.find(&open) {
+        let after_name = &rest[start + open.len()..];
+        if !after_name.starts_with(['>', ' ', '\t', '\r', '\n']) {
+            rest = after_name;
+            continue;
+        }
+        let Some(tag_end) = after_name.find('>') else {
+            break;
+        };
+        let body = &after_name[tag_end + 1..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        texts.push(&body[..end]);
+        rest = &body[end + close.len()..];
+    }
+
+    texts
+}
+
+fn xml_unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        let Some(end) = candidate.find(';') else {
+            unescaped.push_str(candidate);
+            return unescaped;
+        };
+        let entity = &candidate[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(character) => {
+                unescaped.push(character);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn apply_vlc_option(option: &str, start_ms: &mut Option<u32>, end_ms: &mut Option<u32>) {
+    let Some((key, value)) = option.split_once('=') else {
+        return;
+    };
+    let Some(ms) = parse_seconds_ms(value) else {
+        return;
+    };
+    match key.trim() {
+        "start-time" => *start_ms = Some(ms),
+        "stop-time" => *end_ms = Some(ms),
+        _ => {}
+    }
+}
+
+fn parse_seconds_ms(value: &str) -> Option<u32> {
+    let seconds = value.trim().parse::<f64>().ok()?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+    let ms = (seconds * 1000.0).round();
+    (ms <= f64::from(u32::MAX)).then_some(ms as u32)
+}
+
+fn playlist_file_entry(
+    location: &str,
+    base_dir: &Path,
+    start_ms: Option<u32>,
+    end_ms: Option<u32>,
+) -> PlaylistFileEntry {
+    PlaylistFileEntry {
+        location: location.to_string(),
+        path: resolve_entry_path(location, base_dir),
+        start_ms,
+        end_ms,
+    }
+}
+
+fn resolve_entry_path(location: &str, base_dir: &Path) -> Option<PathBuf> {
+    let path = match location.split_once("://") {
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("file") => file_uri_path(rest),
+        Some((scheme, _))
+            if scheme.len() > 1
+                && scheme.chars().all(|character| {
+                    character.is_ascii_alphanumeric() || "+-.".contains(character)
+                }) =>
+        {
+            return None;
+        }
+        _ => PathBuf::from(location),
+    };
+    let path = if path.is_absolute() || is_windows_absolute(&path) {
+        path
+    } else {
+        base_dir.join(path)
+    };
+    Some(normalize_lexically(&path))
+}
+
+fn file_uri_path(rest: &str) -> PathBuf {
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let decoded = percent_decode(rest);
+    let windows_drive = decoded.len() >= 3
+        && decoded.starts_with('/')
+        && decoded.as_bytes()[1].is_ascii_alphabetic()
+        && decoded.as_bytes()[2] == b':';
+    if windows_drive {
+        PathBuf::from(&decoded[1..])
+    } else {
+        PathBuf::from(decoded)
+    }
+}
+
+fn is_windows_absolute(path: &Path) -> bool {
+    let text = path.to_string_lossy();
+    let bytes = text.as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/')
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(byte) = value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            index += 3;
+            continue;
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
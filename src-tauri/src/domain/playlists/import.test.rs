@@ -0,0 +1,187 @@
+use super::import::{
+    PlaylistFileFormat, import_playlist_file, imported_playlist_name, parse_playlist_file,
+    playlist_file_format,
+};
+use super::model::PlaylistImportUnresolvedReason;
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, run_async, temp_path, write_file,
+};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn m3u_entries_resolve_relative_paths_and_keep_vlc_edit_ranges() {
+    let contents = concat!(
+        "#EXTM3U\n",
+        "#PLAYLIST:Focus\n",
+        "#EXTINF:56,Intro\n",
+        "#EXTVLCOPT:start-time=12.345\n",
+        "#EXTVLCOPT:stop-time=67.890\n",
+        "../albums/Intro.m4a\n",
+        "\n",
+        "#EXTINF:120,Outro\n",
+        "/music/Outro.flac\n",
+        "https://example.com/stream.mp3\n",
+    );
+
+    let parsed = parse_playlist_file(
+        PlaylistFileFormat::M3u,
+        contents,
+        Path::new("/music/playlists"),
+    );
+
+    assert_eq!(parsed.title.as_deref(), Some("Focus"));
+    assert_eq!(parsed.entries.len(), 3);
+    assert_eq!(
+        parsed.entries[0].path,
+        Some(PathBuf::from("/music/albums/Intro.m4a"))
+    );
+    assert_eq!(parsed.entries[0].start_ms, Some(12_345));
+    assert_eq!(parsed.entries[0].end_ms, Some(67_890));
+    assert_eq!(
+        parsed.entries[1].path,
+        Some(PathBuf::from("/music/Outro.flac"))
+    );
+    assert_eq!(parsed.entries[1].start_ms, None);
+    assert_eq!(parsed.entries[2].path, None);
+    assert_eq!(parsed.entries[2].location, "https://example.com/stream.mp3");
+}
+
+#[test]
+fn pls_entries_follow_file_numbers_not_line_order() {
+    let contents = concat!(
+        "[playlist]\n",
+        "File2=second.mp3\n",
+        "Title2=Second\n",
+        "File1=first.mp3\n",
+        "NumberOfEntries=2\n",
+        "Version=2\n",
+    );
+
+    let parsed = parse_playlist_file(PlaylistFileFormat::Pls, contents, Path::new("/music"));
+
+    let paths = parsed
+        .entries
+        .iter()
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            Some(PathBuf::from("/music/first.mp3")),
+            Some(PathBuf::from("/music/second.mp3")),
+        ]
+    );
+}
+
+#[test]
+fn xspf_entries_decode_file_uris_entities_and_vlc_options() {
+    let contents = concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n",
+        "  <title>Focus &amp; Ambient</title>\n",
+        "  <trackList>\n",
+        "    <track>\n",
+        "      <location>file:///music/focus%20%26%20calm/Intro.m4a</location>\n",
+        "      <title>Intro</title>\n",
+        "      <extension application=\"http://www.videolan.org/vlc/playlist/0\">\n",
+        "        <vlc:option>start-time=1.5</vlc:option>\n",
+        "        <vlc:option>stop-time=30</vlc:option>\n",
+        "      </extension>\n",
+        "    </track>\n",
+        "    <track>\n",
+        "      <location>file:///C:/Users/me/Music/Outro.flac</location>\n",
+        "    </track>\n",
+        "  </trackList>\n",
+        "</playlist>\n",
+    );
+
+    let parsed = parse_playlist_file(PlaylistFileFormat::Xspf, contents, Path::new("/tmp"));
+
+    assert_eq!(parsed.title.as_deref(), Some("Focus & Ambient"));
+    assert_eq!(
+        parsed.entries[0].path,
+        Some(PathBuf::from("/music/focus & calm/Intro.m4a"))
+    );
+    assert_eq!(parsed.entries[0].start_ms, Some(1_500));
+    assert_eq!(parsed.entries[0].end_ms, Some(30_000));
+    assert_eq!(
+        parsed.entries[1].path,
+        Some(PathBuf::from("C:/Users/me/Music/Outro.flac"))
+    );
+}
+
+#[test]
+fn playlist_format_and_name_fall_back_to_content_and_file_stem() {
+    assert_eq!(
+        playlist_file_format(Path::new("/music/list.txt"), "#EXTM3U\nsong.mp3\n"),
+        Some(PlaylistFileFormat::M3u)
+    );
+    assert_eq!(
+        playlist_file_format(Path::new("/music/list.PLS"), ""),
+        Some(PlaylistFileFormat::Pls)
+    );
+    assert_eq!(
+        playlist_file_format(Path::new("/music/list.txt"), "song.mp3"),
+        None
+    );
+
+    let path = Path::new("/music/Road Trip.m3u8");
+    assert_eq!(imported_playlist_name(Some(" "), None, path), "Road Trip");
+    assert_eq!(imported_playlist_name(None, Some("Focus"), path), "Focus");
+    assert_eq!(
+        imported_playlist_name(Some("Mine"), Some("Focus"), path),
+        "Mine"
+    );
+}
+
+#[test]
+fn files_outside_the_library_are_reported_without_importing_their_folder() {
+    let _guard = acquire_db_test_lock();
+    let root = temp_path("playlist_import");
+    let album = root.join("Album");
+    write_file(&album.join("One.mp3"), b"one");
+    write_file(&album.join("Two.mp3"), b"two");
+    write_file(&album.join("Unlisted.mp3"), b"unlisted");
+    let playlist_path = root.join("Trip.m3u");
+    write_file(
+        &playlist_path,
+        b"#EXTM3U\nAlbum/One.mp3\nAlbum/Two.mp3\nAlbum/Gone.mp3\n",
+    );
+
+    run_async(async {
+        bootstrap_db().await;
+        let result = import_playlist_file(&playlist_path, None, &root)
+            .await
+            .expect("playlist import should succeed");
+
+        assert_eq!(result.playlist.name, "Trip");
+        assert!(result.playlist.extra.is_empty());
+        assert_eq!(
+            result
+                .unresolved
+                .iter()
+                .map(|entry| (entry.location.as_str(), entry.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Album/One.mp3",
+                    PlaylistImportUnresolvedReason::NotInLibrary
+                ),
+                (
+                    "Album/Two.mp3",
+                    PlaylistImportUnresolvedReason::NotInLibrary
+                ),
+                (
+                    "Album/Gone.mp3",
+                    PlaylistImportUnresolvedReason::MissingFile
+                ),
+            ]
+        );
+        assert_eq!(
+            result.candidate_folders,
+            vec![album.to_string_lossy().to_string()]
+        );
+    });
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -1,7 +1,15 @@
+pub mod bulk_edit;
 #[cfg(not(test))]
 pub mod cmd;
+#[cfg(not(test))]
+pub mod exclude_expiry;
+pub mod export;
+pub mod groups;
+pub mod import;
 pub mod model;
 pub mod repo;
+pub mod search;
+pub mod trash;
 
 #[cfg(not(test))]
 pub use cmd::*;
@@ -10,6 +18,10 @@ pub use cmd::*;
 pub(crate) static PLAYLIST_DB_TEST_LOCK: std::sync::LazyLock<std::sync::Mutex<()>> =
     std::sync::LazyLock::new(|| std::sync::Mutex::new(()));
 
+#[cfg(test)]
+#[path = "bulk_edit.test.rs"]
+mod bulk_edit_test;
+
 #[cfg(test)]
 #[path = "model.test.rs"]
 mod model_test;
@@ -17,3 +29,23 @@ mod model_test;
 #[cfg(test)]
 #[path = "repo.test.rs"]
 mod repo_test;
+
+#[cfg(test)]
+#[path = "export.test.rs"]
+mod export_test;
+
+#[cfg(test)]
+#[path = "groups.test.rs"]
+mod groups_test;
+
+#[cfg(test)]
+#[path = "import.test.rs"]
+mod import_test;
+
+#[cfg(test)]
+#[path = "search.test.rs"]
+mod search_test;
+
+#[cfg(test)]
+#[path = "trash.test.rs"]
+mod trash_test;
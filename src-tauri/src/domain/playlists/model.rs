@@ -1,8 +1,10 @@
+use crate::domain::meta::model::collection_dir;
 use appdb::model::meta::{ModelMeta, ViewParams};
 use appdb::query::RawSqlStmt;
 use appdb::{AutoFill, Crud, Store, View};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::{Path, PathBuf};
 use surrealdb::types::{RecordId, Table};
 use surrealdb_types::SurrealValue;
 
@@ -16,11 +18,66 @@ pub struct PlayList {
     pub groups: Vec<Group>,
     #[foreign]
     pub extra: Vec<Music>,
+    #[serde(default)]
+    pub playlists: Option<Vec<String>>,
+    #[serde(default)]
+    pub rules: Option<PlaylistRules>,
     #[pagin]
     #[fill(now)]
     pub created_at: AutoFill,
 }
 
+/// Behavior:
+///   Smart playlist rules select library music by stored criteria instead of
+///   explicit refs. Every present criterion must hold; a rule set without
+///   criteria selects the whole playable library.
+///
+/// Core invariants:
+///   - Rules are evaluated when the playlist plays, so membership follows
+///     library evidence (liked state, loudness, added date) without rewrites.
+///   - Tracks without loudness or added-date evidence never satisfy a range
+///     on that evidence.
+///   - `added_after`/`added_before` compare against RFC 3339 UTC timestamps;
+///     a date prefix such as `2026-01-01` is accepted.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, SurrealValue, Type)]
+pub struct PlaylistRules {
+    #[serde(default)]
+    pub liked: Option<bool>,
+    #[serde(default)]
+    pub min_integrated_lufs: Option<f32>,
+    #[serde(default)]
+    pub max_integrated_lufs: Option<f32>,
+    #[serde(default)]
+    pub source_collection_urls: Vec<String>,
+    #[serde(default)]
+    pub min_duration_ms: Option<u32>,
+    #[serde(default)]
+    pub max_duration_ms: Option<u32>,
+    #[serde(default)]
+    pub added_after: Option<String>,
+    #[serde(default)]
+    pub added_before: Option<String>,
+}
+
+impl PlaylistRules {
+    pub fn is_valid(&self) -> bool {
+        self.min_integrated_lufs.is_none_or(f32::is_finite)
+            && self.max_integrated_lufs.is_none_or(f32::is_finite)
+            && match (self.min_integrated_lufs, self.max_integrated_lufs) {
+                (Some(min), Some(max)) => min <= max,
+                _ => true,
+            }
+            && match (self.min_duration_ms, self.max_duration_ms) {
+                (Some(min), Some(max)) => min <= max,
+                _ => true,
+            }
+            && self
+                .source_collection_urls
+                .iter()
+                .all(|url| !url.trim().is_empty())
+    }
+}
+
 /// Behavior:
 ///   Playlist write requests carry UI-selected library refs, not stored
 ///   collection/group rows. The playlist repository owns the projection from
@@ -32,17 +89,22 @@ pub struct PlayList {
 ///   - Cache, fallback, and draft surfaces are not allowed to materialize
 ///     stable playlist storage rows.
 ///   - Repeating the same request resolves to the same referenced records.
+///   - `playlists` names other stored playlists whose selection is included;
+///     the include graph must stay acyclic.
 #[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct PlayListWriteRequest {
     pub name: String,
     pub collections: Vec<PlaylistCollectionRef>,
     pub groups: Vec<PlaylistGroupRef>,
     pub extra: Vec<Music>,
+    #[serde(default)]
+    pub playlists: Vec<String>,
+    #[serde(default)]
+    pub rules: Option<PlaylistRules>,
     pub created_at: AutoFill,
 }
 
 impl PlayListWriteRequest {
-    #[cfg(test)]
     pub(crate) fn from_playlist(playlist: &PlayList) -> Self {
         Self {
             name: playlist.name.clone(),
@@ -53,6 +115,8 @@ impl PlayListWriteRequest {
                 .collect(),
             groups: playlist.groups.iter().map(PlaylistGroupRef::from).collect(),
             extra: playlist.extra.clone(),
+            playlists: playlist.playlists.clone().unwrap_or_default(),
+            rules: playlist.rules.clone(),
             created_at: playlist.created_at.clone(),
         }
     }
@@ -122,6 +186,17 @@ pub struct Collection {
     pub musics: Vec<Music>,
     pub last_updated: String,
     pub enable_updates: Option<bool>,
+    /// Named save root the collection lives on; `None` is the default root.
+    /// `folder` is relative to that root.
+    #[serde(default)]
+    pub save_root: Option<String>,
+}
+
+impl Collection {
+    /// The collection folder on its own save root.
+    pub fn dir(&self, default_root: &Path) -> PathBuf {
+        collection_dir(default_root, self.save_root.as_deref(), &self.folder)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, SurrealValue, Type)]
@@ -207,9 +282,49 @@ pub struct PlayListConfigView {
     pub groups: Vec<GroupSurfaceView>,
     #[view(nested)]
     pub extra: Vec<Music>,
+    #[serde(default)]
+    pub playlists: Option<Vec<String>>,
+    #[serde(default)]
+    pub rules: Option<PlaylistRules>,
     pub created_at: AutoFill,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistExportFormat {
+    M3u8,
+    Xspf,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct PlaylistExportResult {
+    pub path: String,
+    pub track_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct PlaylistImportResult {
+    pub playlist: PlayListWriteRequest,
+    pub unresolved: Vec<PlaylistImportUnresolvedEntry>,
+    /// Folders holding entries that are not in the library yet; importing
+    /// one is left to the user.
+    pub candidate_folders: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistImportUnresolvedReason {
+    RemoteLocation,
+    MissingFile,
+    NotInLibrary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Type)]
+pub struct PlaylistImportUnresolvedEntry {
+    pub location: String,
+    pub reason: PlaylistImportUnresolvedReason,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct ConfigLibraryView {
     pub collections: Vec<CollectionSurfaceView>,
@@ -260,6 +375,13 @@ pub struct Music {
     pub liked: bool,
     #[serde(default)]
     pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    /// `.lrc` sidecar next to the audio file, in the same form as `path`.
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, SurrealValue, Type)]
@@ -283,6 +405,41 @@ pub struct LoudnessProfile {
     pub model_adjustment_db: Option<f32>,
 }
 
+/// Behavior:
+///   Embedded container tags read from the audio file with the managed FFmpeg.
+///
+/// Core invariants:
+///   - Only non-empty tag values are kept; a file without any usable tag
+///     stores `None` on `Music.tags`, never an empty `MusicTags`.
+///   - `title` is source evidence for title normalization; `Music.name` stays
+///     the displayed identity.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, SurrealValue, Type)]
+pub struct MusicTags {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub album: Option<String>,
+    #[serde(default)]
+    pub genre: Option<String>,
+    #[serde(default)]
+    pub year: Option<u32>,
+    #[serde(default)]
+    pub track_number: Option<u32>,
+}
+
+impl MusicTags {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.artist.is_none()
+            && self.album.is_none()
+            && self.genre.is_none()
+            && self.year.is_none()
+            && self.track_number.is_none()
+    }
+}
+
 impl LoudnessProfile {
     pub fn from_integrated_lufs(integrated_lufs: f32) -> Option<Self> {
         if !is_valid_loudness_evidence(integrated_lufs) {
@@ -348,6 +505,12 @@ pub struct MusicSpectrumView {
     pub liked: bool,
     #[serde(default)]
     pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
 }
 
 impl MusicSpectrumView {
@@ -364,6 +527,9 @@ impl MusicSpectrumView {
             end_ms: self.end_ms,
             liked: self.liked,
             loudness_profile: self.loudness_profile,
+            added_at: self.added_at,
+            tags: self.tags,
+            lyrics_path: self.lyrics_path,
         }
     }
 }
@@ -405,7 +571,10 @@ impl ViewParams for PlaylistRelationPlayableTrackViewParams {
             out.start_ms AS start_ms,
             out.end_ms AS end_ms,
             out.liked AS liked,
-            out.loudness_profile AS loudness_profile
+            out.loudness_profile AS loudness_profile,
+            out.added_at AS added_at,
+            out.tags AS tags,
+            out.lyrics_path AS lyrics_path
         FROM $relation
         WHERE in IN $owner_records
             AND record::tb(out) = $music_table
@@ -432,6 +601,12 @@ pub struct PlaylistRelationPlayableTrackView {
     pub liked: bool,
     #[serde(default)]
     pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -469,7 +644,10 @@ impl ViewParams for RandomPlaylistRelationPlayableTrackViewParams {
             out.start_ms AS start_ms,
             out.end_ms AS end_ms,
             out.liked AS liked,
-            out.loudness_profile AS loudness_profile
+            out.loudness_profile AS loudness_profile,
+            out.added_at AS added_at,
+            out.tags AS tags,
+            out.lyrics_path AS lyrics_path
         FROM $relation
         WHERE in IN $owner_records
             AND record::tb(out) = $music_table
@@ -495,6 +673,12 @@ pub struct RandomPlaylistRelationPlayableTrackView {
     pub liked: bool,
     #[serde(default)]
     pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -526,7 +710,10 @@ impl ViewParams for PlaylistRecordPlayableTrackViewParams {
             start_ms,
             end_ms,
             liked,
-            loudness_profile
+            loudness_profile,
+            added_at,
+            tags,
+            lyrics_path
         FROM $music_table
         WHERE id IN $music_records
             AND path IS NOT NONE
@@ -547,6 +734,131 @@ pub struct PlaylistRecordPlayableTrackView {
     pub liked: bool,
     #[serde(default)]
     pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
+}
+
+/// Rule filters shared by every rule-selected track query;
+/// [`playlist_rule_playable_track_stmt`] appends the ordering and paging.
+const PLAYLIST_RULE_PLAYABLE_TRACK_SQL: &str = r#"
+        SELECT
+            in AS collection_record,
+            in.folder AS collection_folder,
+            in.save_root AS collection_save_root,
+            out AS music_record,
+            position,
+            out.occurrence_id AS occurrence_id,
+            out.name AS name,
+            out.alias AS alias,
+            out.canonical_music_id AS canonical_music_id,
+            out.url AS url,
+            out.path AS path,
+            out.start_ms AS start_ms,
+            out.end_ms AS end_ms,
+            out.liked AS liked,
+            out.loudness_profile AS loudness_profile,
+            out.added_at AS added_at,
+            out.tags AS tags,
+            out.lyrics_path AS lyrics_path
+        FROM includes
+        WHERE record::tb(in) = $collection_table
+            AND record::tb(out) = $music_table
+            AND out.path IS NOT NONE
+            AND ($liked_only = false OR out.liked = true)
+            AND ($liked = NONE OR out.liked = $liked)
+            AND (array::len($collection_urls) = 0 OR in.url IN $collection_urls)
+            AND ($min_integrated_lufs = NONE OR (out.loudness_profile IS NOT NONE
+                AND out.loudness_profile.integrated_lufs >= $min_integrated_lufs))
+            AND ($max_integrated_lufs = NONE OR (out.loudness_profile IS NOT NONE
+                AND out.loudness_profile.integrated_lufs <= $max_integrated_lufs))
+            AND ($min_duration_ms = NONE OR out.end_ms - out.start_ms >= $min_duration_ms)
+            AND ($max_duration_ms = NONE OR out.end_ms - out.start_ms <= $max_duration_ms)
+            AND ($added_after = NONE OR (out.added_at IS NOT NONE AND out.added_at >= $added_after))
+            AND ($added_before = NONE OR (out.added_at IS NOT NONE AND out.added_at < $added_before))
+"#;
+
+/// How the tracks a rule set selects are ordered and paged.
+#[derive(Debug, Clone, Copy)]
+pub enum PlaylistRuleTrackOrder {
+    /// Collection and position order, one page at a time.
+    Library { limit: usize, offset: usize },
+    /// A random sample of at most `limit` tracks.
+    Random { limit: usize },
+}
+
+/**
+ * Behavior:
+ *   Build the statement selecting the playable tracks a rule set matches.
+ *
+ * Core invariants:
+ *   - Ordered paging and random sampling run the same rule filters, so a
+ *     track is eligible in one exactly when it is eligible in the other.
+ *   - Collection urls are trimmed as they are when rules are validated.
+ */
+pub fn playlist_rule_playable_track_stmt(
+    rules: PlaylistRules,
+    liked_only: bool,
+    order: PlaylistRuleTrackOrder,
+) -> RawSqlStmt {
+    let collection_urls = rules
+        .source_collection_urls
+        .iter()
+        .map(|url| url.trim().to_string())
+        .collect::<Vec<_>>();
+    let (tail, limit, offset) = match order {
+        PlaylistRuleTrackOrder::Library { limit, offset } => (
+            "ORDER BY collection_record ASC, position ASC LIMIT $limit START $offset;",
+            limit,
+            offset,
+        ),
+        PlaylistRuleTrackOrder::Random { limit } => ("ORDER BY rand() LIMIT $limit;", limit, 0),
+    };
+
+    RawSqlStmt::new(format!("{PLAYLIST_RULE_PLAYABLE_TRACK_SQL}{tail}"))
+        .bind("collection_table", Collection::table_name().to_string())
+        .bind("music_table", Music::table_name().to_string())
+        .bind("liked_only", liked_only)
+        .bind("liked", rules.liked)
+        .bind("collection_urls", collection_urls)
+        .bind("min_integrated_lufs", rules.min_integrated_lufs)
+        .bind("max_integrated_lufs", rules.max_integrated_lufs)
+        .bind("min_duration_ms", rules.min_duration_ms)
+        .bind("max_duration_ms", rules.max_duration_ms)
+        .bind("added_after", rules.added_after)
+        .bind("added_before", rules.added_before)
+        .bind("limit", limit)
+        .bind("offset", offset)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, SurrealValue)]
+pub struct PlaylistRulePlayableTrackRow {
+    pub collection_record: RecordId,
+    pub collection_folder: String,
+    #[serde(default)]
+    pub collection_save_root: Option<String>,
+    pub music_record: RecordId,
+    pub position: i64,
+    pub occurrence_id: String,
+    pub name: String,
+    pub alias: String,
+    pub canonical_music_id: String,
+    pub url: String,
+    pub path: Option<String>,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub liked: bool,
+    #[serde(default)]
+    pub loudness_profile: Option<LoudnessProfile>,
+    #[serde(default)]
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub tags: Option<MusicTags>,
+    #[serde(default)]
+    pub lyrics_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -585,6 +897,7 @@ impl ViewParams for PlaylistMusicSourceCollectionViewParams {
             in.name AS collection_name,
             in.url AS collection_url,
             in.folder AS collection_folder,
+            in.save_root AS collection_save_root,
             in.last_updated AS collection_last_updated,
             in.enable_updates AS collection_enable_updates,
             position
@@ -601,6 +914,8 @@ pub struct PlaylistMusicSourceCollectionView {
     pub collection_name: String,
     pub collection_url: String,
     pub collection_folder: String,
+    #[serde(default)]
+    pub collection_save_root: Option<String>,
     pub collection_last_updated: String,
     pub collection_enable_updates: Option<bool>,
     pub position: i64,
@@ -666,10 +981,120 @@ pub struct SpectrumMusicContext {
     pub source: Option<SpectrumMusicSourceContext>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct LibrarySearchHit {
+    pub music: Music,
+    pub source: SpectrumMusicSourceContext,
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, SurrealValue, Type)]
+#[serde(rename_all = "snake_case")]
+#[surreal(rename_all = "snake_case")]
+pub enum TrashItemKind {
+    Music,
+    Playlist,
+    Exclude,
+}
+
+/// A deleted music occurrence set, playlist or exclude that can still be
+/// restored until `expires_at`.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct TrashItem {
+    pub id: String,
+    pub kind: TrashItemKind,
+    pub label: String,
+    pub deleted_at: String,
+    pub expires_at: String,
+}
+
+/// One music occurrence inside a collection, addressed by its source range.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Type)]
+pub struct MusicRangeRef {
+    pub url: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+}
+
+impl MusicRangeRef {
+    pub fn matches(&self, music: &Music) -> bool {
+        self.url == music.url && self.start_ms == music.start_ms && self.end_ms == music.end_ms
+    }
+}
+
+/// One edit of a bulk library edit, addressed by the music identity it
+/// applies to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Type)]
+pub struct MusicBulkEdit {
+    pub url: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub action: MusicBulkEditAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MusicBulkEditAction {
+    SetLiked {
+        liked: bool,
+    },
+    /// Excludes the music from `playlists`, or from every playlist when empty.
+    Exclude {
+        #[serde(default)]
+        expires_at: Option<String>,
+        #[serde(default)]
+        playlists: Vec<String>,
+    },
+    SetAlias {
+        alias: String,
+    },
+    SetRange {
+        start_ms: u32,
+        end_ms: u32,
+    },
+    /// Moves the occurrence in `collection_url` to one of its groups.
+    MoveToGroup {
+        collection_url: String,
+        group_url: String,
+    },
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MusicBulkEditStatus {
+    Applied,
+    NotFound,
+    Failed,
+    /// Applied, then undone because a later edit of the batch failed.
+    RolledBack,
+    /// Not attempted because an earlier edit of the batch failed.
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MusicBulkEditResult {
+    pub url: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+    pub status: MusicBulkEditStatus,
+    pub error: Option<String>,
+    /// The edited music; `None` for deletes and edits that were not applied.
+    pub music: Option<Music>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, SurrealValue, Store, Type)]
 pub struct Exclude {
     #[foreign]
     pub music: Music,
+    /// RFC 3339 instant after which the exclude lifts itself; `None` keeps it
+    /// until it is removed.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Playlists the exclude is limited to; empty excludes the music from
+    /// every playlist.
+    #[serde(default)]
+    pub playlists: Vec<String>,
     #[pagin]
     #[fill(now)]
     pub created_at: AutoFill,
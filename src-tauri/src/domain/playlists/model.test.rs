@@ -357,6 +357,9 @@ fn sample_collection(
                 end_ms: 42_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
             Music {
                 occurrence_id: String::new(),
@@ -374,10 +377,14 @@ fn sample_collection(
                 end_ms: 84_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
         ],
         last_updated: "2026-04-12T12:00:00+00:00".to_string(),
         enable_updates,
+        save_root: None,
     }
 }
 
@@ -426,6 +433,8 @@ fn sample_playlist() -> PlayList {
             },
         ],
         extra: vec![],
+        playlists: None,
+        rules: None,
 
         created_at: AutoFill::pending(),
     }
@@ -1,22 +1,27 @@
 use super::model::{
     AddExcludeResult, Collection, CollectionGroupMembershipView, CollectionGroupOwner,
     CollectionSurfaceView, ConfigLibraryView, Exclude, ExcludeAvailability, Group,
-    GroupSurfaceView, LoudnessProfile, Music, MusicSpectrumView, PlayList, PlayListConfigView,
-    PlayListListView, PlayListWriteRequest, PlaylistCollectionRef, PlaylistGroupRef,
-    PlaylistMusicGroupView, PlaylistMusicGroupViewParams, PlaylistMusicSourceCollectionView,
-    PlaylistMusicSourceCollectionViewParams, PlaylistRecordPlayableTrackView,
-    PlaylistRecordPlayableTrackViewParams, PlaylistRelationPlayableTrackView,
-    PlaylistRelationPlayableTrackViewParams, RandomPlaylistRelationPlayableTrackView,
-    RandomPlaylistRelationPlayableTrackViewParams, RemoveExcludeResult, SpectrumMusicContext,
-    SpectrumMusicSourceContext, canonical_music_id_for_source,
+    GroupSurfaceView, LoudnessProfile, Music, MusicRangeRef, MusicSpectrumView, PlayList,
+    PlayListConfigView, PlayListListView, PlayListWriteRequest, PlaylistCollectionRef,
+    PlaylistGroupRef, PlaylistMusicGroupView, PlaylistMusicGroupViewParams,
+    PlaylistMusicSourceCollectionView, PlaylistMusicSourceCollectionViewParams,
+    PlaylistRecordPlayableTrackView, PlaylistRecordPlayableTrackViewParams,
+    PlaylistRelationPlayableTrackView, PlaylistRelationPlayableTrackViewParams,
+    PlaylistRulePlayableTrackRow, PlaylistRuleTrackOrder, PlaylistRules,
+    RandomPlaylistRelationPlayableTrackView, RandomPlaylistRelationPlayableTrackViewParams,
+    RemoveExcludeResult, SpectrumMusicContext, SpectrumMusicSourceContext,
+    canonical_music_id_for_source, playlist_rule_playable_track_stmt,
 };
+use crate::domain::meta::model::{collection_dir, save_root_dir};
 use anyhow::{Result, bail};
 use appdb::connection::get_db;
 use appdb::error::{DBError, classify_db_error};
 use appdb::graph;
 use appdb::model::meta::{ModelMeta, ResolveRecordId};
+use appdb::query::query_bound_take;
 use appdb::repository::Repo;
 use appdb::{AutoFill, Crud, Id, Order, Store};
+use chrono::{DateTime, Utc};
 use rand::RngExt;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -201,14 +206,72 @@ pub async fn list_config_library() -> Result<ConfigLibraryView> {
     })
 }
 
-pub async fn add_exclude(music: Music) -> Result<AddExcludeResult> {
+/// Excludes a music identity from playback everywhere, until `expires_at`
+/// when given.
+pub async fn add_exclude(music: Music, expires_at: Option<String>) -> Result<AddExcludeResult> {
+    add_scoped_exclude(music, expires_at, vec![]).await
+}
+
+/// Excludes a music identity only while `playlist_name` plays.
+pub async fn add_playlist_exclude(
+    music: Music,
+    playlist_name: &str,
+    expires_at: Option<String>,
+) -> Result<AddExcludeResult> {
+    add_scoped_exclude(music, expires_at, vec![playlist_name.to_string()]).await
+}
+
+/**
+ * Behavior:
+ *   Exclude a music identity from the given playlists, or from every
+ *   playlist when `playlists` is empty.
+ *
+ * Core invariants:
+ *   - One exclude row exists per music identity. Scopes of an active scoped
+ *     exclude are merged; an active global exclude is kept as it is.
+ *   - Only global excludes count toward exclude availability.
+ */
+pub async fn add_scoped_exclude(
+    music: Music,
+    expires_at: Option<String>,
+    playlists: Vec<String>,
+) -> Result<AddExcludeResult> {
+    let expires_at = expires_at
+        .as_deref()
+        .map(|expires_at| normalize_exclude_expiry(expires_at, Utc::now()))
+        .transpose()?;
     let music = with_music_occurrence_id(music);
-    let record = exclude_record_id(&music);
+    let id = exclude_record_id(&music);
+    let record = RecordId::new(StoredExclude::table_name(), id.to_string());
+
+    let mut scope = Vec::new();
+    if !playlists.is_empty() {
+        match load_exclude_state(record.clone()).await? {
+            Some(state) if state.is_active(Utc::now()) && state.is_global() => {
+                return Ok(AddExcludeResult {
+                    exclude: Repo::<StoredExclude>::get_record(record)
+                        .await?
+                        .into_public(),
+                    exclude_availability: load_exclude_availability().await?,
+                });
+            }
+            Some(state) if state.is_active(Utc::now()) => scope = state.playlists(),
+            _ => {}
+        }
+        for name in playlists {
+            if !scope.contains(&name) {
+                scope.push(name);
+            }
+        }
+    }
+
     let saved = Repo::<StoredExclude>::upsert_at(
-        RecordId::new(StoredExclude::table_name(), record.to_string()),
+        record,
         StoredExclude {
-            id: record,
+            id,
             music: music.clone(),
+            expires_at,
+            playlists: scope,
             created_at: AutoFill::pending(),
         },
     )
@@ -235,6 +298,45 @@ pub async fn set_music_liked_by_identity(
     apply_music_mutation_to_records(&records, MusicMutation::Liked { liked }).await
 }
 
+/**
+ * Behavior:
+ *   Point the occurrences of one music identity inside one collection at a
+ *   different audio file.
+ *
+ * Core invariants:
+ *   - Occurrences of the same identity in other collections keep their path,
+ *     because `path` is resolved against each collection's own folder.
+ */
+pub async fn set_collection_music_path_by_identity(
+    collection_url: &str,
+    url: &str,
+    start_ms: u32,
+    end_ms: u32,
+    path: &str,
+) -> Result<Option<Music>> {
+    ensure_collection_graph_schema().await?;
+
+    let mut records = Vec::new();
+    for record in find_music_record_ids_by_identity(url, start_ms, end_ms).await? {
+        for collection in load_music_parent_collection_ids(&record).await? {
+            if let Some(row) = load_collection_shell_row(&collection).await?
+                && row.url == collection_url
+            {
+                records.push(record.clone());
+                break;
+            }
+        }
+    }
+
+    apply_music_mutation_to_records(
+        &records,
+        MusicMutation::FilePath {
+            path: path.to_string(),
+        },
+    )
+    .await
+}
+
 pub async fn set_music_loudness_profile_by_identity(
     url: &str,
     start_ms: u32,
@@ -329,6 +431,95 @@ pub async fn is_music_identity_excluded_for_playback(
     is_music_canonical_id_excluded(&canonical_music_id).await
 }
 
+/// Like [`is_music_identity_excluded_for_playback`], also counting excludes
+/// scoped to `playlist_name`.
+pub async fn is_music_identity_excluded_for_playlist(
+    url: &str,
+    start_ms: u32,
+    end_ms: u32,
+    playlist_name: &str,
+) -> Result<bool> {
+    ensure_collection_graph_schema().await?;
+    let canonical_music_id = canonical_music_id_for_source(url, start_ms, end_ms);
+    is_music_canonical_id_excluded_for_playlist(&canonical_music_id, playlist_name).await
+}
+
+/// The exclude covering the identity of `music`, expired or not.
+pub async fn get_exclude(music: &Music) -> Result<Option<Exclude>> {
+    let record = RecordId::new(
+        StoredExclude::table_name(),
+        exclude_record_id(music).to_string(),
+    );
+    match Repo::<StoredExclude>::get_record(record).await {
+        Ok(exclude) => Ok(Some(exclude.into_public())),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) | DBError::NotFound => Ok(None),
+            other => Err(other.into()),
+        },
+    }
+}
+
+/**
+ * Behavior:
+ *   Lift every timed exclude whose expiry is at or before `now` and return
+ *   the music that became playable again.
+ *
+ * Core invariants:
+ *   - Exclude availability of the owners of each lifted music is refreshed.
+ */
+pub async fn lift_expired_excludes(now: DateTime<Utc>) -> Result<Vec<Music>> {
+    let excludes = match StoredExclude::list().await {
+        Ok(excludes) => excludes,
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    let mut lifted = Vec::new();
+    for exclude in excludes {
+        if !exclude
+            .expires_at
+            .as_deref()
+            .is_some_and(|expires_at| exclude_expiry_passed(expires_at, now))
+        {
+            continue;
+        }
+
+        let record = RecordId::new(StoredExclude::table_name(), exclude.id.to_string());
+        match Repo::<StoredExclude>::delete_record(record).await {
+            Ok(()) => {}
+            Err(error) => match classify_db_error(&error) {
+                DBError::MissingTable(_) | DBError::NotFound => continue,
+                other => return Err(other.into()),
+            },
+        }
+        refresh_exclude_availability_for_music_identity(&exclude.music).await?;
+        lifted.push(exclude.music);
+    }
+
+    Ok(lifted)
+}
+
+/// Earliest expiry among the timed excludes, so the sweep can run right when
+/// it passes; unreadable expiries are skipped here and lifted by any sweep.
+pub async fn next_exclude_expiry() -> Result<Option<DateTime<Utc>>> {
+    let excludes = match StoredExclude::list().await {
+        Ok(excludes) => excludes,
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => return Ok(None),
+            other => return Err(other.into()),
+        },
+    };
+
+    Ok(excludes
+        .iter()
+        .filter_map(|exclude| exclude.expires_at.as_deref())
+        .filter_map(|expires_at| DateTime::parse_from_rfc3339(expires_at).ok())
+        .map(|expires_at| expires_at.with_timezone(&Utc))
+        .min())
+}
+
 pub async fn remove_exclude(music: &Music) -> Result<RemoveExcludeResult> {
     let record = RecordId::new(
         StoredExclude::table_name(),
@@ -431,6 +622,7 @@ pub struct PlaylistPlaybackSelection {
     pub collections: Vec<PlaylistPlaybackCollectionRef>,
     pub groups: Vec<PlaylistPlaybackGroupRef>,
     pub extra: Vec<PlaylistPlaybackExtraRef>,
+    pub rules: Vec<PlaylistRules>,
     pub download_scopes: Vec<String>,
 }
 
@@ -448,6 +640,7 @@ pub struct PlaylistPlaybackCollectionRef {
     name: String,
     pub url: String,
     pub folder: String,
+    save_root: Option<String>,
     last_updated: String,
     enable_updates: Option<bool>,
 }
@@ -463,6 +656,7 @@ impl PlaylistPlaybackCollectionRef {
             name: _name.to_string(),
             url: url.to_string(),
             folder: folder.to_string(),
+            save_root: None,
             last_updated: String::new(),
             enable_updates: None,
         }
@@ -489,6 +683,7 @@ struct PlaylistPlaybackSourceCollectionRef {
     record: RecordId,
     owner: CollectionGroupOwner,
     folder: String,
+    save_root: Option<String>,
 }
 
 impl From<&PlaylistPlaybackCollectionRef> for PlaylistPlaybackSourceCollectionRef {
@@ -497,6 +692,7 @@ impl From<&PlaylistPlaybackCollectionRef> for PlaylistPlaybackSourceCollectionRe
             record: value.record.clone(),
             owner: value.as_group_owner(),
             folder: value.folder.clone(),
+            save_root: value.save_root.clone(),
         }
     }
 }
@@ -560,6 +756,9 @@ impl PlaylistPlaybackExtraRef {
 }
 
 impl PlaylistPlaybackSelection {
+    /// Whether a collection, group or extra ref of this selection holds the
+    /// source. Rule sets are not evaluated here; callers re-resolve rule
+    /// playlists through their rule query.
     #[cfg(test)]
     pub fn contains_track_source(&self, source: &PlaylistPlaybackTrackSource) -> bool {
         self.collections
@@ -581,7 +780,8 @@ impl PlaylistPlaybackSelection {
  *   Project a committed playlist row into the stable playback selection domain.
  *
  * Core invariants:
- *   - The playlist row is the only source of selected collection/group/extra refs.
+ *   - The playlist row and the playlists it includes are the only sources of
+ *     selected collection/group/extra refs and smart rule sets.
  *   - Download readiness is represented by explicit collection URL scopes
  *     owned by this projection, not inferred by downloads or UI fallback.
  *   - Group-only selections carry parent collection scopes when persisted
@@ -599,6 +799,8 @@ fn push_unique_download_scope(scopes: &mut Vec<String>, url: &str) {
 #[derive(Debug, Clone)]
 pub struct PlaylistPlaybackTrackSource {
     pub collection_folder: String,
+    /// Named save root of the collection; `None` is the default root.
+    pub collection_save_root: Option<String>,
     pub music: Music,
 }
 
@@ -610,18 +812,37 @@ pub async fn get_playlist_playback_selection_by_name(
     let Some(row) = load_playlist_playback_row_by_name(name).await? else {
         return Ok(None);
     };
+    let playlist_name = row.name.clone();
+    let rows = expand_included_playlist_playback_rows(row).await?;
+
+    let mut collection_records = Vec::new();
+    let mut group_records = Vec::new();
+    let mut extra_records = Vec::new();
+    let mut rule_sets = Vec::new();
+    for row in rows {
+        collection_records.extend(row.collections);
+        group_records.extend(row.groups);
+        extra_records.extend(row.extra);
+        if let Some(rules) = row.rules
+            && !rule_sets.contains(&rules)
+        {
+            rule_sets.push(rules);
+        }
+    }
 
-    let mut collections = Vec::with_capacity(row.collections.len());
+    let collection_records = unique_record_ids(&collection_records);
+    let mut collections = Vec::with_capacity(collection_records.len());
     let mut download_scopes = Vec::new();
-    for record in row.collections {
+    for record in collection_records {
         if let Some(collection) = load_playlist_playback_collection_ref(&record).await? {
             push_unique_download_scope(&mut download_scopes, &collection.url);
             collections.push(collection);
         }
     }
 
-    let mut groups = Vec::with_capacity(row.groups.len());
-    for record in row.groups {
+    let group_records = unique_record_ids(&group_records);
+    let mut groups = Vec::with_capacity(group_records.len());
+    for record in group_records {
         if let Some(group) = load_playlist_playback_group_ref(&record).await? {
             push_unique_download_scope(&mut download_scopes, &group.url);
             for url in load_group_parent_collection_urls(&group).await? {
@@ -631,21 +852,76 @@ pub async fn get_playlist_playback_selection_by_name(
         }
     }
 
-    let extra = row
-        .extra
+    let extra = unique_record_ids(&extra_records)
         .into_iter()
         .map(|record| PlaylistPlaybackExtraRef { record })
         .collect();
 
+    for rules in &rule_sets {
+        for url in &rules.source_collection_urls {
+            push_unique_download_scope(&mut download_scopes, url.trim());
+        }
+    }
+
     Ok(Some(PlaylistPlaybackSelection {
-        playlist_name: row.name,
+        playlist_name,
         collections,
         groups,
         extra,
+        rules: rule_sets,
         download_scopes,
     }))
 }
 
+/**
+ * Behavior:
+ *   Expand a playlist row into itself followed by every playlist it includes,
+ *   depth first in declaration order.
+ *
+ * Core invariants:
+ *   - Each playlist contributes once, so shared base playlists do not
+ *     duplicate refs or rule sets.
+ *   - A reference back into the active include path is a cycle. It is logged
+ *     and skipped; writes reject cycles, so this only guards rows that became
+ *     cyclic outside the validated write path.
+ *   - Missing included playlists contribute nothing.
+ */
+async fn expand_included_playlist_playback_rows(
+    root: PlaylistPlaybackRow,
+) -> Result<Vec<PlaylistPlaybackRow>> {
+    let mut visited = HashSet::from([root.name.clone()]);
+    let mut path = vec![(root.name.clone(), root.playlists.clone(), 0usize)];
+    let mut rows = vec![root];
+
+    while let Some((parent, includes, next)) = path.last_mut() {
+        let Some(include) = includes.get(*next).cloned() else {
+            path.pop();
+            continue;
+        };
+        *next += 1;
+        let parent = parent.clone();
+
+        if path.iter().any(|(name, _, _)| *name == include) {
+            log::warn!(
+                target: "playlists",
+                "playlist_include_cycle_skipped playlist=\"{parent}\" include=\"{include}\""
+            );
+            continue;
+        }
+        if !visited.insert(include.clone()) {
+            continue;
+        }
+        let Some(row) = load_playlist_playback_row_by_name(&include).await? else {
+            continue;
+        };
+
+        path.push((row.name.clone(), row.playlists.clone(), 0));
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
 pub async fn load_playlist_playback_track_sources(
     selection: &PlaylistPlaybackSelection,
     limit: usize,
@@ -676,6 +952,8 @@ async fn load_playlist_playback_track_sources_by_filter(
         .await?;
     append_group_playback_track_sources(selection, limit, liked_only, &mut seen, &mut sources)
         .await?;
+    append_rule_playback_track_sources(selection, limit, liked_only, &mut seen, &mut sources)
+        .await?;
     append_extra_playback_track_sources(selection, limit, liked_only, &mut seen, &mut sources)
         .await?;
 
@@ -690,7 +968,10 @@ async fn load_playlist_playback_track_sources_by_filter(
  *
  * Core invariants:
  *   - The stable input domain is `PlaylistPlaybackSelection`; no fallback or
- *     cache may widen membership outside its collection/group/extra refs.
+ *     cache may widen membership outside its collection/group/extra refs and
+ *     stored smart rules.
+ *   - Each smart rule set is one explicit owner domain, sampled by the
+ *     database against its rule predicates like any other owner.
  *   - Collection and group owners are sampled as lightweight refs; music rows
  *     are loaded only inside the selected owner being probed.
  *   - `extra` is one explicit owner domain. Selecting it then samples its
@@ -733,6 +1014,7 @@ pub async fn load_random_playlist_playback_track_sources(
         if owner_index < selection.collections.len() {
             append_random_collection_playback_track_sources(
                 &selection.collections[owner_index],
+                &selection.playlist_name,
                 owner_source_limit,
                 &mut seen,
                 &mut sources,
@@ -742,6 +1024,19 @@ pub async fn load_random_playlist_playback_track_sources(
             let group_index = owner_index - selection.collections.len();
             append_random_group_playback_track_sources(
                 &selection.groups[group_index],
+                &selection.playlist_name,
+                owner_source_limit,
+                &mut seen,
+                &mut sources,
+            )
+            .await?;
+        } else if let Some(rules) = owner_index
+            .checked_sub(selection.collections.len() + selection.groups.len())
+            .and_then(|rule_index| selection.rules.get(rule_index))
+        {
+            append_random_rule_playback_track_sources(
+                rules,
+                &selection.playlist_name,
                 owner_source_limit,
                 &mut seen,
                 &mut sources,
@@ -762,7 +1057,10 @@ pub async fn load_random_playlist_playback_track_sources(
 }
 
 fn playlist_playback_random_owner_count(selection: &PlaylistPlaybackSelection) -> usize {
-    selection.collections.len() + selection.groups.len() + usize::from(!selection.extra.is_empty())
+    selection.collections.len()
+        + selection.groups.len()
+        + selection.rules.len()
+        + usize::from(!selection.extra.is_empty())
 }
 
 fn playlist_playback_owner_probe_limit(owner_count: usize, source_limit: usize) -> usize {
@@ -821,9 +1119,75 @@ pub async fn delete_playlist_by_name(name: &str) -> Result<bool> {
     };
 
     Repo::<PlayList>::delete_record(record).await?;
+    replace_included_playlist_refs(name, None).await?;
+    replace_exclude_playlist_scope(name, None).await?;
     Ok(true)
 }
 
+/**
+ * Behavior:
+ *   List every playlist that includes `name` directly or through other
+ *   included playlists.
+ *
+ * Core invariants:
+ *   - The result never contains `name` and lists each dependent once, even
+ *     when stored includes are cyclic.
+ */
+pub async fn list_dependent_playlist_names(name: &str) -> Result<Vec<String>> {
+    let mut visited = HashSet::from([name.to_string()]);
+    let mut pending = vec![name.to_string()];
+    let mut dependents = Vec::new();
+    while let Some(included) = pending.pop() {
+        for row in load_playlist_include_rows(&included).await? {
+            if visited.insert(row.name.clone()) {
+                pending.push(row.name.clone());
+                dependents.push(row.name);
+            }
+        }
+    }
+
+    Ok(dependents)
+}
+
+/**
+ * Behavior:
+ *   List the playlists that include `name` directly.
+ */
+pub async fn list_including_playlist_names(name: &str) -> Result<Vec<String>> {
+    Ok(load_playlist_include_rows(name)
+        .await?
+        .into_iter()
+        .map(|row| row.name)
+        .collect())
+}
+
+/**
+ * Behavior:
+ *   Append `included` to the include list of every named playlist that still
+ *   exists.
+ *
+ * Core invariants:
+ *   - Include lists never name the same playlist twice.
+ */
+pub async fn add_included_playlist_ref(included: &str, playlist_names: &[String]) -> Result<()> {
+    let db = get_db()?;
+    for name in playlist_names {
+        let Some(record) = find_unique_record_id_by_string_field::<PlayList>("name", name).await?
+        else {
+            continue;
+        };
+        db.query(
+            "UPDATE ONLY $record SET playlists = array::union(playlists ?? [], [$included]) RETURN NONE;",
+        )
+        .bind(("record", record))
+        .bind(("included", included.to_string()))
+        .await?
+        .check()?;
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct PlaylistSurfaceUpsertResult {
     pub playlist: PlayListListView,
@@ -859,6 +1223,8 @@ pub async fn upsert_playlist_surface(
     playlist: &PlayListWriteRequest,
     previous_name: Option<&str>,
 ) -> Result<PlaylistSurfaceUpsertResult> {
+    ensure_playlist_rules_valid(playlist)?;
+    ensure_playlist_includes_valid(playlist, previous_name).await?;
     let foreign_ids = resolve_playlist_foreign_record_ids(playlist).await?;
     let storage = playlist_surface_storage_row_from_request(playlist);
     let existing_record = match previous_name {
@@ -869,8 +1235,12 @@ pub async fn upsert_playlist_surface(
         Some(record) => load_playlist_playback_row_by_record(record).await?,
         None => None,
     };
-    let playback_selection_changed =
-        playlist_playback_foreign_refs_changed(previous_playback_row.as_ref(), &foreign_ids);
+    let playback_selection_changed = playlist_playback_foreign_refs_changed(
+        previous_playback_row.as_ref(),
+        &foreign_ids,
+        &playlist.playlists,
+        playlist.rules.as_ref(),
+    );
     let record = match existing_record.clone() {
         Some(record) => record,
         None => resolve_playlist_create_record_id(&playlist.name).await?,
@@ -893,12 +1263,77 @@ pub async fn upsert_playlist_surface(
                 .await?
         }
     };
+    if let Some(previous_name) = previous_name
+        && previous_name != playlist.name
+    {
+        replace_included_playlist_refs(previous_name, Some(&playlist.name)).await?;
+        replace_exclude_playlist_scope(previous_name, Some(&playlist.name)).await?;
+    }
     Ok(PlaylistSurfaceUpsertResult {
         playlist,
         playback_selection_changed,
     })
 }
 
+fn ensure_playlist_rules_valid(playlist: &PlayListWriteRequest) -> Result<()> {
+    if let Some(rules) = playlist.rules.as_ref()
+        && !rules.is_valid()
+    {
+        bail!(
+            "playlist `{}` rules must use finite, ordered ranges and non-empty collection urls",
+            playlist.name
+        );
+    }
+
+    Ok(())
+}
+
+/**
+ * Behavior:
+ *   Reject playlist includes that name missing playlists or would make the
+ *   include graph cyclic.
+ *
+ * Core invariants:
+ *   - Both the written name and the name being renamed from count as the
+ *     playlist itself, so a rename cannot close a cycle through stale refs.
+ */
+async fn ensure_playlist_includes_valid(
+    playlist: &PlayListWriteRequest,
+    previous_name: Option<&str>,
+) -> Result<()> {
+    let is_self = |name: &str| name == playlist.name || previous_name == Some(name);
+    for include in &playlist.playlists {
+        if is_self(include) {
+            bail!("playlist `{}` cannot include itself", playlist.name);
+        }
+        let Some(row) = load_playlist_playback_row_by_name(include).await? else {
+            bail!(
+                "playlist `{}` cannot include missing playlist `{include}`",
+                playlist.name
+            );
+        };
+
+        let mut visited = HashSet::from([row.name]);
+        let mut pending = row.playlists;
+        while let Some(name) = pending.pop() {
+            if is_self(&name) {
+                bail!(
+                    "playlist `{}` cannot include `{include}` because it would create an include cycle",
+                    playlist.name
+                );
+            }
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            if let Some(row) = load_playlist_playback_row_by_name(&name).await? {
+                pending.extend(row.playlists);
+            }
+        }
+    }
+
+    Ok(())
+}
+
 async fn resolve_playlist_create_record_id(name: &str) -> Result<RecordId> {
     if find_unique_record_id_by_string_field::<PlayList>("name", name)
         .await?
@@ -938,6 +1373,8 @@ fn playlist_surface_storage_row_from_request(playlist: &PlayListWriteRequest) ->
         collections: Vec::new(),
         groups: Vec::new(),
         extra: Vec::new(),
+        playlists: (!playlist.playlists.is_empty()).then(|| playlist.playlists.clone()),
+        rules: playlist.rules.clone(),
         created_at: playlist.created_at.clone(),
     }
 }
@@ -978,8 +1415,866 @@ pub async fn remove_extra(
         return get_playlist_config_by_name(playlist_name).await;
     }
 
-    update_playlist_extra_record_ids(&record, &extra).await?;
-    get_playlist_config_by_name(playlist_name).await
+    update_playlist_extra_record_ids(&record, &extra).await?;
+    get_playlist_config_by_name(playlist_name).await
+}
+
+/// Playlists pinning one music occurrence in `extra`, each with the index of
+/// the pin in its list.
+pub async fn list_playlist_extra_pins(music: &Music) -> Result<Vec<(String, usize)>> {
+    let music_record = with_music_occurrence_id(music.clone())
+        .resolve_record_id()
+        .await?;
+    let mut pins = Vec::new();
+    for playlist_record in load_playlist_ids_containing_extra_record(&music_record).await? {
+        let Some(row) = load_playlist_playback_row_by_record(&playlist_record).await? else {
+            continue;
+        };
+        if let Some(position) = row.extra.iter().position(|record| record == &music_record) {
+            pins.push((row.name, position));
+        }
+    }
+
+    Ok(pins)
+}
+
+/// Pins music in a playlist's `extra` at `position`, clamped to the list.
+/// Returns false when the playlist is gone; an existing pin is kept as is.
+pub async fn insert_extra_at(playlist_name: &str, music: &Music, position: usize) -> Result<bool> {
+    let Some(record) =
+        find_unique_record_id_by_string_field::<PlayList>("name", playlist_name).await?
+    else {
+        return Ok(false);
+    };
+
+    let music_record = with_music_occurrence_id(music.clone())
+        .resolve_record_id()
+        .await?;
+    let mut extra = load_playlist_extra_record_ids(&record).await?;
+    if !extra.contains(&music_record) {
+        extra.insert(position.min(extra.len()), music_record);
+        update_playlist_extra_record_ids(&record, &extra).await?;
+    }
+    Ok(true)
+}
+
+/// Names of the named save roots that still hold at least one collection.
+pub async fn list_collection_save_root_names() -> Result<Vec<String>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT VALUE save_root FROM $table WHERE save_root != NONE;")
+        .bind(("table", Table::from(Collection::table_name())))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(vec![]),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    let mut names: Vec<String> = result.take(0)?;
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Named save root of every collection that is not on the default root.
+async fn load_collection_save_roots() -> Result<HashMap<RecordId, String>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT id, save_root FROM $table WHERE save_root != NONE;")
+        .bind(("table", Table::from(Collection::table_name())))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(HashMap::new()),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(HashMap::new()),
+            other => return Err(other.into()),
+        },
+    };
+
+    let rows: Vec<CollectionSaveRootRow> = result.take(0)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.id, row.save_root))
+        .collect())
+}
+
+/**
+ * Behavior:
+ *   List the groups a collection owns through `Collection -> include ->
+ *   Group`, including groups that hold no music yet.
+ */
+pub async fn list_collection_groups(collection_url: &str) -> Result<Vec<Group>> {
+    let Some(record) =
+        find_unique_record_id_by_string_field::<Collection>("url", collection_url).await?
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut groups = Vec::new();
+    for group_record in load_relation_out_ids("include", &record, Group::table_name()).await? {
+        match Repo::<Group>::get_record(group_record).await {
+            Ok(group) => groups.push(group),
+            Err(error) => match classify_db_error(&error) {
+                DBError::MissingTable(_) | DBError::NotFound => {}
+                other => return Err(other.into()),
+            },
+        }
+    }
+    groups.sort_by(|left, right| left.name.cmp(&right.name));
+    Ok(groups)
+}
+
+/**
+ * Behavior:
+ *   Store a new group under an existing collection.
+ *
+ * Core invariants:
+ *   - Group urls are unique across the library.
+ */
+pub async fn create_collection_group(collection_url: &str, group: Group) -> Result<Group> {
+    ensure_collection_graph_schema().await?;
+
+    let Some(collection) = get_collection_by_url(collection_url).await? else {
+        bail!("collection `{collection_url}` not found");
+    };
+    if group.url == collection.url
+        || find_unique_record_id_by_string_field::<Group>("url", &group.url)
+            .await?
+            .is_some()
+    {
+        bail!("group `{}` already exists", group.url);
+    }
+
+    Repo::<Group>::create(group.bind_collection(&collection)).await
+}
+
+pub async fn group_url_exists(group_url: &str) -> Result<bool> {
+    ensure_collection_graph_schema().await?;
+    Ok(
+        find_unique_record_id_by_string_field::<Group>("url", group_url)
+            .await?
+            .is_some(),
+    )
+}
+
+/// Renames a stored group in place; its url, folder and music keep pointing
+/// at the same record.
+pub async fn rename_group(group_url: &str, name: &str) -> Result<Option<Group>> {
+    let Some(record) = find_unique_record_id_by_string_field::<Group>("url", group_url).await?
+    else {
+        return Ok(None);
+    };
+
+    let mut group = Repo::<Group>::get_record(record.clone()).await?;
+    group.name = name.to_string();
+    Ok(Some(Repo::<Group>::update_at(record, group).await?))
+}
+
+/**
+ * Behavior:
+ *   Move the referenced music occurrences of one collection into another
+ *   group of the same collection.
+ *
+ * Core invariants:
+ *   - The target is the collection itself or one of its groups.
+ *   - Occurrence ids follow the group, so playlist extras that pinned a moved
+ *     occurrence are repointed at its new record.
+ *   - A move that would collide with an occurrence already in the target
+ *     group is rejected.
+ */
+pub async fn move_collection_musics_to_group(
+    collection_url: &str,
+    musics: &[MusicRangeRef],
+    group_url: &str,
+) -> Result<Option<Collection>> {
+    let _collection_write = acquire_collection_write_composition_lock().await;
+    move_collection_musics_to_group_locked(collection_url, musics, group_url).await
+}
+
+/// `move_collection_musics_to_group` for callers already holding the
+/// composition lock.
+pub(crate) async fn move_collection_musics_to_group_locked(
+    collection_url: &str,
+    musics: &[MusicRangeRef],
+    group_url: &str,
+) -> Result<Option<Collection>> {
+    let Some(mut collection) = get_collection_by_url(collection_url).await? else {
+        return Ok(None);
+    };
+    let target = if group_url == collection.url {
+        Group {
+            name: collection.name.clone(),
+            url: collection.url.clone(),
+            collection: CollectionGroupOwner::from(&collection),
+            folder: collection.folder.clone(),
+        }
+    } else {
+        list_collection_groups(collection_url)
+            .await?
+            .into_iter()
+            .find(|group| group.url == group_url)
+            .ok_or_else(|| {
+                anyhow::anyhow!("group `{group_url}` does not belong to `{collection_url}`")
+            })?
+    };
+
+    let mut moved = Vec::new();
+    for music in &mut collection.musics {
+        if music.group.url == target.url || !musics.iter().any(|range| range.matches(music)) {
+            continue;
+        }
+        let previous_occurrence_id = music.occurrence_id.clone();
+        music.group = target.clone();
+        assign_music_occurrence_id(music);
+        moved.push((previous_occurrence_id, music.occurrence_id.clone()));
+    }
+    if moved.is_empty() {
+        return Ok(Some(collection));
+    }
+    let mut occurrence_ids = HashSet::new();
+    if !collection
+        .musics
+        .iter()
+        .all(|music| occurrence_ids.insert(music.occurrence_id.clone()))
+    {
+        bail!(
+            "group `{}` already holds one of the moved musics",
+            target.url
+        );
+    }
+
+    let mut moved_records = Vec::new();
+    for (previous_occurrence_id, next_occurrence_id) in moved {
+        if let Some(record) =
+            find_unique_record_id_by_string_field::<Music>("occurrence_id", &previous_occurrence_id)
+                .await?
+        {
+            moved_records.push((record, next_occurrence_id));
+        }
+    }
+    let saved = upsert_collection(&collection).await?;
+    for (previous_record, next_occurrence_id) in moved_records {
+        if let Some(next_record) =
+            find_unique_record_id_by_string_field::<Music>("occurrence_id", &next_occurrence_id)
+                .await?
+        {
+            replace_playlist_extra_record_refs(&previous_record, &next_record).await?;
+        }
+    }
+
+    Ok(Some(saved))
+}
+
+/**
+ * Behavior:
+ *   Split every occurrence of one music range at the given cut points, and
+ *   return the pieces of the first occurrence in range order.
+ *
+ * Core invariants:
+ *   - Cut points must increase and fall strictly inside the range; anything
+ *     else is rejected before a write.
+ *   - Pieces keep the group, liked flag and loudness evidence of the
+ *     occurrence they replace, and get their own canonical id.
+ *   - Every owning collection is planned and checked before the first write,
+ *     and all writes happen under the composition lock.
+ *   - The split is all or nothing: when a write fails, every collection and
+ *     playlist extra list already rewritten is restored from its snapshot.
+ *   - Playlist extras that pinned a split occurrence list all of its pieces
+ *     in its place.
+ */
+pub async fn split_music(
+    url: &str,
+    start_ms: u32,
+    end_ms: u32,
+    cut_points: &[u32],
+) -> Result<Option<Vec<Music>>> {
+    if cut_points.is_empty() {
+        bail!("a music split needs at least one cut point");
+    }
+    let mut bounds = Vec::with_capacity(cut_points.len() + 2);
+    bounds.push(start_ms);
+    for cut_ms in cut_points {
+        if bounds.last().is_some_and(|previous| previous >= cut_ms) || *cut_ms >= end_ms {
+            bail!("cut points must increase inside {start_ms}..{end_ms}");
+        }
+        bounds.push(*cut_ms);
+    }
+    bounds.push(end_ms);
+
+    let _collection_write = acquire_collection_write_composition_lock().await;
+    let mut collection_urls = Vec::new();
+    for (collection_url, _) in list_music_occurrences_by_identity(url, start_ms, end_ms).await? {
+        if !collection_urls.contains(&collection_url) {
+            collection_urls.push(collection_url);
+        }
+    }
+
+    let mut plans = Vec::new();
+    for collection_url in collection_urls {
+        let Some(snapshot) = get_collection_by_url(&collection_url).await? else {
+            continue;
+        };
+        let mut collection = snapshot.clone();
+        let mut split = Vec::new();
+        let mut musics = Vec::with_capacity(collection.musics.len() + cut_points.len());
+        for music in std::mem::take(&mut collection.musics) {
+            if music.url != url || music.start_ms != start_ms || music.end_ms != end_ms {
+                musics.push(music);
+                continue;
+            }
+            let pieces = split_music_pieces(&music, &bounds);
+            split.push((
+                music.occurrence_id,
+                pieces
+                    .iter()
+                    .map(|piece| piece.occurrence_id.clone())
+                    .collect::<Vec<_>>(),
+            ));
+            musics.extend(pieces);
+        }
+        if split.is_empty() {
+            continue;
+        }
+        collection.musics = musics;
+        let mut occurrence_ids = HashSet::new();
+        if !collection
+            .musics
+            .iter()
+            .all(|music| occurrence_ids.insert(music.occurrence_id.clone()))
+        {
+            bail!("collection `{collection_url}` already holds one of the split ranges");
+        }
+        plans.push((snapshot, collection, split));
+    }
+
+    let mut split_records = Vec::new();
+    for (_, _, split) in &plans {
+        for (previous_occurrence_id, piece_occurrence_ids) in split {
+            if let Some(record) = find_unique_record_id_by_string_field::<Music>(
+                "occurrence_id",
+                previous_occurrence_id,
+            )
+            .await?
+            {
+                split_records.push((
+                    record,
+                    previous_occurrence_id.as_str(),
+                    piece_occurrence_ids.as_slice(),
+                ));
+            }
+        }
+    }
+    let mut extra_snapshots = Vec::new();
+    for (record, _, _) in &split_records {
+        for playlist_record in load_playlist_ids_containing_extra_record(record).await? {
+            if extra_snapshots
+                .iter()
+                .any(|(candidate, _)| *candidate == playlist_record)
+            {
+                continue;
+            }
+            let extra = load_playlist_extra_record_ids(&playlist_record).await?;
+            extra_snapshots.push((playlist_record, extra));
+        }
+    }
+
+    let mut written = Vec::with_capacity(plans.len());
+    let result: Result<Option<Vec<Music>>> = async {
+        let mut first_pieces = None;
+        for (snapshot, collection, split) in &plans {
+            written.push(snapshot.clone());
+            let saved = upsert_collection(collection).await?;
+            if first_pieces.is_none() {
+                let piece_occurrence_ids = &split[0].1;
+                first_pieces = Some(
+                    saved
+                        .musics
+                        .into_iter()
+                        .filter(|music| piece_occurrence_ids.contains(&music.occurrence_id))
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+        for (previous_record, _, piece_occurrence_ids) in &split_records {
+            let mut piece_records = Vec::with_capacity(piece_occurrence_ids.len());
+            for occurrence_id in piece_occurrence_ids.iter() {
+                if let Some(record) =
+                    find_unique_record_id_by_string_field::<Music>("occurrence_id", occurrence_id)
+                        .await?
+                {
+                    piece_records.push(record);
+                }
+            }
+            replace_playlist_extra_record_refs_with_many(previous_record, &piece_records).await?;
+        }
+        Ok(first_pieces)
+    }
+    .await;
+
+    if let Err(error) = &result {
+        log::warn!(
+            target: "playlists",
+            "music_split_rolled_back url=\"{url}\" range={start_ms}..{end_ms} error={error:#}"
+        );
+        restore_collection_snapshots(&written).await;
+        let pinned = split_records
+            .iter()
+            .map(|(record, occurrence_id, _)| (record.clone(), occurrence_id.to_string()))
+            .collect::<Vec<_>>();
+        restore_pinned_playlist_extras(&pinned, &extra_snapshots).await;
+    }
+    result
+}
+
+/// Rewrites collections from snapshots taken under the composition lock, after
+/// a change spanning several of them failed part way. Failures are logged so
+/// the remaining snapshots are still restored.
+pub(crate) async fn restore_collection_snapshots(snapshots: &[Collection]) {
+    for snapshot in snapshots {
+        if let Err(error) = upsert_collection(snapshot).await {
+            log::error!(
+                target: "playlists",
+                "collection_snapshot_restore_failed collection=\"{}\" error={error:#}",
+                snapshot.url,
+            );
+        }
+    }
+}
+
+/**
+ * Behavior:
+ *   Snapshot collections before a change spanning several of them, with what
+ *   a wholesale restore cannot rebuild from the collections alone.
+ *
+ * Core invariants:
+ *   - Take it under the composition lock and restore it before releasing.
+ *   - Playlist extras pinning an occurrence of `identities` are kept by
+ *     occurrence id, because a restored occurrence may be a new record.
+ *   - Unliked occurrences of `identities` are remembered, because saving a
+ *     collection inherits liked evidence from other occurrences.
+ */
+pub(crate) async fn snapshot_collections(
+    collection_urls: &[String],
+    identities: &[MusicRangeRef],
+) -> Result<CollectionSnapshot> {
+    let mut snapshot = CollectionSnapshot {
+        collections: Vec::with_capacity(collection_urls.len()),
+        pinned: Vec::new(),
+        extras: Vec::new(),
+        unliked: Vec::new(),
+    };
+    for collection_url in collection_urls {
+        let Some(collection) = get_collection_by_url(collection_url).await? else {
+            continue;
+        };
+        for music in collection
+            .musics
+            .iter()
+            .filter(|music| identities.iter().any(|range| range.matches(music)))
+        {
+            if !music.liked && !snapshot.unliked.iter().any(|range| range.matches(music)) {
+                snapshot.unliked.push(MusicRangeRef {
+                    url: music.url.clone(),
+                    start_ms: music.start_ms,
+                    end_ms: music.end_ms,
+                });
+            }
+            let Some(record) = find_unique_record_id_by_string_field::<Music>(
+                "occurrence_id",
+                &music.occurrence_id,
+            )
+            .await?
+            else {
+                continue;
+            };
+            for playlist_record in load_playlist_ids_containing_extra_record(&record).await? {
+                if snapshot
+                    .extras
+                    .iter()
+                    .any(|(candidate, _)| *candidate == playlist_record)
+                {
+                    continue;
+                }
+                let extra = load_playlist_extra_record_ids(&playlist_record).await?;
+                snapshot.extras.push((playlist_record, extra));
+            }
+            snapshot.pinned.push((record, music.occurrence_id.clone()));
+        }
+        snapshot.collections.push(collection);
+    }
+    Ok(snapshot)
+}
+
+/// Collections taken by `snapshot_collections`, with the playlist extras and
+/// liked state their restore needs.
+pub(crate) struct CollectionSnapshot {
+    collections: Vec<Collection>,
+    pinned: Vec<(RecordId, String)>,
+    extras: Vec<(RecordId, Vec<RecordId>)>,
+    unliked: Vec<MusicRangeRef>,
+}
+
+/// Puts a snapshot back. Failures are logged so the rest is still restored,
+/// and reported together at the end.
+pub(crate) async fn restore_collections(snapshot: &CollectionSnapshot) -> Result<()> {
+    let mut failed = 0;
+    for collection in &snapshot.collections {
+        if let Err(error) = upsert_collection(collection).await {
+            log::error!(
+                target: "playlists",
+                "collection_snapshot_restore_failed collection=\"{}\" error={error:#}",
+                collection.url,
+            );
+            failed += 1;
+        }
+    }
+    for range in &snapshot.unliked {
+        if let Err(error) =
+            set_music_liked_by_identity(&range.url, range.start_ms, range.end_ms, false).await
+        {
+            log::error!(
+                target: "playlists",
+                "music_liked_restore_failed url=\"{}\" range={}..{} error={error:#}",
+                range.url,
+                range.start_ms,
+                range.end_ms,
+            );
+            failed += 1;
+        }
+    }
+    failed += restore_pinned_playlist_extras(&snapshot.pinned, &snapshot.extras).await;
+    if failed > 0 {
+        bail!("{failed} restore steps failed");
+    }
+    Ok(())
+}
+
+/// Puts back snapshotted playlist extra lists and returns how many failed. A
+/// restored occurrence is a new record, so references to a pinned record are
+/// pointed at the record that now holds the same occurrence.
+async fn restore_pinned_playlist_extras(
+    pinned: &[(RecordId, String)],
+    extra_snapshots: &[(RecordId, Vec<RecordId>)],
+) -> usize {
+    let mut failed = 0;
+    for (playlist_record, extra) in extra_snapshots {
+        let mut restored = Vec::with_capacity(extra.len());
+        for record in extra {
+            let occurrence_id = pinned
+                .iter()
+                .find(|(candidate, _)| candidate == record)
+                .map(|(_, occurrence_id)| occurrence_id.as_str());
+            let record = match occurrence_id {
+                Some(occurrence_id) => {
+                    match find_unique_record_id_by_string_field::<Music>(
+                        "occurrence_id",
+                        occurrence_id,
+                    )
+                    .await
+                    {
+                        Ok(Some(record)) => record,
+                        _ => continue,
+                    }
+                }
+                None => record.clone(),
+            };
+            restored.push(record);
+        }
+        if let Err(error) = update_playlist_extra_record_ids(playlist_record, &restored).await {
+            log::error!(
+                target: "playlists",
+                "playlist_extra_restore_failed playlist={playlist_record:?} error={error:#}",
+            );
+            failed += 1;
+        }
+    }
+    failed
+}
+
+fn split_music_pieces(music: &Music, bounds: &[u32]) -> Vec<Music> {
+    let piece_count = bounds.len() - 1;
+    bounds
+        .windows(2)
+        .enumerate()
+        .map(|(index, range)| {
+            let mut piece = music.clone();
+            piece.alias = format!("{} ({}/{piece_count})", music.alias, index + 1);
+            piece.start_ms = range[0];
+            piece.end_ms = range[1];
+            piece.canonical_music_id =
+                canonical_music_id_for_source(&piece.url, range[0], range[1]);
+            assign_music_occurrence_id(&mut piece);
+            piece
+        })
+        .collect()
+}
+
+/**
+ * Behavior:
+ *   Delete a group that holds no music.
+ *
+ * Core invariants:
+ *   - Groups with music or referenced by a playlist are kept and reported.
+ *   - The `include` edge and exclude availability of the group go with it.
+ */
+pub async fn delete_empty_group(group_url: &str) -> Result<bool> {
+    let Some(record) = find_unique_record_id_by_string_field::<Group>("url", group_url).await?
+    else {
+        return Ok(false);
+    };
+    if !load_relation_out_ids("grouped", &record, Music::table_name())
+        .await?
+        .is_empty()
+    {
+        bail!("group `{group_url}` still holds music");
+    }
+    let playlists = load_playlist_names_referencing_group(&record).await?;
+    if !playlists.is_empty() {
+        bail!(
+            "group `{group_url}` is used by playlists: {}",
+            playlists.join(", ")
+        );
+    }
+
+    delete_music_relation_edges("include", &record).await?;
+    match Repo::<Group>::delete_record(record).await {
+        Ok(()) => {}
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) | DBError::NotFound => {}
+            other => return Err(other.into()),
+        },
+    }
+    delete_exclude_availability_for_missing_group_urls(vec![group_url.to_string()]).await?;
+    Ok(true)
+}
+
+async fn load_playlist_names_referencing_group(group_record: &RecordId) -> Result<Vec<String>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT VALUE name FROM $table WHERE $group_record IN groups;")
+        .bind(("table", Table::from(PlayList::table_name())))
+        .bind(("group_record", group_record.clone()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(vec![]),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    Ok(result.take(0)?)
+}
+
+/// Stores a group with its current owner and folder, moving its `include`
+/// edge when the owner changed.
+pub async fn rebind_group(group: &Group) -> Result<Option<Group>> {
+    let Some(record) = find_unique_record_id_by_string_field::<Group>("url", &group.url).await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(Repo::<Group>::update_at(record, group.clone()).await?))
+}
+
+/**
+ * Behavior:
+ *   Drop a collection whose music and groups were already taken over by
+ *   another collection.
+ *
+ * Core invariants:
+ *   - Music still included by another collection is kept; only occurrences
+ *     left without a parent are deleted.
+ *   - Playlists stop referencing the collection.
+ */
+pub async fn delete_absorbed_collection(collection_url: &str) -> Result<bool> {
+    let _collection_write = acquire_collection_write_composition_lock().await;
+    let Some(record) =
+        find_unique_record_id_by_string_field::<Collection>("url", collection_url).await?
+    else {
+        return Ok(false);
+    };
+
+    let music_records = load_collection_music_ids(&record).await?;
+    delete_relation_edges_from("includes", &record).await?;
+    delete_relation_edges_from("include", &record).await?;
+    update_playlist_collection_refs(&record, None, None).await?;
+    match Repo::<Collection>::delete_record(record.clone()).await {
+        Ok(()) => {}
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) | DBError::NotFound => {}
+            other => return Err(other.into()),
+        },
+    }
+    delete_orphaned_music_records(music_records, &[]).await?;
+    delete_exclude_availability_record(ExcludeOwnerKind::Collection, &record).await?;
+    Ok(true)
+}
+
+/// Points playlists that select the whole collection at one of its groups
+/// instead, returning the names of the playlists that changed.
+pub async fn replace_playlist_collection_with_group(
+    collection_url: &str,
+    group_url: &str,
+) -> Result<Vec<String>> {
+    let Some(collection_record) =
+        find_unique_record_id_by_string_field::<Collection>("url", collection_url).await?
+    else {
+        return Ok(vec![]);
+    };
+    let group_record = find_unique_record_id_by_string_field::<Group>("url", group_url).await?;
+    update_playlist_collection_refs(&collection_record, None, group_record.as_ref()).await
+}
+
+/// Adds `added_url` to every playlist that selects `existing_url`, returning
+/// the names of the playlists that changed.
+pub async fn add_playlist_collection_alongside(
+    existing_url: &str,
+    added_url: &str,
+) -> Result<Vec<String>> {
+    let Some(existing_record) =
+        find_unique_record_id_by_string_field::<Collection>("url", existing_url).await?
+    else {
+        return Ok(vec![]);
+    };
+    let Some(added_record) =
+        find_unique_record_id_by_string_field::<Collection>("url", added_url).await?
+    else {
+        return Ok(vec![]);
+    };
+    update_playlist_collection_refs(&existing_record, Some(&added_record), None).await
+}
+
+/// The collection and group selections of the playlists that select one
+/// collection, kept to undo a reshape that failed part way.
+pub(crate) struct PlaylistSelectionSnapshot(Vec<(RecordId, Vec<RecordId>, Vec<RecordId>)>);
+
+pub(crate) async fn snapshot_playlist_selections(
+    collection_url: &str,
+) -> Result<PlaylistSelectionSnapshot> {
+    let Some(collection_record) =
+        find_unique_record_id_by_string_field::<Collection>("url", collection_url).await?
+    else {
+        return Ok(PlaylistSelectionSnapshot(vec![]));
+    };
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT VALUE id FROM $table WHERE $collection_record IN collections;")
+        .bind(("table", Table::from(PlayList::table_name())))
+        .bind(("collection_record", collection_record))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(PlaylistSelectionSnapshot(vec![])),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(PlaylistSelectionSnapshot(vec![])),
+            other => return Err(other.into()),
+        },
+    };
+    let records: Vec<RecordId> = result.take(0)?;
+
+    let mut selections = Vec::with_capacity(records.len());
+    for record in records {
+        if let Some(row) = load_playlist_playback_row_by_record(&record).await? {
+            selections.push((record, row.collections, row.groups));
+        }
+    }
+    Ok(PlaylistSelectionSnapshot(selections))
+}
+
+/// Puts the snapshotted selections back; failures are logged so the other
+/// playlists are still restored.
+pub(crate) async fn restore_playlist_selections(snapshot: &PlaylistSelectionSnapshot) {
+    let Ok(db) = get_db() else {
+        return;
+    };
+    for (record, collections, groups) in &snapshot.0 {
+        let restored = db
+            .query(
+                "UPDATE ONLY $record SET collections = $collections, groups = $groups RETURN NONE;",
+            )
+            .bind(("record", record.clone()))
+            .bind(("collections", collections.clone()))
+            .bind(("groups", groups.clone()))
+            .await
+            .and_then(|result| result.check());
+        if let Err(error) = restored {
+            log::error!(
+                target: "playlists",
+                "playlist_selection_restore_failed playlist={record:?} error={error}",
+            );
+        }
+    }
+}
+
+/// Rewrites the playlists selecting `collection_record`: with an
+/// `added_collection` the selection is kept and extended, otherwise it is
+/// dropped, and `added_group` is selected in its place.
+async fn update_playlist_collection_refs(
+    collection_record: &RecordId,
+    added_collection: Option<&RecordId>,
+    added_group: Option<&RecordId>,
+) -> Result<Vec<String>> {
+    let collections = match added_collection {
+        Some(_) => "array::union(collections, [$added_collection])",
+        None => "array::complement(collections, [$collection_record])",
+    };
+    let groups = match added_group {
+        Some(_) => "array::union(groups, [$added_group])",
+        None => "groups",
+    };
+    let db = get_db()?;
+    let mut result = match db
+        .query(format!(
+            "SELECT VALUE name FROM $table WHERE $collection_record IN collections;
+             UPDATE $table SET collections = {collections}, groups = {groups} WHERE $collection_record IN collections RETURN NONE;"
+        ))
+        .bind(("table", Table::from(PlayList::table_name())))
+        .bind(("collection_record", collection_record.clone()))
+        .bind(("added_collection", added_collection.cloned()))
+        .bind(("added_group", added_group.cloned()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(vec![]),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    Ok(result.take(0)?)
 }
 
 pub async fn set_collection_updates(url: &str, enabled: bool) -> Result<Option<Collection>> {
@@ -1048,6 +2343,9 @@ enum MusicMutation {
     EndTrim {
         next_end_ms: u32,
     },
+    FilePath {
+        path: String,
+    },
 }
 
 async fn apply_music_mutation_to_records(
@@ -1177,6 +2475,14 @@ async fn apply_music_mutation_to_record(
             .check()?;
             get_music_record_after_normalizing_required_owner_edges(record).await
         }
+        MusicMutation::FilePath { path } => {
+            db.query("UPDATE ONLY $record SET path = $path RETURN NONE;")
+                .bind(("record", record.clone()))
+                .bind(("path", path))
+                .await?
+                .check()?;
+            get_music_record_after_normalizing_required_owner_edges(record).await
+        }
     }
 }
 
@@ -1473,16 +2779,9 @@ async fn replace_excluded_music_identity(
         StoredExclude::table_name(),
         exclude_record_id(source_music).to_string(),
     );
-    let exists = match Repo::<StoredExclude>::exists_record(source_record.clone()).await {
-        Ok(exists) => exists,
-        Err(error) => match classify_db_error(&error) {
-            DBError::MissingTable(_) | DBError::NotFound => false,
-            other => return Err(other.into()),
-        },
-    };
-    if !exists {
+    let Some(state) = load_exclude_state(source_record.clone()).await? else {
         return Ok(false);
-    }
+    };
 
     let target_record = RecordId::new(
         StoredExclude::table_name(),
@@ -1501,6 +2800,8 @@ async fn replace_excluded_music_identity(
             StoredExclude {
                 id: exclude_record_id(target_music),
                 music: target_music.clone(),
+                expires_at: state.expires_at.clone(),
+                playlists: state.playlists(),
                 created_at: AutoFill::pending(),
             },
         )
@@ -1534,6 +2835,14 @@ async fn move_music_relation_edges(
 async fn replace_playlist_extra_record_refs(
     source_record: &RecordId,
     target_record: &RecordId,
+) -> Result<()> {
+    replace_playlist_extra_record_refs_with_many(source_record, std::slice::from_ref(target_record))
+        .await
+}
+
+async fn replace_playlist_extra_record_refs_with_many(
+    source_record: &RecordId,
+    target_records: &[RecordId],
 ) -> Result<()> {
     for playlist_record in load_playlist_ids_containing_extra_record(source_record).await? {
         let extra = load_playlist_extra_record_ids(&playlist_record).await?;
@@ -1541,15 +2850,15 @@ async fn replace_playlist_extra_record_refs(
         let mut replaced = false;
         let next_extra = extra
             .into_iter()
-            .filter_map(|record| {
-                let next_record = if record == *source_record {
+            .flat_map(|record| {
+                if record == *source_record {
                     replaced = true;
-                    target_record.clone()
+                    target_records.to_vec()
                 } else {
-                    record
-                };
-                seen.insert(next_record.clone()).then_some(next_record)
+                    vec![record]
+                }
             })
+            .filter(|record| seen.insert(record.clone()))
             .collect::<Vec<_>>();
         if replaced {
             update_playlist_extra_record_ids(&playlist_record, &next_extra).await?;
@@ -1560,6 +2869,16 @@ async fn replace_playlist_extra_record_refs(
 }
 
 pub async fn create_music(source_collection_url: &str, music: &Music) -> Result<Music> {
+    create_music_at(source_collection_url, music, None).await
+}
+
+/// Creates a music at `position` in its collection, or last without one or
+/// when the collection got shorter.
+pub async fn create_music_at(
+    source_collection_url: &str,
+    music: &Music,
+    position: Option<usize>,
+) -> Result<Music> {
     ensure_collection_graph_schema().await?;
 
     if music.start_ms >= music.end_ms {
@@ -1582,7 +2901,10 @@ pub async fn create_music(source_collection_url: &str, music: &Music) -> Result<
         return Ok(music.clone());
     }
 
-    collection.musics.push(music.clone());
+    let position = position
+        .unwrap_or(collection.musics.len())
+        .min(collection.musics.len());
+    collection.musics.insert(position, music.clone());
     let saved = upsert_collection(&collection).await?;
     Ok(saved
         .musics
@@ -1608,6 +2930,7 @@ pub async fn delete_music(url: &str, start_ms: u32, end_ms: u32) -> Result<bool>
         let parent_collections = load_music_parent_collection_ids(&record).await?;
         let parent_groups = load_music_group_ids(&record).await?;
         delete_music_parent_edges(&record).await?;
+        replace_playlist_extra_record_refs_with_many(&record, &[]).await?;
 
         match Music::delete_record(record).await {
             Ok(()) => {}
@@ -1623,6 +2946,35 @@ pub async fn delete_music(url: &str, start_ms: u32, end_ms: u32) -> Result<bool>
     Ok(true)
 }
 
+/**
+ * Behavior:
+ *   List every stored occurrence of one music identity paired with the url of
+ *   the collection that owns it.
+ *
+ * Core invariants:
+ *   - Occurrences without an owning collection are skipped because they cannot
+ *     be recreated through `create_music`.
+ */
+pub async fn list_music_occurrences_by_identity(
+    url: &str,
+    start_ms: u32,
+    end_ms: u32,
+) -> Result<Vec<(String, Music)>> {
+    ensure_collection_graph_schema().await?;
+
+    let mut occurrences = Vec::new();
+    for record in find_music_record_ids_by_identity(url, start_ms, end_ms).await? {
+        let music = get_music_record_after_normalizing_required_owner_edges(&record).await?;
+        for collection in load_music_parent_collection_ids(&record).await? {
+            if let Some(row) = load_collection_shell_row(&collection).await? {
+                occurrences.push((row.url, music.clone()));
+            }
+        }
+    }
+
+    Ok(occurrences)
+}
+
 pub async fn list_musics_by_file_path(file_path: &Path, save_root: &Path) -> Result<Vec<Music>> {
     Ok(load_spectrum_music_context(file_path, save_root, None)
         .await?
@@ -1662,10 +3014,6 @@ pub async fn load_spectrum_music_context(
 ) -> Result<SpectrumMusicContext> {
     ensure_collection_graph_schema().await?;
 
-    let relative_file_path = file_path
-        .strip_prefix(save_root)
-        .ok()
-        .map(Path::to_path_buf);
     let collections = match CollectionSurfaceView::list_records().await {
         Ok(collections) => collections,
         Err(error) => match classify_db_error(&error) {
@@ -1673,12 +3021,16 @@ pub async fn load_spectrum_music_context(
             other => return Err(other.into()),
         },
     };
+    let collection_save_roots = load_collection_save_roots().await?;
     let mut seen = HashSet::new();
     let mut file_music_records = Vec::<PendingSpectrumMusicContextRecord>::new();
 
     for collection in collections {
-        if !is_collection_candidate_for_file_path(&collection.folder, relative_file_path.as_deref())
-        {
+        let collection_save_root = collection_save_roots
+            .get(collection.id())
+            .map(String::as_str);
+        let collection_root = save_root_dir(save_root, collection_save_root);
+        if !is_collection_candidate_for_file_path(&collection_root, &collection.folder, file_path) {
             continue;
         }
 
@@ -1692,9 +3044,12 @@ pub async fn load_spectrum_music_context(
 
         for music_record in music_records {
             let music_view = music_record.value();
-            let Some(resolved_path) =
-                resolve_music_file_path(save_root, &collection.folder, music_view.path.as_deref())
-            else {
+            let Some(resolved_path) = resolve_music_file_path(
+                save_root,
+                collection_save_root,
+                &collection.folder,
+                music_view.path.as_deref(),
+            ) else {
                 continue;
             };
 
@@ -1969,6 +3324,14 @@ async fn inherit_canonical_music_evidence(collection: &Collection) -> Result<Col
         {
             music.loudness_profile = Some(profile);
         }
+        if music.added_at.is_none() {
+            music.added_at = Some(
+                match canonical_music_id_added_at_evidence(&music.canonical_music_id).await? {
+                    Some(added_at) => added_at,
+                    None => chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                },
+            );
+        }
     }
 
     Ok(collection)
@@ -2038,6 +3401,35 @@ async fn canonical_music_id_loudness_profile_evidence(
     Ok(None)
 }
 
+async fn canonical_music_id_added_at_evidence(canonical_music_id: &str) -> Result<Option<String>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query(
+            "SELECT VALUE added_at FROM $table
+             WHERE canonical_music_id = $canonical_music_id AND added_at IS NOT NONE
+             ORDER BY added_at ASC LIMIT 1;",
+        )
+        .bind(("table", Table::from(Music::table_name())))
+        .bind(("canonical_music_id", canonical_music_id.to_string()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(None),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(None),
+            other => return Err(other.into()),
+        },
+    };
+
+    let added_at: Vec<String> = result.take(0)?;
+    Ok(added_at.into_iter().next())
+}
+
 /// Collection persistence owns its graph schema so callers never need to
 /// remember a separate bootstrap step before writing or hydrating musics.
 async fn ensure_collection_graph_schema() -> Result<()> {
@@ -2111,7 +3503,9 @@ async fn load_collection_music_ids(record: &RecordId) -> Result<Vec<RecordId>> {
 async fn load_playlist_playback_row_by_name(name: &str) -> Result<Option<PlaylistPlaybackRow>> {
     let db = get_db()?;
     let mut result = match db
-        .query("SELECT name, collections, groups, extra FROM $table WHERE name = $name LIMIT 2;")
+        .query(
+            "SELECT name, collections, groups, extra, playlists, rules FROM $table WHERE name = $name LIMIT 2;",
+        )
         .bind(("table", Table::from(PlayList::table_name())))
         .bind(("name", name.to_string()))
         .await
@@ -2146,7 +3540,7 @@ async fn load_playlist_playback_row_by_record(
 ) -> Result<Option<PlaylistPlaybackRow>> {
     let db = get_db()?;
     let mut result = match db
-        .query("SELECT name, collections, groups, extra FROM ONLY $record;")
+        .query("SELECT name, collections, groups, extra, playlists, rules FROM ONLY $record;")
         .bind(("record", record.clone()))
         .await
     {
@@ -2173,12 +3567,16 @@ fn project_playlist_playback_raw_row(row: PlaylistPlaybackRawRow) -> Result<Play
         collections: project_record_refs(row.collections)?,
         groups: project_record_refs(row.groups)?,
         extra: project_required_record_refs(row.extra, "extra")?,
+        playlists: row.playlists.unwrap_or_default(),
+        rules: row.rules,
     })
 }
 
 fn playlist_playback_foreign_refs_changed(
     previous: Option<&PlaylistPlaybackRow>,
     next: &PlaylistForeignRecordIds,
+    next_playlists: &[String],
+    next_rules: Option<&PlaylistRules>,
 ) -> bool {
     let Some(previous) = previous else {
         return true;
@@ -2187,6 +3585,8 @@ fn playlist_playback_foreign_refs_changed(
     previous.collections != next.collections
         || previous.groups != next.groups
         || previous.extra != next.extra
+        || previous.playlists != next_playlists
+        || previous.rules.as_ref() != next_rules
 }
 
 fn project_record_refs(values: serde_json::Value) -> Result<Vec<RecordId>> {
@@ -2259,6 +3659,62 @@ async fn load_playlist_ids_containing_extra_record(
     Ok(result.take(0)?)
 }
 
+async fn load_playlist_include_rows(included: &str) -> Result<Vec<PlaylistIncludeRow>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT id, name, playlists FROM $table WHERE $included IN playlists;")
+        .bind(("table", Table::from(PlayList::table_name())))
+        .bind(("included", included.to_string()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(vec![]),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(vec![]),
+            other => return Err(other.into()),
+        },
+    };
+
+    Ok(result.take(0)?)
+}
+
+/**
+ * Behavior:
+ *   Point includes of `previous` at `next`, or drop them when the included
+ *   playlist was deleted.
+ *
+ * Core invariants:
+ *   - Include lists keep their order and never name the same playlist twice.
+ */
+async fn replace_included_playlist_refs(previous: &str, next: Option<&str>) -> Result<()> {
+    let db = get_db()?;
+    for row in load_playlist_include_rows(previous).await? {
+        let mut playlists = Vec::with_capacity(row.playlists.len());
+        for name in row.playlists {
+            let name = match (name == previous, next) {
+                (false, _) => name,
+                (true, Some(next)) => next.to_string(),
+                (true, None) => continue,
+            };
+            if !playlists.contains(&name) {
+                playlists.push(name);
+            }
+        }
+        db.query("UPDATE ONLY $record SET playlists = $playlists RETURN NONE;")
+            .bind(("record", row.id))
+            .bind(("playlists", playlists))
+            .await?
+            .check()?;
+    }
+
+    Ok(())
+}
+
 async fn update_playlist_extra_record_ids(record: &RecordId, extra: &[RecordId]) -> Result<()> {
     let db = get_db()?;
     db.query("UPDATE ONLY $record SET extra = $extra RETURN NONE;")
@@ -2282,6 +3738,7 @@ async fn load_playlist_playback_collection_ref(
         name: row.name,
         url: row.url,
         folder: row.folder,
+        save_root: row.save_root,
         last_updated: row.last_updated,
         enable_updates: row.enable_updates,
     }))
@@ -2331,7 +3788,9 @@ async fn load_group_shell_row(record: &RecordId) -> Result<Option<GroupShellRow>
 async fn load_collection_shell_row(record: &RecordId) -> Result<Option<CollectionShellRow>> {
     let db = get_db()?;
     let mut result = match db
-        .query("SELECT id, name, url, folder, last_updated, enable_updates FROM ONLY $record;")
+        .query(
+            "SELECT id, name, url, folder, save_root, last_updated, enable_updates FROM ONLY $record;",
+        )
         .bind(("record", record.clone()))
         .await
     {
@@ -2393,7 +3852,12 @@ async fn append_collection_playback_track_sources(
                 let Some(music) = playable_track_music_from_relation_row(row, group) else {
                     continue;
                 };
-                if is_music_canonical_id_excluded(&music.canonical_music_id).await? {
+                if is_music_canonical_id_excluded_for_playlist(
+                    &music.canonical_music_id,
+                    &selection.playlist_name,
+                )
+                .await?
+                {
                     continue;
                 }
                 append_playback_track_source(collection, music, seen, sources);
@@ -2468,11 +3932,17 @@ async fn append_group_playback_track_sources(
                     ) else {
                         continue;
                     };
-                    if is_music_canonical_id_excluded(&music.canonical_music_id).await? {
+                    if is_music_canonical_id_excluded_for_playlist(
+                        &music.canonical_music_id,
+                        &selection.playlist_name,
+                    )
+                    .await?
+                    {
                         continue;
                     }
                     append_playback_track_source_from_folder(
                         &collection.folder,
+                        collection.save_root.as_deref(),
                         music,
                         seen,
                         sources,
@@ -2483,65 +3953,236 @@ async fn append_group_playback_track_sources(
                 }
             }
 
-            offset += row_count;
-            if row_count < batch_limit {
-                break;
+            offset += row_count;
+            if row_count < batch_limit {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn append_extra_playback_track_sources(
+    selection: &PlaylistPlaybackSelection,
+    limit: usize,
+    liked_only: bool,
+    seen: &mut HashSet<String>,
+    sources: &mut Vec<PlaylistPlaybackTrackSource>,
+) -> Result<()> {
+    if sources.len() >= limit || selection.extra.is_empty() {
+        return Ok(());
+    }
+
+    let records = selection
+        .extra
+        .iter()
+        .map(|extra| extra.record.clone())
+        .collect::<Vec<_>>();
+    let rows = load_record_playable_track_rows(records, liked_only).await?;
+    let source_collections =
+        load_music_source_collections_for_playback(rows.iter().map(|row| &row.music_record))
+            .await?;
+    let groups = load_music_groups_for_playback(rows.iter().map(|row| &row.music_record)).await?;
+    let mut rows_by_record = rows
+        .into_iter()
+        .map(|row| (row.music_record.clone(), row))
+        .collect::<HashMap<_, _>>();
+
+    for extra in &selection.extra {
+        let Some(row) = rows_by_record.remove(&extra.record) else {
+            continue;
+        };
+        if is_music_canonical_id_excluded_for_playlist(
+            &row.canonical_music_id,
+            &selection.playlist_name,
+        )
+        .await?
+        {
+            continue;
+        }
+        let Some(collection) = source_collections
+            .get(&row.music_record)
+            .and_then(|collections| collections.first())
+        else {
+            continue;
+        };
+        let Some(group) = groups.get(&row.music_record).cloned() else {
+            continue;
+        };
+        let Some(music) = playable_track_music_from_record_row(row, group) else {
+            continue;
+        };
+        append_playback_track_source_from_folder(
+            &collection.folder,
+            collection.save_root.as_deref(),
+            music,
+            seen,
+            sources,
+        );
+        if sources.len() >= limit {
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Behavior:
+ *   Append library music matched by each smart rule set in the selection.
+ *
+ * Core invariants:
+ *   - Rule predicates are evaluated by the database over `Collection ->
+ *     includes -> Music` edges, so the source collection folder is the edge
+ *     owner and never guessed from a group.
+ *   - Excludes and canonical de-duplication apply exactly as for explicit refs.
+ */
+async fn append_rule_playback_track_sources(
+    selection: &PlaylistPlaybackSelection,
+    limit: usize,
+    liked_only: bool,
+    seen: &mut HashSet<String>,
+    sources: &mut Vec<PlaylistPlaybackTrackSource>,
+) -> Result<()> {
+    for rules in &selection.rules {
+        append_rule_set_playback_track_sources(
+            rules,
+            &selection.playlist_name,
+            limit,
+            liked_only,
+            seen,
+            sources,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn append_rule_set_playback_track_sources(
+    rules: &PlaylistRules,
+    playlist_name: &str,
+    limit: usize,
+    liked_only: bool,
+    seen: &mut HashSet<String>,
+    sources: &mut Vec<PlaylistPlaybackTrackSource>,
+) -> Result<()> {
+    if sources.len() >= limit {
+        return Ok(());
+    }
+
+    let mut offset = 0usize;
+    loop {
+        let remaining = limit.saturating_sub(sources.len());
+        if remaining == 0 {
+            return Ok(());
+        }
+
+        let batch_limit = remaining.max(32);
+        let rows = load_rule_playable_track_rows(
+            rules.clone(),
+            liked_only,
+            PlaylistRuleTrackOrder::Library {
+                limit: batch_limit,
+                offset,
+            },
+        )
+        .await?;
+        if rows.is_empty() {
+            break;
+        }
+
+        let row_count = rows.len();
+        let groups =
+            load_music_groups_for_playback(rows.iter().map(|row| &row.music_record)).await?;
+        for row in rows {
+            let Some(group) = groups.get(&row.music_record).cloned() else {
+                continue;
+            };
+            let collection_folder = row.collection_folder.clone();
+            let collection_save_root = row.collection_save_root.clone();
+            let Some(music) = playable_track_music_from_rule_row(row, group) else {
+                continue;
+            };
+            if is_music_canonical_id_excluded_for_playlist(&music.canonical_music_id, playlist_name)
+                .await?
+            {
+                continue;
             }
+            append_playback_track_source_from_folder(
+                &collection_folder,
+                collection_save_root.as_deref(),
+                music,
+                seen,
+                sources,
+            );
+            if sources.len() >= limit {
+                return Ok(());
+            }
+        }
+
+        offset += row_count;
+        if row_count < batch_limit {
+            break;
         }
     }
 
     Ok(())
 }
 
-async fn append_extra_playback_track_sources(
-    selection: &PlaylistPlaybackSelection,
+async fn append_random_rule_playback_track_sources(
+    rules: &PlaylistRules,
+    playlist_name: &str,
     limit: usize,
-    liked_only: bool,
     seen: &mut HashSet<String>,
     sources: &mut Vec<PlaylistPlaybackTrackSource>,
 ) -> Result<()> {
-    if sources.len() >= limit || selection.extra.is_empty() {
+    if limit == 0 {
         return Ok(());
     }
+    let target_len = sources.len().saturating_add(limit);
 
-    let records = selection
-        .extra
-        .iter()
-        .map(|extra| extra.record.clone())
-        .collect::<Vec<_>>();
-    let rows = load_record_playable_track_rows(records, liked_only).await?;
-    let source_collections =
-        load_music_source_collections_for_playback(rows.iter().map(|row| &row.music_record))
-            .await?;
+    let rows = load_rule_playable_track_rows(
+        rules.clone(),
+        false,
+        PlaylistRuleTrackOrder::Random {
+            limit: random_relation_playable_track_probe_limit_for_source_limit(limit),
+        },
+    )
+    .await?;
+    if rows.is_empty() {
+        return Ok(());
+    }
     let groups = load_music_groups_for_playback(rows.iter().map(|row| &row.music_record)).await?;
-    let mut rows_by_record = rows
-        .into_iter()
-        .map(|row| (row.music_record.clone(), row))
-        .collect::<HashMap<_, _>>();
 
-    for extra in &selection.extra {
-        let Some(row) = rows_by_record.remove(&extra.record) else {
-            continue;
-        };
-        if is_music_canonical_id_excluded(&row.canonical_music_id).await? {
-            continue;
+    for row in rows {
+        if sources.len() >= target_len {
+            return Ok(());
         }
-        let Some(collection) = source_collections
-            .get(&row.music_record)
-            .and_then(|collections| collections.first())
-        else {
+
+        if is_music_canonical_id_excluded_for_playlist(&row.canonical_music_id, playlist_name)
+            .await?
+        {
             continue;
-        };
+        }
+
         let Some(group) = groups.get(&row.music_record).cloned() else {
             continue;
         };
-        let Some(music) = playable_track_music_from_record_row(row, group) else {
+        let collection_folder = row.collection_folder.clone();
+        let collection_save_root = row.collection_save_root.clone();
+        let Some(music) = playable_track_music_from_rule_row(row, group) else {
             continue;
         };
-        append_playback_track_source_from_folder(&collection.folder, music, seen, sources);
-        if sources.len() >= limit {
-            return Ok(());
-        }
+
+        append_playback_track_source_from_folder(
+            &collection_folder,
+            collection_save_root.as_deref(),
+            music,
+            seen,
+            sources,
+        );
     }
 
     Ok(())
@@ -2549,6 +4190,7 @@ async fn append_extra_playback_track_sources(
 
 async fn append_random_collection_playback_track_sources(
     collection: &PlaylistPlaybackCollectionRef,
+    playlist_name: &str,
     limit: usize,
     seen: &mut HashSet<String>,
     sources: &mut Vec<PlaylistPlaybackTrackSource>,
@@ -2575,7 +4217,9 @@ async fn append_random_collection_playback_track_sources(
             return Ok(());
         }
 
-        if is_music_canonical_id_excluded(&row.canonical_music_id).await? {
+        if is_music_canonical_id_excluded_for_playlist(&row.canonical_music_id, playlist_name)
+            .await?
+        {
             continue;
         }
 
@@ -2594,6 +4238,7 @@ async fn append_random_collection_playback_track_sources(
 
 async fn append_random_group_playback_track_sources(
     group: &PlaylistPlaybackGroupRef,
+    playlist_name: &str,
     limit: usize,
     seen: &mut HashSet<String>,
     sources: &mut Vec<PlaylistPlaybackTrackSource>,
@@ -2627,7 +4272,9 @@ async fn append_random_group_playback_track_sources(
             return Ok(());
         }
 
-        if is_music_canonical_id_excluded(&row.canonical_music_id).await? {
+        if is_music_canonical_id_excluded_for_playlist(&row.canonical_music_id, playlist_name)
+            .await?
+        {
             continue;
         }
 
@@ -2650,7 +4297,13 @@ async fn append_random_group_playback_track_sources(
             continue;
         };
 
-        append_playback_track_source_from_folder(&collection.folder, music, seen, sources);
+        append_playback_track_source_from_folder(
+            &collection.folder,
+            collection.save_root.as_deref(),
+            music,
+            seen,
+            sources,
+        );
     }
 
     Ok(())
@@ -2658,13 +4311,14 @@ async fn append_random_group_playback_track_sources(
 
 async fn load_extra_playback_track_source(
     extra: &PlaylistPlaybackExtraRef,
+    playlist_name: &str,
 ) -> Result<Option<PlaylistPlaybackTrackSource>> {
     let mut rows = load_record_playable_track_rows(vec![extra.record.clone()], false).await?;
     let Some(row) = rows.pop() else {
         return Ok(None);
     };
 
-    if is_music_canonical_id_excluded(&row.canonical_music_id).await? {
+    if is_music_canonical_id_excluded_for_playlist(&row.canonical_music_id, playlist_name).await? {
         return Ok(None);
     }
 
@@ -2688,6 +4342,7 @@ async fn load_extra_playback_track_source(
 
     Ok(Some(project_playback_track_source_from_folder(
         &collection.folder,
+        collection.save_root.as_deref(),
         music,
     )))
 }
@@ -2713,12 +4368,17 @@ async fn append_random_extra_playback_track_sources(
             return Ok(());
         }
 
-        let Some(source) = load_extra_playback_track_source(&selection.extra[extra_index]).await?
+        let Some(source) = load_extra_playback_track_source(
+            &selection.extra[extra_index],
+            &selection.playlist_name,
+        )
+        .await?
         else {
             continue;
         };
         append_playback_track_source_from_folder(
             &source.collection_folder,
+            source.collection_save_root.as_deref(),
             source.music,
             seen,
             sources,
@@ -2790,6 +4450,29 @@ async fn load_random_relation_playable_track_rows(
     }
 }
 
+async fn load_rule_playable_track_rows(
+    rules: PlaylistRules,
+    liked_only: bool,
+    order: PlaylistRuleTrackOrder,
+) -> Result<Vec<PlaylistRulePlayableTrackRow>> {
+    let limit = match order {
+        PlaylistRuleTrackOrder::Library { limit, .. }
+        | PlaylistRuleTrackOrder::Random { limit } => limit,
+    };
+    if limit == 0 {
+        return Ok(vec![]);
+    }
+
+    let stmt = playlist_rule_playable_track_stmt(rules, liked_only, order);
+    match query_bound_take::<PlaylistRulePlayableTrackRow>(stmt, Some(0)).await {
+        Ok(rows) => Ok(rows),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => Ok(vec![]),
+            other => Err(other.into()),
+        },
+    }
+}
+
 async fn load_record_playable_track_rows(
     music_records: Vec<RecordId>,
     liked_only: bool,
@@ -2848,6 +4531,7 @@ async fn load_music_source_collections_for_playback<'a>(
                 },
                 record: row.collection_record,
                 folder: row.collection_folder,
+                save_root: row.collection_save_root,
             });
     }
 
@@ -2925,6 +4609,9 @@ fn playable_track_music_from_relation_row(
         end_ms: row.end_ms,
         liked: row.liked,
         loudness_profile: row.loudness_profile,
+        added_at: row.added_at,
+        tags: row.tags,
+        lyrics_path: row.lyrics_path,
     })
 }
 
@@ -2944,6 +4631,9 @@ fn playable_track_music_from_random_relation_row(
         end_ms: row.end_ms,
         liked: row.liked,
         loudness_profile: row.loudness_profile,
+        added_at: row.added_at,
+        tags: row.tags,
+        lyrics_path: row.lyrics_path,
     })
 }
 
@@ -2963,6 +4653,31 @@ fn playable_track_music_from_record_row(
         end_ms: row.end_ms,
         liked: row.liked,
         loudness_profile: row.loudness_profile,
+        added_at: row.added_at,
+        tags: row.tags,
+        lyrics_path: row.lyrics_path,
+    })
+}
+
+fn playable_track_music_from_rule_row(
+    row: PlaylistRulePlayableTrackRow,
+    group: Group,
+) -> Option<Music> {
+    Some(Music {
+        occurrence_id: row.occurrence_id,
+        name: row.name,
+        alias: row.alias,
+        group,
+        canonical_music_id: row.canonical_music_id,
+        url: row.url,
+        path: Some(row.path?),
+        start_ms: row.start_ms,
+        end_ms: row.end_ms,
+        liked: row.liked,
+        loudness_profile: row.loudness_profile,
+        added_at: row.added_at,
+        tags: row.tags,
+        lyrics_path: row.lyrics_path,
     })
 }
 
@@ -3023,11 +4738,18 @@ fn append_playback_track_source(
     seen: &mut HashSet<String>,
     sources: &mut Vec<PlaylistPlaybackTrackSource>,
 ) {
-    append_playback_track_source_from_folder(&collection.folder, music, seen, sources);
+    append_playback_track_source_from_folder(
+        &collection.folder,
+        collection.save_root.as_deref(),
+        music,
+        seen,
+        sources,
+    );
 }
 
 fn append_playback_track_source_from_folder(
     collection_folder: &str,
+    collection_save_root: Option<&str>,
     music: Music,
     seen: &mut HashSet<String>,
     sources: &mut Vec<PlaylistPlaybackTrackSource>,
@@ -3036,18 +4758,21 @@ fn append_playback_track_source_from_folder(
         return;
     }
 
-    sources.push(PlaylistPlaybackTrackSource {
-        collection_folder: collection_folder.to_string(),
+    sources.push(project_playback_track_source_from_folder(
+        collection_folder,
+        collection_save_root,
         music,
-    });
+    ));
 }
 
 fn project_playback_track_source_from_folder(
     collection_folder: &str,
+    collection_save_root: Option<&str>,
     music: Music,
 ) -> PlaylistPlaybackTrackSource {
     PlaylistPlaybackTrackSource {
         collection_folder: collection_folder.to_string(),
+        collection_save_root: collection_save_root.map(ToOwned::to_owned),
         music,
     }
 }
@@ -3073,20 +4798,146 @@ async fn is_music_record_excluded_for_playback(record: &RecordId, out_table: &st
 }
 
 async fn is_music_canonical_id_excluded(canonical_music_id: &str) -> Result<bool> {
+    is_music_canonical_id_excluded_in(canonical_music_id, None).await
+}
+
+async fn is_music_canonical_id_excluded_for_playlist(
+    canonical_music_id: &str,
+    playlist_name: &str,
+) -> Result<bool> {
+    is_music_canonical_id_excluded_in(canonical_music_id, Some(playlist_name)).await
+}
+
+/// Global excludes apply everywhere; scoped excludes only while one of
+/// their playlists plays.
+async fn is_music_canonical_id_excluded_in(
+    canonical_music_id: &str,
+    playlist_name: Option<&str>,
+) -> Result<bool> {
     let record = RecordId::new(
         StoredExclude::table_name(),
         exclude_canonical_record_id(canonical_music_id).to_string(),
     );
 
-    match Repo::<StoredExclude>::exists_record(record).await {
-        Ok(exists) => Ok(exists),
+    Ok(load_exclude_state(record)
+        .await?
+        .is_some_and(|state| state.is_active(Utc::now()) && state.applies_to(playlist_name)))
+}
+
+async fn load_exclude_state(record: RecordId) -> Result<Option<ExcludeStateRow>> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT expires_at, playlists FROM $record;")
+        .bind(("record", record))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(None),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(None),
+            other => return Err(other.into()),
+        },
+    };
+
+    let rows: Vec<ExcludeStateRow> = result.take(0)?;
+    Ok(rows.into_iter().next())
+}
+
+/**
+ * Behavior:
+ *   Point excludes scoped to playlist `previous` at `next`, or drop that
+ *   scope when the playlist was deleted.
+ *
+ * Core invariants:
+ *   - An exclude that loses its last playlist is deleted instead of turning
+ *     into a global exclude.
+ */
+async fn replace_exclude_playlist_scope(previous: &str, next: Option<&str>) -> Result<()> {
+    let db = get_db()?;
+    let mut result = match db
+        .query("SELECT id, playlists FROM $table WHERE $previous IN playlists;")
+        .bind(("table", Table::from(StoredExclude::table_name())))
+        .bind(("previous", previous.to_string()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(result) => result,
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => return Ok(()),
+                other => return Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => return Ok(()),
+            other => return Err(other.into()),
+        },
+    };
+    let rows: Vec<ExcludeScopeRow> = result.take(0)?;
+
+    for row in rows {
+        let mut playlists = Vec::with_capacity(row.playlists.len());
+        for name in row.playlists {
+            let name = match (name == previous, next) {
+                (false, _) => name,
+                (true, Some(next)) => next.to_string(),
+                (true, None) => continue,
+            };
+            if !playlists.contains(&name) {
+                playlists.push(name);
+            }
+        }
+
+        if playlists.is_empty() {
+            delete_exclude_record_id(row.id).await?;
+        } else {
+            db.query("UPDATE ONLY $record SET playlists = $playlists RETURN NONE;")
+                .bind(("record", row.id))
+                .bind(("playlists", playlists))
+                .await?
+                .check()?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn delete_exclude_record_id(record: RecordId) -> Result<()> {
+    match Repo::<StoredExclude>::delete_record(record).await {
+        Ok(()) => Ok(()),
         Err(error) => match classify_db_error(&error) {
-            DBError::MissingTable(_) => Ok(false),
+            DBError::MissingTable(_) | DBError::NotFound => Ok(()),
             other => Err(other.into()),
         },
     }
 }
 
+/// Rejects expiries that are unreadable or already passed at `now`, since such
+/// an exclude would hide music until the next sweep happens to lift it.
+fn normalize_exclude_expiry(expires_at: &str, now: DateTime<Utc>) -> Result<String> {
+    let parsed = DateTime::parse_from_rfc3339(expires_at)
+        .map(|expires_at| expires_at.with_timezone(&Utc))
+        .map_err(|_| {
+            anyhow::anyhow!("exclude expiry `{expires_at}` is not an RFC 3339 timestamp")
+        })?;
+    if parsed <= now {
+        bail!("exclude expiry `{expires_at}` has already passed");
+    }
+    Ok(parsed.to_rfc3339())
+}
+
+/// Unreadable expiries count as passed so a broken row cannot hide music
+/// forever.
+pub(crate) fn exclude_expiry_passed(expires_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(expires_at)
+        .map(|expires_at| expires_at <= now)
+        .unwrap_or(true)
+}
+
 async fn load_relation_in_ids(
     relation: &str,
     record: &RecordId,
@@ -3371,6 +5222,29 @@ async fn delete_music_relation_edges(relation: &str, record: &RecordId) -> Resul
     }
 }
 
+async fn delete_relation_edges_from(relation: &str, record: &RecordId) -> Result<()> {
+    let db = get_db()?;
+
+    match db
+        .query("DELETE $rel WHERE in = $record RETURN NONE;")
+        .bind(("rel", Table::from(relation)))
+        .bind(("record", record.clone()))
+        .await
+    {
+        Ok(result) => match result.check() {
+            Ok(_) => Ok(()),
+            Err(error) => match DBError::from(error) {
+                DBError::MissingTable(_) => Ok(()),
+                other => Err(other.into()),
+            },
+        },
+        Err(error) => match classify_db_error(&error.into()) {
+            DBError::MissingTable(_) => Ok(()),
+            other => Err(other.into()),
+        },
+    }
+}
+
 async fn find_unique_record_id_by_string_field<T>(
     field: &str,
     value: &str,
@@ -3818,6 +5692,8 @@ async fn playlist_storage_row_from_request(
         collections,
         groups,
         extra: playlist.extra.clone(),
+        playlists: (!playlist.playlists.is_empty()).then(|| playlist.playlists.clone()),
+        rules: playlist.rules.clone(),
         created_at: playlist.created_at.clone(),
     })
 }
@@ -3882,8 +5758,9 @@ fn stable_record_key(seed: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
-fn resolve_music_file_path(
+pub(crate) fn resolve_music_file_path(
     save_root: &Path,
+    collection_save_root: Option<&str>,
     collection_folder: &str,
     relative_path: Option<&str>,
 ) -> Option<PathBuf> {
@@ -3892,14 +5769,15 @@ fn resolve_music_file_path(
         return Some(path);
     }
 
-    Some(save_root.join(collection_folder).join(path))
+    Some(collection_dir(save_root, collection_save_root, collection_folder).join(path))
 }
 
 fn is_collection_candidate_for_file_path(
+    collection_root: &Path,
     collection_folder: &str,
-    relative_file_path: Option<&Path>,
+    file_path: &Path,
 ) -> bool {
-    let Some(relative_file_path) = relative_file_path else {
+    let Ok(relative_file_path) = file_path.strip_prefix(collection_root) else {
         return true;
     };
     let collection_folder = Path::new(collection_folder);
@@ -3920,6 +5798,10 @@ struct StoredExclude {
     id: Id,
     #[foreign]
     music: Music,
+    #[serde(default)]
+    expires_at: Option<String>,
+    #[serde(default)]
+    playlists: Vec<String>,
     #[pagin]
     #[fill(now)]
     created_at: AutoFill,
@@ -3929,11 +5811,52 @@ impl StoredExclude {
     fn into_public(self) -> Exclude {
         Exclude {
             music: self.music,
+            expires_at: self.expires_at,
+            playlists: self.playlists,
             created_at: self.created_at,
         }
     }
 }
 
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+struct ExcludeStateRow {
+    expires_at: Option<String>,
+    playlists: Option<Vec<String>>,
+}
+
+impl ExcludeStateRow {
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        !self
+            .expires_at
+            .as_deref()
+            .is_some_and(|expires_at| exclude_expiry_passed(expires_at, now))
+    }
+
+    fn is_global(&self) -> bool {
+        self.playlists.as_ref().is_none_or(Vec::is_empty)
+    }
+
+    fn applies_to(&self, playlist_name: Option<&str>) -> bool {
+        self.is_global()
+            || playlist_name.is_some_and(|name| {
+                self.playlists
+                    .as_ref()
+                    .is_some_and(|playlists| playlists.iter().any(|scope| scope == name))
+            })
+    }
+
+    fn playlists(self) -> Vec<String> {
+        self.playlists.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+struct ExcludeScopeRow {
+    #[serde(deserialize_with = "appdb::serde_utils::id::deserialize_record_id_or_compat_string")]
+    id: RecordId,
+    playlists: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SurrealValue)]
 #[serde(rename_all = "snake_case")]
 enum ExcludeOwnerKind {
@@ -4022,6 +5945,10 @@ struct PlaylistPlaybackRawRow {
     collections: serde_json::Value,
     groups: serde_json::Value,
     extra: serde_json::Value,
+    #[serde(default)]
+    playlists: Option<Vec<String>>,
+    #[serde(default)]
+    rules: Option<PlaylistRules>,
 }
 
 #[derive(Debug, Clone)]
@@ -4030,6 +5957,16 @@ struct PlaylistPlaybackRow {
     collections: Vec<RecordId>,
     groups: Vec<RecordId>,
     extra: Vec<RecordId>,
+    playlists: Vec<String>,
+    rules: Option<PlaylistRules>,
+}
+
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+struct PlaylistIncludeRow {
+    #[serde(deserialize_with = "appdb::serde_utils::id::deserialize_record_id_or_compat_string")]
+    id: RecordId,
+    name: String,
+    playlists: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, SurrealValue)]
@@ -4039,6 +5976,8 @@ struct CollectionShellRow {
     name: String,
     url: String,
     folder: String,
+    #[serde(default)]
+    save_root: Option<String>,
     last_updated: String,
     enable_updates: Option<bool>,
 }
@@ -4055,6 +5994,13 @@ impl CollectionShellRow {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+struct CollectionSaveRootRow {
+    #[serde(deserialize_with = "appdb::serde_utils::id::deserialize_record_id_or_compat_string")]
+    id: RecordId,
+    save_root: String,
+}
+
 #[derive(Debug, Clone, Deserialize, SurrealValue)]
 struct GroupShellRow {
     #[serde(deserialize_with = "appdb::serde_utils::id::deserialize_record_id_or_compat_string")]
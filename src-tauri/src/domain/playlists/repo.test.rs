@@ -1,27 +1,29 @@
 use super::model::{
     Collection, CollectionGroupOwner, CollectionSurfaceView, Exclude, Group, GroupSurfaceView,
     LoudnessProfile, Music, PlayList, PlayListConfigView, PlayListListView, PlayListWriteRequest,
-    canonical_music_id_for_source,
+    PlaylistRules, canonical_music_id_for_source,
 };
 use super::repo::{
     MusicEndTrim, PlaylistPlaybackCollectionRef, PlaylistPlaybackGroupRef,
     PlaylistPlaybackSelection, PlaylistPlaybackTrackSource, SpectrumMusicSourceIdentity,
-    add_exclude, claim_generated_playlist_name, create_music, delete_music,
+    add_exclude, add_playlist_exclude, claim_generated_playlist_name, create_music, delete_music,
     delete_playlist_by_name, get_collection_by_url, get_music_loudness_profile_by_identity,
     get_playlist_by_name, get_playlist_config_by_name, get_playlist_playback_selection_by_name,
-    has_collections, is_music_identity_excluded_for_playback, list_auto_update_collection_urls,
-    list_collections, list_config_library, list_musics_by_file_path, list_playlists,
+    has_collections, is_music_identity_excluded_for_playback, lift_expired_excludes,
+    list_auto_update_collection_urls, list_collections, list_config_library,
+    list_dependent_playlist_names, list_musics_by_file_path, list_playlists,
     load_liked_playlist_playback_track_sources, load_playlist_playback_track_sources,
     load_random_playlist_playback_track_sources, load_spectrum_music_context, music_occurrence_id,
-    playlist_playback_owner_attempt_order, project_music_loudness_identity, push_extra,
-    remove_exclude, remove_extra, set_collection_updates, set_music_liked_by_identity,
-    set_music_loudness_profile_by_identity, trim_collection_music_ends_by_identity, update_music,
-    upsert_collection, upsert_playlist, upsert_playlist_surface,
+    next_exclude_expiry, playlist_playback_owner_attempt_order, project_music_loudness_identity,
+    push_extra, remove_exclude, remove_extra, set_collection_updates, set_music_liked_by_identity,
+    set_music_loudness_profile_by_identity, split_music, trim_collection_music_ends_by_identity,
+    update_music, upsert_collection, upsert_playlist, upsert_playlist_surface,
 };
 use crate::domain::playlists::PLAYLIST_DB_TEST_LOCK;
 use appdb::connection::{get_db, reinit_db, reset_db};
 use appdb::model::meta::{ModelMeta, ResolveRecordId};
 use appdb::{AutoFill, Crud};
+use chrono::{Duration, Utc};
 use serde_json::json;
 use std::path::PathBuf;
 use std::sync::LazyLock;
@@ -105,6 +107,7 @@ fn sample_collection(url: &str, enable_updates: Option<bool>) -> Collection {
         musics: vec![],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates,
+        save_root: None,
     }
 }
 
@@ -162,9 +165,13 @@ fn grouped_collection(url: &str) -> Collection {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         }],
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates: Some(false),
+        save_root: None,
     }
 }
 
@@ -182,6 +189,9 @@ fn named_music(name: &str, group: Group, path: &str) -> Music {
         end_ms: 180_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -198,6 +208,7 @@ fn collection_with_musics(
         musics,
         last_updated: "2026-04-12T00:00:00+00:00".to_string(),
         enable_updates,
+        save_root: None,
     }
 }
 
@@ -215,6 +226,9 @@ fn shared_music(collection_url: &str, collection_folder: &str) -> Music {
         end_ms: 180_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -248,9 +262,13 @@ fn sample_playlist(name: &str) -> PlayList {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: Some(false),
+            save_root: None,
         }],
         groups: vec![Group {
             name: "Disc 1".to_string(),
@@ -259,6 +277,8 @@ fn sample_playlist(name: &str) -> PlayList {
             folder: "Disc 1".to_string(),
         }],
         extra: vec![],
+        playlists: None,
+        rules: None,
 
         created_at: AutoFill::resolved(format!("2026-04-12T00:00:00.{:09}Z", 0)),
     }
@@ -287,6 +307,9 @@ fn sample_excluded_music() -> Music {
         end_ms: 180_000,
         liked: false,
         loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
     }
 }
 
@@ -719,10 +742,10 @@ fn add_exclude_is_idempotent_and_remove_exclude_deletes_the_row() {
         ))
         .await
         .expect("exclude identity collection should exist before exclude writes");
-        let first = add_exclude(music.clone())
+        let first = add_exclude(music.clone(), None)
             .await
             .expect("first exclude add should succeed");
-        let second = add_exclude(music.clone())
+        let second = add_exclude(music.clone(), None)
             .await
             .expect("second exclude add should reuse the same row");
         let exclude_count = count_excludes().await;
@@ -775,10 +798,10 @@ fn exclude_identity_keeps_different_segments_separate() {
         .await
         .expect("segmented exclude collection should exist before exclude writes");
 
-        add_exclude(first_segment.clone())
+        add_exclude(first_segment.clone(), None)
             .await
             .expect("first segment exclude should succeed");
-        add_exclude(second_segment.clone())
+        add_exclude(second_segment.clone(), None)
             .await
             .expect("second segment exclude should succeed");
 
@@ -795,6 +818,95 @@ fn exclude_identity_keeps_different_segments_separate() {
     });
 }
 
+#[test]
+fn timed_exclude_stops_counting_after_expiry_and_is_lifted() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_collection_write_schema().await;
+
+        let music = sample_excluded_music();
+        let collection_url = music.group.collection.url.clone();
+        upsert_collection(&collection_with_musics(
+            &collection_url,
+            &music.group.collection.folder,
+            Some(false),
+            vec![music.clone()],
+        ))
+        .await
+        .expect("timed exclude collection should exist before exclude writes");
+
+        assert!(
+            add_exclude(music.clone(), Some("next week".to_string()))
+                .await
+                .is_err()
+        );
+        assert!(
+            add_exclude(
+                music.clone(),
+                Some((Utc::now() - Duration::minutes(1)).to_rfc3339()),
+            )
+            .await
+            .is_err()
+        );
+        let snoozed = add_exclude(
+            music.clone(),
+            Some((Utc::now() + Duration::days(14)).to_rfc3339()),
+        )
+        .await
+        .expect("timed exclude should save");
+        assert!(snoozed.exclude.expires_at.is_some());
+        assert_eq!(
+            next_exclude_expiry()
+                .await
+                .expect("next expiry should load")
+                .map(|expires_at| expires_at.to_rfc3339()),
+            snoozed.exclude.expires_at
+        );
+        assert!(
+            snoozed
+                .exclude_availability
+                .fully_excluded_collection_urls
+                .contains(&collection_url)
+        );
+        assert!(
+            is_music_identity_excluded_for_playback(&music.url, music.start_ms, music.end_ms)
+                .await
+                .expect("exclude lookup should succeed")
+        );
+        assert!(
+            lift_expired_excludes(Utc::now())
+                .await
+                .expect("sweep should succeed")
+                .is_empty()
+        );
+
+        let lifted = lift_expired_excludes(Utc::now() + Duration::days(15))
+            .await
+            .expect("sweep should succeed");
+        assert_eq!(lifted.len(), 1);
+        assert_eq!(lifted[0].url, music.url);
+        assert_eq!(count_excludes().await, 0);
+        assert_eq!(
+            next_exclude_expiry()
+                .await
+                .expect("next expiry should load"),
+            None
+        );
+        assert!(
+            !list_config_library()
+                .await
+                .expect("config library should reload availability")
+                .exclude_availability
+                .fully_excluded_collection_urls
+                .contains(&collection_url)
+        );
+
+        reset_db();
+    });
+}
+
 #[test]
 fn remove_exclude_returns_false_when_table_is_missing() {
     let _guard = acquire_db_test_lock();
@@ -834,7 +946,7 @@ fn exclude_availability_marks_and_restores_fully_excluded_owners() {
         upsert_collection(&collection)
             .await
             .expect("collection should exist before exclude availability updates");
-        let first_result = add_exclude(first_music.clone())
+        let first_result = add_exclude(first_music.clone(), None)
             .await
             .expect("first exclude should update availability");
 
@@ -851,7 +963,7 @@ fn exclude_availability_marks_and_restores_fully_excluded_owners() {
                 .is_empty()
         );
 
-        let second_result = add_exclude(second_music.clone())
+        let second_result = add_exclude(second_music.clone(), None)
             .await
             .expect("second exclude should update availability");
 
@@ -948,7 +1060,7 @@ fn list_config_library_does_not_rebuild_missing_exclude_availability() {
                 .is_empty()
         );
 
-        add_exclude(music)
+        add_exclude(music, None)
             .await
             .expect("exclude write should update availability");
         let library_after_exclude = list_config_library()
@@ -1163,6 +1275,9 @@ fn upsert_collection_bootstraps_collection_graph_schema_on_clean_db() {
                 end_ms: 316_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
         );
 
@@ -1264,6 +1379,156 @@ fn update_music_changes_display_alias_and_range_from_original_identity() {
     });
 }
 
+#[test]
+fn split_music_replaces_the_range_with_pieces_that_inherit_its_evidence() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let mut collection = grouped_collection("https://example.com/music-split");
+        collection.musics[0].liked = true;
+        collection.musics[0].loudness_profile = LoudnessProfile::from_integrated_lufs(-14.5);
+        let collection = upsert_collection(&collection)
+            .await
+            .expect("grouped collection should save before split");
+        let original = collection.musics[0].clone();
+        upsert_playlist_surface(
+            &PlayListWriteRequest::from_playlist(&PlayList {
+                name: "Split Extras".to_string(),
+                collections: vec![],
+                groups: vec![],
+                extra: vec![],
+                playlists: None,
+                rules: None,
+                created_at: AutoFill::pending(),
+            }),
+            None,
+        )
+        .await
+        .expect("extra playlist should save");
+        push_extra("Split Extras", original.clone())
+            .await
+            .expect("original should be pinned");
+
+        assert!(
+            split_music(&original.url, 0, 180_000, &[60_000, 30_000])
+                .await
+                .is_err()
+        );
+        assert!(
+            split_music(&original.url, 0, 180_000, &[180_000])
+                .await
+                .is_err()
+        );
+        let pieces = split_music(&original.url, 0, 180_000, &[60_000, 120_000])
+            .await
+            .expect("music split should succeed")
+            .expect("split target should exist");
+
+        let ranges = pieces
+            .iter()
+            .map(|piece| (piece.alias.as_str(), piece.start_ms, piece.end_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                ("Track (1/3)", 0, 60_000),
+                ("Track (2/3)", 60_000, 120_000),
+                ("Track (3/3)", 120_000, 180_000),
+            ]
+        );
+        assert!(pieces.iter().all(|piece| {
+            piece.liked
+                && piece.group.url == original.group.url
+                && piece.loudness_profile == original.loudness_profile
+                && piece.canonical_music_id
+                    == music_canonical_id(&piece.url, piece.start_ms, piece.end_ms)
+        }));
+
+        let reloaded = get_collection_by_url(&collection.url)
+            .await
+            .expect("split collection should reload")
+            .expect("split collection should exist");
+        assert_eq!(reloaded.musics.len(), 3);
+        assert!(
+            !reloaded
+                .musics
+                .iter()
+                .any(|music| music.end_ms - music.start_ms == 180_000)
+        );
+        let pinned = get_playlist_by_name("Split Extras")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should exist");
+        assert_eq!(
+            pinned
+                .extra
+                .iter()
+                .map(|music| (music.start_ms, music.end_ms))
+                .collect::<Vec<_>>(),
+            vec![(0, 60_000), (60_000, 120_000), (120_000, 180_000)]
+        );
+        assert!(
+            split_music(&original.url, 0, 180_000, &[90_000])
+                .await
+                .expect("missing split target should not fail")
+                .is_none()
+        );
+
+        reset_db();
+    });
+}
+
+#[test]
+fn split_music_rejected_in_one_collection_leaves_every_collection_untouched() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let first = upsert_collection(&grouped_collection("https://example.com/split-first"))
+            .await
+            .expect("first collection should save");
+        let track_url = first.musics[0].url.clone();
+        let mut second = grouped_collection("https://example.com/split-second");
+        second.musics[0].url = track_url.clone();
+        let mut piece = second.musics[0].clone();
+        piece.end_ms = 90_000;
+        second.musics.push(piece);
+        upsert_collection(&second)
+            .await
+            .expect("second collection should save");
+
+        assert!(
+            split_music(&track_url, 0, 180_000, &[90_000])
+                .await
+                .is_err()
+        );
+
+        for url in [
+            "https://example.com/split-first",
+            "https://example.com/split-second",
+        ] {
+            let reloaded = get_collection_by_url(url)
+                .await
+                .expect("collection should reload")
+                .expect("collection should exist");
+            assert!(
+                reloaded
+                    .musics
+                    .iter()
+                    .any(|music| music.start_ms == 0 && music.end_ms == 180_000),
+                "{url} should keep the unsplit range"
+            );
+        }
+
+        reset_db();
+    });
+}
+
 #[test]
 fn trim_collection_music_end_moves_identity_and_clears_loudness_profile() {
     let _guard = acquire_db_test_lock();
@@ -1601,12 +1866,14 @@ fn tail_trim_absorbs_existing_target_occurrence_without_unique_index_conflict()
             collections: vec![],
             groups: vec![],
             extra: vec![original.clone()],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         upsert_playlist(&playlist, None)
             .await
             .expect("playlist extra should save before tail trim");
-        add_exclude(original.clone())
+        add_exclude(original.clone(), None)
             .await
             .expect("longer music exclude should save before tail trim");
         let collection_record = saved
@@ -1827,6 +2094,9 @@ fn loudness_identity_projection_exact_match_uses_file_path() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         let second = Music {
             occurrence_id: String::new(),
@@ -1840,6 +2110,9 @@ fn loudness_identity_projection_exact_match_uses_file_path() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         upsert_collection(&collection_with_musics(
             collection_url,
@@ -1906,6 +2179,9 @@ fn canonical_music_identity_shares_liked_state_across_future_occurrences() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         let second_music = Music {
             occurrence_id: String::new(),
@@ -1919,6 +2195,9 @@ fn canonical_music_identity_shares_liked_state_across_future_occurrences() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
 
         upsert_collection(&collection_with_musics(
@@ -2032,6 +2311,9 @@ fn canonical_music_identity_shares_loudness_state_across_future_occurrences() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         let second_music = Music {
             occurrence_id: String::new(),
@@ -2045,6 +2327,9 @@ fn canonical_music_identity_shares_loudness_state_across_future_occurrences() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
 
         upsert_collection(&collection_with_musics(
@@ -2120,6 +2405,9 @@ fn loudness_profile_identity_ignores_non_unique_group_relations() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
 
         upsert_collection(&collection_with_musics(
@@ -2203,6 +2491,9 @@ fn create_music_appends_to_the_source_collection_once() {
             end_ms: 180_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
 
         let first = create_music(&collection.url, &created_music)
@@ -2257,6 +2548,9 @@ fn create_music_rejects_missing_source_collection() {
                 end_ms: 180_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             },
         )
         .await
@@ -2306,6 +2600,9 @@ fn delete_music_removes_only_the_matching_music_identity() {
                     end_ms: 120_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -2327,6 +2624,9 @@ fn delete_music_removes_only_the_matching_music_identity() {
                     end_ms: 240_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
             ],
         );
@@ -2394,6 +2694,9 @@ fn list_musics_by_file_path_reads_matching_database_music_records() {
                     end_ms: 120_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -2415,6 +2718,9 @@ fn list_musics_by_file_path_reads_matching_database_music_records() {
                     end_ms: 240_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -2436,6 +2742,9 @@ fn list_musics_by_file_path_reads_matching_database_music_records() {
                     end_ms: 60_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
             ],
         );
@@ -2493,6 +2802,9 @@ fn load_spectrum_music_context_carries_source_owner_evidence_without_playlist_hy
                     end_ms: 120_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -2510,6 +2822,9 @@ fn load_spectrum_music_context_carries_source_owner_evidence_without_playlist_hy
                     end_ms: 240_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
                 Music {
                     occurrence_id: String::new(),
@@ -2527,6 +2842,9 @@ fn load_spectrum_music_context_carries_source_owner_evidence_without_playlist_hy
                     end_ms: 60_000,
                     liked: false,
                     loudness_profile: None,
+                    added_at: None,
+                    tags: None,
+                    lyrics_path: None,
                 },
             ],
         );
@@ -2600,6 +2918,9 @@ fn load_spectrum_music_context_filters_collection_candidates_by_path_components(
                 end_ms: 120_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
         );
         let neighbor = collection_with_musics(
@@ -2626,6 +2947,9 @@ fn load_spectrum_music_context_filters_collection_candidates_by_path_components(
                 end_ms: 120_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
         );
         let _ = upsert_collection(&collection)
@@ -2992,7 +3316,7 @@ fn list_config_library_reads_collection_and_group_surfaces() {
         let group_record = insert_group_row("repo-config-library-group", &playlist.groups[0]).await;
         insert_collection_group_edge(&collection_record, &group_record).await;
         let excluded_music = playlist.collections[0].musics[0].clone();
-        add_exclude(excluded_music.clone())
+        add_exclude(excluded_music.clone(), None)
             .await
             .expect("exclude row should save before config library load");
 
@@ -3099,6 +3423,8 @@ fn upsert_playlist_does_not_clobber_existing_collection_graph_data() {
             }],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -3163,6 +3489,8 @@ fn upsert_playlist_surface_is_immediately_usable_for_playback_selection() {
             }],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -3231,6 +3559,8 @@ fn upsert_playlist_surface_rename_is_immediately_usable_for_playback_selection()
             }],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let renamed = PlayList {
@@ -3289,6 +3619,8 @@ fn upsert_playlist_surface_releases_previous_title_for_later_create() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let renamed = PlayList {
@@ -3300,6 +3632,8 @@ fn upsert_playlist_surface_releases_previous_title_for_later_create() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
 
@@ -3349,6 +3683,8 @@ fn upsert_playlist_surface_rejects_create_when_title_is_still_occupied() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let duplicate = PlayList {
@@ -3356,6 +3692,8 @@ fn upsert_playlist_surface_rejects_create_when_title_is_still_occupied() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
 
@@ -3388,6 +3726,8 @@ fn claim_generated_playlist_name_uses_repository_name_index_not_visible_seed() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
 
@@ -3465,6 +3805,8 @@ fn upsert_playlist_surface_rename_reports_playback_selection_change_only_for_ref
             }],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let changed = PlayList {
@@ -3531,6 +3873,8 @@ fn upsert_playlist_surface_writes_extra_refs_without_hydrating_music() {
             collections: vec![],
             groups: vec![],
             extra: vec![extra_music.clone()],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let upsert = upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
@@ -3602,6 +3946,8 @@ fn upsert_playlist_persists_extra_and_config_view_hydrates_music() {
             collections: vec![],
             groups: vec![],
             extra: vec![extra_music.clone()],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         let saved = upsert_playlist(&playlist, None)
@@ -3680,6 +4026,8 @@ fn get_playlist_config_repairs_duplicate_extra_music_owner_edges_before_hydratin
             collections: vec![],
             groups: vec![],
             extra: vec![saved_extra_music.clone()],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         upsert_playlist(&playlist, None)
@@ -3728,6 +4076,8 @@ fn push_extra_is_idempotent_and_remove_extra_updates_config() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         upsert_playlist(&playlist, None)
@@ -3791,6 +4141,8 @@ fn push_extra_updates_only_extra_refs() {
             collections: vec![],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         upsert_playlist(&playlist, None)
@@ -3860,9 +4212,12 @@ fn upsert_playlist_rejects_unknown_collection_refs() {
                 musics: vec![],
                 last_updated: "2026-04-12T00:00:00+00:00".to_string(),
                 enable_updates: None,
+                save_root: None,
             }],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -3914,6 +4269,8 @@ fn upsert_playlist_rejects_unknown_group_refs() {
                 folder: "Missing Disc".to_string(),
             }],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -3988,6 +4345,9 @@ fn playlist_playback_selection_reads_refs_without_hydrating_unselected_collectio
             end_ms: 60_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         let selected_collection = collection_with_musics(
             "https://example.com/selected",
@@ -4015,6 +4375,9 @@ fn playlist_playback_selection_reads_refs_without_hydrating_unselected_collectio
                 end_ms: 60_000,
                 liked: false,
                 loudness_profile: None,
+                added_at: None,
+                tags: None,
+                lyrics_path: None,
             }],
         );
 
@@ -4054,6 +4417,8 @@ fn playlist_playback_selection_reads_refs_without_hydrating_unselected_collectio
             collections: vec![selected_collection.clone()],
             groups: vec![selected_group.clone()],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4131,7 +4496,7 @@ fn playlist_playback_sources_skip_excluded_music() {
             &[selected_music_record, playable_music_record],
         )
         .await;
-        add_exclude(excluded_music.clone())
+        add_exclude(excluded_music.clone(), None)
             .await
             .expect("exclude row should save before playback source load");
 
@@ -4140,6 +4505,8 @@ fn playlist_playback_sources_skip_excluded_music() {
             collections: vec![selected_collection.clone()],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4174,6 +4541,112 @@ fn playlist_playback_sources_skip_excluded_music() {
     });
 }
 
+#[test]
+fn playlist_scoped_exclude_only_hides_music_in_that_playlist() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let group = collection_group(
+            "Disc 1",
+            "https://example.com/scoped-exclude#disc-1",
+            "Disc 1",
+        );
+        let skipped_music = named_music("Scoped Skip", group.clone(), "Skip.m4a");
+        let playable_music = named_music("Scoped Keep", group.clone(), "Keep.m4a");
+        let collection = collection_with_musics(
+            "https://example.com/scoped-exclude",
+            "youtube/scoped-exclude",
+            Some(false),
+            vec![skipped_music.clone(), playable_music.clone()],
+        );
+        let collection_record =
+            insert_collection_row("scoped-exclude-collection", &collection).await;
+        let group_record = insert_group_row("scoped-exclude-group", &group).await;
+        insert_collection_group_edge(&collection_record, &group_record).await;
+        let skipped_record = insert_music_row("scoped-exclude-skip", &skipped_music).await;
+        let playable_record = insert_music_row("scoped-exclude-keep", &playable_music).await;
+        insert_music_edges(
+            &collection_record,
+            &[skipped_record.clone(), playable_record.clone()],
+        )
+        .await;
+        insert_group_edges(&group_record, &[skipped_record, playable_record]).await;
+        for (key, name) in [
+            ("scoped-exclude-workout", "Workout"),
+            ("scoped-exclude-everything", "Everything"),
+        ] {
+            let playlist = PlayList {
+                name: name.to_string(),
+                collections: vec![collection.clone()],
+                groups: vec![],
+                extra: vec![],
+                playlists: None,
+                rules: None,
+                created_at: AutoFill::pending(),
+            };
+            insert_playlist_row(
+                key,
+                &playlist,
+                std::slice::from_ref(&collection_record),
+                &[],
+                &[],
+            )
+            .await;
+        }
+
+        let scoped = add_playlist_exclude(skipped_music.clone(), "Workout", None)
+            .await
+            .expect("scoped exclude should save");
+        assert_eq!(scoped.exclude.playlists, vec!["Workout".to_string()]);
+        assert!(
+            scoped
+                .exclude_availability
+                .fully_excluded_group_urls
+                .is_empty()
+        );
+        assert!(
+            !is_music_identity_excluded_for_playback(
+                &skipped_music.url,
+                skipped_music.start_ms,
+                skipped_music.end_ms
+            )
+            .await
+            .expect("exclude lookup should succeed")
+        );
+
+        let workout = get_playlist_playback_selection_by_name("Workout")
+            .await
+            .expect("playback selection lookup should succeed")
+            .expect("playback selection should exist");
+        let workout_sources = load_playlist_playback_track_sources(&workout, 4)
+            .await
+            .expect("playback sources should load");
+        assert_eq!(workout_sources.len(), 1);
+        assert_eq!(workout_sources[0].music.url, playable_music.url);
+        let everything = get_playlist_playback_selection_by_name("Everything")
+            .await
+            .expect("playback selection lookup should succeed")
+            .expect("playback selection should exist");
+        assert_eq!(
+            load_playlist_playback_track_sources(&everything, 4)
+                .await
+                .expect("playback sources should load")
+                .len(),
+            2
+        );
+
+        delete_playlist_by_name("Workout")
+            .await
+            .expect("playlist delete should succeed");
+        assert_eq!(count_excludes().await, 0);
+
+        reset_db();
+    });
+}
+
 #[test]
 fn playlist_playback_sources_include_extra_music() {
     let _guard = acquire_db_test_lock();
@@ -4203,6 +4676,8 @@ fn playlist_playback_sources_include_extra_music() {
             collections: vec![],
             groups: vec![],
             extra: vec![extra_music.clone()],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         insert_playlist_row(
@@ -4285,6 +4760,8 @@ fn liked_playlist_playback_sources_include_liked_extra_music() {
             collections: vec![],
             groups: vec![],
             extra: vec![liked_extra.clone(), unliked_extra],
+            playlists: None,
+            rules: None,
             created_at: AutoFill::pending(),
         };
         insert_playlist_row(
@@ -4365,7 +4842,7 @@ fn liked_playlist_playback_sources_skip_excluded_music() {
             &[selected_music_record.clone(), playable_music_record],
         )
         .await;
-        add_exclude(excluded_music)
+        add_exclude(excluded_music, None)
             .await
             .expect("exclude row should save before liked playback source load");
 
@@ -4374,6 +4851,8 @@ fn liked_playlist_playback_sources_skip_excluded_music() {
             collections: vec![selected_collection],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4431,7 +4910,7 @@ fn playlist_playback_sources_return_empty_when_all_music_is_excluded() {
             std::slice::from_ref(&selected_music_record),
         )
         .await;
-        add_exclude(excluded_music)
+        add_exclude(excluded_music, None)
             .await
             .expect("exclude row should save before playback source load");
 
@@ -4440,6 +4919,8 @@ fn playlist_playback_sources_return_empty_when_all_music_is_excluded() {
             collections: vec![selected_collection],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4514,6 +4995,8 @@ fn playlist_playback_sources_deduplicate_collection_group_and_extra_by_canonical
             collections: vec![collection],
             groups: vec![group],
             extra: vec![duplicate_music.clone()],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4629,6 +5112,8 @@ fn liked_playlist_playback_sources_preserve_owner_and_position_order() {
             collections: vec![first_collection, second_collection],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4684,6 +5169,8 @@ fn group_playlist_playback_sources_skip_music_without_parent_collection() {
             collections: vec![],
             groups: vec![group.clone()],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4764,6 +5251,8 @@ fn extra_playlist_playback_sources_preserve_playlist_ref_order_after_filtering()
                 skipped_music.clone(),
                 first_music.clone(),
             ],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4822,6 +5311,9 @@ fn playlist_playback_selection_adds_parent_download_scope_for_group_only_refs()
             end_ms: 60_000,
             liked: false,
             loudness_profile: None,
+            added_at: None,
+            tags: None,
+            lyrics_path: None,
         };
         let selected_collection = collection_with_musics(
             "https://example.com/group-only",
@@ -4853,6 +5345,8 @@ fn playlist_playback_selection_adds_parent_download_scope_for_group_only_refs()
             collections: vec![],
             groups: vec![selected_group.clone()],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -4943,6 +5437,8 @@ fn playlist_playback_sources_respect_ordered_window_limit() {
             collections: vec![selected_collection.clone()],
             groups: vec![selected_group.clone()],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -5070,6 +5566,8 @@ fn random_playlist_playback_source_uses_selected_playlist_scope() {
             collections: vec![selected_collection.clone()],
             groups: vec![selected_group],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -5137,6 +5635,8 @@ fn random_playlist_playback_source_skips_music_without_downloaded_path() {
             collections: vec![collection],
             groups: vec![],
             extra: vec![],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -5208,6 +5708,8 @@ fn extra_playlist_playback_sources_skip_music_without_downloaded_path() {
             collections: vec![],
             groups: vec![],
             extra: vec![pending_music, playable_music.clone()],
+            playlists: None,
+            rules: None,
 
             created_at: AutoFill::pending(),
         };
@@ -5252,15 +5754,18 @@ fn playlist_playback_selection_contains_only_its_own_track_sources() {
     let group = collection_group("Selected Group", "https://example.com/group", "Disc 1");
     let inside_collection = PlaylistPlaybackTrackSource {
         collection_folder: "youtube/selected".to_string(),
+        collection_save_root: None,
         music: named_music("A", group.clone(), "A.m4a"),
     };
     let inside_group = PlaylistPlaybackTrackSource {
         collection_folder: "youtube/other".to_string(),
+        collection_save_root: None,
         music: named_music("B", group.clone(), "B.m4a"),
     };
     let outside_group = collection_group("Other Group", "https://example.com/other", "Disc 1");
     let outside = PlaylistPlaybackTrackSource {
         collection_folder: "youtube/other".to_string(),
+        collection_save_root: None,
         music: named_music("C", outside_group, "C.m4a"),
     };
     let selection = PlaylistPlaybackSelection {
@@ -5272,10 +5777,495 @@ fn playlist_playback_selection_contains_only_its_own_track_sources() {
             "Disc 1",
         )],
         extra: vec![],
+        rules: vec![],
         download_scopes: vec![],
     };
 
     assert!(selection.contains_track_source(&inside_collection));
     assert!(selection.contains_track_source(&inside_group));
     assert!(!selection.contains_track_source(&outside));
+
+    let rules_only = PlaylistPlaybackSelection {
+        playlist_name: "Smart".to_string(),
+        collections: vec![],
+        groups: vec![],
+        extra: vec![],
+        rules: vec![PlaylistRules::default()],
+        download_scopes: vec![],
+    };
+    assert!(!rules_only.contains_track_source(&outside));
+}
+
+#[test]
+fn smart_playlist_rules_select_matching_library_music() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let selected_url = "https://example.com/smart-selected";
+        let selected_folder = "youtube/smart-selected";
+        let selected_group = collection_group("Smart Selected", selected_url, selected_folder);
+        let mut liked_loud = named_music("Liked Loud", selected_group.clone(), "Liked Loud.m4a");
+        liked_loud.liked = true;
+        liked_loud.loudness_profile = LoudnessProfile::from_integrated_lufs(-9.0);
+        let mut liked_quiet = named_music("Liked Quiet", selected_group.clone(), "Liked Quiet.m4a");
+        liked_quiet.liked = true;
+        liked_quiet.loudness_profile = LoudnessProfile::from_integrated_lufs(-21.0);
+        let mut liked_short = named_music("Liked Short", selected_group, "Liked Short.m4a");
+        liked_short.liked = true;
+        liked_short.end_ms = 30_000;
+        liked_short.canonical_music_id = music_canonical_id(&liked_short.url, 0, 30_000);
+        liked_short.loudness_profile = LoudnessProfile::from_integrated_lufs(-8.0);
+        upsert_collection(&collection_with_musics(
+            selected_url,
+            selected_folder,
+            Some(false),
+            vec![liked_loud.clone(), liked_quiet, liked_short],
+        ))
+        .await
+        .expect("selected smart collection should persist");
+
+        let other_url = "https://example.com/smart-other";
+        let other_folder = "youtube/smart-other";
+        let mut other_liked_loud = named_music(
+            "Other Liked Loud",
+            collection_group("Smart Other", other_url, other_folder),
+            "Other Liked Loud.m4a",
+        );
+        other_liked_loud.liked = true;
+        other_liked_loud.loudness_profile = LoudnessProfile::from_integrated_lufs(-10.0);
+        upsert_collection(&collection_with_musics(
+            other_url,
+            other_folder,
+            Some(false),
+            vec![other_liked_loud],
+        ))
+        .await
+        .expect("other smart collection should persist");
+
+        let rules = PlaylistRules {
+            liked: Some(true),
+            min_integrated_lufs: Some(-14.0),
+            max_integrated_lufs: None,
+            source_collection_urls: vec![selected_url.to_string()],
+            min_duration_ms: Some(60_000),
+            max_duration_ms: None,
+            added_after: Some("2000-01-01".to_string()),
+            added_before: None,
+        };
+        let playlist = PlayList {
+            name: "Smart Loud Likes".to_string(),
+            collections: vec![],
+            groups: vec![],
+            extra: vec![],
+            playlists: None,
+            rules: Some(rules.clone()),
+            created_at: AutoFill::pending(),
+        };
+        let created =
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+                .await
+                .expect("smart playlist should save");
+        assert!(created.playback_selection_changed);
+
+        let selection = get_playlist_playback_selection_by_name(&playlist.name)
+            .await
+            .expect("smart playback selection lookup should succeed")
+            .expect("smart playback selection should exist");
+        let sources = load_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("smart playback sources should load");
+        let random_sources = load_random_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("random smart playback sources should load");
+
+        assert_eq!(selection.rules, vec![rules.clone()]);
+        assert_eq!(selection.download_scopes, vec![selected_url.to_string()]);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].music.url, liked_loud.url);
+        assert_eq!(sources[0].collection_folder, selected_folder);
+        assert!(sources[0].music.added_at.is_some());
+        assert_eq!(random_sources.len(), 1);
+        assert_eq!(random_sources[0].music.url, liked_loud.url);
+
+        let unchanged = upsert_playlist_surface(
+            &PlayListWriteRequest::from_playlist(&playlist),
+            Some(&playlist.name),
+        )
+        .await
+        .expect("unchanged smart playlist should save");
+        assert!(!unchanged.playback_selection_changed);
+
+        let widened = PlayList {
+            rules: Some(PlaylistRules {
+                source_collection_urls: vec![],
+                ..rules
+            }),
+            ..playlist.clone()
+        };
+        let changed = upsert_playlist_surface(
+            &PlayListWriteRequest::from_playlist(&widened),
+            Some(&playlist.name),
+        )
+        .await
+        .expect("widened smart playlist should save");
+        assert!(changed.playback_selection_changed);
+
+        let selection = get_playlist_playback_selection_by_name(&playlist.name)
+            .await
+            .expect("widened smart playback selection lookup should succeed")
+            .expect("widened smart playback selection should exist");
+        let sources = load_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("widened smart playback sources should load");
+        assert_eq!(sources.len(), 2);
+
+        reset_db();
+    });
+}
+
+#[test]
+fn smart_playlist_loudness_ceiling_skips_unanalysed_music() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let url = "https://example.com/smart-quiet";
+        let folder = "youtube/smart-quiet";
+        let group = collection_group("Smart Quiet", url, folder);
+        let mut quiet = named_music("Quiet", group.clone(), "Quiet.m4a");
+        quiet.loudness_profile = LoudnessProfile::from_integrated_lufs(-20.0);
+        let mut loud = named_music("Loud", group.clone(), "Loud.m4a");
+        loud.loudness_profile = LoudnessProfile::from_integrated_lufs(-8.0);
+        let unanalysed = named_music("Unanalysed", group, "Unanalysed.m4a");
+        upsert_collection(&collection_with_musics(
+            url,
+            folder,
+            Some(false),
+            vec![quiet.clone(), loud, unanalysed],
+        ))
+        .await
+        .expect("smart quiet collection should persist");
+
+        let playlist = PlayList {
+            name: "Smart Quiet".to_string(),
+            collections: vec![],
+            groups: vec![],
+            extra: vec![],
+            playlists: None,
+            rules: Some(PlaylistRules {
+                max_integrated_lufs: Some(-14.0),
+                ..PlaylistRules::default()
+            }),
+            created_at: AutoFill::pending(),
+        };
+        upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+            .await
+            .expect("smart quiet playlist should save");
+
+        let selection = get_playlist_playback_selection_by_name(&playlist.name)
+            .await
+            .expect("smart quiet selection lookup should succeed")
+            .expect("smart quiet selection should exist");
+        let sources = load_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("smart quiet sources should load");
+        let random_sources = load_random_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("random smart quiet sources should load");
+
+        assert_eq!(
+            sources
+                .iter()
+                .map(|source| source.music.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![quiet.url.as_str()]
+        );
+        assert_eq!(random_sources.len(), 1);
+        assert_eq!(random_sources[0].music.url, quiet.url);
+
+        reset_db();
+    });
+}
+
+#[test]
+fn smart_playlist_rules_reject_inverted_ranges() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+
+        let request = PlayListWriteRequest {
+            name: "Inverted Smart".to_string(),
+            collections: vec![],
+            groups: vec![],
+            extra: vec![],
+            playlists: vec![],
+            rules: Some(PlaylistRules {
+                min_duration_ms: Some(120_000),
+                max_duration_ms: Some(60_000),
+                ..PlaylistRules::default()
+            }),
+            created_at: AutoFill::pending(),
+        };
+
+        assert!(upsert_playlist_surface(&request, None).await.is_err());
+        assert!(
+            get_playlist_by_name(&request.name)
+                .await
+                .expect("playlist lookup should succeed")
+                .is_none()
+        );
+
+        reset_db();
+    });
+}
+
+fn including_playlist(name: &str, collections: Vec<Collection>, playlists: &[&str]) -> PlayList {
+    PlayList {
+        name: name.to_string(),
+        collections,
+        groups: vec![],
+        extra: vec![],
+        playlists: Some(playlists.iter().map(|name| name.to_string()).collect()),
+        rules: None,
+        created_at: AutoFill::pending(),
+    }
+}
+
+#[test]
+fn included_playlists_merge_into_playback_selection() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let focus_url = "https://example.com/focus-base";
+        let focus_folder = "youtube/focus-base";
+        let focus = collection_with_musics(
+            focus_url,
+            focus_folder,
+            Some(false),
+            vec![named_music(
+                "Focus Track",
+                collection_group("Focus Base", focus_url, focus_folder),
+                "Focus Track.m4a",
+            )],
+        );
+        let ambient_url = "https://example.com/ambient-base";
+        let ambient_folder = "youtube/ambient-base";
+        let ambient = collection_with_musics(
+            ambient_url,
+            ambient_folder,
+            Some(false),
+            vec![named_music(
+                "Ambient Track",
+                collection_group("Ambient Base", ambient_url, ambient_folder),
+                "Ambient Track.m4a",
+            )],
+        );
+        upsert_collection(&focus)
+            .await
+            .expect("focus collection should persist");
+        upsert_collection(&ambient)
+            .await
+            .expect("ambient collection should persist");
+
+        for playlist in [
+            including_playlist("Focus", vec![focus.clone()], &[]),
+            including_playlist("Ambient", vec![ambient], &[]),
+            including_playlist("Mix", vec![focus], &["Focus", "Ambient"]),
+            including_playlist("Mix Plus", vec![], &["Mix", "Focus"]),
+        ] {
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+                .await
+                .expect("playlist should save");
+        }
+
+        let selection = get_playlist_playback_selection_by_name("Mix Plus")
+            .await
+            .expect("included playback selection lookup should succeed")
+            .expect("included playback selection should exist");
+        let sources = load_playlist_playback_track_sources(&selection, 8)
+            .await
+            .expect("included playback sources should load");
+
+        assert_eq!(
+            selection
+                .collections
+                .iter()
+                .map(|collection| collection.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![focus_url, ambient_url]
+        );
+        assert_eq!(
+            selection.download_scopes,
+            vec![focus_url.to_string(), ambient_url.to_string()]
+        );
+        assert_eq!(sources.len(), 2);
+        let mut dependents = list_dependent_playlist_names("Focus")
+            .await
+            .expect("dependent playlists should load");
+        dependents.sort();
+        assert_eq!(dependents, vec!["Mix".to_string(), "Mix Plus".to_string()]);
+
+        let renamed = including_playlist("Focus Set", vec![], &[]);
+        upsert_playlist_surface(
+            &PlayListWriteRequest::from_playlist(&renamed),
+            Some("Focus"),
+        )
+        .await
+        .expect("included playlist rename should save");
+        assert!(
+            delete_playlist_by_name("Ambient")
+                .await
+                .expect("included playlist delete should succeed")
+        );
+
+        let mix = get_playlist_by_name("Mix")
+            .await
+            .expect("mix lookup should succeed")
+            .expect("mix should exist");
+        let mix_plus = get_playlist_by_name("Mix Plus")
+            .await
+            .expect("mix plus lookup should succeed")
+            .expect("mix plus should exist");
+        assert_eq!(mix.playlists, Some(vec!["Focus Set".to_string()]));
+        assert_eq!(
+            mix_plus.playlists,
+            Some(vec!["Mix".to_string(), "Focus Set".to_string()])
+        );
+
+        reset_db();
+    });
+}
+
+#[test]
+fn included_playlists_reject_missing_refs_and_cycles() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let base = including_playlist("Base", vec![], &[]);
+        let mix = including_playlist("Mix", vec![], &["Base"]);
+        for playlist in [&base, &mix] {
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(playlist), None)
+                .await
+                .expect("playlist should save");
+        }
+
+        let missing = including_playlist("Missing Include", vec![], &["Nowhere"]);
+        let itself = including_playlist("Base", vec![], &["Base"]);
+        let cycle = including_playlist("Base", vec![], &["Mix"]);
+        let renamed_cycle = including_playlist("Base Renamed", vec![], &["Mix"]);
+
+        assert!(
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&missing), None)
+                .await
+                .is_err()
+        );
+        assert!(
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&itself), Some("Base"))
+                .await
+                .is_err()
+        );
+        assert!(
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&cycle), Some("Base"))
+                .await
+                .is_err()
+        );
+        assert!(
+            upsert_playlist_surface(
+                &PlayListWriteRequest::from_playlist(&renamed_cycle),
+                Some("Base")
+            )
+            .await
+            .is_err()
+        );
+        assert!(
+            get_playlist_by_name("Base")
+                .await
+                .expect("base lookup should succeed")
+                .expect("base should exist")
+                .playlists
+                .unwrap_or_default()
+                .is_empty()
+        );
+
+        reset_db();
+    });
+}
+
+#[test]
+fn playback_selection_skips_stored_include_cycles() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        ensure_db().await;
+        bootstrap_playlist_read_schema().await;
+
+        let left_url = "https://example.com/cycle-left";
+        let left = collection_with_musics(
+            left_url,
+            "youtube/cycle-left",
+            Some(false),
+            vec![named_music(
+                "Left Track",
+                collection_group("Cycle Left", left_url, "youtube/cycle-left"),
+                "Left Track.m4a",
+            )],
+        );
+        let right_url = "https://example.com/cycle-right";
+        let right = collection_with_musics(
+            right_url,
+            "youtube/cycle-right",
+            Some(false),
+            vec![named_music(
+                "Right Track",
+                collection_group("Cycle Right", right_url, "youtube/cycle-right"),
+                "Right Track.m4a",
+            )],
+        );
+        upsert_collection(&left)
+            .await
+            .expect("left collection should persist");
+        upsert_collection(&right)
+            .await
+            .expect("right collection should persist");
+        upsert_playlist(&including_playlist("Left", vec![left], &["Right"]), None)
+            .await
+            .expect("left playlist row should persist");
+        upsert_playlist(&including_playlist("Right", vec![right], &["Left"]), None)
+            .await
+            .expect("right playlist row should persist");
+
+        let selection = get_playlist_playback_selection_by_name("Left")
+            .await
+            .expect("cyclic playback selection lookup should succeed")
+            .expect("cyclic playback selection should exist");
+
+        assert_eq!(
+            selection
+                .collections
+                .iter()
+                .map(|collection| collection.url.as_str())
+                .collect::<Vec<_>>(),
+            vec![left_url, right_url]
+        );
+        assert_eq!(
+            list_dependent_playlist_names("Left")
+                .await
+                .expect("cyclic dependents should load"),
+            vec!["Right".to_string()]
+        );
+
+        reset_db();
+    });
 }
@@ -0,0 +1,489 @@
+use super::model::{LibrarySearchHit, Music, SpectrumMusicSourceContext};
+#[cfg(not(test))]
+use super::repo;
+#[cfg(not(test))]
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+#[cfg(not(test))]
+use std::sync::{Mutex, OnceLock};
+
+#[cfg(not(test))]
+const LIBRARY_SEARCH_LOG_TARGET: &str = "library_search";
+#[cfg(not(test))]
+const LIBRARY_SEARCH_DEFAULT_LIMIT: usize = 50;
+#[cfg(not(test))]
+const LIBRARY_SEARCH_MAX_LIMIT: usize = 500;
+const PHRASE_EXACT_BONUS: f32 = 0.5;
+const PHRASE_CONTAINED_BONUS: f32 = 0.25;
+
+#[cfg(not(test))]
+static LIBRARY_SEARCH_RUNTIME: OnceLock<Mutex<LibrarySearchRuntime>> = OnceLock::new();
+
+#[cfg(not(test))]
+#[derive(Debug, Default)]
+struct LibrarySearchRuntime {
+    index: LibrarySearchIndex,
+    ready: bool,
+    revision: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct LibrarySearchIndex {
+    entries: HashMap<String, LibrarySearchEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct LibrarySearchEntry {
+    music: Music,
+    fields: Vec<LibrarySearchField>,
+    title_compact: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct LibrarySearchField {
+    weight: f32,
+    terms: Vec<SearchTerm>,
+    cjk_text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SearchTerm {
+    Word(String),
+    Cjk(String),
+}
+
+/**
+ * Behavior:
+ *   Rank library music against a free-text query over title, alias, group,
+ *   collection and embedded tag fields.
+ *
+ * Core invariants:
+ *   - Every query term must match some field; typos are tolerated per word
+ *     by length, and CJK runs match by substring or character bigrams since
+ *     they carry no word boundaries.
+ *   - Katakana folds to hiragana and full-width ASCII folds to ASCII, so
+ *     the same title typed either way matches.
+ *   - The index is built lazily from the repo and then kept current by music
+ *     change notifications; a library-wide change only marks it stale.
+ */
+#[cfg(not(test))]
+pub async fn search_library(query: &str, limit: Option<u32>) -> Result<Vec<LibrarySearchHit>> {
+    let limit = limit
+        .map(|limit| limit as usize)
+        .unwrap_or(LIBRARY_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, LIBRARY_SEARCH_MAX_LIMIT);
+    if parse_search_terms(query).is_empty() {
+        return Ok(vec![]);
+    }
+
+    let rebuild_revision = {
+        let runtime = lock_runtime()?;
+        (!runtime.ready).then_some(runtime.revision)
+    };
+    if let Some(revision) = rebuild_revision {
+        let collections = repo::list_collections().await?;
+        let mut index = LibrarySearchIndex::default();
+        for collection in collections {
+            for music in collection.musics {
+                index.upsert(music);
+            }
+        }
+
+        let mut runtime = lock_runtime()?;
+        if runtime.revision == revision {
+            runtime.ready = true;
+        }
+        log::info!(
+            target: LIBRARY_SEARCH_LOG_TARGET,
+            "library_search_index_rebuilt entries={} ready={}",
+            index.entry_count(),
+            runtime.ready
+        );
+        // A concurrent change leaves the index stale, but this query still
+        // sees the freshly loaded library.
+        runtime.index = index;
+    }
+
+    Ok(lock_runtime()?.index.search(query, limit))
+}
+
+#[cfg(not(test))]
+pub(crate) fn notify_music_changed(music: &Music) {
+    let Ok(mut runtime) = lock_runtime() else {
+        return;
+    };
+    runtime.revision += 1;
+    if runtime.ready {
+        runtime.index.upsert(music.clone());
+    }
+}
+
+#[cfg(not(test))]
+pub(crate) fn notify_library_changed() {
+    let Ok(mut runtime) = lock_runtime() else {
+        return;
+    };
+    runtime.revision += 1;
+    runtime.ready = false;
+}
+
+#[cfg(not(test))]
+fn lock_runtime() -> Result<std::sync::MutexGuard<'static, LibrarySearchRuntime>> {
+    LIBRARY_SEARCH_RUNTIME
+        .get_or_init(|| Mutex::new(LibrarySearchRuntime::default()))
+        .lock()
+        .map_err(|_| anyhow!("library search index lock is poisoned"))
+}
+
+impl LibrarySearchIndex {
+    pub(crate) fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn upsert(&mut self, music: Music) {
+        let key = music_search_key(&music);
+        self.entries
+            .insert(key, LibrarySearchEntry::from_music(music));
+    }
+
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<LibrarySearchHit> {
+        let terms = parse_search_terms(query);
+        if terms.is_empty() {
+            return vec![];
+        }
+        let phrase = compact_search_text(query);
+
+        let mut scored = self
+            .entries
+            .values()
+            .filter_map(|entry| Some((entry.score(&terms, &phrase)?, entry)))
+            .collect::<Vec<_>>();
+        scored.sort_by(|(left_score, left), (right_score, right)| {
+            right_score
+                .total_cmp(left_score)
+                .then_with(|| left.music.alias.cmp(&right.music.alias))
+                .then_with(|| left.music.occurrence_id.cmp(&right.music.occurrence_id))
+        });
+        scored.truncate(limit);
+
+        let source_end_ms = self.source_end_ms(scored.iter().map(|(_, entry)| &entry.music));
+        scored
+            .into_iter()
+            .map(|(score, entry)| LibrarySearchHit {
+                source: source_context(&entry.music, &source_end_ms),
+                music: entry.music.clone(),
+                score,
+            })
+            .collect()
+    }
+
+    /// End of the longest ranged occurrence of each hit's source file in its
+    /// collection, gathered in one pass over the index.
+    fn source_end_ms<'a>(
+        &'a self,
+        hits: impl Iterator<Item = &'a Music>,
+    ) -> HashMap<(&'a str, &'a str), u32> {
+        let sources = hits.map(source_key).collect::<HashSet<_>>();
+        let mut source_end_ms = HashMap::<(&str, &str), u32>::new();
+        for candidate in self.entries.values().map(|entry| &entry.music) {
+            let key = source_key(candidate);
+            if candidate.start_ms < candidate.end_ms && sources.contains(&key) {
+                let end_ms = source_end_ms.entry(key).or_default();
+                *end_ms = (*end_ms).max(candidate.end_ms);
+            }
+        }
+        source_end_ms
+    }
+}
+
+fn source_key(music: &Music) -> (&str, &str) {
+    (music.group.collection.url.as_str(), music.url.as_str())
+}
+
+fn source_context(
+    music: &Music,
+    source_end_ms: &HashMap<(&str, &str), u32>,
+) -> SpectrumMusicSourceContext {
+    let collection_url = &music.group.collection.url;
+    let source_end_ms = source_end_ms
+        .get(&source_key(music))
+        .copied()
+        .unwrap_or(music.end_ms);
+
+    SpectrumMusicSourceContext {
+        source_collection_url: collection_url.clone(),
+        source_end_ms,
+        source_group: music.group.clone(),
+        source_path: music.path.clone(),
+        source_start_ms: music.start_ms,
+        source_url: music.url.clone(),
+    }
+}
+
+impl LibrarySearchEntry {
+    fn from_music(music: Music) -> Self {
+        let mut fields = Vec::new();
+        let mut push_field = |weight: f32, text: &str| {
+            let terms = parse_search_terms(text);
+            if terms.is_empty() {
+                return;
+            }
+            let cjk_text = terms
+                .iter()
+                .filter_map(|term| match term {
+                    SearchTerm::Cjk(text) => Some(text.as_str()),
+                    SearchTerm::Word(_) => None,
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            fields.push(LibrarySearchField {
+                weight,
+                terms,
+                cjk_text,
+            });
+        };
+
+        push_field(1.0, &music.alias);
+        push_field(0.95, &music.name);
+        if let Some(tags) = music.tags.as_ref() {
+            if let Some(title) = tags.title.as_deref() {
+                push_field(0.9, title);
+            }
+            if let Some(artist) = tags.artist.as_deref() {
+                push_field(0.75, artist);
+            }
+            if let Some(album) = tags.album.as_deref() {
+                push_field(0.65, album);
+            }
+            if let Some(genre) = tags.genre.as_deref() {
+                push_field(0.45, genre);
+            }
+            if let Some(year) = tags.year {
+                push_field(0.4, &year.to_string());
+            }
+        }
+        push_field(0.6, &music.group.name);
+        push_field(0.55, &music.group.collection.name);
+
+        let title_compact = [music.alias.as_str(), music.name.as_str()]
+            .into_iter()
+            .chain(music.tags.as_ref().and_then(|tags| tags.title.as_deref()))
+            .map(compact_search_text)
+            .filter(|text| !text.is_empty())
+            .collect();
+
+        Self {
+            music,
+            fields,
+            title_compact,
+        }
+    }
+
+    fn score(&self, terms: &[SearchTerm], phrase: &str) -> Option<f32> {
+        let mut total = 0.0;
+        for term in terms {
+            let best = self
+                .fields
+                .iter()
+                .map(|field| field.match_term(term) * field.weight)
+                .fold(0.0_f32, f32::max);
+            if best <= 0.0 {
+                return None;
+            }
+            total += best;
+        }
+        let mut score = total / terms.len() as f32;
+
+        if !phrase.is_empty() {
+            if self.title_compact.iter().any(|title| title == phrase) {
+                score += PHRASE_EXACT_BONUS;
+            } else if self
+                .title_compact
+                .iter()
+                .any(|title| title.contains(phrase))
+            {
+                score += PHRASE_CONTAINED_BONUS;
+            }
+        }
+        Some(score)
+    }
+}
+
+impl LibrarySearchField {
+    fn match_term(&self, term: &SearchTerm) -> f32 {
+        match term {
+            SearchTerm::Word(query) => self
+                .terms
+                .iter()
+                .filter_map(|candidate| match candidate {
+                    SearchTerm::Word(word) => Some(match_word(query, word)),
+                    SearchTerm::Cjk(_) => None,
+                })
+                .fold(0.0, f32::max),
+            SearchTerm::Cjk(query) => match_cjk(query, &self.cjk_text),
+        }
+    }
+}
+
+fn match_word(query: &str, word: &str) -> f32 {
+    if query == word {
+        return 1.0;
+    }
+    if word.starts_with(query) {
+        return 0.9;
+    }
+    let query_len = query.chars().count();
+    if query_len >= 3 && word.contains(query) {
+        return 0.7;
+    }
+
+    let allowed = allowed_typos(query_len);
+    if allowed == 0 {
+        return 0.0;
+    }
+    let full = edit_distance(query, word);
+    if full <= allowed {
+        return 0.8 - 0.2 * full as f32;
+    }
+    // Tolerate typos in a word that is still being typed.
+    let prefix = word.chars().take(query_len).collect::<String>();
+    let partial = edit_distance(query, &prefix);
+    if partial <= allowed {
+        return 0.7 - 0.2 * partial as f32;
+    }
+    0.0
+}
+
+fn match_cjk(query: &str, text: &str) -> f32 {
+    if text.is_empty() {
+        return 0.0;
+    }
+    if text.contains(query) {
+        return 1.0;
+    }
+
+    let query_bigrams = char_bigrams(query);
+    if query_bigrams.is_empty() {
+        return 0.0;
+    }
+    let text_bigrams = char_bigrams(text).into_iter().collect::<HashSet<_>>();
+    let matched = query_bigrams
+        .iter()
+        .filter(|bigram| text_bigrams.contains(*bigram))
+        .count();
+    let coverage = matched as f32 / query_bigrams.len() as f32;
+    if coverage >= 0.5 { 0.7 * coverage } else { 0.0 }
+}
+
+fn allowed_typos(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent swaps.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let left = left.chars().collect::<Vec<_>>();
+    let right = right.chars().collect::<Vec<_>>();
+    let width = right.len() + 1;
+    let mut rows = vec![0usize; (left.len() + 1) * width];
+    for (j, cell) in rows.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+    for i in 1..=left.len() {
+        rows[i * width] = i;
+        for j in 1..=right.len() {
+            let cost = usize::from(left[i - 1] != right[j - 1]);
+            let mut best = (rows[(i - 1) * width + j] + 1)
+                .min(rows[i * width + j - 1] + 1)
+                .min(rows[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                best = best.min(rows[(i - 2) * width + j - 2] + 1);
+            }
+            rows[i * width + j] = best;
+        }
+    }
+    rows[left.len() * width + right.len()]
+}
+
+fn char_bigrams(text: &str) -> Vec<(char, char)> {
+    let chars = text
+        .chars()
+        .filter(|value| !value.is_whitespace())
+        .collect::<Vec<_>>();
+    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+fn parse_search_terms(text: &str) -> Vec<SearchTerm> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut current_is_cjk = false;
+
+    for value in text.chars().flat_map(fold_search_char) {
+        if !value.is_alphanumeric() {
+            push_search_term(&mut terms, &mut current, current_is_cjk);
+            continue;
+        }
+        let is_cjk = is_cjk_char(value);
+        if is_cjk != current_is_cjk {
+            push_search_term(&mut terms, &mut current, current_is_cjk);
+            current_is_cjk = is_cjk;
+        }
+        current.push(value);
+    }
+    push_search_term(&mut terms, &mut current, current_is_cjk);
+    terms
+}
+
+fn push_search_term(terms: &mut Vec<SearchTerm>, current: &mut String, is_cjk: bool) {
+    if current.is_empty() {
+        return;
+    }
+    let text = std::mem::take(current);
+    terms.push(if is_cjk {
+        SearchTerm::Cjk(text)
+    } else {
+        SearchTerm::Word(text)
+    });
+}
+
+fn compact_search_text(text: &str) -> String {
+    text.chars()
+        .flat_map(fold_search_char)
+        .filter(|value| value.is_alphanumeric())
+        .collect()
+}
+
+fn fold_search_char(value: char) -> impl Iterator<Item = char> {
+    let code = value as u32;
+    let folded = match code {
+        // Full-width ASCII variants.
+        0xFF01..=0xFF5E => char::from_u32(code - 0xFEE0).unwrap_or(value),
+        // Katakana to hiragana, so kana spelling does not split matches.
+        0x30A1..=0x30F6 => char::from_u32(code - 0x60).unwrap_or(value),
+        _ => value,
+    };
+    folded.to_lowercase()
+}
+
+fn is_cjk_char(value: char) -> bool {
+    matches!(
+        value as u32,
+        0x3040..=0x30FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0x20000..=0x2FA1F
+    )
+}
+
+fn music_search_key(music: &Music) -> String {
+    if music.occurrence_id.is_empty() {
+        format!("{}#{}", music.group.url, music.canonical_music_id)
+    } else {
+        music.occurrence_id.clone()
+    }
+}
@@ -0,0 +1,167 @@
+use super::model::{CollectionGroupOwner, Group, Music, MusicTags, canonical_music_id_for_source};
+use super::search::LibrarySearchIndex;
+
+fn test_group(name: &str, url: &str, collection_name: &str) -> Group {
+    Group {
+        name: name.to_string(),
+        url: url.to_string(),
+        collection: CollectionGroupOwner {
+            name: collection_name.to_string(),
+            url: "https://example.com/collection".to_string(),
+            folder: "youtube/collection".to_string(),
+            last_updated: "2026-04-12T12:00:00+00:00".to_string(),
+            enable_updates: Some(false),
+        },
+        folder: "youtube/collection".to_string(),
+    }
+}
+
+fn test_music(name: &str, url: &str, start_ms: u32, end_ms: u32, group: Group) -> Music {
+    Music {
+        occurrence_id: format!("{}:{url}:{start_ms}:{end_ms}", group.url),
+        name: name.to_string(),
+        alias: name.to_string(),
+        group,
+        canonical_music_id: canonical_music_id_for_source(url, start_ms, end_ms),
+        url: url.to_string(),
+        path: Some(format!("{name}.m4a")),
+        start_ms,
+        end_ms,
+        liked: false,
+        loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
+    }
+}
+
+fn sample_index() -> LibrarySearchIndex {
+    let ost = test_group("TENET OST", "https://example.com/tenet", "Soundtracks");
+    let jp = test_group("夜のドライブ", "https://example.com/night", "J-Pop");
+    let mut fast_cars = test_music(
+        "Fast Cars",
+        "https://example.com/fast-cars",
+        0,
+        180_000,
+        ost.clone(),
+    );
+    fast_cars.tags = Some(MusicTags {
+        artist: Some("Ludwig Göransson".to_string()),
+        album: Some("Tenet".to_string()),
+        year: Some(2020),
+        ..MusicTags::default()
+    });
+
+    let mut index = LibrarySearchIndex::default();
+    index.upsert(fast_cars);
+    index.upsert(test_music(
+        "Turnstile",
+        "https://example.com/turnstile",
+        0,
+        120_000,
+        ost,
+    ));
+    index.upsert(test_music(
+        "夜に駆ける",
+        "https://example.com/yoru",
+        0,
+        90_000,
+        jp.clone(),
+    ));
+    index.upsert(test_music(
+        "夜に駆ける",
+        "https://example.com/yoru",
+        90_000,
+        261_000,
+        jp.clone(),
+    ));
+    index.upsert(test_music(
+        "晴天を誇れ",
+        "https://example.com/seiten",
+        0,
+        200_000,
+        jp,
+    ));
+    index
+}
+
+#[test]
+fn search_tolerates_typos_and_matches_tag_fields() {
+    let index = sample_index();
+
+    let typo = index.search("turnstyle", 10);
+    assert_eq!(typo[0].music.name, "Turnstile");
+
+    let artist = index.search("goransson", 10);
+    assert_eq!(artist[0].music.name, "Fast Cars");
+
+    let artist = index.search("ludwig 2020", 10);
+    assert_eq!(artist.len(), 1);
+    assert_eq!(artist[0].music.name, "Fast Cars");
+}
+
+#[test]
+fn search_requires_every_query_term_and_ranks_titles_over_groups() {
+    let index = sample_index();
+
+    assert!(index.search("fast turnstile", 10).is_empty());
+
+    let hits = index.search("tenet", 10);
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].music.name, "Fast Cars");
+}
+
+#[test]
+fn search_matches_cjk_substrings_and_folds_katakana() {
+    let index = sample_index();
+
+    let hits = index.search("駆ける", 10);
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit.music.name == "夜に駆ける"));
+
+    let kana = index.search("どらいぶ", 10);
+    assert_eq!(kana.len(), 3, "group name matches in hiragana");
+
+    let near = index.search("晴天を誇る", 10);
+    assert_eq!(near[0].music.name, "晴天を誇れ");
+}
+
+#[test]
+fn search_hits_carry_source_context_for_the_whole_source_file() {
+    let index = sample_index();
+
+    let hits = index.search("夜に駆ける", 10);
+    let first = hits
+        .iter()
+        .find(|hit| hit.music.start_ms == 0)
+        .expect("first range should match");
+
+    assert_eq!(first.source.source_url, "https://example.com/yoru");
+    assert_eq!(first.source.source_start_ms, 0);
+    assert_eq!(first.source.source_end_ms, 261_000);
+    assert_eq!(
+        first.source.source_collection_url,
+        "https://example.com/collection"
+    );
+    assert_eq!(first.source.source_group.name, "夜のドライブ");
+}
+
+#[test]
+fn index_upserts_replace_the_entry_of_an_occurrence() {
+    let mut index = sample_index();
+    assert_eq!(index.entry_count(), 5);
+
+    let group = test_group("TENET OST", "https://example.com/tenet", "Soundtracks");
+    let mut renamed = test_music(
+        "Turnstile",
+        "https://example.com/turnstile",
+        0,
+        120_000,
+        group,
+    );
+    renamed.alias = "Inversion".to_string();
+    index.upsert(renamed);
+
+    assert_eq!(index.entry_count(), 5);
+    assert_eq!(index.search("inversion", 10).len(), 1);
+}
@@ -0,0 +1,371 @@
+use super::model::{
+    AddExcludeResult, Music, PlayListListView, PlayListWriteRequest, RemoveExcludeResult,
+    TrashItem, TrashItemKind,
+};
+use super::repo;
+use anyhow::{Result, bail};
+use appdb::error::{DBError, classify_db_error};
+use appdb::model::meta::ModelMeta;
+use appdb::repository::Repo;
+use appdb::{Id, Store};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::RecordId;
+use surrealdb_types::SurrealValue;
+
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, SurrealValue, Store)]
+struct StoredTrashEntry {
+    id: Id,
+    kind: TrashItemKind,
+    label: String,
+    deleted_at: String,
+    expires_at: String,
+    payload: String,
+}
+
+impl StoredTrashEntry {
+    fn into_public(self) -> TrashItem {
+        TrashItem {
+            id: self.id.to_string(),
+            kind: self.kind,
+            label: self.label,
+            deleted_at: self.deleted_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TrashPayload {
+    Music {
+        occurrences: Vec<TrashedMusicOccurrence>,
+    },
+    Playlist {
+        playlist: PlayListWriteRequest,
+        including_playlists: Vec<String>,
+    },
+    Exclude {
+        music: Music,
+        #[serde(default)]
+        expires_at: Option<String>,
+        #[serde(default)]
+        playlists: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TrashedMusicOccurrence {
+    collection_url: String,
+    music: Music,
+    /// Index in the collection when trashed; older entries have none.
+    #[serde(default)]
+    position: Option<usize>,
+    #[serde(default)]
+    playlist_pins: Vec<TrashedExtraPin>,
+}
+
+/// A playlist that pinned the occurrence in `extra`, and where.
+#[derive(Debug, Serialize, Deserialize)]
+struct TrashedExtraPin {
+    playlist: String,
+    position: usize,
+}
+
+/// What a restore brought back, so callers can replay the matching
+/// library notifications.
+#[derive(Debug, Clone)]
+pub enum RestoredTrashItem {
+    Music(Vec<Music>),
+    Playlist(PlayListListView),
+    Exclude(AddExcludeResult),
+}
+
+/**
+ * Behavior:
+ *   Snapshot every occurrence of one music identity into the trash, then
+ *   delete it from the library.
+ *
+ * Core invariants:
+ *   - Nothing is written to the trash when the identity has no occurrence.
+ *   - The trash entry is saved before the delete and taken back when the
+ *     delete fails, so a deleted music always has an entry.
+ *   - Each occurrence keeps its index in the collection to be restored there.
+ *   - Each occurrence keeps the playlists pinning it in `extra`, since the
+ *     delete drops those pins.
+ */
+pub async fn trash_music(url: &str, start_ms: u32, end_ms: u32) -> Result<Option<TrashItem>> {
+    let occurrences = repo::list_music_occurrences_by_identity(url, start_ms, end_ms).await?;
+    let Some((_, first)) = occurrences.first() else {
+        return Ok(None);
+    };
+    let label = first.alias.clone();
+    let mut trashed = Vec::with_capacity(occurrences.len());
+    for (collection_url, music) in occurrences {
+        let position = repo::get_collection_by_url(&collection_url)
+            .await?
+            .and_then(|collection| {
+                collection.musics.iter().position(|candidate| {
+                    candidate.url == music.url
+                        && candidate.start_ms == music.start_ms
+                        && candidate.end_ms == music.end_ms
+                })
+            });
+        let playlist_pins = repo::list_playlist_extra_pins(&music)
+            .await?
+            .into_iter()
+            .map(|(playlist, position)| TrashedExtraPin { playlist, position })
+            .collect();
+        trashed.push(TrashedMusicOccurrence {
+            collection_url,
+            music,
+            position,
+            playlist_pins,
+        });
+    }
+    let payload = TrashPayload::Music {
+        occurrences: trashed,
+    };
+
+    let item = save_entry(TrashItemKind::Music, label, &payload).await?;
+    let deleted = repo::delete_music(url, start_ms, end_ms).await;
+    keep_entry_if_deleted(item, deleted).await
+}
+
+/**
+ * Behavior:
+ *   Snapshot a playlist and the playlists including it into the trash, then
+ *   delete it.
+ *
+ * Core invariants:
+ *   - The trash entry is saved before the delete and taken back when the
+ *     delete fails.
+ */
+pub async fn trash_playlist(name: &str) -> Result<Option<TrashItem>> {
+    let Some(playlist) = repo::get_playlist_by_name(name).await? else {
+        return Ok(None);
+    };
+    let payload = TrashPayload::Playlist {
+        playlist: PlayListWriteRequest::from_playlist(&playlist),
+        including_playlists: repo::list_including_playlist_names(name).await?,
+    };
+
+    let item = save_entry(TrashItemKind::Playlist, name.to_string(), &payload).await?;
+    let deleted = repo::delete_playlist_by_name(name).await;
+    keep_entry_if_deleted(item, deleted).await
+}
+
+/// Removes an exclude and keeps it in the trash when a row was removed. The
+/// entry is saved first and taken back when nothing was removed.
+pub async fn trash_exclude(music: &Music) -> Result<RemoveExcludeResult> {
+    let (expires_at, playlists) = repo::get_exclude(music)
+        .await?
+        .map(|exclude| (exclude.expires_at, exclude.playlists))
+        .unwrap_or_default();
+    let payload = TrashPayload::Exclude {
+        music: music.clone(),
+        expires_at,
+        playlists,
+    };
+    let item = save_entry(TrashItemKind::Exclude, music.alias.clone(), &payload).await?;
+    match repo::remove_exclude(music).await {
+        Ok(result) => {
+            if !result.removed {
+                delete_entry(&item.id).await?;
+            }
+            Ok(result)
+        }
+        Err(error) => {
+            discard_entry(&item.id).await;
+            Err(error)
+        }
+    }
+}
+
+/// Lists restorable items newest first, dropping entries past retention.
+pub async fn list_trash() -> Result<Vec<TrashItem>> {
+    purge_expired_trash(Utc::now()).await?;
+    let mut items = load_entries()
+        .await?
+        .into_iter()
+        .map(StoredTrashEntry::into_public)
+        .collect::<Vec<_>>();
+    items.sort_by(|left, right| right.deleted_at.cmp(&left.deleted_at));
+    Ok(items)
+}
+
+/**
+ * Behavior:
+ *   Recreate a trashed item and drop it from the trash.
+ *
+ * Core invariants:
+ *   - A playlist is never restored over a playlist that took its name.
+ *   - Restoring music that already exists again is a no-op per occurrence.
+ *   - Music goes back to its index in each collection and is pinned again
+ *     in the playlists that still exist.
+ */
+pub async fn restore_trash_item(id: &str) -> Result<Option<RestoredTrashItem>> {
+    let Some(entry) = load_entry(id).await? else {
+        return Ok(None);
+    };
+    let restored = match serde_json::from_str::<TrashPayload>(&entry.payload)? {
+        TrashPayload::Music { occurrences } => {
+            let mut restored = Vec::with_capacity(occurrences.len());
+            for occurrence in occurrences {
+                let music = repo::create_music_at(
+                    &occurrence.collection_url,
+                    &occurrence.music,
+                    occurrence.position,
+                )
+                .await?;
+                for pin in &occurrence.playlist_pins {
+                    repo::insert_extra_at(&pin.playlist, &music, pin.position).await?;
+                }
+                restored.push(music);
+            }
+            RestoredTrashItem::Music(restored)
+        }
+        TrashPayload::Playlist {
+            playlist,
+            including_playlists,
+        } => {
+            if repo::get_playlist_by_name(&playlist.name).await?.is_some() {
+                bail!("playlist `{}` already exists", playlist.name);
+            }
+            let saved = repo::upsert_playlist_surface(&playlist, None).await?;
+            repo::add_included_playlist_ref(&playlist.name, &including_playlists).await?;
+            RestoredTrashItem::Playlist(saved.playlist)
+        }
+        TrashPayload::Exclude {
+            music,
+            expires_at,
+            playlists,
+        } => RestoredTrashItem::Exclude(
+            repo::add_scoped_exclude(music, expires_at, playlists).await?,
+        ),
+    };
+
+    delete_entry(id).await?;
+    Ok(Some(restored))
+}
+
+/// Permanently drops one trash entry.
+pub async fn purge_trash_item(id: &str) -> Result<bool> {
+    if load_entry(id).await?.is_none() {
+        return Ok(false);
+    }
+    delete_entry(id).await?;
+    Ok(true)
+}
+
+/// Permanently drops every trash entry whose retention ended before `now`.
+pub async fn purge_expired_trash(now: DateTime<Utc>) -> Result<u32> {
+    let mut purged = 0;
+    for entry in load_entries().await? {
+        let expired = DateTime::parse_from_rfc3339(&entry.expires_at)
+            .map(|expires_at| expires_at <= now)
+            .unwrap_or(true);
+        if expired {
+            delete_entry(&entry.id.to_string()).await?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+async fn save_entry(
+    kind: TrashItemKind,
+    label: String,
+    payload: &TrashPayload,
+) -> Result<TrashItem> {
+    let deleted_at = Utc::now();
+    let prefix = match kind {
+        TrashItemKind::Music => "music",
+        TrashItemKind::Playlist => "playlist",
+        TrashItemKind::Exclude => "exclude",
+    };
+    let id = format!(
+        "{prefix}-{}",
+        deleted_at.timestamp_nanos_opt().unwrap_or_default()
+    );
+    let entry = Repo::<StoredTrashEntry>::upsert_at(
+        trash_record_id(&id),
+        StoredTrashEntry {
+            id: Id::from(id.clone()),
+            kind,
+            label,
+            deleted_at: deleted_at.to_rfc3339(),
+            expires_at: (deleted_at + Duration::days(TRASH_RETENTION_DAYS)).to_rfc3339(),
+            payload: serde_json::to_string(payload)?,
+        },
+    )
+    .await?;
+    Ok(entry.into_public())
+}
+
+/// Keeps a freshly saved entry only when its delete removed something.
+async fn keep_entry_if_deleted(
+    item: TrashItem,
+    deleted: Result<bool>,
+) -> Result<Option<TrashItem>> {
+    match deleted {
+        Ok(true) => Ok(Some(item)),
+        Ok(false) => {
+            delete_entry(&item.id).await?;
+            Ok(None)
+        }
+        Err(error) => {
+            discard_entry(&item.id).await;
+            Err(error)
+        }
+    }
+}
+
+/// Takes back the entry of a failed delete; the delete error is what the
+/// caller reports, so a failure here is only logged.
+async fn discard_entry(id: &str) {
+    if let Err(error) = delete_entry(id).await {
+        log::warn!(
+            target: "playlists",
+            "trash_entry_discard_failed id=\"{}\" error=\"{}\"",
+            id,
+            error
+        );
+    }
+}
+
+async fn load_entries() -> Result<Vec<StoredTrashEntry>> {
+    match Repo::<StoredTrashEntry>::list().await {
+        Ok(entries) => Ok(entries),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) => Ok(vec![]),
+            other => Err(other.into()),
+        },
+    }
+}
+
+async fn load_entry(id: &str) -> Result<Option<StoredTrashEntry>> {
+    match Repo::<StoredTrashEntry>::get_record(trash_record_id(id)).await {
+        Ok(entry) => Ok(Some(entry)),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) | DBError::NotFound => Ok(None),
+            other => Err(other.into()),
+        },
+    }
+}
+
+async fn delete_entry(id: &str) -> Result<()> {
+    match Repo::<StoredTrashEntry>::delete_record(trash_record_id(id)).await {
+        Ok(()) => Ok(()),
+        Err(error) => match classify_db_error(&error) {
+            DBError::MissingTable(_) | DBError::NotFound => Ok(()),
+            other => Err(other.into()),
+        },
+    }
+}
+
+fn trash_record_id(id: &str) -> RecordId {
+    RecordId::new(StoredTrashEntry::table_name(), id.to_string())
+}
@@ -0,0 +1,296 @@
+use super::model::{Collection, Music, PlayList, PlayListWriteRequest, TrashItemKind};
+use super::repo::{
+    add_exclude, get_collection_by_url, get_playlist_by_name,
+    is_music_identity_excluded_for_playback, upsert_collection, upsert_playlist_surface,
+};
+use super::trash::{
+    RestoredTrashItem, TRASH_RETENTION_DAYS, list_trash, purge_expired_trash, purge_trash_item,
+    restore_trash_item, trash_exclude, trash_music, trash_playlist,
+};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, owner_group, ranged_music, run_async,
+};
+use appdb::AutoFill;
+use appdb::connection::reset_db;
+use chrono::{Duration, Utc};
+
+const COLLECTION_FOLDER: &str = "youtube/trash-demo";
+
+fn collection(url: &str, musics: Vec<Music>) -> Collection {
+    crate::domain::test_support::collection("Trash Demo", url, COLLECTION_FOLDER, musics)
+}
+
+fn music(collection_url: &str, name: &str, start_ms: u32, end_ms: u32) -> Music {
+    ranged_music(
+        name,
+        &owner_group("Trash Demo", collection_url, COLLECTION_FOLDER),
+        start_ms,
+        end_ms,
+    )
+}
+
+fn playlist(name: &str, collections: Vec<Collection>, playlists: &[&str]) -> PlayList {
+    PlayList {
+        name: name.to_string(),
+        collections,
+        groups: vec![],
+        extra: vec![],
+        playlists: Some(playlists.iter().map(|name| name.to_string()).collect()),
+        rules: None,
+        created_at: AutoFill::pending(),
+    }
+}
+
+#[test]
+fn trashed_music_restores_alias_liked_state_range_and_position() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let url = "https://example.com/trash-music";
+        let mut edited = music(url, "Edited", 5_000, 95_000);
+        edited.alias = "Edited Alias".to_string();
+        edited.liked = true;
+        upsert_collection(&collection(
+            url,
+            vec![edited, music(url, "Kept", 0, 60_000)],
+        ))
+        .await
+        .expect("collection should save");
+
+        let item = trash_music("https://example.com/watch/Edited", 5_000, 95_000)
+            .await
+            .expect("music should move to trash")
+            .expect("existing music should produce a trash item");
+        assert_eq!(item.kind, TrashItemKind::Music);
+        assert_eq!(item.label, "Edited Alias");
+        assert!(
+            trash_music("https://example.com/watch/Edited", 5_000, 95_000)
+                .await
+                .expect("repeated trash should succeed")
+                .is_none()
+        );
+        let remaining = get_collection_by_url(url)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(remaining.musics.len(), 1);
+
+        let Some(RestoredTrashItem::Music(restored)) = restore_trash_item(&item.id)
+            .await
+            .expect("music restore should succeed")
+        else {
+            panic!("music trash item should restore music");
+        };
+        assert_eq!(restored.len(), 1);
+
+        let collection = get_collection_by_url(url)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(
+            collection
+                .musics
+                .iter()
+                .map(|music| music.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Edited", "Kept"]
+        );
+        let restored = &collection.musics[0];
+        assert_eq!(restored.alias, "Edited Alias");
+        assert!(restored.liked);
+        assert_eq!((restored.start_ms, restored.end_ms), (5_000, 95_000));
+        assert!(list_trash().await.expect("trash should list").is_empty());
+
+        reset_db();
+    });
+}
+
+fn extra_names(playlist: &PlayList) -> Vec<&str> {
+    playlist
+        .extra
+        .iter()
+        .map(|music| music.name.as_str())
+        .collect()
+}
+
+#[test]
+fn trashed_music_restores_its_playlist_pins_in_place() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let url = "https://example.com/trash-pins";
+        let saved = upsert_collection(&collection(
+            url,
+            vec![
+                music(url, "Pinned", 0, 60_000),
+                music(url, "Kept", 0, 60_000),
+            ],
+        ))
+        .await
+        .expect("collection should save");
+        for (name, extra) in [
+            ("Pins", saved.musics.clone()),
+            ("Solo", vec![saved.musics[0].clone()]),
+        ] {
+            let mut request = PlayListWriteRequest::from_playlist(&playlist(name, vec![], &[]));
+            request.extra = extra;
+            upsert_playlist_surface(&request, None)
+                .await
+                .expect("playlist should save");
+        }
+
+        let item = trash_music("https://example.com/watch/Pinned", 0, 60_000)
+            .await
+            .expect("music should move to trash")
+            .expect("existing music should produce a trash item");
+        let pins = get_playlist_by_name("Pins")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should remain");
+        assert_eq!(extra_names(&pins), vec!["Kept"]);
+
+        restore_trash_item(&item.id)
+            .await
+            .expect("music restore should succeed")
+            .expect("trash item should exist");
+
+        for (name, expected) in [("Pins", vec!["Pinned", "Kept"]), ("Solo", vec!["Pinned"])] {
+            let playlist = get_playlist_by_name(name)
+                .await
+                .expect("playlist lookup should succeed")
+                .expect("playlist should remain");
+            assert_eq!(extra_names(&playlist), expected, "{name}");
+        }
+
+        reset_db();
+    });
+}
+
+#[test]
+fn trashed_playlist_restores_with_its_including_playlists() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let url = "https://example.com/trash-playlist";
+        let focus = collection(url, vec![music(url, "Focus Track", 0, 60_000)]);
+        upsert_collection(&focus)
+            .await
+            .expect("collection should save");
+        for playlist in [
+            playlist("Focus", vec![focus.clone()], &[]),
+            playlist("Mix", vec![], &["Focus"]),
+        ] {
+            upsert_playlist_surface(&PlayListWriteRequest::from_playlist(&playlist), None)
+                .await
+                .expect("playlist should save");
+        }
+
+        let item = trash_playlist("Focus")
+            .await
+            .expect("playlist should move to trash")
+            .expect("existing playlist should produce a trash item");
+        assert!(
+            get_playlist_by_name("Focus")
+                .await
+                .expect("playlist lookup should succeed")
+                .is_none()
+        );
+        let mix = get_playlist_by_name("Mix")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("including playlist should remain");
+        assert_eq!(mix.playlists.unwrap_or_default(), Vec::<String>::new());
+
+        let Some(RestoredTrashItem::Playlist(restored)) = restore_trash_item(&item.id)
+            .await
+            .expect("playlist restore should succeed")
+        else {
+            panic!("playlist trash item should restore a playlist");
+        };
+        assert_eq!(restored.name, "Focus");
+
+        let focus = get_playlist_by_name("Focus")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("playlist should be restored");
+        assert_eq!(focus.collections.len(), 1);
+        assert_eq!(focus.collections[0].url, url);
+        let mix = get_playlist_by_name("Mix")
+            .await
+            .expect("playlist lookup should succeed")
+            .expect("including playlist should remain");
+        assert_eq!(mix.playlists.unwrap_or_default(), vec!["Focus".to_string()]);
+
+        reset_db();
+    });
+}
+
+#[test]
+fn removed_exclude_restores_and_expired_trash_is_purged() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let url = "https://example.com/trash-exclude";
+        let blocked = music(url, "Blocked", 0, 60_000);
+        upsert_collection(&collection(url, vec![blocked.clone()]))
+            .await
+            .expect("collection should save");
+        add_exclude(blocked.clone(), None)
+            .await
+            .expect("exclude should save");
+
+        let removed = trash_exclude(&blocked)
+            .await
+            .expect("exclude should move to trash");
+        assert!(removed.removed);
+        let trash = list_trash().await.expect("trash should list");
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].kind, TrashItemKind::Exclude);
+
+        let Some(RestoredTrashItem::Exclude(restored)) = restore_trash_item(&trash[0].id)
+            .await
+            .expect("exclude restore should succeed")
+        else {
+            panic!("exclude trash item should restore an exclude");
+        };
+        assert_eq!(restored.exclude.music.url, blocked.url);
+        assert!(
+            is_music_identity_excluded_for_playback(&blocked.url, 0, 60_000)
+                .await
+                .expect("exclude lookup should succeed")
+        );
+
+        trash_exclude(&blocked)
+            .await
+            .expect("exclude should move to trash again");
+        let expired_at = Utc::now() + Duration::days(TRASH_RETENTION_DAYS + 1);
+        assert_eq!(
+            purge_expired_trash(Utc::now())
+                .await
+                .expect("fresh trash should not purge"),
+            0
+        );
+        assert_eq!(
+            purge_expired_trash(expired_at)
+                .await
+                .expect("expired trash should purge"),
+            1
+        );
+        assert!(
+            !purge_trash_item("exclude-missing")
+                .await
+                .expect("missing trash purge should succeed")
+        );
+        assert!(list_trash().await.expect("trash should list").is_empty());
+
+        reset_db();
+    });
+}
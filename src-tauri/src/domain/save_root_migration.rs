@@ -0,0 +1,515 @@
+use crate::domain::collection_relocation::{
+    CollectionRelocationProgress, collection_folder_within_root, copy_collection_tree,
+    ensure_empty_relocation_target, relocate_collection_record,
+};
+use crate::domain::downloads::repo as download_repo;
+use crate::domain::meta::model::MetaInfo;
+use crate::domain::meta::repo as meta_repo;
+use crate::domain::playlists::repo as collection_repo;
+use anyhow::{Result, anyhow, bail};
+use appdb::error::{DBError, classify_db_error};
+use appdb::model::meta::ModelMeta;
+use appdb::repository::Repo;
+use appdb::{Id, Store};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use surrealdb::types::RecordId;
+use surrealdb_types::SurrealValue;
+
+#[cfg(test)]
+#[path = "save_root_migration.test.rs"]
+mod tests;
+
+const SAVE_ROOT_MIGRATION_RECORD_KEY: &str = "singleton";
+
+static SAVE_ROOT_MIGRATION_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, SurrealValue, Type)]
+#[serde(rename_all = "snake_case")]
+#[surreal(rename_all = "snake_case")]
+pub enum SaveRootMigrationMode {
+    /// Remove the old collection folders once the new root is in use.
+    Move,
+    /// Leave the old collection folders in place.
+    Copy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, SurrealValue, Type)]
+#[serde(rename_all = "snake_case")]
+#[surreal(rename_all = "snake_case")]
+pub enum SaveRootMigrationStage {
+    Copying,
+    Switching,
+    Cleaning,
+    Completed,
+}
+
+/// The persisted state of the latest save path migration. Every finished
+/// step is checkpointed so an interrupted migration continues where it
+/// stopped.
+#[derive(Debug, Serialize, Deserialize, Clone, SurrealValue, Store, Type)]
+pub struct SaveRootMigration {
+    pub id: Id,
+    pub from_path: String,
+    pub to_path: String,
+    pub mode: SaveRootMigrationMode,
+    pub stage: SaveRootMigrationStage,
+    pub collection_urls: Vec<String>,
+    pub copied_collection_urls: Vec<String>,
+    pub last_error: Option<String>,
+    pub started_at: String,
+    pub updated_at: String,
+}
+
+impl SaveRootMigration {
+    pub fn is_finished(&self) -> bool {
+        self.stage == SaveRootMigrationStage::Completed
+    }
+}
+
+struct SaveRootMigrationGuard;
+
+impl SaveRootMigrationGuard {
+    fn acquire() -> Result<Self> {
+        if SAVE_ROOT_MIGRATION_ACTIVE
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            bail!("a save path migration is already running");
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for SaveRootMigrationGuard {
+    fn drop(&mut self) {
+        SAVE_ROOT_MIGRATION_ACTIVE.store(false, Ordering::Release);
+    }
+}
+
+pub async fn get_save_root_migration() -> Result<Option<SaveRootMigration>> {
+    match Repo::<SaveRootMigration>::get_record(save_root_migration_record_id()).await {
+        Ok(migration) => Ok(Some(migration)),
+        Err(error) => match classify_db_error(&error) {
+            DBError::NotFound | DBError::MissingTable(_) => Ok(None),
+            other => Err(other.into()),
+        },
+    }
+}
+
+async fn save_save_root_migration(migration: &mut SaveRootMigration) -> Result<()> {
+    migration.updated_at = Utc::now().to_rfc3339();
+    *migration =
+        Repo::<SaveRootMigration>::upsert_at(save_root_migration_record_id(), migration.clone())
+            .await?;
+    Ok(())
+}
+
+fn save_root_migration_record_id() -> RecordId {
+    RecordId::new(
+        SaveRootMigration::table_name(),
+        SAVE_ROOT_MIGRATION_RECORD_KEY,
+    )
+}
+
+/// Refuses a plain `save_path` change while a migration still owns it.
+pub async fn ensure_save_path_change_allowed(next: &MetaInfo) -> Result<()> {
+    let Some(migration) = get_save_root_migration().await? else {
+        return Ok(());
+    };
+    let current = meta_repo::get_meta_info().await?;
+    let current_save_path = current.and_then(|meta| meta.save_path);
+    if !migration.is_finished() && next.save_path != current_save_path {
+        bail!(
+            "the save path is being migrated to {}; resume that migration before changing it",
+            migration.to_path
+        );
+    }
+
+    Ok(())
+}
+
+/// Refuses to start work that writes collection folders or records while a
+/// migration is running or waits to be resumed.
+pub(crate) async fn ensure_no_save_root_migration() -> Result<()> {
+    if SAVE_ROOT_MIGRATION_ACTIVE.load(Ordering::Acquire) {
+        bail!("a save path migration is running; wait for it to finish");
+    }
+    if let Some(migration) = get_save_root_migration().await?
+        && !migration.is_finished()
+    {
+        bail!(
+            "a save path migration to {} is unfinished; resume it first",
+            migration.to_path
+        );
+    }
+
+    Ok(())
+}
+
+/**
+ * Behavior:
+ *   Record a migration of every collection on the default save root to
+ *   `to_path` without touching any file yet.
+ *
+ * Core invariants:
+ *   - Only one unfinished migration exists at a time.
+ *   - The new path is absolute, differs from the current one and from every
+ *     named save root.
+ *   - No collection is merged into an existing non-empty folder and no
+ *     collection folder is nested in its own target.
+ *   - Collections on named roots or outside the default root stay put.
+ */
+pub async fn begin_save_root_migration(
+    meta: &MetaInfo,
+    to_path: &str,
+    mode: SaveRootMigrationMode,
+) -> Result<SaveRootMigration> {
+    if let Some(migration) = get_save_root_migration().await?
+        && !migration.is_finished()
+    {
+        bail!(
+            "a save path migration to {} is unfinished; resume it first",
+            migration.to_path
+        );
+    }
+
+    let from_root = meta
+        .save_root_path(None)
+        .ok_or_else(|| anyhow!("save path should always be configured"))?;
+    let to_root = PathBuf::from(to_path);
+    if !to_root.is_absolute() {
+        bail!("the new save path must be absolute");
+    }
+    let mut next = meta.clone();
+    next.save_path = Some(to_path.to_string());
+    meta_repo::validate_save_roots(&next)?;
+    if to_root == from_root {
+        bail!("the new save path is the current save path");
+    }
+    if download_repo::list_tasks()
+        .await?
+        .iter()
+        .any(|task| task.status.is_active())
+    {
+        bail!("the save path cannot be migrated while downloads are running");
+    }
+
+    let mut collection_urls = Vec::new();
+    for collection in collection_repo::list_collections().await? {
+        let Some(folder) = migrated_collection_folder(&collection.folder, &from_root)
+            .filter(|_| collection.save_root.is_none())
+        else {
+            continue;
+        };
+        let source_dir = from_root.join(&folder);
+        let target_dir = to_root.join(&folder);
+        if target_dir.starts_with(&source_dir) || source_dir.starts_with(&target_dir) {
+            bail!(
+                "collection `{}` cannot be migrated into {}",
+                collection.url,
+                target_dir.display()
+            );
+        }
+        ensure_empty_relocation_target(&target_dir)?;
+        collection_urls.push(collection.url);
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let mut migration = SaveRootMigration {
+        id: Id::from(SAVE_ROOT_MIGRATION_RECORD_KEY.to_string()),
+        from_path: from_root.to_string_lossy().to_string(),
+        to_path: to_path.to_string(),
+        mode,
+        stage: SaveRootMigrationStage::Copying,
+        collection_urls,
+        copied_collection_urls: vec![],
+        last_error: None,
+        started_at: now.clone(),
+        updated_at: now,
+    };
+    save_save_root_migration(&mut migration).await?;
+    Ok(migration)
+}
+
+/// The folder of a default-root collection relative to `root`, or `None` for
+/// collections that live outside it.
+fn migrated_collection_folder(folder: &str, root: &Path) -> Option<PathBuf> {
+    let path = Path::new(folder);
+    if path.is_absolute() && !path.starts_with(root) {
+        return None;
+    }
+    Some(collection_folder_within_root(folder, root))
+}
+
+/**
+ * Behavior:
+ *   Run or resume a recorded migration: copy and verify every collection
+ *   folder, switch the save path and collection records over, then drop the
+ *   old folders in move mode.
+ *
+ * Core invariants:
+ *   - The save path only changes after every collection was copied and
+ *     verified, so an interrupted migration keeps playing from the old root.
+ *   - Each copied collection is checkpointed; resuming re-copies at most the
+ *     collection that was in flight.
+ *   - A failure is recorded on the migration and leaves it resumable.
+ */
+pub async fn run_save_root_migration(
+    on_progress: impl FnMut(&CollectionRelocationProgress) + Send + 'static,
+) -> Result<SaveRootMigration> {
+    let _guard = SaveRootMigrationGuard::acquire()?;
+    let Some(mut migration) = get_save_root_migration().await? else {
+        bail!("no save path migration was started");
+    };
+    if migration.is_finished() {
+        return Ok(migration);
+    }
+
+    migration.last_error = None;
+    match advance_save_root_migration(&mut migration, on_progress).await {
+        Ok(()) => Ok(migration),
+        Err(error) => {
+            log::warn!(
+                target: "save_root_migration",
+                "migration_interrupted to_path=\"{}\" stage=\"{:?}\" error=\"{}\"",
+                migration.to_path,
+                migration.stage,
+                error
+            );
+            migration.last_error = Some(error.to_string());
+            save_save_root_migration(&mut migration).await?;
+            Err(error)
+        }
+    }
+}
+
+async fn advance_save_root_migration(
+    migration: &mut SaveRootMigration,
+    mut on_progress: impl FnMut(&CollectionRelocationProgress) + Send + 'static,
+) -> Result<()> {
+    let from_root = PathBuf::from(&migration.from_path);
+    let to_root = PathBuf::from(&migration.to_path);
+
+    if migration.stage == SaveRootMigrationStage::Copying {
+        for url in migration.collection_urls.clone() {
+            if migration.copied_collection_urls.contains(&url) {
+                continue;
+            }
+            if let Some(collection) = collection_repo::get_collection_by_url(&url).await?
+                && let Some(folder) = migrated_collection_folder(&collection.folder, &from_root)
+            {
+                let source_dir = from_root.join(&folder);
+                let target_dir = to_root.join(&folder);
+                let collection_url = url.clone();
+                on_progress = tokio::task::spawn_blocking(move || {
+                    copy_collection_tree(
+                        &collection_url,
+                        &source_dir,
+                        &target_dir,
+                        &mut on_progress,
+                    )
+                    .map(|_| on_progress)
+                })
+                .await??;
+            }
+
+            migration.copied_collection_urls.push(url);
+            save_save_root_migration(migration).await?;
+        }
+
+        migration.stage = SaveRootMigrationStage::Switching;
+        save_save_root_migration(migration).await?;
+    }
+
+    if migration.stage == SaveRootMigrationStage::Switching {
+        for url in &migration.copied_collection_urls {
+            let Some(collection) = collection_repo::get_collection_by_url(url).await? else {
+                continue;
+            };
+            let Some(folder) = migrated_collection_folder(&collection.folder, &from_root) else {
+                continue;
+            };
+            let source_dir = from_root.join(&folder);
+            let target_folder = folder.to_string_lossy().replace('\\', "/");
+            let needs_update = collection.folder != target_folder
+                || collection.musics.iter().any(|music| {
+                    music
+                        .path
+                        .as_deref()
+                        .is_some_and(|path| Path::new(path).starts_with(&source_dir))
+                });
+            if needs_update {
+                let relocated = relocate_collection_record(
+                    &collection,
+                    target_folder,
+                    None,
+                    &source_dir,
+                    &to_root.join(&folder),
+                );
+                collection_repo::upsert_collection(&relocated).await?;
+            }
+        }
+
+        let mut meta = meta_repo::get_meta_info()
+            .await?
+            .ok_or_else(|| anyhow!("save path should always be configured"))?;
+        meta.save_path = Some(migration.to_path.clone());
+        meta_repo::save_meta_info(meta).await?;
+
+        migration.stage = match migration.mode {
+            SaveRootMigrationMode::Move => SaveRootMigrationStage::Cleaning,
+            SaveRootMigrationMode::Copy => SaveRootMigrationStage::Completed,
+        };
+        save_save_root_migration(migration).await?;
+    }
+
+    if migration.stage == SaveRootMigrationStage::Cleaning {
+        for url in &migration.copied_collection_urls {
+            let Some(collection) = collection_repo::get_collection_by_url(url).await? else {
+                continue;
+            };
+            if collection.save_root.is_some() || Path::new(&collection.folder).is_absolute() {
+                continue;
+            }
+            let source_dir = from_root.join(&collection.folder);
+            if source_dir.is_dir()
+                && let Err(error) = std::fs::remove_dir_all(&source_dir)
+            {
+                log::warn!(
+                    target: "save_root_migration",
+                    "source_cleanup_failed collection_url=\"{}\" path=\"{}\" error=\"{}\"",
+                    url,
+                    source_dir.display(),
+                    error
+                );
+            }
+        }
+
+        migration.stage = SaveRootMigrationStage::Completed;
+        save_save_root_migration(migration).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(test))]
+fn emit_migration_progress(app: &tauri::AppHandle, progress: &CollectionRelocationProgress) {
+    use tauri_specta::Event;
+
+    if let Err(error) = progress.clone().emit(app) {
+        log::warn!(
+            target: "save_root_migration",
+            "progress_emit_failed collection_url=\"{}\" error=\"{}\"",
+            progress.collection_url,
+            error
+        );
+    }
+}
+
+#[cfg(not(test))]
+async fn run_save_root_migration_for_app(app: &tauri::AppHandle) -> Result<SaveRootMigration> {
+    let progress_app = app.clone();
+    let migration =
+        run_save_root_migration(move |progress| emit_migration_progress(&progress_app, progress))
+            .await?;
+
+    crate::domain::playlist_playback::service::notify_music_library_inputs_changed(
+        "save_root_migration",
+    );
+    crate::domain::playlist_playback::service::notify_playable_library_changed();
+    // Downloads held back while the migration was unfinished resume now.
+    if let Err(error) = crate::domain::downloads::service::recover_incomplete_download_tasks().await
+    {
+        log::warn!(
+            target: "save_root_migration",
+            "held_back_downloads_resume_failed error=\"{}\"",
+            error
+        );
+    }
+    Ok(migration)
+}
+
+/// Resumes a migration that was interrupted by the app closing.
+#[cfg(not(test))]
+pub(crate) fn initialize_runtime(app: tauri::AppHandle) {
+    let _ = std::thread::Builder::new()
+        .name("save-root-migration-recovery".to_string())
+        .spawn(move || {
+            tauri::async_runtime::block_on(async move {
+                match get_save_root_migration().await {
+                    Ok(Some(migration)) if !migration.is_finished() => {
+                        match run_save_root_migration_for_app(&app).await {
+                            Ok(migration) => log::info!(
+                                target: "save_root_migration",
+                                "migration_resumed to_path=\"{}\"",
+                                migration.to_path
+                            ),
+                            Err(error) => log::error!(
+                                target: "save_root_migration",
+                                "migration_resume_failed error=\"{}\"",
+                                error
+                            ),
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(error) => log::error!(
+                        target: "save_root_migration",
+                        "migration_lookup_failed error=\"{}\"",
+                        error
+                    ),
+                }
+            });
+        });
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn migrate_save_root(
+    app: tauri::AppHandle,
+    save_path: String,
+    mode: SaveRootMigrationMode,
+) -> Result<SaveRootMigration, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    crate::domain::meta::service::resolve_save_root(&app)
+        .await
+        .map_err(|error| error.to_string())?;
+    let meta = meta_repo::get_meta_info()
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| "save path should always be configured".to_string())?;
+
+    begin_save_root_migration(&meta, &save_path, mode)
+        .await
+        .map_err(|error| error.to_string())?;
+    run_save_root_migration_for_app(&app)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_save_root_migration(
+    app: tauri::AppHandle,
+) -> Result<SaveRootMigration, String> {
+    let _restore_guard = crate::domain::library_backup::try_acquire_library_write_guard()
+        .map_err(|error| error.to_string())?;
+    run_save_root_migration_for_app(&app)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(not(test))]
+#[tauri::command]
+#[specta::specta]
+pub async fn get_save_root_migration_state() -> Result<Option<SaveRootMigration>, String> {
+    get_save_root_migration()
+        .await
+        .map_err(|error| error.to_string())
+}
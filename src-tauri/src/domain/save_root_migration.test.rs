@@ -0,0 +1,248 @@
+use super::{
+    SaveRootMigrationMode, SaveRootMigrationStage, begin_save_root_migration,
+    ensure_no_save_root_migration, ensure_save_path_change_allowed, get_save_root_migration,
+    run_save_root_migration,
+};
+use crate::domain::collection_merge::merge_collections;
+use crate::domain::collection_relocation::move_collection_to_save_root;
+use crate::domain::meta::model::{MetaInfo, SaveRoot};
+use crate::domain::meta::repo::{get_meta_info, save_meta_info};
+use crate::domain::playlists::model::{Collection, canonical_music_id_for_source};
+use crate::domain::playlists::repo::{get_collection_by_url, upsert_collection};
+use crate::domain::test_support::{
+    acquire_db_test_lock, bootstrap_db, music, owner_group, run_async, temp_path, write_file,
+};
+use appdb::connection::reset_db;
+use std::path::PathBuf;
+
+const COLLECTION_URL: &str = "https://example.com/migrate";
+const COLLECTION_FOLDER: &str = "example/Migrate";
+
+fn collection(url: &str, folder: &str, path: String, save_root: Option<String>) -> Collection {
+    let mut music = music("Migrated", &owner_group("Migrate", url, folder));
+    music.url = format!("{url}/watch");
+    music.canonical_music_id = canonical_music_id_for_source(&music.url, 0, 60_000);
+    music.path = Some(path);
+    let mut collection =
+        crate::domain::test_support::collection("Migrate", url, folder, vec![music]);
+    collection.save_root = save_root;
+    collection
+}
+
+#[test]
+fn move_migration_resumes_after_an_interrupted_copy() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let old_root = temp_path("old");
+        let new_root = temp_path("new");
+        let hdd_root = temp_path("hdd");
+        let source_dir = old_root.join(COLLECTION_FOLDER);
+        write_file(&source_dir.join("Migrated.m4a"), b"audio");
+        write_file(&source_dir.join(".slisic.collection.toml"), b"version = 1");
+        write_file(&source_dir.join(".slisic.leaves/leaf.toml"), b"leaf");
+        let absolute_music = source_dir.join("Disc 2/Absolute.m4a");
+        write_file(&absolute_music, b"absolute");
+        upsert_collection(&collection(
+            COLLECTION_URL,
+            COLLECTION_FOLDER,
+            absolute_music.to_string_lossy().to_string(),
+            None,
+        ))
+        .await
+        .expect("default root collection should save");
+        let hdd_folder = hdd_root.join("Elsewhere");
+        upsert_collection(&collection(
+            "https://example.com/hdd",
+            &hdd_folder.to_string_lossy(),
+            "Track.m4a".to_string(),
+            Some("hdd".to_string()),
+        ))
+        .await
+        .expect("named root collection should save");
+        let meta = save_meta_info(MetaInfo {
+            save_path: Some(old_root.to_string_lossy().to_string()),
+            save_roots: Some(vec![SaveRoot {
+                name: "hdd".to_string(),
+                path: hdd_root.to_string_lossy().to_string(),
+            }]),
+        })
+        .await
+        .expect("meta should save");
+
+        assert!(
+            begin_save_root_migration(&meta, "relative/root", SaveRootMigrationMode::Move)
+                .await
+                .is_err()
+        );
+        assert!(
+            begin_save_root_migration(
+                &meta,
+                &hdd_root.to_string_lossy(),
+                SaveRootMigrationMode::Move
+            )
+            .await
+            .is_err()
+        );
+
+        let migration = begin_save_root_migration(
+            &meta,
+            &new_root.to_string_lossy(),
+            SaveRootMigrationMode::Move,
+        )
+        .await
+        .expect("migration should begin");
+        assert_eq!(migration.collection_urls, vec![COLLECTION_URL.to_string()]);
+        assert!(
+            begin_save_root_migration(
+                &meta,
+                &new_root.to_string_lossy(),
+                SaveRootMigrationMode::Copy
+            )
+            .await
+            .is_err()
+        );
+        let mut changed = meta.clone();
+        changed.save_path = Some(temp_path("other").to_string_lossy().to_string());
+        assert!(ensure_save_path_change_allowed(&changed).await.is_err());
+        assert!(ensure_save_path_change_allowed(&meta).await.is_ok());
+
+        // A file in place of the target folder makes the first run fail mid-copy.
+        write_file(&new_root.join("example"), b"blocks the collection folder");
+        assert!(run_save_root_migration(|_| {}).await.is_err());
+        let interrupted = get_save_root_migration()
+            .await
+            .expect("migration lookup should succeed")
+            .expect("migration should be recorded");
+        assert_eq!(interrupted.stage, SaveRootMigrationStage::Copying);
+        assert!(interrupted.last_error.is_some());
+        assert!(ensure_no_save_root_migration().await.is_err());
+        let relocation =
+            move_collection_to_save_root(&meta, "https://example.com/hdd", "default", |_| {})
+                .await
+                .expect_err("relocation should wait for the migration");
+        assert!(relocation.to_string().contains("migration"));
+        let merge = merge_collections(&old_root, COLLECTION_URL, "https://example.com/hdd")
+            .await
+            .expect_err("merge should wait for the migration");
+        assert!(merge.to_string().contains("migration"));
+        assert!(source_dir.join("Migrated.m4a").is_file());
+        assert_eq!(
+            get_meta_info()
+                .await
+                .expect("meta lookup should succeed")
+                .and_then(|meta| meta.save_path),
+            meta.save_path
+        );
+
+        std::fs::remove_file(new_root.join("example")).expect("blocker should be removed");
+        let finished = run_save_root_migration(|_| {})
+            .await
+            .expect("resumed migration should finish");
+
+        let target_dir = new_root.join(COLLECTION_FOLDER);
+        assert_eq!(finished.stage, SaveRootMigrationStage::Completed);
+        assert_eq!(finished.last_error, None);
+        assert!(ensure_no_save_root_migration().await.is_ok());
+        assert!(target_dir.join("Migrated.m4a").is_file());
+        assert!(target_dir.join(".slisic.collection.toml").is_file());
+        assert!(target_dir.join(".slisic.leaves/leaf.toml").is_file());
+        assert!(!source_dir.exists());
+        assert_eq!(
+            get_meta_info()
+                .await
+                .expect("meta lookup should succeed")
+                .and_then(|meta| meta.save_path),
+            Some(new_root.to_string_lossy().to_string())
+        );
+        let stored = get_collection_by_url(COLLECTION_URL)
+            .await
+            .expect("collection lookup should succeed")
+            .expect("collection should exist");
+        assert_eq!(stored.folder, COLLECTION_FOLDER);
+        assert_eq!(
+            stored.musics[0].path.as_deref().map(PathBuf::from),
+            Some(target_dir.join("Disc 2/Absolute.m4a"))
+        );
+        let untouched = get_collection_by_url("https://example.com/hdd")
+            .await
+            .expect("collection lookup should succeed")
+            .expect("named root collection should exist");
+        assert_eq!(PathBuf::from(untouched.folder), hdd_folder);
+
+        let _ = std::fs::remove_dir_all(old_root);
+        let _ = std::fs::remove_dir_all(new_root);
+        reset_db();
+    });
+}
+
+#[test]
+fn copy_migration_keeps_the_old_folders() {
+    let _guard = acquire_db_test_lock();
+
+    run_async(async {
+        bootstrap_db().await;
+
+        let old_root = temp_path("copy_old");
+        let new_root = temp_path("copy_new");
+        write_file(
+            &old_root.join(COLLECTION_FOLDER).join("Migrated.m4a"),
+            b"audio",
+        );
+        upsert_collection(&collection(
+            COLLECTION_URL,
+            COLLECTION_FOLDER,
+            "Migrated.m4a".to_string(),
+            None,
+        ))
+        .await
+        .expect("collection should save");
+        let meta = save_meta_info(MetaInfo {
+            save_path: Some(old_root.to_string_lossy().to_string()),
+            save_roots: None,
+        })
+        .await
+        .expect("meta should save");
+
+        begin_save_root_migration(
+            &meta,
+            &new_root.to_string_lossy(),
+            SaveRootMigrationMode::Copy,
+        )
+        .await
+        .expect("migration should begin");
+        let finished = run_save_root_migration(|_| {})
+            .await
+            .expect("migration should finish");
+
+        assert_eq!(finished.stage, SaveRootMigrationStage::Completed);
+        assert!(
+            old_root
+                .join(COLLECTION_FOLDER)
+                .join("Migrated.m4a")
+                .is_file()
+        );
+        assert!(
+            new_root
+                .join(COLLECTION_FOLDER)
+                .join("Migrated.m4a")
+                .is_file()
+        );
+        assert_eq!(
+            get_collection_by_url(COLLECTION_URL)
+                .await
+                .expect("collection lookup should succeed")
+                .expect("collection should exist")
+                .musics[0]
+                .path
+                .as_deref(),
+            Some("Migrated.m4a")
+        );
+
+        let _ = std::fs::remove_dir_all(old_root);
+        let _ = std::fs::remove_dir_all(new_root);
+        reset_db();
+    });
+}
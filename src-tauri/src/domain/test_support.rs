@@ -0,0 +1,122 @@
+use crate::domain::playlists::PLAYLIST_DB_TEST_LOCK;
+use crate::domain::playlists::model::{
+    Collection, CollectionGroupOwner, Group, Music, canonical_music_id_for_source,
+};
+use appdb::connection::{get_db, reinit_db};
+use appdb::model::meta::ModelMeta;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::runtime::Runtime;
+
+pub(crate) const LAST_UPDATED: &str = "2026-04-12T00:00:00+00:00";
+
+pub(crate) static DB_TEST_RT: LazyLock<Runtime> =
+    LazyLock::new(|| Runtime::new().expect("domain test runtime should be created"));
+
+/// A path below the temp dir that no other test run uses; nothing is created.
+pub(crate) fn temp_path(label: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock before epoch")
+        .as_nanos();
+
+    std::env::temp_dir().join(format!("slisic_{label}_{}_{}", std::process::id(), nanos))
+}
+
+pub(crate) fn write_file(path: &Path, contents: &[u8]) {
+    std::fs::create_dir_all(path.parent().expect("file should have a parent"))
+        .expect("parent folder should be created");
+    std::fs::write(path, contents).expect("file should be written");
+}
+
+pub(crate) fn run_async<T>(fut: impl Future<Output = T>) -> T {
+    DB_TEST_RT.block_on(fut)
+}
+
+/// Serializes the tests that share the global database handle.
+pub(crate) fn acquire_db_test_lock() -> MutexGuard<'static, ()> {
+    PLAYLIST_DB_TEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Points the global database at a fresh folder with the collection graph
+/// tables defined.
+pub(crate) async fn bootstrap_db() {
+    reinit_db(temp_path("db"))
+        .await
+        .expect("test database should initialize");
+    get_db()
+        .expect("test database handle should exist")
+        .query(format!(
+            "DEFINE TABLE IF NOT EXISTS {} SCHEMALESS;
+             DEFINE TABLE IF NOT EXISTS includes TYPE RELATION SCHEMALESS;
+             DEFINE TABLE IF NOT EXISTS include TYPE RELATION SCHEMALESS;
+             DEFINE TABLE IF NOT EXISTS grouped TYPE RELATION SCHEMALESS;",
+            Music::table_name()
+        ))
+        .await
+        .expect("test schema bootstrap should succeed")
+        .check()
+        .expect("test schema bootstrap response should succeed");
+}
+
+pub(crate) fn owner(name: &str, url: &str, folder: &str) -> CollectionGroupOwner {
+    CollectionGroupOwner {
+        name: name.to_string(),
+        url: url.to_string(),
+        folder: folder.to_string(),
+        last_updated: LAST_UPDATED.to_string(),
+        enable_updates: Some(false),
+    }
+}
+
+/// The group a collection owns for the music directly in its folder.
+pub(crate) fn owner_group(name: &str, url: &str, folder: &str) -> Group {
+    Group {
+        name: name.to_string(),
+        url: url.to_string(),
+        collection: owner(name, url, folder),
+        folder: folder.to_string(),
+    }
+}
+
+/// A one minute music at `https://example.com/watch/{name}`, stored as
+/// `{name}.m4a`.
+pub(crate) fn music(name: &str, group: &Group) -> Music {
+    ranged_music(name, group, 0, 60_000)
+}
+
+pub(crate) fn ranged_music(name: &str, group: &Group, start_ms: u32, end_ms: u32) -> Music {
+    let url = format!("https://example.com/watch/{name}");
+    Music {
+        occurrence_id: String::new(),
+        name: name.to_string(),
+        alias: name.to_string(),
+        group: group.clone(),
+        canonical_music_id: canonical_music_id_for_source(&url, start_ms, end_ms),
+        url,
+        path: Some(format!("{name}.m4a")),
+        start_ms,
+        end_ms,
+        liked: false,
+        loudness_profile: None,
+        added_at: None,
+        tags: None,
+        lyrics_path: None,
+    }
+}
+
+pub(crate) fn collection(name: &str, url: &str, folder: &str, musics: Vec<Music>) -> Collection {
+    Collection {
+        name: name.to_string(),
+        url: url.to_string(),
+        folder: folder.to_string(),
+        musics,
+        last_updated: LAST_UPDATED.to_string(),
+        enable_updates: Some(false),
+        save_root: None,
+    }
+}
@@ -1,8 +1,22 @@
 mod domain {
+    pub(crate) mod test_support {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/test_support.rs"
+        ));
+    }
+
     pub mod playlists {
         pub(crate) static PLAYLIST_DB_TEST_LOCK: std::sync::LazyLock<std::sync::Mutex<()>> =
             std::sync::LazyLock::new(|| std::sync::Mutex::new(()));
 
+        pub mod bulk_edit {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/bulk_edit.rs"
+            ));
+        }
+
         pub mod model {
             include!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
@@ -17,6 +31,48 @@ mod domain {
             ));
         }
 
+        pub mod export {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/export.rs"
+            ));
+        }
+
+        pub mod groups {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/groups.rs"
+            ));
+        }
+
+        pub mod import {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/import.rs"
+            ));
+        }
+
+        pub mod search {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/search.rs"
+            ));
+        }
+
+        pub mod trash {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/trash.rs"
+            ));
+        }
+
+        mod bulk_edit_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/bulk_edit.test.rs"
+            ));
+        }
+
         mod model_test {
             include!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
@@ -30,6 +86,41 @@ mod domain {
                 "/src/domain/playlists/repo.test.rs"
             ));
         }
+
+        mod export_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/export.test.rs"
+            ));
+        }
+
+        mod groups_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/groups.test.rs"
+            ));
+        }
+
+        mod import_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/import.test.rs"
+            ));
+        }
+
+        mod search_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/search.test.rs"
+            ));
+        }
+
+        mod trash_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlists/trash.test.rs"
+            ));
+        }
     }
 
     pub mod player {
@@ -40,6 +131,20 @@ mod domain {
             ));
         }
 
+        pub mod history {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/player/history.rs"
+            ));
+        }
+
+        pub mod repo {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/player/repo.rs"
+            ));
+        }
+
         pub mod strategy {
             include!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
@@ -81,9 +186,37 @@ mod domain {
                 "/src/domain/player/strategy.test.rs"
             ));
         }
+
+        mod history_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/player/history.test.rs"
+            ));
+        }
+
+        mod repo_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/player/repo.test.rs"
+            ));
+        }
     }
 
     pub mod playlist_playback {
+        pub mod model {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlist_playback/model.rs"
+            ));
+        }
+
+        pub mod repo {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlist_playback/repo.rs"
+            ));
+        }
+
         pub mod symbolic_program {
             include!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
@@ -125,6 +258,13 @@ mod domain {
                 "/src/domain/playlist_playback/service.test.rs"
             ));
         }
+
+        mod repo_test {
+            include!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/domain/playlist_playback/repo.test.rs"
+            ));
+        }
     }
 
     pub mod meta {
@@ -157,6 +297,24 @@ mod domain {
         }
     }
 
+    pub mod audio_tags {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/audio_tags.rs"
+        ));
+    }
+
+    pub mod cue_sheet {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/cue_sheet.rs"
+        ));
+    }
+
+    pub mod lyrics {
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/domain/lyrics.rs"));
+    }
+
     pub mod loudness_evidence {
         include!(concat!(
             env!("CARGO_MANIFEST_DIR"),
@@ -171,6 +329,13 @@ mod domain {
         ));
     }
 
+    mod audio_segmentation {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/audio_segmentation.rs"
+        ));
+    }
+
     pub mod downloads {
         pub mod model {
             include!(concat!(
@@ -235,4 +400,46 @@ mod domain {
             ));
         }
     }
+
+    mod collection_manifest_sync {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/collection_manifest_sync.rs"
+        ));
+    }
+
+    mod library_integrity {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/library_integrity.rs"
+        ));
+    }
+
+    mod collection_relocation {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/collection_relocation.rs"
+        ));
+    }
+
+    mod collection_merge {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/collection_merge.rs"
+        ));
+    }
+
+    mod library_backup {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/library_backup.rs"
+        ));
+    }
+
+    mod save_root_migration {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/save_root_migration.rs"
+        ));
+    }
 }
@@ -50,6 +50,13 @@ mod domain {
         }
     }
 
+    pub mod audio_tags {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/audio_tags.rs"
+        ));
+    }
+
     pub mod loudness_evidence {
         include!(concat!(
             env!("CARGO_MANIFEST_DIR"),
@@ -57,6 +64,27 @@ mod domain {
         ));
     }
 
+    pub mod audio_tail_trim {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/audio_tail_trim.rs"
+        ));
+    }
+
+    pub mod cue_sheet {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/cue_sheet.rs"
+        ));
+    }
+
+    pub mod lyrics {
+        include!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/domain/lyrics.rs"
+        ));
+    }
+
     pub mod downloads {
         pub mod model {
             include!(concat!(
@@ -197,6 +225,7 @@ fn repair_stale_single_source_collections_restores_playable_music_from_completed
             musics: vec![],
             last_updated: "2026-04-12T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         let absolute_path = save_root
             .join(&collection.folder)
@@ -296,6 +325,7 @@ fn repair_stale_single_source_collections_recovers_stable_file_before_leaf_commi
             musics: vec![],
             last_updated: "2026-06-10T00:00:00+00:00".to_string(),
             enable_updates: None,
+            save_root: None,
         };
         let stable_file_name =
             "Islands of the Lost and Forgotten - Disc 1- Isles of Serenity and Amnesia.m4a";